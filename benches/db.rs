@@ -0,0 +1,68 @@
+//! Benchmarks for the queries that grow with the size of the graph.
+//!
+//! Each run builds a synthetic repository with [`db::generate_fixture`].
+//! The default size keeps `cargo bench` quick; set `WR_BENCH_WIRES` and
+//! `WR_BENCH_EDGES` to measure at larger scale, e.g. 50000 and 200000.
+//! `wr devtool gen` builds the same graphs for trying the CLI by hand.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rusqlite::Connection;
+use std::hint::black_box;
+use tempfile::TempDir;
+use wr::db;
+
+fn size(var: &str, default: usize) -> usize {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn setup() -> (TempDir, Connection, db::Fixture) {
+    let temp_dir = TempDir::new().unwrap();
+    db::init(temp_dir.path()).unwrap();
+    let conn = db::open_at(temp_dir.path()).unwrap();
+    let fixture = db::generate_fixture(
+        &conn,
+        size("WR_BENCH_WIRES", 5_000),
+        size("WR_BENCH_EDGES", 20_000),
+        0,
+    )
+    .unwrap();
+    (temp_dir, conn, fixture)
+}
+
+fn queries(c: &mut Criterion) {
+    let (_temp_dir, conn, fixture) = setup();
+    let oldest = fixture.ids.first().unwrap().as_str();
+    let newest = fixture.ids.last().unwrap().as_str();
+
+    c.bench_function("ready", |b| {
+        b.iter(|| db::get_ready_wires(black_box(&conn)).unwrap())
+    });
+
+    c.bench_function("list_wires_with_deps", |b| {
+        b.iter(|| db::list_wires_with_deps(black_box(&conn), &db::ListFilter::default()).unwrap())
+    });
+
+    // The oldest wire depends on nothing, so checking an edge from it to
+    // the newest searches everything the newest wire depends on
+    c.bench_function("add_dependency_cycle_check", |b| {
+        b.iter(|| {
+            if db::add_dependency(&conn, oldest, black_box(newest)).is_ok() {
+                db::remove_dependency(&conn, oldest, newest).unwrap();
+            }
+        })
+    });
+
+    c.bench_function("transitive_dependents", |b| {
+        b.iter(|| db::get_transitive_dependents(&conn, black_box(oldest)).unwrap())
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = queries
+}
+criterion_main!(benches);
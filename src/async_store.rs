@@ -0,0 +1,222 @@
+//! An async handle on a wires repository, for services running on tokio.
+//!
+//! SQLite calls block, so [`AsyncWireStore`] runs each [`WireStore`]
+//! operation on tokio's blocking thread pool instead of the async runtime.
+//! Clones share one connection; calls are serialized.
+//!
+//! Requires the `async` feature.
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use wr::async_store::AsyncWireStore;
+//! use wr::models::Status;
+//!
+//! let store = AsyncWireStore::open_at("/path/to/project").await?;
+//! let wire = store.create("Build API", None, 0).await?;
+//! store.set_status(&wire.id, Status::InProgress).await?;
+//! let ready = store.ready().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::models::{Agent, Status, Tag, TransitiveDependency, Wire, WireId, WireWithDeps};
+use crate::store::{ListFilter, WireStore};
+
+/// A [`WireStore`] whose operations are `async`.
+#[derive(Debug, Clone)]
+pub struct AsyncWireStore {
+    store: Arc<Mutex<WireStore>>,
+}
+
+impl From<WireStore> for AsyncWireStore {
+    fn from(store: WireStore) -> Self {
+        AsyncWireStore {
+            store: Arc::new(Mutex::new(store)),
+        }
+    }
+}
+
+impl AsyncWireStore {
+    /// Opens the repository containing the current directory.
+    pub async fn open() -> Result<Self> {
+        let store = tokio::task::spawn_blocking(WireStore::open).await??;
+        Ok(store.into())
+    }
+
+    /// Opens the repository containing `path`.
+    pub async fn open_at(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let store = tokio::task::spawn_blocking(move || WireStore::open_at(&path)).await??;
+        Ok(store.into())
+    }
+
+    /// Creates a repository in `path` and opens it.
+    pub async fn init(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let store = tokio::task::spawn_blocking(move || WireStore::init(&path)).await??;
+        Ok(store.into())
+    }
+
+    /// Runs `f` with the underlying store on the blocking thread pool, for
+    /// operations without an async wrapper.
+    pub async fn call<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&WireStore) -> Result<T> + Send + 'static,
+    {
+        let store = Arc::clone(&self.store);
+        tokio::task::spawn_blocking(move || {
+            let store = store
+                .lock()
+                .map_err(|_| anyhow!("WireStore lock poisoned by a panicked call"))?;
+            f(&store)
+        })
+        .await?
+    }
+
+    /// See [`WireStore::create`].
+    pub async fn create(
+        &self,
+        title: impl Into<String>,
+        description: Option<String>,
+        priority: i32,
+    ) -> Result<Wire> {
+        let title = title.into();
+        self.call(move |store| store.create(&title, description.as_deref(), priority))
+            .await
+    }
+
+    /// See [`WireStore::get`].
+    pub async fn get(&self, id: &WireId) -> Result<Option<WireWithDeps>> {
+        let id = id.clone();
+        self.call(move |store| store.get(&id)).await
+    }
+
+    /// See [`WireStore::list`].
+    pub async fn list(&self, filter: ListFilter) -> Result<Vec<Wire>> {
+        self.call(move |store| store.list(&filter)).await
+    }
+
+    /// See [`WireStore::ready`].
+    pub async fn ready(&self) -> Result<Vec<Wire>> {
+        self.call(|store| store.ready()).await
+    }
+
+    /// See [`WireStore::set_status`].
+    pub async fn set_status(&self, id: &WireId, status: Status) -> Result<()> {
+        let id = id.clone();
+        self.call(move |store| store.set_status(&id, status)).await
+    }
+
+    /// See [`WireStore::set_priority`].
+    pub async fn set_priority(&self, id: &WireId, priority: i32) -> Result<()> {
+        let id = id.clone();
+        self.call(move |store| store.set_priority(&id, priority))
+            .await
+    }
+
+    /// See [`WireStore::assign`].
+    pub async fn assign(&self, id: &WireId, assignee: Option<&Agent>) -> Result<()> {
+        let (id, assignee) = (id.clone(), assignee.cloned());
+        self.call(move |store| store.assign(&id, assignee.as_ref()))
+            .await
+    }
+
+    /// See [`WireStore::add_dep`].
+    pub async fn add_dep(&self, id: &WireId, depends_on: &WireId) -> Result<()> {
+        let (id, depends_on) = (id.clone(), depends_on.clone());
+        self.call(move |store| store.add_dep(&id, &depends_on))
+            .await
+    }
+
+    /// See [`WireStore::remove_dep`].
+    pub async fn remove_dep(&self, id: &WireId, depends_on: &WireId) -> Result<()> {
+        let (id, depends_on) = (id.clone(), depends_on.clone());
+        self.call(move |store| store.remove_dep(&id, &depends_on))
+            .await
+    }
+
+    /// See [`WireStore::transitive_dependencies`].
+    pub async fn transitive_dependencies(&self, id: &WireId) -> Result<Vec<TransitiveDependency>> {
+        let id = id.clone();
+        self.call(move |store| store.transitive_dependencies(&id))
+            .await
+    }
+
+    /// See [`WireStore::transitive_dependents`].
+    pub async fn transitive_dependents(&self, id: &WireId) -> Result<Vec<TransitiveDependency>> {
+        let id = id.clone();
+        self.call(move |store| store.transitive_dependents(&id))
+            .await
+    }
+
+    /// See [`WireStore::add_tag`].
+    pub async fn add_tag(&self, id: &WireId, tag: &Tag) -> Result<bool> {
+        let (id, tag) = (id.clone(), tag.clone());
+        self.call(move |store| store.add_tag(&id, &tag)).await
+    }
+
+    /// See [`WireStore::remove_tag`].
+    pub async fn remove_tag(&self, id: &WireId, tag: &Tag) -> Result<bool> {
+        let (id, tag) = (id.clone(), tag.clone());
+        self.call(move |store| store.remove_tag(&id, &tag)).await
+    }
+
+    /// See [`WireStore::delete`].
+    pub async fn delete(&self, id: &WireId) -> Result<()> {
+        let id = id.clone();
+        self.call(move |store| store.delete(&id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_async_store_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = AsyncWireStore::init(temp_dir.path()).await.unwrap();
+
+        let schema = store.create("Schema", None, 0).await.unwrap();
+        let api = store
+            .create("API", Some("REST".to_string()), 1)
+            .await
+            .unwrap();
+        store.add_dep(&api.id, &schema.id).await.unwrap();
+
+        let ready = store.ready().await.unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, schema.id);
+
+        store.set_status(&schema.id, Status::Done).await.unwrap();
+        let found = store.get(&api.id).await.unwrap().unwrap();
+        assert_eq!(found.wire.description.as_deref(), Some("REST"));
+        assert_eq!(found.depends_on[0].status, Status::Done);
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_the_connection() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = AsyncWireStore::init(temp_dir.path()).await.unwrap();
+        let other = store.clone();
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let store = other.clone();
+                tokio::spawn(async move { store.create(format!("Wire {}", i), None, 0).await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        let wires = store.list(ListFilter::default()).await.unwrap();
+        assert_eq!(wires.len(), 4);
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::Result;
+use clap::{Arg, Command, ValueEnum};
+use serde::Serialize;
+use wr::format::print_json;
+
+/// Formats supported by `wr agent-help`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AgentHelpFormat {
+    /// Plain text, ready to paste into a system prompt
+    Text,
+    /// The same contract as a JSON object
+    Json,
+}
+
+/// The working loop agents are expected to follow.
+const LOOP: &[(&str, &str)] = &[
+    (
+        "wr next --agent <AGENT>",
+        "claim and start the best ready wire; null when nothing is ready",
+    ),
+    ("wr show <ID>", "read the description and dependencies"),
+    ("wr done <ID>", "finish it, then go back to `wr next`"),
+];
+
+/// Shapes of the JSON objects commands print. `tests/agent_help_test.rs`
+/// checks the wire shape against `wr show`.
+const SHAPES: &[(&str, &str)] = &[
+    (
+        "wire",
+        "{id, title, description, status, priority, created_at, updated_at, tags, depends_on, blocks}",
+    ),
+    ("dependency", "{id, title, status} (entries of depends_on and blocks)"),
+    ("status", "TODO | IN_PROGRESS | PAUSED | BLOCKED | DONE | CANCELLED"),
+    (
+        "error",
+        "{error, code, ...context} on stderr, e.g. cycle, candidates, lease",
+    ),
+    (
+        "envelope",
+        "with --envelope: {ok: true, command, args, data} on stdout; {ok: false, command, args, error, ...context} on stderr",
+    ),
+];
+
+/// Process exit codes. `tests/agent_help_test.rs` checks every
+/// [`WireError`](wr::models::WireError) kind is listed under its code.
+const EXIT_CODES: &[(i32, &str)] = &[
+    (0, "success; result on stdout"),
+    (1, "the command failed; JSON error on stderr"),
+    (2, "not a wires repository (code not_a_repository)"),
+    (3, "wire not found (wire_not_found)"),
+    (4, "dependency would create a cycle (circular_dependency)"),
+    (5, "repository already initialized (already_initialized)"),
+    (6, "title: lookup matched several wires (ambiguous_wire)"),
+    (
+        7,
+        "idempotency key used for another command (idempotency_key_reused)",
+    ),
+    (8, "claimed by another agent (already_claimed)"),
+    (9, "not claimed by this agent (not_claimed_by)"),
+    (10, "ID not reserved (not_reserved)"),
+    (11, "IN_PROGRESS limit reached (in_progress_limit)"),
+    (12, "status change not allowed (invalid_transition)"),
+    (13, "imported wires already exist (import_conflicts)"),
+    (
+        14,
+        "snapshot doesn't match its digest or manifest (snapshot_unverified)",
+    ),
+    (15, "wr ping found the repository unusable (unhealthy)"),
+    (
+        16,
+        "[permissions] don't allow this agent the command (permission_denied)",
+    ),
+    (64, "invalid arguments; usage message on stderr"),
+];
+
+/// A command in the usage contract.
+#[derive(Debug, Serialize)]
+struct CommandSpec {
+    usage: String,
+    about: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    args: Vec<ArgSpec>,
+}
+
+/// One argument or option of a command.
+#[derive(Debug, Serialize)]
+struct ArgSpec {
+    name: String,
+    help: String,
+    required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env: Option<String>,
+}
+
+/// Prints the agent usage contract for `cli`, built from its definitions so
+/// it never drifts from the real commands.
+pub fn run(cli: &Command, format: AgentHelpFormat) -> Result<()> {
+    let mut commands = Vec::new();
+    collect_commands(cli, cli.get_name(), &[], &mut commands);
+
+    match format {
+        AgentHelpFormat::Json => {
+            let pairs = |items: &[(&str, &str)]| {
+                items
+                    .iter()
+                    .map(|(a, b)| serde_json::json!([a, b]))
+                    .collect::<Vec<_>>()
+            };
+            print_json(&serde_json::json!({
+                "loop": pairs(LOOP),
+                "shapes": SHAPES.iter().copied().collect::<std::collections::BTreeMap<_, _>>(),
+                "exit_codes": EXIT_CODES
+                    .iter()
+                    .map(|(code, meaning)| (code.to_string(), *meaning))
+                    .collect::<std::collections::BTreeMap<_, _>>(),
+                "commands": commands,
+            }))?;
+        }
+        AgentHelpFormat::Text => print!("{}", format_text(cli, &commands)),
+    }
+
+    Ok(())
+}
+
+fn format_text(cli: &Command, commands: &[CommandSpec]) -> String {
+    let mut output = format!(
+        "{} {}: {}\n\n",
+        cli.get_name(),
+        cli.get_version().unwrap_or_default(),
+        cli.get_about().map(|a| a.to_string()).unwrap_or_default()
+    );
+
+    output.push_str("LOOP\n");
+    for (i, (command, why)) in LOOP.iter().enumerate() {
+        output.push_str(&format!("{}. {}  # {}\n", i + 1, command, why));
+    }
+
+    output.push_str(
+        "\nOUTPUT\nstdout is JSON when not a terminal (`-f json` forces it where offered).\n",
+    );
+    for (name, shape) in SHAPES {
+        output.push_str(&format!("{}: {}\n", name, shape));
+    }
+
+    output.push_str("\nEXIT CODES\n");
+    for (code, meaning) in EXIT_CODES {
+        output.push_str(&format!("{}: {}\n", code, meaning));
+    }
+
+    output.push_str("\nCOMMANDS\n");
+    for command in commands {
+        output.push_str(&format!("{}  # {}\n", command.usage, command.about));
+    }
+
+    output
+}
+
+/// Walks `cmd`'s subcommands depth-first, recording every runnable one.
+///
+/// `inherited` holds the options of parent commands (like `wr multi --roots`),
+/// which their subcommands need too.
+fn collect_commands<'a>(
+    cmd: &'a Command,
+    path: &str,
+    inherited: &[&'a Arg],
+    out: &mut Vec<CommandSpec>,
+) {
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() || sub.get_name() == "help" {
+            continue;
+        }
+        let path = format!("{} {}", path, sub.get_name());
+        let own: Vec<&Arg> = sub
+            .get_arguments()
+            .filter(|a| !a.is_hide_set() && !matches!(a.get_id().as_str(), "help" | "version"))
+            .collect();
+        let args: Vec<&Arg> = own.iter().chain(inherited).copied().collect();
+
+        // Commands with only subcommands (like `wr tag`) aren't runnable themselves
+        if !sub.has_subcommands() || sub.is_args_conflicts_with_subcommands_set() {
+            out.push(CommandSpec {
+                usage: usage(&path, &args),
+                about: sub.get_about().map(|a| a.to_string()).unwrap_or_default(),
+                args: args.iter().map(|a| arg_spec(a)).collect(),
+            });
+            collect_commands(sub, &path, inherited, out);
+        } else {
+            collect_commands(sub, &path, &args, out);
+        }
+    }
+}
+
+/// Renders a one-line usage such as `wr claim <ID> --agent <AGENT> [--ttl <TTL>]`.
+fn usage(path: &str, args: &[&Arg]) -> String {
+    let mut usage = path.to_string();
+    for arg in args.iter().filter(|a| a.is_positional()) {
+        let name = value_name(arg);
+        let repeat = if takes_many(arg) { "..." } else { "" };
+        if arg.is_required_set() {
+            usage.push_str(&format!(" <{}>{}", name, repeat));
+        } else {
+            usage.push_str(&format!(" [{}]{}", name, repeat));
+        }
+    }
+    for arg in args.iter().filter(|a| !a.is_positional()) {
+        let flag = option_name(arg);
+        if arg.is_required_set() {
+            usage.push_str(&format!(" {}", flag));
+        } else {
+            usage.push_str(&format!(" [{}]", flag));
+        }
+    }
+    usage
+}
+
+fn arg_spec(arg: &Arg) -> ArgSpec {
+    ArgSpec {
+        name: if arg.is_positional() {
+            value_name(arg)
+        } else {
+            option_name(arg)
+        },
+        help: arg.get_help().map(|h| h.to_string()).unwrap_or_default(),
+        required: arg.is_required_set(),
+        env: arg.get_env().map(|e| e.to_string_lossy().into_owned()),
+    }
+}
+
+/// `--name <VALUE>` for options, `--name` for flags.
+fn option_name(arg: &Arg) -> String {
+    let long = arg
+        .get_long()
+        .map(|l| format!("--{}", l))
+        .or_else(|| arg.get_short().map(|s| format!("-{}", s)))
+        .unwrap_or_else(|| arg.get_id().to_string());
+    if arg.get_action().takes_values() {
+        format!("{} <{}>", long, value_name(arg))
+    } else {
+        long
+    }
+}
+
+fn value_name(arg: &Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| arg.get_id().as_str().to_uppercase())
+}
+
+fn takes_many(arg: &Arg) -> bool {
+    arg.get_num_args().is_some_and(|n| n.max_values() > 1)
+        || matches!(arg.get_action(), clap::ArgAction::Append)
+}
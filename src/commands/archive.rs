@@ -0,0 +1,56 @@
+use anyhow::Result;
+use serde_json::json;
+use std::time::Duration;
+use wr::db;
+use wr::format::{print_json, print_result};
+
+pub fn archive(
+    wire_ids: &[String],
+    all_finished: bool,
+    older_than: Option<Duration>,
+    dry_run: bool,
+) -> Result<()> {
+    let conn = db::open()?;
+
+    let filter = db::ArchiveFilter {
+        wire_ids: wire_ids
+            .iter()
+            .map(|id| db::resolve_wire_id(&conn, id))
+            .collect::<Result<_>>()?,
+        all_finished,
+        older_than: older_than.map(|d| i64::try_from(d.as_secs())).transpose()?,
+    };
+    let wires = db::archive_wires(&conn, &filter, dry_run)?;
+
+    let archived: Vec<_> = wires
+        .iter()
+        .map(|w| json!({"id": w.id, "title": w.title, "status": w.status}))
+        .collect();
+    let output = json!({
+        "archived": archived,
+        "dry_run": dry_run
+    });
+
+    // A dry run's report is the result, even when quiet
+    if dry_run {
+        print_json(&output)?;
+    } else {
+        print_result(&output)?;
+    }
+    Ok(())
+}
+
+pub fn unarchive(wire_id: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let restored = db::unarchive_wire(&conn, wire_id)?;
+
+    let output = json!({
+        "id": wire_id,
+        "action": if restored { "unarchived" } else { "unchanged" }
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+use wr::models::{Agent, WireError};
+
+pub fn assign(wire_id: &str, assignee: Option<&Agent>) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    db::assign_wire(&conn, wire_id, assignee)?;
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+
+    let output = json!({
+        "id": wire.id,
+        "assignee": wire.assignee,
+        "updated_at": wire.updated_at_ms
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
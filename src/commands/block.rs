@@ -0,0 +1,76 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::{
+    db,
+    format::{is_quiet, print_json, print_json_as, print_result, Format},
+};
+
+pub fn block_on(reason: &str, wire_ids: &[String]) -> Result<()> {
+    let conn = db::open()?;
+    let wire_ids = wire_ids
+        .iter()
+        .map(|id| db::resolve_wire_id(&conn, id))
+        .collect::<Result<Vec<_>>>()?;
+
+    let blocker = db::add_external_blocker(&conn, reason, &wire_ids)?;
+
+    // Like `wr new`, quiet output is just what to refer to it by
+    if is_quiet() {
+        println!("{}", blocker.id);
+    } else {
+        print_json(&blocker)?;
+    }
+    Ok(())
+}
+
+pub fn unblock(reference: &str) -> Result<()> {
+    let conn = db::open()?;
+
+    let blocker = db::clear_external_blocker(&conn, reference)?;
+
+    let output = json!({
+        "blocker": blocker.id,
+        "reason": blocker.reason,
+        "action": "cleared",
+        "unblocked": blocker.wires
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+pub fn list(format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let blockers = db::list_external_blockers(&conn, None)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&blockers, format)?,
+        Format::Table => {
+            if blockers.is_empty() {
+                println!("No external blockers.");
+            }
+            for blocker in &blockers {
+                let ids: Vec<&str> = blocker.wires.iter().map(|id| id.as_str()).collect();
+                println!("#{}  {}  ← {}", blocker.id, blocker.reason, ids.join(", "));
+            }
+        }
+        Format::Markdown => {
+            if blockers.is_empty() {
+                println!("*No external blockers.*");
+            }
+            for blocker in &blockers {
+                let ids: Vec<String> = blocker.wires.iter().map(|id| format!("`{}`", id)).collect();
+                println!(
+                    "- [ ] {} *(#{}, blocks {})*",
+                    blocker.reason,
+                    blocker.id,
+                    ids.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
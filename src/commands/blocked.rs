@@ -0,0 +1,56 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::{print_result, Format};
+use wr::models::{Status, WireError};
+
+use super::list::{self, Filters};
+
+pub fn block(wire_id: &str, reason: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    db::block_wire(&conn, wire_id, reason)?;
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+
+    let output = json!({
+        "id": wire.id,
+        "status": wire.status,
+        "reason": wire.blocked_reason,
+        "updated_at": wire.updated_at_ms
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+pub fn unblock(wire_id: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    db::unblock_wire(&conn, wire_id)?;
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+
+    let output = json!({
+        "id": wire.id,
+        "status": wire.status,
+        "updated_at": wire.updated_at_ms
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+/// Same as `wr list --status blocked`, ignoring `hide_statuses`
+pub fn list(format: Option<Format>) -> Result<()> {
+    let filters = Filters {
+        status: vec![Status::Blocked],
+        show_hidden: true,
+        ..Default::default()
+    };
+    list::run(&filters, None, None, None, format.map(Into::into))
+}
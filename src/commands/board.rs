@@ -0,0 +1,65 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use wr::{
+    config, db,
+    format::{format_board_markdown, format_board_table, print_json_as, terminal_width, Format},
+    models::{Agent, BoardColumn, Status, Tag},
+};
+
+/// Shows wires in one column per status.
+///
+/// Columns for statuses hidden by `hide_statuses` are left out unless
+/// `show_hidden`, as are `PAUSED` and `BLOCKED` while they have no wires.
+pub fn run(
+    tag: Option<&Tag>,
+    assignee: Option<&Agent>,
+    show_hidden: bool,
+    width: Option<usize>,
+    format: Option<Format>,
+) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let hidden_statuses = if show_hidden {
+        Vec::new()
+    } else {
+        config::load()?.hide_statuses
+    };
+    let conn = db::open()?;
+    let filter = db::WireFilter {
+        tag: tag.cloned(),
+        assignee: assignee.cloned(),
+        ..Default::default()
+    };
+    let mut wires: Vec<_> = db::filter_wires(&conn, &filter)?
+        .into_iter()
+        .map(|wd| wd.wire)
+        .collect();
+    wires.sort_by_key(|w| std::cmp::Reverse(w.priority));
+
+    let columns: Vec<BoardColumn> = Status::value_variants()
+        .iter()
+        .filter(|status| !hidden_statuses.contains(status))
+        .map(|&status| BoardColumn {
+            status,
+            wires: wires
+                .iter()
+                .filter(|w| w.status == status)
+                .cloned()
+                .collect(),
+        })
+        .filter(|column| {
+            !column.wires.is_empty() || !matches!(column.status, Status::Paused | Status::Blocked)
+        })
+        .collect();
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&columns, format)?,
+        Format::Table => {
+            let width = width.unwrap_or_else(terminal_width);
+            print!("{}", format_board_table(&columns, width))
+        }
+        Format::Markdown => print!("{}", format_board_markdown(&columns)),
+    }
+
+    Ok(())
+}
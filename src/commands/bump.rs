@@ -0,0 +1,51 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+use wr::models::WireError;
+
+/// Move a wire's priority by `delta`: up for `wr bump`, down for `wr drop`.
+pub fn run(wire_id: &str, delta: i32) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let (from, to) = db::adjust_priority(&conn, wire_id, delta)?;
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+    print_result(&json!({
+        "id": wire.id,
+        "priority": to,
+        "from": from,
+        "updated_at": wire.updated_at_ms
+    }))?;
+    Ok(())
+}
+
+/// Parses `wr bump`'s step count: a number, optionally written `+N`.
+pub fn parse_raise(s: &str) -> Result<i32, String> {
+    parse_steps(s, '+', '-', "wr drop")
+}
+
+/// Parses `wr drop`'s step count: a number, optionally written `-N`.
+pub fn parse_lower(s: &str) -> Result<i32, String> {
+    parse_steps(s, '-', '+', "wr bump")
+}
+
+/// A positive step count, allowing the `sign` of the command's direction and
+/// refusing the `opposite` one rather than silently ignoring it
+fn parse_steps(s: &str, sign: char, opposite: char, instead: &str) -> Result<i32, String> {
+    if let Some(steps) = s.strip_prefix(opposite) {
+        return Err(format!(
+            "'{}' goes the other way; use `{} {}`",
+            s, instead, steps
+        ));
+    }
+    let digits = s.strip_prefix(sign).unwrap_or(s);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid step count '{}': use a whole number", s));
+    }
+    digits
+        .parse()
+        .map_err(|_| format!("Step count '{}' is too large", s))
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::io::{self, IsTerminal};
+use wr::{
+    db,
+    format::{format_burndown_table, print_json},
+};
+
+/// Formats supported by `wr burndown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BurndownFormat {
+    /// A JSON array of days
+    Json,
+    /// Comma-separated values with a header row, for spreadsheets and plotting
+    Csv,
+    /// Human-readable columns
+    Table,
+}
+
+pub fn run(since: i64, format: Option<BurndownFormat>) -> Result<()> {
+    let format = format.unwrap_or_else(|| {
+        if io::stdout().is_terminal() {
+            BurndownFormat::Table
+        } else {
+            BurndownFormat::Json
+        }
+    });
+
+    let conn = db::open()?;
+    let days = db::stats::burndown(&conn, since)?;
+
+    match format {
+        BurndownFormat::Json => print_json(&days)?,
+        BurndownFormat::Csv => {
+            println!("date,open,done,completed");
+            for day in &days {
+                println!("{},{},{},{}", day.date, day.open, day.done, day.completed);
+            }
+        }
+        BurndownFormat::Table => print!("{}", format_burndown_table(&days)),
+    }
+
+    Ok(())
+}
@@ -1,22 +1,47 @@
 use anyhow::Result;
+use rusqlite::Connection;
 use serde_json::json;
+use wr::config;
 use wr::db;
-use wr::models::{Status, WireError};
+use wr::format::print_result;
+use wr::models::{CancelReason, WireError};
 
-pub fn run(wire_id: &str) -> Result<()> {
+pub fn run(wire_id: &str, reason: Option<&str>, full: bool) -> Result<()> {
     let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
 
-    db::update_wire(&conn, wire_id, None, None, Some(Status::Cancelled), None)?;
+    let reason = reason.map(|r| parse_reason(&conn, r)).transpose()?;
+    if reason.is_none() && config::load()?.require_cancel_reason {
+        anyhow::bail!(
+            "A cancel reason is required: --reason duplicate|obsolete|wontfix|superseded-by:<id>"
+        );
+    }
 
-    let wire = db::get_wire_with_deps(&conn, wire_id)
-        .map_err(|_| WireError::WireNotFound(wire_id.to_string()))?;
+    db::cancel_wire(&conn, wire_id, reason.as_ref())?;
 
-    let output = json!({
-        "id": wire.wire.id,
-        "status": wire.wire.status,
-        "updated_at": wire.wire.updated_at
-    });
+    let output = if full {
+        serde_json::to_value(db::get_wire_with_deps(&conn, wire_id)?)?
+    } else {
+        let wire = db::get_wire(&conn, wire_id)?
+            .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+        json!({
+            "id": wire.id,
+            "status": wire.status,
+            "cancel_reason": wire.cancel_reason,
+            "updated_at": wire.updated_at_ms
+        })
+    };
 
-    println!("{}", serde_json::to_string(&output)?);
+    print_result(&output)?;
     Ok(())
 }
+
+/// Parses a `--reason`, resolving the ID in `superseded-by:<id>` like any
+/// other wire reference.
+fn parse_reason(conn: &Connection, reason: &str) -> Result<CancelReason> {
+    let Some(by) = reason.strip_prefix(CancelReason::SUPERSEDED_BY) else {
+        return reason.parse().map_err(anyhow::Error::msg);
+    };
+    let by = db::resolve_wire_id(conn, by)?;
+    Ok(CancelReason::SupersededBy(by.parse()?))
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use wr::{
+    db,
+    format::{print_json_as, Format},
+};
+
+/// Optional features orchestrators may ask about, and whether this build has
+/// them. Features not implemented yet are reported too, as `false`, so
+/// callers can rely on the key being present.
+const FEATURES: &[(&str, bool)] = &[
+    ("async", cfg!(feature = "async")),
+    ("encryption", false),
+    ("mcp", false),
+    ("postgres", false),
+    ("serve", false),
+    ("tui", cfg!(feature = "tui")),
+];
+
+/// What this `wr` binary supports.
+#[derive(Debug, Serialize)]
+struct Capabilities {
+    version: &'static str,
+    /// Schema version this build migrates databases to
+    schema_version: i64,
+    /// Schema version of the repository in the current directory, before
+    /// migration (`None` outside a repository)
+    repo_schema_version: Option<i64>,
+    features: BTreeMap<&'static str, bool>,
+}
+
+pub fn run(format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let repo_schema_version = match db::find_db() {
+        Ok(db_path) => Some(db::repo_schema_version(&db_path)?),
+        Err(_) => None,
+    };
+
+    let capabilities = Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        schema_version: db::SCHEMA_VERSION,
+        repo_schema_version,
+        features: FEATURES.iter().copied().collect(),
+    };
+
+    let repo = capabilities
+        .repo_schema_version
+        .map_or_else(|| "-".to_string(), |v| v.to_string());
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&capabilities, format)?,
+        Format::Table => {
+            println!("version              {}", capabilities.version);
+            println!("schema_version       {}", capabilities.schema_version);
+            println!("repo_schema_version  {}", repo);
+            for (name, enabled) in &capabilities.features {
+                println!("{:<20} {}", name, if *enabled { "yes" } else { "no" });
+            }
+        }
+        Format::Markdown => {
+            println!("- version: `{}`", capabilities.version);
+            println!("- schema version: {}", capabilities.schema_version);
+            println!("- repository schema version: {}", repo);
+            for (name, enabled) in &capabilities.features {
+                println!("- {}: {}", name, if *enabled { "yes" } else { "no" });
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use serde_json::json;
+use std::time::Duration;
+use wr::db;
+use wr::format::print_result;
+use wr::models::Agent;
+
+pub fn claim(wire_id: &str, agent: &Agent, ttl: Duration) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let ttl_secs = i64::try_from(ttl.as_secs())?;
+    let lease = db::claim_wire(&conn, wire_id, agent, ttl_secs)?;
+
+    print_result(&lease)?;
+    Ok(())
+}
+
+pub fn release(wire_id: &str, agent: Option<&Agent>) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let released = db::release_wire(&conn, wire_id, agent)?;
+
+    let output = json!({
+        "id": wire_id,
+        "action": if released { "released" } else { "unchanged" }
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use clap_complete::env::{Bash, Elvish, EnvCompleter, Fish, Powershell, Zsh};
+use clap_complete::CompletionCandidate;
+use std::io;
+use wr::db;
+
+/// Shells `wr completions` writes scripts for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+    Elvish,
+}
+
+/// Environment variable the script sets when it calls back into `wr`
+const COMPLETE_ENV: &str = "COMPLETE";
+
+/// Prints the registration script for `shell`. The script asks `wr` itself
+/// for candidates on every completion, so wire IDs come from the repository
+/// the shell is in.
+pub fn run(shell: CompletionShell) -> Result<()> {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+        CompletionShell::Powershell => &Powershell,
+        CompletionShell::Elvish => &Elvish,
+    };
+    // Call back into the binary the way it was invoked, as `wr` on the PATH
+    let bin = std::env::args().next().unwrap_or_else(|| "wr".to_string());
+
+    completer.write_registration(COMPLETE_ENV, "wr", "wr", &bin, &mut io::stdout())?;
+    Ok(())
+}
+
+/// Wire IDs in the current repository, with titles as help, most recently
+/// created first. Empty outside a repository.
+pub fn wire_ids() -> Vec<CompletionCandidate> {
+    let Ok(wires) = db::open().and_then(|conn| db::list_wires(&conn, &db::ListFilter::default()))
+    else {
+        return Vec::new();
+    };
+    wires
+        .into_iter()
+        .map(|wire| CompletionCandidate::new(wire.id.as_str()).help(Some(wire.title.into())))
+        .collect()
+}
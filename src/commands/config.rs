@@ -0,0 +1,78 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::ErrorKind;
+use toml_edit::DocumentMut;
+use wr::config::{self, Config};
+use wr::db;
+use wr::format::{print_json, print_result};
+
+/// Print one setting, or every setting, with defaults filled in.
+pub fn get(key: Option<&str>) -> Result<()> {
+    let config = config::load()?;
+
+    let output = match key {
+        Some(key) => json!({ "key": key, "value": lookup(&config, key)? }),
+        None => serde_json::to_value(&config)?,
+    };
+
+    print_json(&output)?;
+    Ok(())
+}
+
+/// Write one setting to `.wires/config.toml`, keeping the rest of the file.
+///
+/// `raw` is read as a TOML value (`true`, `10`, `["DONE"]`); anything that
+/// isn't one is taken as a string, so `table` needs no quotes.
+pub fn set(key: &str, raw: &str) -> Result<()> {
+    // Known keys are always in tables, so the edit below can't hit a value
+    lookup(&config::load()?, key)?;
+
+    let path = config::config_path(&db::find_db()?);
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let mut doc: DocumentMut = source
+        .parse()
+        .with_context(|| format!("Invalid {}", path.display()))?;
+
+    let value = raw
+        .parse::<toml_edit::Value>()
+        .unwrap_or_else(|_| raw.into());
+    let (tables, name) = match key.rsplit_once('.') {
+        Some((tables, name)) => (tables.split('.').collect(), name),
+        None => (Vec::new(), key),
+    };
+    let mut item = doc.as_item_mut();
+    for table in tables {
+        if item.get(table).is_none() {
+            item[table] = toml_edit::table();
+        }
+        item = &mut item[table];
+    }
+    item[name] = toml_edit::value(value);
+
+    // Check the whole file, so unknown keys and bad values never get written
+    let updated = doc.to_string();
+    let config: Config = toml::from_str(&updated)
+        .map_err(|e| anyhow!("Invalid value for {}: {}", key, e.message()))?;
+    fs::write(&path, updated).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let output = json!({ "key": key, "value": lookup(&config, key)? });
+    print_result(&output)?;
+    Ok(())
+}
+
+/// The value of a dotted key such as `in_progress.limit`
+fn lookup(config: &Config, key: &str) -> Result<Value> {
+    let mut value = serde_json::to_value(config)?;
+    for part in key.split('.') {
+        value = value
+            .get_mut(part)
+            .map(Value::take)
+            .ok_or_else(|| anyhow!("Unknown config key: {}", key))?;
+    }
+    Ok(value)
+}
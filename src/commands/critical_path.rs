@@ -0,0 +1,44 @@
+use anyhow::Result;
+use wr::{
+    db,
+    format::{format_wire_markdown, format_wire_table, print_json_as, Format},
+    models::WireWithDeps,
+};
+
+pub fn run(weighted: bool, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let path = db::critical_path(&conn, weighted)?;
+
+    if format.is_json() {
+        return print_json_as(&path, format);
+    }
+
+    let count = path.wires.len();
+    let wires_with_deps: Vec<WireWithDeps> =
+        path.wires.into_iter().map(WireWithDeps::from).collect();
+
+    match format {
+        Format::Markdown => {
+            if count > 0 {
+                println!(
+                    "**Critical path:** {} wire(s), weight {} (start at the top)\n",
+                    count, path.weight
+                );
+            }
+            print!("{}", format_wire_markdown(&wires_with_deps))
+        }
+        _ => {
+            if count > 0 {
+                println!(
+                    "Critical path: {} wire(s), weight {} (start at the top)",
+                    count, path.weight
+                );
+            }
+            print!("{}", format_wire_table(&wires_with_deps))
+        }
+    }
+
+    Ok(())
+}
@@ -1,18 +1,38 @@
 use anyhow::Result;
 use serde_json::json;
 use wr::db;
+use wr::format::print_result;
+use wr::models::{DependencyKind, IdempotencyKey};
 
-pub fn run(wire_id: &str, depends_on: &str) -> Result<()> {
+pub fn run(
+    wire_id: &str,
+    depends_on: &str,
+    kind: DependencyKind,
+    idempotency_key: Option<&IdempotencyKey>,
+    full: bool,
+) -> Result<()> {
     let conn = db::open()?;
 
-    db::add_dependency(&conn, wire_id, depends_on)?;
+    let output = db::with_idempotency_key(&conn, idempotency_key, "dep", || {
+        let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+        let depends_on = &db::resolve_wire_id(&conn, depends_on)?;
 
-    let output = json!({
-        "wire_id": wire_id,
-        "depends_on": depends_on,
-        "action": "added"
-    });
+        db::add_dependency_with_kind(&conn, wire_id, depends_on, kind)?;
 
-    println!("{}", serde_json::to_string(&output)?);
+        if full {
+            return Ok(serde_json::to_value(db::get_wire_with_deps(
+                &conn, wire_id,
+            )?)?);
+        }
+
+        Ok(json!({
+            "wire_id": wire_id,
+            "depends_on": depends_on,
+            "kind": kind,
+            "action": "added"
+        }))
+    })?;
+
+    print_result(&output)?;
     Ok(())
 }
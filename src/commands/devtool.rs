@@ -0,0 +1,19 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+
+/// Add a synthetic graph to the repository, for benchmarks.
+pub fn gen(wires: usize, edges: usize, seed: u64) -> Result<()> {
+    let conn = db::open()?;
+
+    let fixture = db::generate_fixture(&conn, wires, edges, seed)?;
+
+    let output = json!({
+        "wires": fixture.ids.len(),
+        "edges": fixture.edges,
+        "seed": seed,
+    });
+    print_result(&output)?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::io::{self, IsTerminal};
+use wr::{
+    db,
+    format::{format_dossier_markdown, print_json},
+    models::Agent,
+};
+
+/// Formats supported by `wr doc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DocFormat {
+    /// A single JSON object
+    Json,
+    /// One Markdown document
+    Markdown,
+}
+
+pub fn run(wire_id: &str, agent: Option<&Agent>, format: Option<DocFormat>) -> Result<()> {
+    let format = format.unwrap_or_else(|| {
+        if io::stdout().is_terminal() {
+            DocFormat::Markdown
+        } else {
+            DocFormat::Json
+        }
+    });
+
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let dossier = db::dossier(&conn, wire_id, agent)?;
+
+    match format {
+        DocFormat::Json => print_json(&dossier)?,
+        DocFormat::Markdown => print!("{}", format_dossier_markdown(&dossier)),
+    }
+
+    Ok(())
+}
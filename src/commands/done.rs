@@ -1,10 +1,12 @@
 use anyhow::Result;
 use serde_json::json;
 use wr::db;
+use wr::format::print_result;
 use wr::models::{Status, WireError};
 
-pub fn run(wire_id: &str) -> Result<()> {
+pub fn run(wire_id: &str, full: bool) -> Result<()> {
     let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
 
     // Check for incomplete dependencies
     let incomplete_deps = db::check_incomplete_dependencies(&conn, wire_id)?;
@@ -12,14 +14,17 @@ pub fn run(wire_id: &str) -> Result<()> {
     // Update status to DONE
     db::update_wire(&conn, wire_id, None, None, Some(Status::Done), None)?;
 
-    let wire = db::get_wire_with_deps(&conn, wire_id)
-        .map_err(|_| WireError::WireNotFound(wire_id.to_string()))?;
-
-    let mut output = json!({
-        "id": wire.wire.id,
-        "status": wire.wire.status,
-        "updated_at": wire.wire.updated_at
-    });
+    let mut output = if full {
+        serde_json::to_value(db::get_wire_with_deps(&conn, wire_id)?)?
+    } else {
+        let wire = db::get_wire(&conn, wire_id)?
+            .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+        json!({
+            "id": wire.id,
+            "status": wire.status,
+            "updated_at": wire.updated_at_ms
+        })
+    };
 
     // Add warnings if there are incomplete dependencies
     if !incomplete_deps.is_empty() {
@@ -37,6 +42,6 @@ pub fn run(wire_id: &str) -> Result<()> {
         output["warnings"] = json!(warnings);
     }
 
-    println!("{}", serde_json::to_string(&output)?);
+    print_result(&output)?;
     Ok(())
 }
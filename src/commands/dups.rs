@@ -0,0 +1,72 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use wr::dups::{find_duplicates, DuplicatePair};
+use wr::format::{print_json_as, Format};
+use wr::models::{DependencyKind, WireId};
+use wr::{config, db};
+
+/// Report pairs of wires with alike titles, leaving out pairs already
+/// marked as duplicates with `wr dep --kind duplicates`.
+pub fn run(threshold: Option<u8>, all: bool, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+    let threshold = match threshold {
+        Some(threshold) => threshold,
+        None => config::load()?.duplicates.threshold,
+    };
+
+    let conn = db::open()?;
+    let wires: Vec<_> = db::list_wires(&conn, &db::ListFilter::default())?
+        .into_iter()
+        .filter(|w| all || w.status.is_blocking())
+        .collect();
+    let marked: HashSet<(WireId, WireId)> = db::list_dependencies(&conn)?
+        .into_iter()
+        .filter(|d| d.kind == DependencyKind::Duplicates)
+        .flat_map(|d| {
+            [
+                (d.wire_id.clone(), d.depends_on.clone()),
+                (d.depends_on, d.wire_id),
+            ]
+        })
+        .collect();
+
+    let pairs: Vec<DuplicatePair> = find_duplicates(&wires, threshold)
+        .into_iter()
+        .filter(|p| !marked.contains(&(p.first.id.clone(), p.second.id.clone())))
+        .collect();
+
+    if format.is_json() {
+        return print_json_as(&pairs, format);
+    }
+    if pairs.is_empty() {
+        println!("No likely duplicates.");
+        return Ok(());
+    }
+
+    match format {
+        Format::Markdown => {
+            for pair in &pairs {
+                println!(
+                    "- **{}%** `{}` {} / `{}` {}",
+                    pair.similarity,
+                    pair.first.id,
+                    pair.first.title,
+                    pair.second.id,
+                    pair.second.title
+                );
+            }
+        }
+        _ => {
+            for pair in &pairs {
+                println!(
+                    "{:>4}%  {}  {}",
+                    pair.similarity,
+                    pair.first.id.as_str(),
+                    pair.first.title
+                );
+                println!("       {}  {}", pair.second.id.as_str(), pair.second.title);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::json;
+use std::path::Path;
+use std::process::Command;
+use wr::db;
+use wr::edit::WireBuffer;
+use wr::format::print_result;
+use wr::models::WireError;
+
+/// Opens the wire's title, tags and description in the user's editor and
+/// saves whatever changed once it exits.
+pub fn run(wire_id: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+
+    let path = std::env::temp_dir().join(format!("wr-edit-{}-{}.md", wire_id, std::process::id()));
+    std::fs::write(&path, WireBuffer::from_wire(&wire).render())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    open_editor(&path)?;
+
+    // Keep the file when its contents can't be used, so the edits aren't lost
+    let changed = std::fs::read_to_string(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|text| db::edit_wire(&conn, wire_id, &WireBuffer::parse(&text)?))
+        .map_err(|e| anyhow!("{}; edits not saved, they are in {}", e, path.display()))?;
+    let _ = std::fs::remove_file(&path);
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+    print_result(&json!({
+        "id": wire.id,
+        "action": if changed { "edited" } else { "unchanged" },
+        "title": wire.title,
+        "tags": wire.tags,
+        "updated_at": wire.updated_at_ms
+    }))?;
+    Ok(())
+}
+
+/// Runs `$VISUAL`, `$EDITOR` or `vi` on `path` and waits for it. The
+/// variable may hold arguments too, like `code --wait`.
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{}'", editor))?;
+    if !status.success() {
+        bail!(
+            "Editor '{}' exited with {}; edits not saved, they are in {}",
+            editor,
+            status,
+            path.display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use wr::{
+    db,
+    format::{format_epic_progress_markdown, format_epic_progress_table, print_json_as, Format},
+};
+
+pub fn progress(wire_id: &str, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let progress = db::epic_progress(&conn, wire_id)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&progress, format)?,
+        Format::Table => print!("{}", format_epic_progress_table(&progress)),
+        Format::Markdown => print!("{}", format_epic_progress_markdown(&progress)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use std::io::{self, Write};
+use std::time::Duration;
+use wr::{
+    db::{self, EventFilter},
+    format::print_json,
+    models::EventKind,
+};
+
+/// How often `--follow` checks the event log for new entries
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+pub fn run(
+    after: Option<i64>,
+    since: Option<i64>,
+    wire: Option<&str>,
+    kinds: &[EventKind],
+    follow: bool,
+) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = wire.map(|w| db::resolve_wire_id(&conn, w)).transpose()?;
+
+    // Following with no starting point streams only what happens from now on
+    let after = match (after, since) {
+        (None, None) if follow => Some(db::last_event_seq(&conn)?),
+        _ => after,
+    };
+    let mut filter = EventFilter {
+        since,
+        wire_id,
+        after,
+    };
+
+    loop {
+        let version = db::data_version(&conn)?;
+        for event in db::list_events(&conn, &filter)? {
+            filter.after = Some(event.seq);
+            if kinds.is_empty() || kinds.contains(&event.kind) {
+                print_json(&event)?;
+            }
+        }
+        if !follow {
+            return Ok(());
+        }
+        io::stdout().flush()?;
+
+        while db::data_version(&conn)? == version {
+            std::thread::sleep(FOLLOW_INTERVAL);
+        }
+    }
+}
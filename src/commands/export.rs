@@ -0,0 +1,121 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use wr::db;
+use wr::format::{self, escape_xml, print_json};
+use wr::models::{Dependency, Wire};
+
+/// Formats supported by `wr export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Full snapshot that `wr import` can load back
+    Json,
+    /// GraphML for Gephi, yEd and other graph tools
+    Graphml,
+}
+
+pub fn run(format: ExportFormat, manifest: bool) -> Result<()> {
+    let conn = db::open()?;
+
+    match format {
+        ExportFormat::Json => {
+            // Pretty-printed so snapshots committed to git diff line by line
+            let mut snapshot = db::export_snapshot(&conn)?;
+            if manifest {
+                snapshot.manifest = Some(snapshot.compute_manifest());
+            }
+            if format::envelope().is_some() {
+                print_json(&snapshot)?;
+            } else {
+                println!("{}", serde_json::to_string_pretty(&snapshot)?);
+            }
+        }
+        ExportFormat::Graphml => {
+            if manifest {
+                bail!("--manifest needs --format json");
+            }
+            // Sorted like the JSON snapshot, so exports diff cleanly
+            let mut wires = db::list_wires(&conn, &db::ListFilter::default())?;
+            wires.sort_by(|a, b| a.id.cmp(&b.id));
+            let deps = db::list_dependencies(&conn)?;
+            print!("{}", format_graphml(&wires, &deps))
+        }
+    }
+
+    Ok(())
+}
+
+fn format_graphml(wires: &[Wire], deps: &[Dependency]) -> String {
+    let mut output = String::new();
+
+    output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+
+    // Attribute declarations, so tools can type the data columns
+    for (id, attr_type) in [
+        ("title", "string"),
+        ("description", "string"),
+        ("status", "string"),
+        ("priority", "int"),
+        ("tags", "string"),
+        ("cancel_reason", "string"),
+        ("blocked_reason", "string"),
+        ("assignee", "string"),
+        ("created_at", "long"),
+        ("updated_at", "long"),
+    ] {
+        output.push_str(&format!(
+            "  <key id=\"{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+            id, id, attr_type
+        ));
+    }
+
+    output.push_str("  <graph id=\"wires\" edgedefault=\"directed\">\n");
+
+    for wire in wires {
+        output.push_str(&format!("    <node id=\"{}\">\n", wire.id.as_str()));
+        output.push_str(&data_line("title", &wire.title));
+        if let Some(ref desc) = wire.description {
+            output.push_str(&data_line("description", desc));
+        }
+        output.push_str(&data_line("status", wire.status.as_str()));
+        output.push_str(&data_line("priority", &wire.priority.to_string()));
+        if !wire.tags.is_empty() {
+            let tags: Vec<&str> = wire.tags.iter().map(|t| t.as_str()).collect();
+            output.push_str(&data_line("tags", &tags.join(",")));
+        }
+        if let Some(ref reason) = wire.cancel_reason {
+            output.push_str(&data_line("cancel_reason", &reason.to_string()));
+        }
+        if let Some(ref reason) = wire.blocked_reason {
+            output.push_str(&data_line("blocked_reason", reason));
+        }
+        if let Some(ref assignee) = wire.assignee {
+            output.push_str(&data_line("assignee", assignee.as_str()));
+        }
+        output.push_str(&data_line("created_at", &wire.created_at_ms.to_string()));
+        output.push_str(&data_line("updated_at", &wire.updated_at_ms.to_string()));
+        output.push_str("    </node>\n");
+    }
+
+    // Edges point from the dependent wire to the wire it depends on,
+    // matching the direction used by `wr graph`. IDs come from the
+    // endpoints, so adding or removing an edge doesn't renumber the rest.
+    for dep in deps {
+        output.push_str(&format!(
+            "    <edge id=\"{}-{}\" source=\"{}\" target=\"{}\"/>\n",
+            dep.wire_id.as_str(),
+            dep.depends_on.as_str(),
+            dep.wire_id.as_str(),
+            dep.depends_on.as_str()
+        ));
+    }
+
+    output.push_str("  </graph>\n");
+    output.push_str("</graphml>\n");
+
+    output
+}
+
+fn data_line(key: &str, value: &str) -> String {
+    format!("      <data key=\"{}\">{}</data>\n", key, escape_xml(value))
+}
@@ -1,7 +1,12 @@
-use anyhow::{anyhow, Result};
-use serde::Serialize;
-use wr::db;
-use wr::models::WireId;
+use anyhow::{anyhow, Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use wr::format::{escape_dot, escape_mermaid, print_json, print_jsonl, print_result};
+use wr::models::{Dependency, DependencyKind, LayoutHints, WireId};
+use wr::{config, db};
 
 #[derive(Serialize)]
 struct GraphNode {
@@ -9,12 +14,32 @@ struct GraphNode {
     title: String,
     status: String,
     priority: i32,
+    #[serde(skip_serializing_if = "LayoutHints::is_empty")]
+    layout: LayoutHints,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 struct GraphEdge {
     from: WireId,
     to: WireId,
+    #[serde(default, skip_serializing_if = "DependencyKind::is_blocking")]
+    kind: DependencyKind,
+}
+
+impl From<&Dependency> for GraphEdge {
+    fn from(dep: &Dependency) -> Self {
+        GraphEdge {
+            from: dep.wire_id.clone(),
+            to: dep.depends_on.clone(),
+            kind: dep.kind,
+        }
+    }
+}
+
+/// Edge list accepted by `wr graph apply`; nodes are ignored if present
+#[derive(Deserialize)]
+struct EdgeList {
+    edges: Vec<GraphEdge>,
 }
 
 #[derive(Serialize)]
@@ -23,71 +48,358 @@ struct Graph {
     edges: Vec<GraphEdge>,
 }
 
-pub fn run(format: Option<&str>) -> Result<()> {
+pub fn run(format: Option<&str>, include_archived: bool, show_hidden: bool) -> Result<()> {
     let conn = db::open()?;
+    let hidden = hidden_ids(&conn, include_archived, show_hidden)?;
 
-    // Get all wires as nodes
-    let wires = db::list_wires(&conn, None)?;
+    // Get all wires as nodes, sorted by ID like `wr export` so saved
+    // graphs diff cleanly
+    let mut wires = db::list_wires(&conn, &db::ListFilter::default())?;
+    wires.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut layout = db::list_layout_hints(&conn)?;
     let nodes: Vec<GraphNode> = wires
         .iter()
+        .filter(|w| !hidden.contains(&w.id))
         .map(|w| GraphNode {
             id: w.id.clone(),
             title: w.title.clone(),
             status: w.status.as_str().to_string(),
             priority: w.priority,
+            layout: layout.remove(&w.id).unwrap_or_default(),
         })
         .collect();
 
     // Get all dependencies as edges
-    let mut stmt = conn.prepare("SELECT wire_id, depends_on FROM dependencies")?;
-    let edges: Vec<GraphEdge> = stmt
-        .query_map([], |row| {
-            Ok(GraphEdge {
-                from: row.get(0)?,
-                to: row.get(1)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+    let edges: Vec<GraphEdge> = db::list_dependencies(&conn)?
+        .into_iter()
+        .filter(|d| !hidden.contains(&d.wire_id) && !hidden.contains(&d.depends_on))
+        .map(|d| GraphEdge::from(&d))
+        .collect();
 
     let graph = Graph { nodes, edges };
 
     match format {
         Some("dot") => print_dot(&graph),
-        Some("json") | None => println!("{}", serde_json::to_string(&graph)?),
+        Some("mermaid") => print_mermaid(&graph),
+        Some("json") | None => print_json(&graph)?,
+        Some("jsonl") => print_jsonl(&graph_lines(&graph)?)?,
+        Some("adjacency") => print_json(&adjacency(&graph))?,
         Some("table") => {
             return Err(anyhow!(
-                "graph does not support table format. Use: json, dot"
+                "graph does not support table format. Use: json, dot, adjacency, jsonl, mermaid"
+            ))
+        }
+        Some(other) => {
+            return Err(anyhow!(
+                "Invalid format: {}. Valid: json, dot, adjacency, jsonl, mermaid",
+                other
             ))
         }
-        Some(other) => return Err(anyhow!("Invalid format: {}. Valid: json, dot", other)),
     }
 
     Ok(())
 }
 
+/// The graph as one record per node, then one per edge, each tagged with
+/// `"type": "node"` or `"type": "edge"`
+fn graph_lines(graph: &Graph) -> Result<Vec<serde_json::Value>> {
+    fn tagged<T: Serialize>(kind: &str, item: &T) -> Result<serde_json::Value> {
+        let mut value = serde_json::to_value(item)?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert("type".to_string(), json!(kind));
+        }
+        Ok(value)
+    }
+
+    let nodes = graph.nodes.iter().map(|node| tagged("node", node));
+    let edges = graph.edges.iter().map(|edge| tagged("edge", edge));
+    nodes.chain(edges).collect()
+}
+
+/// Reconcile the dependency table with a graph read from stdin.
+///
+/// Accepts either the node/edge object produced by `wr graph` or the
+/// adjacency map produced by `wr graph --format adjacency`. Only blocking
+/// edges are reconciled; edges of other kinds are ignored.
+pub fn apply(dry_run: bool) -> Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read graph from stdin")?;
+
+    let mut desired: Vec<Dependency> = parse_edges(&input)?
+        .into_iter()
+        .filter(|d| d.kind.is_blocking())
+        .collect();
+
+    let conn = db::open()?;
+
+    // `wr graph` leaves out archived and hidden wires, so keep their edges
+    // rather than treating them as deleted
+    let hidden = hidden_ids(&conn, false, false)?;
+    desired.extend(
+        db::list_dependencies(&conn)?
+            .into_iter()
+            .filter(|d| d.kind.is_blocking())
+            .filter(|d| hidden.contains(&d.wire_id) || hidden.contains(&d.depends_on)),
+    );
+
+    let changes = db::sync_dependencies(&conn, &desired, dry_run)?;
+
+    let added: Vec<GraphEdge> = changes.added.iter().map(GraphEdge::from).collect();
+    let removed: Vec<GraphEdge> = changes.removed.iter().map(GraphEdge::from).collect();
+
+    let output = json!({
+        "added": added,
+        "removed": removed,
+        "dry_run": dry_run
+    });
+
+    if dry_run {
+        print_json(&output)?;
+    } else {
+        print_result(&output)?;
+    }
+    Ok(())
+}
+
+/// Wires `wr graph` leaves out: archived ones, and those whose status the
+/// config hides
+fn hidden_ids(
+    conn: &Connection,
+    include_archived: bool,
+    show_hidden: bool,
+) -> Result<HashSet<WireId>> {
+    let mut hidden = if include_archived {
+        HashSet::new()
+    } else {
+        db::list_archived_ids(conn)?
+    };
+
+    if !show_hidden {
+        let config = config::load()?;
+        hidden.extend(
+            db::list_wires(conn, &db::ListFilter::default())?
+                .into_iter()
+                .filter(|w| config.hides(w.status))
+                .map(|w| w.id),
+        );
+    }
+
+    Ok(hidden)
+}
+
+fn parse_edges(input: &str) -> Result<Vec<Dependency>> {
+    let value: serde_json::Value = serde_json::from_str(input).context("Invalid graph JSON")?;
+
+    let edges = if value.get("edges").is_some() {
+        let list: EdgeList = serde_json::from_value(value).context("Invalid graph JSON")?;
+        list.edges
+    } else {
+        let map: BTreeMap<WireId, Vec<WireId>> =
+            serde_json::from_value(value).context("Invalid adjacency JSON")?;
+        map.into_iter()
+            .flat_map(|(from, targets)| {
+                targets.into_iter().map(move |to| GraphEdge {
+                    from: from.clone(),
+                    to,
+                    kind: DependencyKind::Blocks,
+                })
+            })
+            .collect()
+    };
+
+    Ok(edges
+        .into_iter()
+        .map(|e| Dependency {
+            wire_id: e.from,
+            depends_on: e.to,
+            kind: e.kind,
+        })
+        .collect())
+}
+
+/// Build a compact adjacency list: each wire ID maps to the IDs it depends on.
+///
+/// Only blocking edges are listed, since the map has no room for a kind.
+/// Every node gets an entry (possibly empty) so isolated wires are not lost.
+/// Keys are sorted to keep the output stable between runs.
+fn adjacency(graph: &Graph) -> BTreeMap<&str, Vec<&str>> {
+    let mut adjacency: BTreeMap<&str, Vec<&str>> = graph
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), Vec::new()))
+        .collect();
+
+    for edge in graph.edges.iter().filter(|e| e.kind.is_blocking()) {
+        adjacency
+            .entry(edge.from.as_str())
+            .or_default()
+            .push(edge.to.as_str());
+    }
+
+    for targets in adjacency.values_mut() {
+        targets.sort_unstable();
+    }
+
+    adjacency
+}
+
 fn print_dot(graph: &Graph) {
     println!("digraph wires {{");
+    println!("    charset=\"UTF-8\";");
     println!("    rankdir=LR;");
     println!("    node [shape=box];");
 
-    for node in &graph.nodes {
-        // Escape quotes in title for DOT format
-        let escaped_title = node.title.replace('"', "\\\"");
-        println!(
-            "    \"{}\" [label=\"{}\\n{}\"];",
+    let line = |node: &GraphNode| {
+        let fill = node
+            .layout
+            .color
+            .as_ref()
+            .map(|color| format!(", style=filled, fillcolor=\"{}\"", color))
+            .unwrap_or_default();
+        format!(
+            "\"{}\" [label=\"{}\\n{}\"{}];",
             node.id.as_str(),
-            escaped_title,
-            node.status
-        );
+            escape_dot(&node.title),
+            node.status,
+            fill
+        )
+    };
+
+    let clusters = group_by(&graph.nodes, |n| n.layout.cluster.as_deref());
+    for node in graph.nodes.iter().filter(|n| n.layout.cluster.is_none()) {
+        println!("    {}", line(node));
+    }
+    for (i, (label, members)) in clusters.iter().enumerate() {
+        println!("    subgraph \"cluster_{}\" {{", i);
+        println!("        label=\"{}\";", escape_dot(label));
+        for member in members {
+            println!("        {}", line(member));
+        }
+        println!("    }}");
+    }
+
+    for members in group_by(&graph.nodes, |n| n.layout.rank.as_deref()).values() {
+        let ids: Vec<String> = members
+            .iter()
+            .map(|n| format!("\"{}\";", n.id.as_str()))
+            .collect();
+        println!("    {{ rank=same; {} }}", ids.join(" "));
     }
 
+    // Non-blocking edges are dashed and labeled with their kind
     for edge in &graph.edges {
+        let style = if edge.kind.is_blocking() {
+            String::new()
+        } else {
+            format!(" [label=\"{}\", style=dashed]", edge.kind)
+        };
         println!(
-            "    \"{}\" -> \"{}\";",
+            "    \"{}\" -> \"{}\"{};",
             edge.from.as_str(),
-            edge.to.as_str()
+            edge.to.as_str(),
+            style
         );
     }
 
     println!("}}");
 }
+
+/// Prints a Mermaid flowchart. Clusters become subgraphs and colors node
+/// styles; Mermaid has no rank constraints, so ranks are left out.
+fn print_mermaid(graph: &Graph) {
+    println!("flowchart LR");
+
+    let line = |node: &GraphNode| {
+        format!(
+            "{}[\"{}<br/>{}\"]",
+            node.id.as_str(),
+            escape_mermaid(&node.title),
+            node.status
+        )
+    };
+
+    let clusters = group_by(&graph.nodes, |n| n.layout.cluster.as_deref());
+    for node in graph.nodes.iter().filter(|n| n.layout.cluster.is_none()) {
+        println!("    {}", line(node));
+    }
+    for (i, (label, members)) in clusters.iter().enumerate() {
+        println!("    subgraph cluster_{} [\"{}\"]", i, escape_mermaid(label));
+        for member in members {
+            println!("        {}", line(member));
+        }
+        println!("    end");
+    }
+
+    for edge in &graph.edges {
+        if edge.kind.is_blocking() {
+            println!("    {} --> {}", edge.from.as_str(), edge.to.as_str());
+        } else {
+            println!(
+                "    {} -. {} .-> {}",
+                edge.from.as_str(),
+                edge.kind,
+                edge.to.as_str()
+            );
+        }
+    }
+
+    for node in &graph.nodes {
+        if let Some(color) = &node.layout.color {
+            println!("    style {} fill:{}", node.id.as_str(), color);
+        }
+    }
+}
+
+/// Nodes grouped by a layout hint, groups ordered by name; nodes without
+/// the hint are left out
+fn group_by(
+    nodes: &[GraphNode],
+    hint: impl Fn(&GraphNode) -> Option<&str>,
+) -> BTreeMap<&str, Vec<&GraphNode>> {
+    let mut groups: BTreeMap<&str, Vec<&GraphNode>> = BTreeMap::new();
+    for node in nodes {
+        if let Some(name) = hint(node) {
+            groups.entry(name).or_default().push(node);
+        }
+    }
+    groups
+}
+
+/// Sets or clears how a wire is drawn by `wr graph -f dot` and `-f mermaid`.
+///
+/// Hints not given keep their current value; `clear` removes all of them
+/// first.
+pub fn layout(
+    wire_id: &str,
+    rank: Option<String>,
+    cluster: Option<String>,
+    color: Option<String>,
+    clear: bool,
+) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let mut hints = if clear {
+        LayoutHints::default()
+    } else {
+        db::get_layout_hints(&conn, wire_id)?
+    };
+    let set = |value: Option<String>, current: &mut Option<String>| {
+        if let Some(value) = value {
+            *current = Some(value).filter(|v| !v.is_empty());
+        }
+    };
+    set(rank, &mut hints.rank);
+    set(cluster, &mut hints.cluster);
+    set(color, &mut hints.color);
+
+    db::set_layout_hints(&conn, wire_id, &hints)?;
+
+    print_result(&json!({
+        "id": wire_id,
+        "layout": hints
+    }))?;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use wr::db;
+use wr::format::print_result;
+use wr::models::Agent;
+
+pub fn run(wire_id: &str, agent: Option<&Agent>, note: Option<&str>) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let handoff = db::handoff(&conn, wire_id, agent, note)?;
+
+    print_result(&handoff)?;
+    Ok(())
+}
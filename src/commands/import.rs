@@ -0,0 +1,103 @@
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::Path;
+use wr::db;
+use wr::format::print_result;
+use wr::models::{ConflictSide, ImportStrategy, Snapshot, WireConflict};
+
+/// Load a snapshot written by `wr export --format json`.
+///
+/// `path` may be `-` to read from stdin. With a `strategy`, wires that
+/// already exist with different content are settled by it and reported;
+/// without one, any existing ID fails the import. With `verify`, the file's
+/// bytes must have that SHA-256 digest; with `check_manifest`, the snapshot
+/// must match the manifest written by `wr export --manifest`.
+pub fn run(
+    path: &Path,
+    replace: bool,
+    strategy: Option<ImportStrategy>,
+    verify: Option<&str>,
+    check_manifest: bool,
+) -> Result<()> {
+    if strategy == Some(ImportStrategy::Interactive)
+        && (path == Path::new("-") || !io::stdin().is_terminal())
+    {
+        bail!("--strategy interactive needs a terminal and a snapshot file (not -)");
+    }
+
+    let input = if path == Path::new("-") {
+        let mut input = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut input)
+            .context("Failed to read snapshot from stdin")?;
+        input
+    } else {
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?
+    };
+
+    if let Some(digest) = verify {
+        Snapshot::verify_digest(&input, digest)?;
+    }
+    let snapshot: Snapshot = serde_json::from_slice(&input).context("Invalid snapshot JSON")?;
+    if check_manifest {
+        snapshot.check_manifest()?;
+    }
+
+    let conn = db::open()?;
+    let conflicts = match strategy {
+        None => {
+            db::import_snapshot(&conn, &snapshot, replace)?;
+            Vec::new()
+        }
+        Some(ImportStrategy::Interactive) => {
+            let mut answers = HashMap::new();
+            for conflict in db::snapshot_conflicts(&conn, &snapshot)? {
+                answers.insert(conflict.id.clone(), ask(&conflict)?);
+            }
+            db::merge_snapshot(&conn, &snapshot, |c| answers.get(&c.id).copied())?
+        }
+        Some(strategy) => db::merge_snapshot(&conn, &snapshot, |c| c.resolve(strategy))?,
+    };
+
+    let output = json!({
+        "wires": snapshot.wires.len(),
+        "dependencies": snapshot.dependencies.len(),
+        "replaced": replace,
+        "verified": verify.is_some(),
+        "manifest_checked": check_manifest,
+        "conflicts": conflicts
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+/// Shows a conflict on stderr and asks which copy to keep
+fn ask(conflict: &WireConflict) -> Result<ConflictSide> {
+    let mut stderr = io::stderr();
+    writeln!(stderr, "Wire {} differs:", conflict.id)?;
+    for field in &conflict.fields {
+        writeln!(
+            stderr,
+            "  {}: ours {} / theirs {}",
+            field.field, field.local, field.incoming
+        )?;
+    }
+
+    let mut stdin = io::stdin().lock();
+    loop {
+        write!(stderr, "Keep [o]urs or [t]heirs? ")?;
+        stderr.flush()?;
+        let mut answer = String::new();
+        if stdin.read_line(&mut answer)? == 0 {
+            bail!("No answer for wire {}; nothing was imported", conflict.id);
+        }
+        match answer.trim() {
+            "o" | "ours" => return Ok(ConflictSide::Ours),
+            "t" | "theirs" => return Ok(ConflictSide::Theirs),
+            _ => {}
+        }
+    }
+}
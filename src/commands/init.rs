@@ -1,18 +1,64 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde_json::json;
-use std::env;
+use std::fs;
+use std::path::Path;
 use wr::db;
+use wr::format::print_result;
+use wr::plan::Plan;
 
-pub fn run() -> Result<()> {
-    let current_dir = env::current_dir()?;
-    db::init(&current_dir)?;
+pub fn run(force: bool, upgrade: bool, template: Option<&str>) -> Result<()> {
+    let root = db::base_dir()?;
+    let wires_path = root.join(".wires").join("wires.db");
 
-    let wires_path = current_dir.join(".wires").join("wires.db");
-    let output = json!({
-        "status": "initialized",
-        "path": wires_path.display().to_string()
-    });
+    // Load the plan up front so a bad template leaves nothing behind
+    let plan = template.map(load_plan).transpose()?;
+    if let Some(plan) = &plan {
+        plan.resolve()?;
+    }
 
-    println!("{}", serde_json::to_string(&output)?);
+    let mut output = if upgrade {
+        let (from, to) = db::upgrade(&root)?;
+        json!({
+            "status": if from == to { "up_to_date" } else { "upgraded" },
+            "path": wires_path.display().to_string(),
+            "from_version": from,
+            "to_version": to
+        })
+    } else if force {
+        let backup = db::reinit(&root)?;
+        json!({
+            "status": "reinitialized",
+            "path": wires_path.display().to_string(),
+            "backup": backup.map(|b| b.display().to_string())
+        })
+    } else {
+        db::init(&root)?;
+        json!({
+            "status": "initialized",
+            "path": wires_path.display().to_string()
+        })
+    };
+
+    if let Some(plan) = plan {
+        let conn = db::open()?;
+        let wires = db::apply_plan(&conn, &plan)?;
+        output["created"] = json!(wires
+            .iter()
+            .map(|w| json!({"id": w.id, "title": w.title}))
+            .collect::<Vec<_>>());
+    }
+
+    print_result(&output)?;
     Ok(())
 }
+
+/// Loads `template` as a plan file if it names one, otherwise as a built-in template.
+fn load_plan(template: &str) -> Result<Plan> {
+    let path = Path::new(template);
+    if path.is_file() {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read plan file {}", path.display()))?;
+        return Ok(Plan::from_json(&source)?);
+    }
+    Ok(Plan::builtin(template)?)
+}
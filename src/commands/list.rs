@@ -1,23 +1,150 @@
 use anyhow::Result;
+use clap::Args;
+use rusqlite::Connection;
+use std::time::Duration;
 use wr::{
-    db,
-    format::{format_wire_table, print_json, Format},
-    models::Status,
+    config, db,
+    format::{
+        format_wire_columns_table, format_wire_delimited, format_wire_markdown, format_wire_table,
+        print_json_as, Format, ListFormat,
+    },
+    models::{Agent, Consumer, PriorityArg, Status, Tag, WireWithDeps},
+    template::{Columns, OutputTemplate},
+    time::parse_duration,
 };
 
-pub fn run(status_filter: Option<Status>, format: Option<Format>) -> Result<()> {
-    let format = Format::resolve(format);
+/// Wire selection flags shared by `wr list` and bulk `wr tag`
+#[derive(Debug, Clone, Default, Args)]
+pub struct Filters {
+    /// Filter by status (todo, in-progress, paused, blocked, done, cancelled);
+    /// repeat to allow several
+    #[arg(short, long, value_enum)]
+    pub status: Vec<Status>,
+    /// Only wires with at least this priority (a number or level name)
+    #[arg(long, allow_negative_numbers = true)]
+    pub priority_min: Option<PriorityArg>,
+    /// Only wires whose title contains this text, ignoring case
+    #[arg(long)]
+    pub title_contains: Option<String>,
+    /// Only wires with this tag
+    #[arg(short, long)]
+    pub tag: Option<Tag>,
+    /// Only wires assigned to this agent
+    #[arg(long)]
+    pub assignee: Option<Agent>,
+    /// Only wires not updated for at least this long (e.g. 7d, 12h)
+    #[arg(long, value_parser = parse_duration)]
+    pub older_than: Option<Duration>,
+    /// Only wires nothing depends on (the top of the plan)
+    #[arg(long)]
+    pub top_level: bool,
+    /// Only wires that depend on nothing (where work can start)
+    #[arg(long)]
+    pub leaves: bool,
+    /// Include archived wires
+    #[arg(long)]
+    pub include_archived: bool,
+    /// Include statuses hidden by `hide_statuses` in the config
+    #[arg(long)]
+    pub show_hidden: bool,
+}
+
+impl Filters {
+    /// Whether any flag narrows the selection
+    pub fn is_empty(&self) -> bool {
+        self.status.is_empty()
+            && self.priority_min.is_none()
+            && self.title_contains.is_none()
+            && self.tag.is_none()
+            && self.assignee.is_none()
+            && self.older_than.is_none()
+            && !self.top_level
+            && !self.leaves
+    }
+
+    /// The wires these flags select
+    pub fn select(&self, conn: &Connection) -> Result<Vec<WireWithDeps>> {
+        db::filter_wires(conn, &self.to_filter()?)
+    }
+
+    fn to_filter(&self) -> Result<db::WireFilter> {
+        let config = config::load()?;
+        let hidden_statuses = if self.show_hidden {
+            Vec::new()
+        } else {
+            config.hide_statuses
+        };
+        let priority_min = self
+            .priority_min
+            .as_ref()
+            .map(|p| config.priorities.resolve(p))
+            .transpose()?;
+        Ok(db::WireFilter {
+            statuses: self.status.clone(),
+            priority_min,
+            title_contains: self.title_contains.clone(),
+            tag: self.tag.clone(),
+            assignee: self.assignee.clone(),
+            older_than: self
+                .older_than
+                .map(|d| i64::try_from(d.as_secs()))
+                .transpose()?,
+            top_level: self.top_level,
+            leaves: self.leaves,
+            include_archived: self.include_archived,
+            hidden_statuses,
+        })
+    }
+}
+
+/// Lists the wires `filters` select; with a `cursor`, only those changed
+/// since that cursor's last listing.
+pub fn run(
+    filters: &Filters,
+    cursor: Option<&Consumer>,
+    template: Option<&OutputTemplate>,
+    columns: Option<&Columns>,
+    format: Option<ListFormat>,
+) -> Result<()> {
+    let format = ListFormat::resolve(format);
 
     let conn = db::open()?;
-    let wires_with_deps = db::list_wires_with_deps(&conn, status_filter)?;
+    let wires_with_deps = match cursor {
+        Some(cursor) => db::filter_wires_since_last(&conn, &filters.to_filter()?, cursor)?,
+        None => filters.select(&conn)?,
+    };
+
+    if let Some(template) = template {
+        for wd in &wires_with_deps {
+            println!("{}", template.render(&wd.wire));
+        }
+        return Ok(());
+    }
+
+    let Some(general) = format.general() else {
+        let wires = wires_with_deps.iter().map(|wd| &wd.wire);
+        let delimiter = format.delimiter().unwrap_or(b',');
+        print!(
+            "{}",
+            format_wire_delimited(wires, &columns.cloned().unwrap_or_default(), delimiter)?
+        );
+        return Ok(());
+    };
 
-    match format {
-        Format::Json => {
+    match general {
+        Format::Json | Format::Jsonl => {
             // For JSON, extract just the wires to maintain backward compatibility
             let wires: Vec<_> = wires_with_deps.iter().map(|wd| &wd.wire).collect();
-            print_json(&wires)?
+            print_json_as(&wires, general)?
         }
-        Format::Table => print!("{}", format_wire_table(&wires_with_deps)),
+        Format::Table => match columns {
+            Some(columns) => {
+                let wires = wires_with_deps.iter().map(|wd| &wd.wire);
+                print!("{}", format_wire_columns_table(wires, columns))
+            }
+            None => print!("{}", format_wire_table(&wires_with_deps)),
+        },
+        Format::Markdown => print!("{}", format_wire_markdown(&wires_with_deps)),
     }
 
     Ok(())
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::io::{self, IsTerminal};
+use wr::{
+    db::{self, EventFilter},
+    format::{format_event_table, print_json},
+};
+
+/// Formats supported by `wr log`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// A single JSON array
+    Json,
+    /// One JSON object per line, for log pipelines
+    Ndjson,
+    /// Human-readable lines
+    Table,
+}
+
+pub fn run(since: Option<i64>, wire: Option<&str>, format: Option<LogFormat>) -> Result<()> {
+    let format = format.unwrap_or_else(|| {
+        if io::stdout().is_terminal() {
+            LogFormat::Table
+        } else {
+            LogFormat::Json
+        }
+    });
+
+    let conn = db::open()?;
+    let wire_id = wire.map(|w| db::resolve_wire_id(&conn, w)).transpose()?;
+
+    let filter = EventFilter {
+        since,
+        wire_id,
+        ..Default::default()
+    };
+    let events = db::list_events(&conn, &filter)?;
+
+    match format {
+        LogFormat::Json => print_json(&events)?,
+        LogFormat::Ndjson => {
+            for event in &events {
+                print_json(event)?;
+            }
+        }
+        LogFormat::Table => print!("{}", format_event_table(&events)),
+    }
+
+    Ok(())
+}
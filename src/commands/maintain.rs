@@ -0,0 +1,18 @@
+use anyhow::Result;
+use wr::config;
+use wr::db;
+use wr::format::{print_json, print_result};
+
+pub fn run(dry_run: bool) -> Result<()> {
+    let conn = db::open()?;
+    let policy = config::load()?.retention;
+
+    let report = db::apply_retention(&conn, &policy, dry_run)?;
+
+    if dry_run {
+        print_json(&report)?;
+    } else {
+        print_result(&report)?;
+    }
+    Ok(())
+}
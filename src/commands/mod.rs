@@ -1,13 +1,60 @@
+pub mod agent_help;
+pub mod archive;
+pub mod assign;
+pub mod block;
+pub mod blocked;
+pub mod board;
+pub mod bump;
+pub mod burndown;
 pub mod cancel;
+pub mod capabilities;
+pub mod claim;
+pub mod completions;
+pub mod config;
+pub mod critical_path;
 pub mod dep;
+pub mod devtool;
+pub mod doc;
 pub mod done;
+pub mod dups;
+pub mod edit;
+pub mod epic;
+pub mod events;
+pub mod export;
 pub mod graph;
+pub mod handoff;
+pub mod import;
 pub mod init;
+pub mod link;
 pub mod list;
+pub mod log;
+pub mod maintain;
+pub mod multi;
 pub mod new;
+pub mod next;
+pub mod outbox;
+pub mod pause;
+pub mod ping;
+pub mod plan;
+pub mod precondition;
 pub mod ready;
+pub mod renumber_priorities;
+pub mod report;
+pub mod reserve;
+pub mod rewire;
 pub mod rm;
+pub mod search;
 pub mod show;
 pub mod start;
+pub mod stats;
+pub mod supersede;
+pub mod tag;
+pub mod touch;
+pub mod tree;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod undep;
+pub mod undo;
+pub mod unlink;
 pub mod update;
+pub mod why;
@@ -0,0 +1,110 @@
+use anyhow::{anyhow, Result};
+use owo_colors::{OwoColorize, Stream};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use wr::{
+    db,
+    format::{format_wire_markdown, format_wire_table, print_json_as, Format},
+    models::{RepoWire, Status, Tag, WireWithDeps},
+};
+
+pub fn list(
+    roots: &[PathBuf],
+    status_filter: Option<Status>,
+    tag: Option<&Tag>,
+    format: Option<Format>,
+) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let filter = db::ListFilter {
+        statuses: status_filter.into_iter().collect(),
+        ..Default::default()
+    };
+    let mut repos = Vec::with_capacity(roots.len());
+    for root in roots {
+        let conn = open_repo(root)?;
+        let mut wires = db::list_wires_with_deps(&conn, &filter)?;
+        if let Some(tag) = tag {
+            wires.retain(|wd| wd.wire.tags.contains(tag));
+        }
+        let archived = db::list_archived_ids(&conn)?;
+        wires.retain(|wd| !archived.contains(&wd.wire.id));
+        repos.push((root.as_path(), wires));
+    }
+
+    if format.is_json() {
+        let mut merged = merge(&repos);
+        merged.sort_by_key(|rw| std::cmp::Reverse(rw.wire.created_at_ms));
+        return print_json_as(&merged, format);
+    }
+
+    print_sections(&repos, format);
+    Ok(())
+}
+
+pub fn ready(roots: &[PathBuf], format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let mut repos = Vec::with_capacity(roots.len());
+    for root in roots {
+        let conn = open_repo(root)?;
+        let wires: Vec<WireWithDeps> = db::get_ready_wires(&conn)?
+            .into_iter()
+            .map(WireWithDeps::from)
+            .collect();
+        repos.push((root.as_path(), wires));
+    }
+
+    if format.is_json() {
+        let mut merged = merge(&repos);
+        merged.sort_by(|a, b| {
+            b.wire
+                .priority
+                .cmp(&a.wire.priority)
+                .then(a.wire.created_at_ms.cmp(&b.wire.created_at_ms))
+        });
+        return print_json_as(&merged, format);
+    }
+
+    print_sections(&repos, format);
+    Ok(())
+}
+
+fn open_repo(root: &Path) -> Result<Connection> {
+    db::open_at(root).map_err(|e| anyhow!("{}: {}", root.display(), e))
+}
+
+fn merge(repos: &[(&Path, Vec<WireWithDeps>)]) -> Vec<RepoWire> {
+    repos
+        .iter()
+        .flat_map(|(root, wires)| {
+            wires.iter().map(|wd| RepoWire {
+                repo: root.display().to_string(),
+                wire: wd.wire.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Prints one heading and table (or checklist) per repository.
+fn print_sections(repos: &[(&Path, Vec<WireWithDeps>)], format: Format) {
+    for (i, (root, wires)) in repos.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match format {
+            Format::Markdown => {
+                println!("## {}\n", root.display());
+                print!("{}", format_wire_markdown(wires));
+            }
+            _ => {
+                let heading = root.display().to_string();
+                println!(
+                    "{}",
+                    heading.if_supports_color(Stream::Stdout, |text| text.bold())
+                );
+                print!("{}", format_wire_table(wires));
+            }
+        }
+    }
+}
@@ -1,23 +1,137 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde_json::json;
+use std::io::{self, Read};
+use wr::config;
 use wr::db;
-use wr::models::Wire;
+use wr::dups::similar_to;
+use wr::format::{is_quiet, print_json, warn};
+use wr::models::{IdempotencyKey, PriorityArg, Wire, WireId, WireWithDeps};
+use wr::plan::Plan;
+use wr::template::{self, Vars};
+
+pub fn run(
+    title: &str,
+    description: Option<&str>,
+    priority: Option<&PriorityArg>,
+    vars: Vars,
+    id: Option<&WireId>,
+    idempotency_key: Option<&IdempotencyKey>,
+    full: bool,
+) -> Result<()> {
+    // Placeholders are only expanded when variables are given, so existing
+    // titles containing literal braces keep working unchanged
+    let (title, description) = if vars.is_empty() {
+        (title.to_string(), description.map(str::to_string))
+    } else {
+        (
+            template::expand(title, &vars)?,
+            description
+                .map(|d| template::expand(d, &vars))
+                .transpose()?,
+        )
+    };
+    let config = config::load()?;
+    let priority = match priority {
+        Some(priority) => config.priorities.resolve(priority)?,
+        None => config.default_priority,
+    };
 
-pub fn run(title: &str, description: Option<&str>, priority: i32) -> Result<()> {
     let conn = db::open()?;
 
-    let wire = Wire::new(title, description, priority)?;
+    let output = db::with_idempotency_key(&conn, idempotency_key, "new", || {
+        let mut wire = Wire::new(&title, description.as_deref(), priority)?;
+        wire.id = config.id_scheme.generate(&wire.title);
+
+        match id {
+            Some(id) => {
+                wire.id = id.clone();
+                db::insert_reserved_wire(&conn, &mut wire)?;
+            }
+            None => db::insert_wire(&conn, &mut wire)?,
+        }
+
+        if full {
+            return Ok(serde_json::to_value(WireWithDeps::from(wire))?);
+        }
+
+        Ok(json!({
+            "id": wire.id,
+            "title": wire.title,
+            "status": wire.status,
+            "priority": wire.priority,
+            "created_at": wire.created_at_ms
+        }))
+    })?;
 
-    db::insert_wire(&conn, &wire)?;
+    if is_quiet() {
+        println!("{}", output["id"].as_str().unwrap_or_default());
+    } else {
+        print_json(&output)?;
+    }
+
+    if config.duplicates.warn_on_new {
+        let id = output["id"].as_str().unwrap_or_default();
+        warn_similar(&conn, id, &title, config.duplicates.threshold)?;
+    }
+    Ok(())
+}
+
+/// Warns about open wires other than `id` whose titles are at
+/// least `threshold` percent alike to `title`.
+fn warn_similar(conn: &rusqlite::Connection, id: &str, title: &str, threshold: u8) -> Result<()> {
+    let open: Vec<_> = db::list_wires(conn, &db::ListFilter::default())?
+        .into_iter()
+        .filter(|w| w.status.is_blocking() && w.id.as_str() != id)
+        .collect();
+    for (wire, similarity) in similar_to(title, &open, threshold) {
+        warn(format_args!(
+            "{}% alike to open wire {}: {}",
+            similarity,
+            wire.id.as_str(),
+            wire.title
+        ));
+    }
+    Ok(())
+}
+
+/// Creates every wire described by JSON Lines on stdin, all or nothing.
+pub fn batch(idempotency_key: Option<&IdempotencyKey>) -> Result<()> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read wires from stdin")?;
+    let plan = Plan::from_jsonl(&input)?;
+
+    let conn = db::open()?;
 
-    let output = json!({
-        "id": wire.id,
-        "title": wire.title,
-        "status": wire.status,
-        "priority": wire.priority,
-        "created_at": wire.created_at
-    });
+    let output = db::with_idempotency_key(&conn, idempotency_key, "new --stdin", || {
+        let wires = db::apply_plan(&conn, &plan)?;
+        let created: Vec<_> = wires
+            .iter()
+            .zip(&plan.wires)
+            .map(|(wire, item)| {
+                let mut created = json!({
+                    "id": wire.id,
+                    "title": wire.title,
+                    "status": wire.status,
+                    "priority": wire.priority,
+                    "created_at": wire.created_at_ms
+                });
+                if let Some(key) = &item.key {
+                    created["key"] = json!(key);
+                }
+                created
+            })
+            .collect();
+        Ok(json!({ "created": created }))
+    })?;
 
-    println!("{}", serde_json::to_string(&output)?);
+    if is_quiet() {
+        for created in output["created"].as_array().into_iter().flatten() {
+            println!("{}", created["id"].as_str().unwrap_or_default());
+        }
+    } else {
+        print_json(&output)?;
+    }
     Ok(())
 }
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use serde_json::json;
+use std::time::Duration;
+use wr::format::print_json;
+use wr::models::Agent;
+use wr::{config, db};
+
+pub fn run(agent: Option<&Agent>, ttl: Duration) -> Result<()> {
+    let config = config::load()?;
+    let conn = db::open()?;
+
+    let ttl_secs = i64::try_from(ttl.as_secs())?;
+    let Some(next) = db::next_wire(&conn, agent, ttl_secs, &config)? else {
+        // Nothing ready is not an error: agents loop until they see null
+        print_json(&serde_json::Value::Null)?;
+        return Ok(());
+    };
+
+    let wire = db::get_wire_with_deps(&conn, next.wire.id.as_str())?;
+    let mut output = serde_json::to_value(&wire)?;
+    if let Some(lease) = next.lease {
+        output["lease"] = json!({ "agent": lease.agent, "expires_at": lease.expires_at });
+    }
+    if !next.paused.is_empty() {
+        output["paused"] = json!(next
+            .paused
+            .iter()
+            .map(|w| json!({ "id": w.id, "title": w.title }))
+            .collect::<Vec<_>>());
+    }
+
+    print_json(&output)?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::{
+    db,
+    format::{print_json, print_json_as, print_result, Format},
+    models::Consumer,
+};
+
+pub fn pull(consumer: &Consumer, limit: usize) -> Result<()> {
+    let conn = db::open()?;
+
+    let events = db::pull_outbox(&conn, consumer, limit)?;
+    let last_seq = events.last().map(|event| event.seq);
+
+    let output = json!({
+        "consumer": consumer,
+        "events": events,
+        "last_seq": last_seq
+    });
+
+    print_json(&output)?;
+    Ok(())
+}
+
+pub fn ack(consumer: &Consumer, seq: i64) -> Result<()> {
+    let conn = db::open()?;
+
+    let acked_seq = db::ack_outbox(&conn, consumer, seq)?;
+
+    let output = json!({
+        "consumer": consumer,
+        "acked_seq": acked_seq
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+pub fn status(format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let cursors = db::list_outbox_cursors(&conn)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&cursors, format)?,
+        _ => {
+            if cursors.is_empty() {
+                println!("No outbox consumers.");
+            }
+            for cursor in &cursors {
+                println!(
+                    "{}  acked {}  ({} pending)",
+                    cursor.consumer, cursor.acked_seq, cursor.pending
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::format::print_result;
+use wr::models::{Agent, WireError};
+use wr::{config, db};
+
+pub fn pause(wire_id: &str, reason: Option<&str>) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    db::pause_wire(&conn, wire_id, reason)?;
+
+    let wire = db::get_wire_with_deps(&conn, wire_id)?;
+
+    let output = json!({
+        "id": wire.wire.id,
+        "status": wire.wire.status,
+        "reason": wire.paused.map(|p| p.reason),
+        "updated_at": wire.wire.updated_at_ms
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+pub fn resume(wire_id: &str, agent: Option<&Agent>) -> Result<()> {
+    let config = config::load()?;
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let paused = db::resume_wire(&conn, wire_id, agent, &config.in_progress)?;
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+
+    let mut output = json!({
+        "id": wire.id,
+        "status": wire.status,
+        "updated_at": wire.updated_at_ms
+    });
+    if !paused.is_empty() {
+        output["paused"] = json!(paused
+            .iter()
+            .map(|w| json!({ "id": w.id, "title": w.title }))
+            .collect::<Vec<_>>());
+    }
+
+    print_result(&output)?;
+    Ok(())
+}
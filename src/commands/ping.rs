@@ -0,0 +1,58 @@
+use anyhow::Result;
+use wr::db;
+use wr::format::{print_json_as, Format};
+use wr::models::WireError;
+
+/// Reports whether the repository is usable, failing with
+/// [`WireError::Unhealthy`] after the report if it isn't.
+pub fn run(lock: bool, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+    let report = db::check_health(lock)?;
+
+    let yes_no = |ok: bool| if ok { "yes" } else { "no" };
+    let schema = report
+        .schema_version
+        .map_or_else(|| "-".to_string(), |v| v.to_string());
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&report, format)?,
+        Format::Table => {
+            println!("ok              {}", yes_no(report.ok));
+            println!(
+                "database        {}",
+                report.database.as_deref().unwrap_or("-")
+            );
+            println!(
+                "schema_version  {} (supported: {})",
+                schema, report.supported_schema_version
+            );
+            println!("writable        {}", yes_no(report.writable));
+            if let Some(lock) = &report.lock {
+                println!(
+                    "lock            {} ({} ms)",
+                    yes_no(lock.acquired),
+                    lock.wait_ms
+                );
+            }
+        }
+        Format::Markdown => {
+            println!("- ok: {}", yes_no(report.ok));
+            println!(
+                "- database: `{}`",
+                report.database.as_deref().unwrap_or("-")
+            );
+            println!(
+                "- schema version: {} (supported: {})",
+                schema, report.supported_schema_version
+            );
+            println!("- writable: {}", yes_no(report.writable));
+            if let Some(lock) = &report.lock {
+                println!("- lock: {} ({} ms)", yes_no(lock.acquired), lock.wait_ms);
+            }
+        }
+    }
+
+    if !report.ok {
+        return Err(WireError::Unhealthy(report.problems).into());
+    }
+    Ok(())
+}
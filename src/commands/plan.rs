@@ -0,0 +1,38 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+use wr::{
+    format::{format_plan_diff_markdown, format_plan_diff_table, print_json_as, Format},
+    models::Snapshot,
+    plan::{Plan, PlanDiff},
+};
+
+/// Shows how the plan (or snapshot) at `new` differs from the one at `old`.
+pub fn compare(old: &Path, new: &Path, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let diff = PlanDiff::between(&load(old)?, &load(new)?)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&diff, format)?,
+        Format::Table => print!("{}", format_plan_diff_table(&diff)),
+        Format::Markdown => print!("{}", format_plan_diff_markdown(&diff)),
+    }
+
+    Ok(())
+}
+
+/// Loads a `wr export` snapshot, a JSON plan or a JSON Lines plan, and
+/// checks that it resolves.
+fn load(path: &Path) -> Result<Plan> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read plan file {}", path.display()))?;
+    let plan = match serde_json::from_str::<Snapshot>(&source) {
+        Ok(snapshot) => Ok(Plan::from(&snapshot)),
+        Err(_) => Plan::from_json(&source)
+            .or_else(|json_error| Plan::from_jsonl(&source).map_err(|_| json_error)),
+    }
+    .and_then(|plan| plan.resolve().map(|_| plan))
+    .map_err(|e| anyhow!("{}: {}", path.display(), e))?;
+    Ok(plan)
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::{print_json, print_result};
+
+pub fn set(wire_id: &str, command: Option<&str>) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    db::set_precondition(&conn, wire_id, command)?;
+
+    let output = json!({
+        "id": wire_id,
+        "precondition": command
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+pub fn check(wire_id: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let command = db::get_precondition(&conn, wire_id)?;
+    let holds = match &command {
+        Some(command) => db::precondition_holds(&conn, command)?,
+        None => true,
+    };
+
+    let output = json!({
+        "id": wire_id,
+        "precondition": command,
+        "holds": holds
+    });
+
+    print_json(&output)?;
+    Ok(())
+}
@@ -1,25 +1,164 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use clap::Args;
+use clap_complete::engine::ArgValueCandidates;
+use rusqlite::Connection;
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
 use wr::{
+    config::{self, ReadyConfig},
     db,
-    format::{format_wire_table, print_json, Format},
-    models::WireWithDeps,
+    format::{
+        format_wire_columns_table, format_wire_delimited, format_wire_markdown, format_wire_table,
+        print_json_as, Format, ListFormat,
+    },
+    models::{Agent, Tag, Wire, WireWithDeps},
+    template::{Columns, OutputTemplate},
 };
 
-pub fn run(format: Option<Format>) -> Result<()> {
-    let format = Format::resolve(format);
+/// How often `--watch` checks the database for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 
+/// Flags narrowing `wr ready`
+#[derive(Debug, Clone, Default, Args)]
+pub struct ReadyFilters {
+    /// Only wires another agent could pick up (excludes IN_PROGRESS)
+    #[arg(long)]
+    pub claimable: bool,
+    /// Run each wire's `wr precondition` command and leave out wires whose command fails
+    #[arg(long)]
+    pub check_preconditions: bool,
+    /// Agent asking; its own claims stay visible, others' are hidden
+    #[arg(long, env = "WR_AGENT")]
+    pub agent: Option<Agent>,
+    /// Only wires assigned to this agent
+    #[arg(long)]
+    pub assignee: Option<Agent>,
+    /// Only wires with this tag
+    #[arg(short, long)]
+    pub tag: Option<Tag>,
+    /// Only this wire and what it depends on, directly or not
+    #[arg(long, add = ArgValueCandidates::new(super::completions::wire_ids))]
+    pub epic: Option<String>,
+    /// Show at most this many wires, the best first
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Whether a CANCELLED dependency keeps a wire out of the queue
+    /// [default: `ready.cancelled_blocks` in the config, or true]
+    #[arg(long, value_name = "BOOL")]
+    pub cancelled_blocks: Option<bool>,
+}
+
+pub fn run(
+    filters: &ReadyFilters,
+    template: Option<&OutputTemplate>,
+    columns: Option<&Columns>,
+    claim: Option<Duration>,
+    watch: bool,
+    format: Option<ListFormat>,
+) -> Result<()> {
+    let format = ListFormat::resolve(format);
+
+    let config = config::load()?;
     let conn = db::open()?;
-    let wires = db::get_ready_wires(&conn)?;
+    let filter = db::ReadyFilter {
+        claimable: filters.claimable,
+        agent: filters.agent.clone(),
+        assignee: filters.assignee.clone(),
+        tag: filters.tag.clone(),
+        ready: ReadyConfig {
+            cancelled_blocks: filters
+                .cancelled_blocks
+                .unwrap_or(config.ready.cancelled_blocks),
+            ..config.ready.clone()
+        },
+        check_preconditions: filters.check_preconditions,
+        epic: filters
+            .epic
+            .as_deref()
+            .map(|id| db::resolve_wire_id(&conn, id))
+            .transpose()?,
+        limit: filters.limit,
+    };
 
-    match format {
-        Format::Json => print_json(&wires)?,
-        Format::Table => {
-            // Ready wires have no incomplete dependencies by definition
-            let wires_with_deps: Vec<WireWithDeps> =
-                wires.into_iter().map(WireWithDeps::from).collect();
-            print!("{}", format_wire_table(&wires_with_deps))
+    if let Some(ttl) = claim {
+        let agent = filters
+            .agent
+            .as_ref()
+            .ok_or_else(|| anyhow!("--claim needs --agent or WR_AGENT"))?;
+        let ttl_secs = i64::try_from(ttl.as_secs())?;
+        let started = db::claim_ready_wires(&conn, &filter, agent, ttl_secs, &config)?;
+        let wires = started.into_iter().map(|next| next.wire).collect();
+        return print_wires(wires, template, columns, format);
+    }
+
+    if !watch {
+        return render(&conn, &filter, template, columns, format);
+    }
+
+    // Redraw in place on a terminal; when piped, each change is appended,
+    // one JSON array per line
+    let redraw = io::stdout().is_terminal();
+    loop {
+        if redraw {
+            print!("\x1b[2J\x1b[H");
+        }
+        let version = db::data_version(&conn)?;
+        render(&conn, &filter, template, columns, format)?;
+        io::stdout().flush()?;
+        while db::data_version(&conn)? == version {
+            std::thread::sleep(WATCH_INTERVAL);
         }
     }
+}
+
+fn render(
+    conn: &Connection,
+    filter: &db::ReadyFilter,
+    template: Option<&OutputTemplate>,
+    columns: Option<&Columns>,
+    format: ListFormat,
+) -> Result<()> {
+    let wires = db::get_ready_wires_filtered(conn, filter)?;
+    print_wires(wires, template, columns, format)
+}
+
+fn print_wires(
+    wires: Vec<Wire>,
+    template: Option<&OutputTemplate>,
+    columns: Option<&Columns>,
+    format: ListFormat,
+) -> Result<()> {
+    if let Some(template) = template {
+        for wire in &wires {
+            println!("{}", template.render(wire));
+        }
+        return Ok(());
+    }
+
+    let Some(format) = format.general() else {
+        let delimiter = format.delimiter().unwrap_or(b',');
+        print!(
+            "{}",
+            format_wire_delimited(&wires, &columns.cloned().unwrap_or_default(), delimiter)?
+        );
+        return Ok(());
+    };
+
+    if format.is_json() {
+        return print_json_as(&wires, format);
+    }
+    if let (Format::Table, Some(columns)) = (format, columns) {
+        print!("{}", format_wire_columns_table(&wires, columns));
+        return Ok(());
+    }
+
+    // Ready wires have no incomplete dependencies by definition
+    let wires_with_deps: Vec<WireWithDeps> = wires.into_iter().map(WireWithDeps::from).collect();
+
+    match format {
+        Format::Markdown => print!("{}", format_wire_markdown(&wires_with_deps)),
+        _ => print!("{}", format_wire_table(&wires_with_deps)),
+    }
 
     Ok(())
 }
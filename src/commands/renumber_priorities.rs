@@ -0,0 +1,22 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::{print_json, print_result};
+
+pub fn run(step: i32, dry_run: bool) -> Result<()> {
+    let conn = db::open()?;
+
+    let changes = db::renumber_priorities(&conn, step, dry_run)?;
+
+    let output = json!({
+        "changed": changes,
+        "dry_run": dry_run
+    });
+
+    if dry_run {
+        print_json(&output)?;
+    } else {
+        print_result(&output)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use std::time::Duration;
+use wr::{
+    db,
+    format::{
+        format_accuracy_markdown, format_accuracy_table, format_stale_markdown, format_stale_table,
+        print_json_as, Format,
+    },
+};
+
+pub fn stale(older_than: Duration, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let report = db::stale_report(&conn, i64::try_from(older_than.as_secs())?)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&report, format)?,
+        Format::Table => print!("{}", format_stale_table(&report)),
+        Format::Markdown => print!("{}", format_stale_markdown(&report)),
+    }
+
+    Ok(())
+}
+
+pub fn accuracy(format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let report = db::accuracy_report(&conn)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&report, format)?,
+        Format::Table => print!("{}", format_accuracy_table(&report)),
+        Format::Markdown => print!("{}", format_accuracy_markdown(&report)),
+    }
+
+    Ok(())
+}
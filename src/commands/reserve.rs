@@ -0,0 +1,14 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_json;
+
+pub fn run(count: usize) -> Result<()> {
+    let conn = db::open()?;
+
+    let reserved = db::reserve_ids(&conn, count)?;
+
+    let output = json!({ "reserved": reserved });
+    print_json(&output)?;
+    Ok(())
+}
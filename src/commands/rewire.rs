@@ -0,0 +1,21 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+
+pub fn run(from: &str, to: &str) -> Result<()> {
+    let conn = db::open()?;
+    let from = &db::resolve_wire_id(&conn, from)?;
+    let to = &db::resolve_wire_id(&conn, to)?;
+
+    let moved = db::transfer_dependents(&conn, from, to)?;
+
+    let output = json!({
+        "from": from,
+        "to": to,
+        "moved_dependents": moved
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
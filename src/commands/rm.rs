@@ -1,33 +1,20 @@
 use anyhow::Result;
 use serde_json::json;
 use wr::db;
-use wr::models::WireError;
+use wr::format::print_result;
 
 pub fn run(id: &str) -> Result<()> {
     let conn = db::open()?;
+    let id = &db::resolve_wire_id(&conn, id)?;
 
-    // Enable foreign keys for cascade delete to work
-    conn.execute("PRAGMA foreign_keys = ON", [])?;
-
-    // Check if wire exists
-    let exists: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM wires WHERE id = ?1",
-        [id],
-        |row: &rusqlite::Row| row.get(0),
-    )?;
-
-    if exists == 0 {
-        return Err(WireError::WireNotFound(id.to_string()).into());
-    }
-
-    // Delete the wire (dependencies are cascaded by foreign key)
-    conn.execute("DELETE FROM wires WHERE id = ?1", [id])?;
+    // Dependencies, tags and claims are removed along with the wire
+    db::delete_wire(&conn, id)?;
 
     let output = json!({
         "id": id,
         "action": "deleted"
     });
 
-    println!("{}", serde_json::to_string(&output)?);
+    print_result(&output)?;
     Ok(())
 }
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use wr::{
+    db,
+    format::{format_wire_markdown, format_wire_table, print_json_as, Format},
+    models::WireWithDeps,
+};
+
+pub fn run(query: &str, limit: usize, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let hits = db::search_wires(&conn, query, limit)?;
+
+    if format.is_json() {
+        return print_json_as(&hits, format);
+    }
+
+    let wires: Vec<WireWithDeps> = hits
+        .into_iter()
+        .map(|hit| WireWithDeps::from(hit.wire))
+        .collect();
+
+    match format {
+        Format::Markdown => print!("{}", format_wire_markdown(&wires)),
+        _ => print!("{}", format_wire_table(&wires)),
+    }
+
+    Ok(())
+}
@@ -1,20 +1,32 @@
 use anyhow::Result;
 use wr::{
     db,
-    format::{format_wire_detail_table, print_json, Format},
-    models::WireError,
+    format::{
+        format_wire_detail_markdown, format_wire_detail_table, print_json_as, terminal_width,
+        Format,
+    },
+    template::OutputTemplate,
 };
 
-pub fn run(wire_id: &str, format: Option<Format>) -> Result<()> {
+pub fn run(wire_id: &str, template: Option<&OutputTemplate>, format: Option<Format>) -> Result<()> {
     let format = Format::resolve(format);
 
     let conn = db::open()?;
-    let wire_with_deps = db::get_wire_with_deps(&conn, wire_id)
-        .map_err(|_| WireError::WireNotFound(wire_id.to_string()))?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let wire_with_deps = db::get_wire_with_deps(&conn, wire_id)?;
+
+    if let Some(template) = template {
+        println!("{}", template.render(&wire_with_deps.wire));
+        return Ok(());
+    }
 
     match format {
-        Format::Json => print_json(&wire_with_deps)?,
-        Format::Table => print!("{}", format_wire_detail_table(&wire_with_deps)),
+        Format::Json | Format::Jsonl => print_json_as(&wire_with_deps, format)?,
+        Format::Table => print!(
+            "{}",
+            format_wire_detail_table(&wire_with_deps, terminal_width())
+        ),
+        Format::Markdown => print!("{}", format_wire_detail_markdown(&wire_with_deps)),
     }
 
     Ok(())
@@ -1,22 +1,34 @@
 use anyhow::Result;
 use serde_json::json;
-use wr::db;
-use wr::models::{Status, WireError};
+use wr::format::print_result;
+use wr::models::{Agent, WireError};
+use wr::{config, db};
 
-pub fn run(wire_id: &str) -> Result<()> {
+pub fn run(wire_id: &str, agent: Option<&Agent>, full: bool) -> Result<()> {
+    let config = config::load()?;
     let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
 
-    db::update_wire(&conn, wire_id, None, None, Some(Status::InProgress), None)?;
+    let paused = db::start_wire(&conn, wire_id, agent, &config.in_progress)?;
 
-    let wire = db::get_wire_with_deps(&conn, wire_id)
-        .map_err(|_| WireError::WireNotFound(wire_id.to_string()))?;
+    let mut output = if full {
+        serde_json::to_value(db::get_wire_with_deps(&conn, wire_id)?)?
+    } else {
+        let wire = db::get_wire(&conn, wire_id)?
+            .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+        json!({
+            "id": wire.id,
+            "status": wire.status,
+            "updated_at": wire.updated_at_ms
+        })
+    };
+    if !paused.is_empty() {
+        output["paused"] = json!(paused
+            .iter()
+            .map(|w| json!({ "id": w.id, "title": w.title }))
+            .collect::<Vec<_>>());
+    }
 
-    let output = json!({
-        "id": wire.wire.id,
-        "status": wire.wire.status,
-        "updated_at": wire.wire.updated_at
-    });
-
-    println!("{}", serde_json::to_string(&output)?);
+    print_result(&output)?;
     Ok(())
 }
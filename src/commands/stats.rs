@@ -0,0 +1,24 @@
+use anyhow::Result;
+use wr::{
+    db,
+    format::{format_stats_markdown, format_stats_table, print_json_as, Format},
+    models::StatsSort,
+};
+
+pub fn run(sort: Option<StatsSort>, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let mut stats = db::stats::stats(&conn)?;
+    if let Some(sort) = sort {
+        stats.status.sort_by(sort);
+    }
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&stats, format)?,
+        Format::Table => print!("{}", format_stats_table(&stats)),
+        Format::Markdown => print!("{}", format_stats_markdown(&stats)),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+
+pub fn run(old: &str, new: &str) -> Result<()> {
+    let conn = db::open()?;
+    let old = &db::resolve_wire_id(&conn, old)?;
+    let new = &db::resolve_wire_id(&conn, new)?;
+
+    let moved = db::supersede_wire(&conn, old, new)?;
+
+    let wire = db::get_wire_with_deps(&conn, old)?;
+
+    let output = json!({
+        "id": wire.wire.id,
+        "status": wire.wire.status,
+        "cancel_reason": wire.wire.cancel_reason,
+        "superseded_by": new,
+        "moved_dependents": moved,
+        "updated_at": wire.wire.updated_at_ms
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
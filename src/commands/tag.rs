@@ -0,0 +1,147 @@
+use anyhow::{bail, Result};
+use serde_json::json;
+use wr::{
+    db,
+    format::{print_json, print_json_as, print_result, Format},
+    models::{Tag, WireId},
+};
+
+use super::list::Filters;
+
+/// What `wr tag add` and `wr tag rm` apply to
+enum Target {
+    /// `wr tag add <ID> <TAG>`
+    One(String, Tag),
+    /// `wr tag add <TAG>` with filters
+    Bulk(Tag),
+}
+
+impl Target {
+    fn parse(id_or_tag: &str, tag: Option<Tag>, filters: &Filters, dry_run: bool) -> Result<Self> {
+        match tag {
+            Some(_) if !filters.is_empty() => {
+                bail!("Give either a wire ID or filters such as --status, not both")
+            }
+            Some(_) if dry_run => bail!("--dry-run needs filters such as --status"),
+            Some(tag) => Ok(Target::One(id_or_tag.to_string(), tag)),
+            None if filters.is_empty() => bail!(
+                "Missing tag: give a wire ID and a tag, or a tag and filters such as --status"
+            ),
+            None => Ok(Target::Bulk(id_or_tag.parse()?)),
+        }
+    }
+}
+
+pub fn add(id_or_tag: &str, tag: Option<Tag>, filters: &Filters, dry_run: bool) -> Result<()> {
+    match Target::parse(id_or_tag, tag, filters, dry_run)? {
+        Target::One(wire_id, tag) => add_one(&wire_id, &tag),
+        Target::Bulk(tag) => bulk(&tag, true, filters, dry_run),
+    }
+}
+
+pub fn remove(id_or_tag: &str, tag: Option<Tag>, filters: &Filters, dry_run: bool) -> Result<()> {
+    match Target::parse(id_or_tag, tag, filters, dry_run)? {
+        Target::One(wire_id, tag) => remove_one(&wire_id, &tag),
+        Target::Bulk(tag) => bulk(&tag, false, filters, dry_run),
+    }
+}
+
+/// Adds or removes `tag` on every wire the filters select.
+fn bulk(tag: &Tag, add: bool, filters: &Filters, dry_run: bool) -> Result<()> {
+    let conn = db::open()?;
+    let matched = filters.select(&conn)?;
+
+    let changed: Vec<WireId> = if dry_run {
+        matched
+            .iter()
+            .filter(|wd| wd.wire.tags.contains(tag) != add)
+            .map(|wd| wd.wire.id.clone())
+            .collect()
+    } else {
+        let ids: Vec<_> = matched.iter().map(|wd| wd.wire.id.clone()).collect();
+        if add {
+            db::add_tag_to_all(&conn, &ids, tag)?
+        } else {
+            db::remove_tag_from_all(&conn, &ids, tag)?
+        }
+    };
+
+    let output = json!({
+        "tag": tag,
+        "action": if add { "added" } else { "removed" },
+        "ids": changed,
+        "matched": matched.len(),
+        "dry_run": dry_run
+    });
+
+    if dry_run {
+        print_json(&output)?;
+    } else {
+        print_result(&output)?;
+    }
+    Ok(())
+}
+
+fn add_one(wire_id: &str, tag: &Tag) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let added = db::add_tag(&conn, wire_id, tag)?;
+
+    let output = json!({
+        "id": wire_id,
+        "tag": tag,
+        "action": if added { "added" } else { "unchanged" }
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+fn remove_one(wire_id: &str, tag: &Tag) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    let removed = db::remove_tag(&conn, wire_id, tag)?;
+
+    let output = json!({
+        "id": wire_id,
+        "tag": tag,
+        "action": if removed { "removed" } else { "unchanged" }
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
+
+pub fn list(format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let tags = db::list_tags(&conn)?;
+
+    match format {
+        Format::Json | Format::Jsonl => {
+            let tags: Vec<_> = tags
+                .iter()
+                .map(|(tag, count)| json!({ "tag": tag, "count": count }))
+                .collect();
+            print_json_as(&tags, format)?
+        }
+        Format::Table => {
+            if tags.is_empty() {
+                print!("No tags found.");
+            }
+            for (tag, count) in &tags {
+                println!("#{}  {}", tag, count);
+            }
+        }
+        Format::Markdown => {
+            for (tag, count) in &tags {
+                println!("- `#{}` ({})", tag, count);
+            }
+        }
+    }
+
+    Ok(())
+}
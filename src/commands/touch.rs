@@ -0,0 +1,20 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+use wr::models::WireError;
+
+pub fn run(wire_id: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+
+    db::touch_wire(&conn, wire_id)?;
+
+    let wire = db::get_wire(&conn, wire_id)?
+        .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+    print_result(&json!({
+        "id": wire.id,
+        "updated_at": wire.updated_at_ms
+    }))?;
+    Ok(())
+}
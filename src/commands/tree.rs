@@ -0,0 +1,21 @@
+use anyhow::Result;
+use wr::{
+    db,
+    format::{format_tree_markdown, format_tree_table, print_json_as, Format},
+};
+
+pub fn run(wire_id: &str, reverse: bool, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let tree = db::dependency_tree(&conn, wire_id, reverse)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&tree, format)?,
+        Format::Table => print!("{}", format_tree_table(&tree)),
+        Format::Markdown => print!("{}", format_tree_markdown(&tree)),
+    }
+
+    Ok(())
+}
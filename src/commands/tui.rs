@@ -0,0 +1,380 @@
+use anyhow::{bail, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use rusqlite::Connection;
+use std::io::{self, IsTerminal};
+use wr::config::{self, Config};
+use wr::db;
+use wr::models::{DependencyInfo, Status, Wire, WireWithDeps};
+
+const HELP: &str =
+    "j/k move  s start  d done  t todo  p pause  r resume  n new  h hidden  g refresh  q quit";
+
+/// Browses and edits wires in an interactive terminal interface.
+///
+/// Changes go through the same database functions as the other commands,
+/// so they are recorded in the event log and can be undone with `wr undo`,
+/// and `[permissions]` applies to each as the command it stands for.
+pub fn run(show_hidden: bool) -> Result<()> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        bail!("wr tui needs a terminal");
+    }
+
+    let mut app = App {
+        config: config::load()?,
+        conn: db::open()?,
+        agent: std::env::var("WR_AGENT")
+            .ok()
+            .filter(|agent| !agent.is_empty()),
+        wires: Vec::new(),
+        table: TableState::default(),
+        selected: None,
+        mode: Mode::Browse,
+        message: None,
+        show_hidden,
+    };
+    app.refresh(None)?;
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+enum Mode {
+    Browse,
+    /// Typing the title of a new wire
+    Create(String),
+}
+
+/// What the last action did, shown below the list
+enum Message {
+    Info(String),
+    Error(String),
+}
+
+struct App {
+    config: Config,
+    conn: Connection,
+    /// Who `[permissions]` checks changes for, from `WR_AGENT`
+    agent: Option<String>,
+    wires: Vec<Wire>,
+    table: TableState,
+    /// The highlighted wire with its dependencies
+    selected: Option<WireWithDeps>,
+    mode: Mode,
+    message: Option<Message>,
+    show_hidden: bool,
+}
+
+impl App {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && !self.handle_key(key)? {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Reloads the wires, keeping the highlight on `keep` if it is still
+    /// listed, or else on the same row.
+    fn refresh(&mut self, keep: Option<&str>) -> Result<()> {
+        let keep = keep
+            .map(str::to_string)
+            .or_else(|| self.current().map(|w| w.id.to_string()));
+        let hidden = if self.show_hidden {
+            &[][..]
+        } else {
+            &self.config.hide_statuses[..]
+        };
+
+        let mut wires = db::list_wires(&self.conn, &db::ListFilter::default())?;
+        wires.retain(|w| !hidden.contains(&w.status));
+        wires.sort_by_key(|w| (w.status, std::cmp::Reverse(w.priority)));
+        self.wires = wires;
+
+        let index = keep
+            .and_then(|id| self.wires.iter().position(|w| w.id.as_str() == id))
+            .or(self.table.selected())
+            .map(|i| i.min(self.wires.len().saturating_sub(1)))
+            .filter(|_| !self.wires.is_empty());
+        self.table.select(index);
+        self.load_selected()
+    }
+
+    fn load_selected(&mut self) -> Result<()> {
+        self.selected = match self.current() {
+            Some(wire) => Some(db::get_wire_with_deps(&self.conn, wire.id.as_str())?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    fn current(&self) -> Option<&Wire> {
+        self.wires.get(self.table.selected()?)
+    }
+
+    fn select(&mut self, index: usize) -> Result<()> {
+        if !self.wires.is_empty() {
+            self.table.select(Some(index.min(self.wires.len() - 1)));
+            self.load_selected()?;
+        }
+        Ok(())
+    }
+
+    /// Handles a key press; returns false to quit.
+    fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        if let Mode::Create(title) = &mut self.mode {
+            match key.code {
+                KeyCode::Enter => {
+                    let title = std::mem::take(title);
+                    self.mode = Mode::Browse;
+                    self.create(&title)?;
+                }
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Backspace => {
+                    title.pop();
+                }
+                KeyCode::Char(c) => title.push(c),
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        let selected = self.table.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Char('j') | KeyCode::Down => self.select(selected + 1)?,
+            KeyCode::Char('k') | KeyCode::Up => self.select(selected.saturating_sub(1))?,
+            KeyCode::Home => self.select(0)?,
+            KeyCode::End => self.select(usize::MAX)?,
+            KeyCode::Char('s') => self.change_status(Status::InProgress)?,
+            KeyCode::Char('d') => self.change_status(Status::Done)?,
+            KeyCode::Char('t') => self.change_status(Status::Todo)?,
+            KeyCode::Char('p') => self.change_status(Status::Paused)?,
+            KeyCode::Char('r') => self.resume()?,
+            KeyCode::Char('n') => self.mode = Mode::Create(String::new()),
+            KeyCode::Char('h') => {
+                self.show_hidden = !self.show_hidden;
+                self.refresh(None)?;
+            }
+            KeyCode::Char('g') => {
+                self.message = None;
+                self.refresh(None)?;
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Moves the highlighted wire to `status` the way `wr start`, `wr done`,
+    /// `wr pause` or `wr update --status` would.
+    fn change_status(&mut self, status: Status) -> Result<()> {
+        let Some(wire) = self.current() else {
+            return Ok(());
+        };
+        let id = wire.id.to_string();
+        let operation = match status {
+            Status::InProgress => "start",
+            Status::Paused => "pause",
+            Status::Done => "done",
+            _ => "update",
+        };
+        let result = self.authorize(operation).and_then(|()| match status {
+            Status::InProgress => db::start_wire(&self.conn, &id, None, &self.config.in_progress)
+                .map(|paused| {
+                    paused
+                        .iter()
+                        .map(|w| format!(", paused {}", w.id))
+                        .collect::<String>()
+                }),
+            Status::Paused => db::pause_wire(&self.conn, &id, None).map(|()| String::new()),
+            _ => db::update_wire(&self.conn, &id, None, None, Some(status), None)
+                .map(|()| String::new()),
+        });
+        self.report(result.map(|note| format!("{} is now {}{}", id, status.as_str(), note)));
+        self.refresh(Some(&id))
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        let Some(wire) = self.current() else {
+            return Ok(());
+        };
+        let id = wire.id.to_string();
+        let result = self
+            .authorize("resume")
+            .and_then(|()| db::resume_wire(&self.conn, &id, None, &self.config.in_progress));
+        self.report(result.map(|_| format!("{} resumed", id)));
+        self.refresh(Some(&id))
+    }
+
+    /// Creates a wire with the default priority, as `wr new` would.
+    fn create(&mut self, title: &str) -> Result<()> {
+        let wire = self.insert(title);
+        let id = wire.as_ref().ok().map(|w| w.id.to_string());
+        self.report(wire.map(|w| format!("Created {}", w.id)));
+        self.refresh(id.as_deref())
+    }
+
+    fn insert(&self, title: &str) -> Result<Wire> {
+        self.authorize("new")?;
+        let mut wire = Wire::new(title, None, self.config.default_priority)?;
+        wire.id = self.config.id_scheme.generate(&wire.title);
+        db::insert_wire(&self.conn, &mut wire)?;
+        Ok(wire)
+    }
+
+    /// Checks `[permissions]` lets the agent run the command `operation`.
+    fn authorize(&self, operation: &str) -> Result<()> {
+        db::authorize(&self.conn, self.agent.as_deref(), operation)
+    }
+
+    fn report(&mut self, result: Result<String>) {
+        self.message = Some(match result {
+            Ok(info) => Message::Info(info),
+            Err(e) => Message::Error(e.to_string()),
+        });
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, message_area, help_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, details_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(main);
+
+        self.draw_list(frame, list_area);
+        self.draw_details(frame, details_area);
+
+        let message = match &self.mode {
+            Mode::Create(title) => Line::from(vec![
+                Span::from("New wire: ").bold(),
+                Span::from(title.as_str()),
+                Span::from("▏"),
+            ]),
+            Mode::Browse => match &self.message {
+                Some(Message::Info(info)) => Line::from(info.as_str()),
+                Some(Message::Error(error)) => Line::from(error.as_str()).red(),
+                None => Line::default(),
+            },
+        };
+        frame.render_widget(message, message_area);
+        frame.render_widget(Line::from(HELP).dim(), help_area);
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.wires.iter().map(|wire| {
+            Row::new(vec![
+                Span::from(wire.id.to_string()),
+                Span::from(wire.status.as_str()).fg(status_color(wire.status)),
+                Span::from(wire.priority.to_string()),
+                Span::from(wire.title.as_str()),
+            ])
+        });
+        let title = if self.show_hidden {
+            " Wires (all) "
+        } else {
+            " Wires "
+        };
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(7),
+                Constraint::Length(11),
+                Constraint::Length(4),
+                Constraint::Min(0),
+            ],
+        )
+        .header(Row::new(["ID", "STATUS", "PRI", "TITLE"]).bold())
+        .block(Block::bordered().title(title))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn draw_details(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::bordered().title(" Details ");
+        let Some(selected) = &self.selected else {
+            frame.render_widget(Paragraph::new("No wires").block(block), area);
+            return;
+        };
+
+        let wire = &selected.wire;
+        let mut lines = vec![
+            Line::from(wire.title.as_str()).bold(),
+            Line::from(vec![
+                Span::from(wire.id.to_string()).dim(),
+                Span::from("  "),
+                Span::from(wire.status.as_str()).fg(status_color(wire.status)),
+                Span::from(format!("  priority {}", wire.priority)),
+            ]),
+        ];
+        if !wire.tags.is_empty() {
+            let tags: Vec<String> = wire.tags.iter().map(|t| format!("#{}", t)).collect();
+            lines.push(Line::from(tags.join(" ")).dim());
+        }
+        if let Some(description) = &wire.description {
+            lines.push(Line::default());
+            lines.extend(description.lines().map(Line::from));
+        }
+        for (heading, deps) in [
+            ("Depends on", &selected.depends_on),
+            ("Blocks", &selected.blocks),
+        ] {
+            lines.push(Line::default());
+            lines.push(Line::from(heading).bold());
+            if deps.is_empty() {
+                lines.push(Line::from("  none").dim());
+            }
+            lines.extend(deps.iter().map(dependency_line));
+        }
+        if !selected.blocked_on.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from("Waiting on").bold());
+            lines.extend(
+                selected
+                    .blocked_on
+                    .iter()
+                    .map(|b| Line::from(format!("  {}", b.reason))),
+            );
+        }
+
+        let details = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(details, area);
+    }
+}
+
+fn dependency_line(dep: &DependencyInfo) -> Line<'_> {
+    Line::from(vec![
+        Span::from("  "),
+        Span::from(dep.id.to_string()).dim(),
+        Span::from(" "),
+        Span::from(dep.status.as_str()).fg(status_color(dep.status)),
+        Span::from(" "),
+        Span::from(dep.title.as_str()),
+    ])
+}
+
+/// The colors `wr list` uses for each status symbol
+fn status_color(status: Status) -> Color {
+    match status {
+        Status::Todo => Color::Reset,
+        Status::InProgress => Color::Yellow,
+        Status::Paused => Color::Blue,
+        Status::Blocked => Color::Magenta,
+        Status::Done => Color::Green,
+        Status::Cancelled => Color::Red,
+    }
+}
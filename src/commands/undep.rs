@@ -1,9 +1,12 @@
 use anyhow::Result;
 use serde_json::json;
 use wr::db;
+use wr::format::print_result;
 
 pub fn run(wire_id: &str, depends_on: &str) -> Result<()> {
     let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let depends_on = &db::resolve_wire_id(&conn, depends_on)?;
 
     db::remove_dependency(&conn, wire_id, depends_on)?;
 
@@ -13,6 +16,6 @@ pub fn run(wire_id: &str, depends_on: &str) -> Result<()> {
         "action": "removed"
     });
 
-    println!("{}", serde_json::to_string(&output)?);
+    print_result(&output)?;
     Ok(())
 }
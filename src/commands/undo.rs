@@ -0,0 +1,14 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+
+pub fn run(count: usize) -> Result<()> {
+    let conn = db::open()?;
+
+    let undone = db::undo(&conn, count)?;
+
+    let output = json!({ "undone": undone });
+    print_result(&output)?;
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use serde_json::json;
+use wr::db;
+use wr::format::print_result;
+
+pub fn run(wire_id: &str, related: &str) -> Result<()> {
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let related = &db::resolve_wire_id(&conn, related)?;
+
+    db::unlink_wires(&conn, wire_id, related)?;
+
+    let output = json!({
+        "wire_id": wire_id,
+        "related": related,
+        "action": "unlinked"
+    });
+
+    print_result(&output)?;
+    Ok(())
+}
@@ -1,16 +1,25 @@
 use anyhow::Result;
 use serde_json::json;
+use wr::config;
 use wr::db;
-use wr::models::{Status, WireError};
+use wr::format::print_result;
+use wr::models::{PriorityArg, Status, WireError};
 
 pub fn run(
     wire_id: &str,
     title: Option<&str>,
     description: Option<&str>,
     status: Option<Status>,
-    priority: Option<i32>,
+    priority: Option<&PriorityArg>,
+    full: bool,
 ) -> Result<()> {
+    let priority = match priority {
+        Some(priority) => Some(config::load()?.priorities.resolve(priority)?),
+        None => None,
+    };
+
     let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
 
     db::update_wire(
         &conn,
@@ -21,17 +30,20 @@ pub fn run(
         priority,
     )?;
 
-    // Fetch updated wire
-    let wire = db::get_wire_with_deps(&conn, wire_id)
-        .map_err(|_| WireError::WireNotFound(wire_id.to_string()))?;
-
-    let output = json!({
-        "id": wire.wire.id,
-        "status": wire.wire.status,
-        "priority": wire.wire.priority,
-        "updated_at": wire.wire.updated_at
-    });
+    // Fetch updated wire, with its relations only when they are printed
+    let output = if full {
+        serde_json::to_value(db::get_wire_with_deps(&conn, wire_id)?)?
+    } else {
+        let wire = db::get_wire(&conn, wire_id)?
+            .ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
+        json!({
+            "id": wire.id,
+            "status": wire.status,
+            "priority": wire.priority,
+            "updated_at": wire.updated_at_ms
+        })
+    };
 
-    println!("{}", serde_json::to_string(&output)?);
+    print_result(&output)?;
     Ok(())
 }
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use wr::{
+    db,
+    format::{format_readiness_markdown, format_readiness_table, print_json_as, Format},
+    models::Agent,
+};
+
+pub fn run(wire_id: &str, agent: Option<&Agent>, format: Option<Format>) -> Result<()> {
+    let format = Format::resolve(format);
+
+    let conn = db::open()?;
+    let wire_id = &db::resolve_wire_id(&conn, wire_id)?;
+    let why = db::readiness(&conn, wire_id, agent)?;
+
+    match format {
+        Format::Json | Format::Jsonl => print_json_as(&why, format)?,
+        Format::Table => print!("{}", format_readiness_table(&why)),
+        Format::Markdown => print!("{}", format_readiness_markdown(&why)),
+    }
+
+    Ok(())
+}
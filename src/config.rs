@@ -0,0 +1,883 @@
+//! Repository configuration, read from `.wires/config.toml`.
+//!
+//! Every setting is optional; a missing file means the defaults. Unknown
+//! keys are rejected so typos don't silently do nothing.
+//!
+//! ```toml
+//! # Output format on a terminal when `-f` isn't given (piped output stays JSON)
+//! format = "markdown"
+//!
+//! # Priority of new wires when `--priority` isn't given
+//! default_priority = 10
+//!
+//! # IDs of new wires: hex (7 characters, the default), ulid or uuid7
+//! id_scheme = "ulid"
+//!
+//! # Names accepted by `--priority` and shown in place of the number
+//! [priorities]
+//! urgent = 20
+//! high = 10
+//!
+//! # Colored output: auto (when the terminal supports it), always or never
+//! color = "never"
+//!
+//! # Leave abandoned work out of `wr list`, `wr graph` and `wr board`
+//! hide_statuses = ["CANCELLED"]
+//!
+//! # Make `wr cancel` say why (duplicate, obsolete, wontfix, superseded-by:<id>)
+//! require_cancel_reason = true
+//!
+//! # Tidy titles on `wr new`, `wr update` and `wr import`
+//! [titles]
+//! collapse_whitespace = true
+//! strip_emoji = true
+//! max_length = 80
+//!
+//! # One IN_PROGRESS wire per agent; starting another pauses the older one
+//! [in_progress]
+//! limit = 1
+//! when_full = "pause"
+//!
+//! # Where each status may go next; unlisted statuses keep the defaults
+//! [transitions]
+//! CANCELLED = ["TODO", "DONE"]
+//!
+//! # Warn on `wr new` when an open wire's title is at least 90% alike
+//! [duplicates]
+//! threshold = 90
+//! warn_on_new = true
+//!
+//! # How long `wr maintain` keeps bookkeeping; unset kinds are kept forever
+//! [retention]
+//! history = "90d"
+//! archived = "180d"
+//!
+//! # Commands each agent (`WR_AGENT`) may run to change the repository
+//! [permissions]
+//! planner = ["new", "dep", "undep", "update", "tag"]
+//! worker = ["start", "done", "handoff"]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::format::Format;
+use crate::models::{IdScheme, PriorityArg, Status, WireConstructionError, WireError};
+
+/// Name of the config file inside `.wires/`
+pub const CONFIG_NAME: &str = "config.toml";
+
+/// Settings for one repository.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Output format on a terminal when none is asked for
+    pub format: Option<Format>,
+    /// Priority of new wires when none is given
+    pub default_priority: i32,
+    /// How IDs of new wires are generated
+    pub id_scheme: IdScheme,
+    /// Whether output is colored
+    pub color: ColorChoice,
+    /// Statuses left out of `wr list`, `wr graph` and `wr board` unless asked for
+    pub hide_statuses: Vec<Status>,
+    /// Whether `wr cancel` must be given a `--reason`
+    pub require_cancel_reason: bool,
+    /// How many wires each agent may have IN_PROGRESS at once
+    pub in_progress: InProgressPolicy,
+    /// How titles are cleaned up before they are stored
+    pub titles: TitlePolicy,
+    /// What `wr ready` and `wr next` count as ready, and how they rank it
+    pub ready: ReadyConfig,
+    /// Which status changes are allowed
+    pub transitions: TransitionPolicy,
+    /// Named priority levels
+    pub priorities: PriorityLevels,
+    /// When `wr dups` and `wr new` consider two titles alike
+    pub duplicates: DuplicatePolicy,
+    /// How long `wr maintain` keeps history, archived wires and other bookkeeping
+    pub retention: RetentionPolicy,
+    /// Which commands each agent may run
+    pub permissions: Permissions,
+}
+
+/// Which commands each agent may run, by its `WR_AGENT` (or `--agent`) name.
+///
+/// Each agent listed may only run the commands given, named as typed
+/// (`new`, `dep`, `tag add`); naming a command allows all of its
+/// subcommands, and `"*"` allows everything. The `"*"` agent covers every
+/// agent not listed, and callers with no name. Without it, unlisted agents
+/// are unrestricted. Commands that only read are always allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Permissions(BTreeMap<String, Vec<String>>);
+
+impl Permissions {
+    /// The agent name standing for every agent not listed
+    pub const ANYONE: &'static str = "*";
+
+    /// Checks that `actor` may run the command `operation` (its subcommand
+    /// path, like `tag add`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::PermissionDenied`] if the agent's rules leave
+    /// the command out.
+    pub fn check(&self, actor: Option<&str>, operation: &str) -> Result<(), WireError> {
+        let rules = actor
+            .and_then(|actor| self.0.get(actor))
+            .or_else(|| self.0.get(Self::ANYONE));
+        let Some(allowed) = rules else {
+            return Ok(());
+        };
+        let allows = |rule: &String| {
+            rule == Self::ANYONE
+                || operation == rule
+                || operation
+                    .strip_prefix(rule.as_str())
+                    .is_some_and(|rest| rest.starts_with(' '))
+        };
+        if allowed.iter().any(allows) {
+            return Ok(());
+        }
+        Err(WireError::PermissionDenied {
+            actor: actor.map(str::to_string),
+            operation: operation.to_string(),
+            allowed: allowed.clone(),
+        })
+    }
+
+    /// Every command named in the rules, with the agent naming it.
+    pub fn rules(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().flat_map(|(actor, operations)| {
+            operations
+                .iter()
+                .map(move |operation| (actor.as_str(), operation.as_str()))
+        })
+    }
+}
+
+/// When output is colored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color terminals that support it, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+/// What makes a wire ready, and weights ranking ready wires, highest
+/// score first.
+///
+/// A wire's score is the sum of each weight times what it measures.
+/// IN_PROGRESS wires still come before TODO ones; the defaults rank by
+/// priority alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadyConfig {
+    /// Weight of the wire's priority
+    pub priority: i64,
+    /// Weight of each whole day since the wire was created
+    pub age: i64,
+    /// Weight of each open wire depending on this one
+    pub unblocks: i64,
+    /// Whether a CANCELLED dependency holds its dependents back, as an
+    /// unfinished one does, rather than counting as out of the way
+    pub cancelled_blocks: bool,
+}
+
+impl Default for ReadyConfig {
+    fn default() -> Self {
+        ReadyConfig {
+            priority: 1,
+            age: 0,
+            unblocks: 0,
+            cancelled_blocks: true,
+        }
+    }
+}
+
+impl ReadyConfig {
+    /// Whether the weights rank by priority alone, as the defaults do.
+    pub fn ranks_by_priority(&self) -> bool {
+        let default = ReadyConfig::default();
+        (self.priority, self.age, self.unblocks)
+            == (default.priority, default.age, default.unblocks)
+    }
+
+    /// Score of a wire with `priority`, `age` seconds old, that `unblocks`
+    /// open wires are waiting on.
+    pub fn score(&self, priority: i32, age: i64, unblocks: i64) -> i64 {
+        self.priority * i64::from(priority) + self.age * (age / 86_400) + self.unblocks * unblocks
+    }
+}
+
+/// Clean-up applied to every title written, whether by a command, a plan
+/// or `WireStore`; the database applies it.
+///
+/// Titles are always trimmed; the rest is opt-in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TitlePolicy {
+    /// Replace each run of whitespace, including newlines, with one space
+    pub collapse_whitespace: bool,
+    /// Remove emoji and the joiners and selectors that combine them
+    pub strip_emoji: bool,
+    /// Reject titles longer than this many characters
+    pub max_length: Option<NonZeroUsize>,
+}
+
+impl TitlePolicy {
+    /// Returns `title` as it should be stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nothing is left of the title, or it is longer
+    /// than `max_length`.
+    pub fn normalize(&self, title: &str) -> Result<String, WireConstructionError> {
+        let mut title: String = if self.strip_emoji {
+            title.chars().filter(|c| !is_emoji(*c)).collect()
+        } else {
+            title.to_string()
+        };
+        // Stripping emoji can leave doubled spaces behind
+        if self.collapse_whitespace || self.strip_emoji {
+            title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+        }
+        let title = title.trim();
+
+        if title.is_empty() {
+            return Err(WireConstructionError::EmptyTitle);
+        }
+        if let Some(max) = self.max_length {
+            let length = title.chars().count();
+            if length > max.get() {
+                return Err(WireConstructionError::TitleTooLong {
+                    length,
+                    max: max.get(),
+                });
+            }
+        }
+        Ok(title.to_string())
+    }
+}
+
+/// Whether `c` is an emoji, or part of an emoji sequence.
+///
+/// Covers the pictographic blocks rather than the full Unicode emoji
+/// property, which also includes digits and `#`.
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // pictographs, emoticons, transport, flags
+            | 0x2600..=0x27BF // miscellaneous symbols and dingbats
+            | 0x2B1B | 0x2B1C | 0x2B50 | 0x2B55 // squares, star, circle
+            | 0x231A..=0x23FF // watches, hourglasses, media controls
+            | 0x3030 | 0x303D | 0x3297 | 0x3299
+            | 0x200D // zero width joiner
+            | 0x20E3 // combining keycap
+            | 0xFE0E..=0xFE0F // variation selectors
+            | 0xE0020..=0xE007F // tag sequences
+    )
+}
+
+/// Names for priority values, so agents can say `--priority high` instead
+/// of each picking their own numbers.
+///
+/// The defaults are `high` (2), `med` (1) and `low` (0). Levels in the
+/// config are added to these, or change their values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, i32>")]
+pub struct PriorityLevels(BTreeMap<String, i32>);
+
+impl Default for PriorityLevels {
+    fn default() -> Self {
+        PriorityLevels(BTreeMap::from([
+            ("high".to_string(), 2),
+            ("med".to_string(), 1),
+            ("low".to_string(), 0),
+        ]))
+    }
+}
+
+impl TryFrom<BTreeMap<String, i32>> for PriorityLevels {
+    type Error = String;
+
+    fn try_from(levels: BTreeMap<String, i32>) -> Result<Self, Self::Error> {
+        if let Some(name) = levels.keys().find(|name| !PriorityArg::is_valid_name(name)) {
+            return Err(format!(
+                "invalid priority name '{}' (use lowercase letters, digits, - and _)",
+                name
+            ));
+        }
+        let mut defaults = PriorityLevels::default();
+        defaults.0.extend(levels);
+        Ok(defaults)
+    }
+}
+
+impl PriorityLevels {
+    /// The number `priority` stands for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the known levels if `priority` is a name
+    /// that isn't one of them.
+    pub fn resolve(&self, priority: &PriorityArg) -> Result<i32> {
+        match priority {
+            PriorityArg::Value(value) => Ok(*value),
+            PriorityArg::Named(name) => self.0.get(name).copied().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown priority '{}' (known: {})",
+                    name,
+                    self.names().join(", ")
+                )
+            }),
+        }
+    }
+
+    /// The name for `value`, if a level has it. When several do, the
+    /// first alphabetically wins.
+    pub fn name(&self, value: i32) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(_, v)| **v == value)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Level names, highest first
+    fn names(&self) -> Vec<&str> {
+        let mut levels: Vec<_> = self.0.iter().collect();
+        levels.sort_by_key(|(name, value)| (std::cmp::Reverse(**value), name.as_str()));
+        levels.into_iter().map(|(name, _)| name.as_str()).collect()
+    }
+}
+
+/// The status changes a wire may make, checked by every status write.
+///
+/// By default open wires (TODO, IN_PROGRESS, PAUSED, BLOCKED) may move to any
+/// status, while DONE and CANCELLED wires must be reopened as TODO before
+/// anything else. The config lists targets per status; statuses it leaves
+/// out keep their defaults. Staying in the same status is always allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<Status, Vec<Status>>")]
+pub struct TransitionPolicy(BTreeMap<Status, Vec<Status>>);
+
+impl Default for TransitionPolicy {
+    fn default() -> Self {
+        use Status::*;
+
+        let open = vec![Todo, InProgress, Paused, Blocked, Done, Cancelled];
+        TransitionPolicy(BTreeMap::from([
+            (Todo, open.clone()),
+            (InProgress, open.clone()),
+            (Paused, open.clone()),
+            (Blocked, open),
+            (Done, vec![Todo]),
+            (Cancelled, vec![Todo]),
+        ]))
+    }
+}
+
+impl From<BTreeMap<Status, Vec<Status>>> for TransitionPolicy {
+    fn from(overrides: BTreeMap<Status, Vec<Status>>) -> Self {
+        let mut policy = TransitionPolicy::default();
+        policy.0.extend(overrides);
+        policy
+    }
+}
+
+impl TransitionPolicy {
+    /// The statuses a wire in `from` may move to, other than `from` itself.
+    pub fn allowed(&self, from: Status) -> Vec<Status> {
+        self.0
+            .get(&from)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|to| *to != from)
+            .collect()
+    }
+
+    /// Checks that `wire_id` may move from `from` to `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::InvalidTransition`] if it may not.
+    pub fn check(&self, wire_id: &str, from: Status, to: Status) -> Result<(), WireError> {
+        let allowed = self.allowed(from);
+        if from == to || allowed.contains(&to) {
+            return Ok(());
+        }
+        Err(WireError::InvalidTransition {
+            wire_id: wire_id.to_string(),
+            from,
+            to,
+            allowed,
+        })
+    }
+}
+
+/// How long each kind of bookkeeping is kept, enforced by `wr maintain`.
+///
+/// Unset windows keep everything, so nothing is deleted until configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionPolicy {
+    /// Events in the history, except ones an outbox consumer hasn't
+    /// acknowledged yet
+    pub history: Option<RetentionWindow>,
+    /// Archived wires, deleted for good this long after they were archived
+    pub archived: Option<RetentionWindow>,
+    /// Outbox consumers that haven't pulled or acknowledged in this long
+    pub outbox: Option<RetentionWindow>,
+    /// Database backups left by `wr init --force`
+    pub backups: Option<RetentionWindow>,
+    /// Saved results of commands run with `--idempotency-key`
+    pub idempotency_keys: Option<RetentionWindow>,
+}
+
+/// A retention period, written like `30d` or `12w` (see
+/// [`parse_duration`](crate::time::parse_duration)).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RetentionWindow {
+    text: String,
+    duration: std::time::Duration,
+}
+
+impl RetentionWindow {
+    /// The window in whole seconds
+    pub fn as_secs(&self) -> i64 {
+        i64::try_from(self.duration.as_secs()).unwrap_or(i64::MAX)
+    }
+}
+
+impl TryFrom<String> for RetentionWindow {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let duration = crate::time::parse_duration(&text)?;
+        Ok(RetentionWindow { text, duration })
+    }
+}
+
+impl From<RetentionWindow> for String {
+    fn from(window: RetentionWindow) -> Self {
+        window.text
+    }
+}
+
+/// How alike two titles must be to count as likely duplicates.
+///
+/// Similarity is measured as in [`dups`](crate::dups), in percent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DuplicatePolicy {
+    /// Lowest similarity, from 0 to 100, that `wr dups` reports
+    #[serde(deserialize_with = "percent")]
+    pub threshold: u8,
+    /// Whether `wr new` warns about open wires with a title this alike
+    pub warn_on_new: bool,
+}
+
+impl Default for DuplicatePolicy {
+    fn default() -> Self {
+        DuplicatePolicy {
+            threshold: 80,
+            warn_on_new: false,
+        }
+    }
+}
+
+fn percent<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    let value = u8::deserialize(deserializer)?;
+    if value > 100 {
+        return Err(serde::de::Error::custom(format!(
+            "{} is not a percentage from 0 to 100",
+            value
+        )));
+    }
+    Ok(value)
+}
+
+/// Cap on IN_PROGRESS wires per agent, applied by `wr start`.
+///
+/// An agent's IN_PROGRESS wires are the ones it holds a claim on; without
+/// an agent, the unclaimed ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InProgressPolicy {
+    /// Most wires an agent may have IN_PROGRESS (unlimited if unset)
+    pub limit: Option<NonZeroUsize>,
+    /// What starting one more wire does once the limit is reached
+    pub when_full: WhenFull,
+}
+
+/// What `wr start` does when the agent is at its IN_PROGRESS limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WhenFull {
+    /// Pause the agent's least recently updated IN_PROGRESS wire
+    #[default]
+    Pause,
+    /// Refuse to start the new wire
+    Reject,
+}
+
+impl Config {
+    /// Parses a config from TOML.
+    pub fn from_toml(source: &str) -> Result<Self> {
+        Ok(toml::from_str(source)?)
+    }
+
+    /// Reads the config file at `path`, or the defaults if it doesn't exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid config.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(source) => {
+                Config::from_toml(&source).with_context(|| format!("Invalid {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// Whether wires with `status` are hidden from everyday views.
+    pub fn hides(&self, status: Status) -> bool {
+        self.hide_statuses.contains(&status)
+    }
+}
+
+/// Path of the config file for the repository whose database is `db_path`.
+pub fn config_path(db_path: &Path) -> PathBuf {
+    db_path.with_file_name(CONFIG_NAME)
+}
+
+/// The config given to [`use_config`], with the database it belongs to
+static CHOSEN: OnceLock<(PathBuf, Config)> = OnceLock::new();
+
+/// Makes [`for_db`] return `config` for the database at `db_path` for the
+/// rest of the process, instead of reading the file again.
+///
+/// Only the first call has any effect. `wr` reads the config once at
+/// startup and hands it over here, so commands and the database functions
+/// that consult the config share that one read.
+pub fn use_config(db_path: PathBuf, config: Config) {
+    let _ = CHOSEN.set((db_path, config));
+}
+
+/// The config of the repository whose database is at `db_path`: the one
+/// given to [`use_config`] for it, or else read from beside the database.
+///
+/// # Errors
+///
+/// Returns an error if the config has to be read and is invalid.
+pub fn for_db(db_path: &Path) -> Result<Config> {
+    if let Some((_, config)) = CHOSEN.get().filter(|(path, _)| path == db_path) {
+        return Ok(config.clone());
+    }
+    let path = config_path(db_path);
+    crate::format::diag(format_args!("config {}", path.display()));
+    Config::from_file(&path)
+}
+
+/// Loads the config for the repository containing the current directory.
+///
+/// # Errors
+///
+/// Returns an error if there is no repository or its config is invalid.
+#[cfg(feature = "sqlite")]
+pub fn load() -> Result<Config> {
+    for_db(&crate::db::find_db()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_hide_statuses() {
+        let config = Config::from_toml(r#"hide_statuses = ["CANCELLED", "DONE"]"#).unwrap();
+        assert!(config.hides(Status::Cancelled));
+        assert!(config.hides(Status::Done));
+        assert!(!config.hides(Status::Todo));
+    }
+
+    #[test]
+    fn test_parse_in_progress_policy() {
+        let config =
+            Config::from_toml("[in_progress]\nlimit = 2\nwhen_full = \"reject\"\n").unwrap();
+        assert_eq!(config.in_progress.limit, NonZeroUsize::new(2));
+        assert_eq!(config.in_progress.when_full, WhenFull::Reject);
+
+        let config = Config::from_toml("[in_progress]\nlimit = 1\n").unwrap();
+        assert_eq!(config.in_progress.when_full, WhenFull::Pause);
+
+        assert!(Config::from_toml("[in_progress]\nlimit = 0\n").is_err());
+    }
+
+    #[test]
+    fn test_permissions_check() {
+        let permissions =
+            Config::from_toml("[permissions]\nplanner = [\"new\", \"tag\"]\nadmin = [\"*\"]\n")
+                .unwrap()
+                .permissions;
+
+        assert!(permissions.check(Some("planner"), "new").is_ok());
+        assert!(permissions.check(Some("planner"), "tag add").is_ok());
+        assert!(permissions.check(Some("admin"), "rm").is_ok());
+        // Unlisted agents are unrestricted without a "*" entry
+        assert!(permissions.check(Some("other"), "rm").is_ok());
+        assert!(permissions.check(None, "rm").is_ok());
+
+        let err = permissions.check(Some("planner"), "rm").unwrap_err();
+        assert_eq!(err.code(), "permission_denied");
+        assert_eq!(
+            err.to_string(),
+            "Agent 'planner' may not run `wr rm` (allowed: new, tag)"
+        );
+        // A prefix of a word doesn't count
+        assert!(permissions.check(Some("planner"), "newer").is_err());
+    }
+
+    #[test]
+    fn test_permissions_anyone_covers_unlisted_and_unnamed() {
+        let permissions = Config::from_toml("[permissions]\n\"*\" = []\nworker = [\"done\"]\n")
+            .unwrap()
+            .permissions;
+        assert!(permissions.check(Some("worker"), "done").is_ok());
+        assert!(permissions.check(Some("stranger"), "done").is_err());
+        assert_eq!(
+            permissions.check(None, "new").unwrap_err().to_string(),
+            "Agents without a name may not run `wr new` (it may only read)"
+        );
+    }
+
+    #[test]
+    fn test_parse_id_scheme() {
+        assert_eq!(Config::default().id_scheme, IdScheme::Hex);
+        let config = Config::from_toml("id_scheme = \"uuid7\"").unwrap();
+        assert_eq!(config.id_scheme, IdScheme::Uuid7);
+        assert!(Config::from_toml("id_scheme = \"uuid4\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_require_cancel_reason() {
+        assert!(!Config::default().require_cancel_reason);
+        let config = Config::from_toml("require_cancel_reason = true").unwrap();
+        assert!(config.require_cancel_reason);
+    }
+
+    #[test]
+    fn test_priority_levels_resolve_names_and_numbers() {
+        let levels = PriorityLevels::default();
+        assert_eq!(levels.resolve(&PriorityArg::Value(7)).unwrap(), 7);
+        assert_eq!(
+            levels.resolve(&PriorityArg::Named("high".into())).unwrap(),
+            2
+        );
+        let err = levels
+            .resolve(&PriorityArg::Named("urgent".into()))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown priority 'urgent' (known: high, med, low)"
+        );
+        assert_eq!(levels.name(1), Some("med"));
+        assert_eq!(levels.name(7), None);
+    }
+
+    #[test]
+    fn test_parse_priorities_extends_defaults() {
+        let config = Config::from_toml("[priorities]\nurgent = 20\nhigh = 10\n").unwrap();
+        let levels = &config.priorities;
+        assert_eq!(
+            levels
+                .resolve(&PriorityArg::Named("urgent".into()))
+                .unwrap(),
+            20
+        );
+        assert_eq!(levels.name(10), Some("high"));
+        assert_eq!(levels.name(0), Some("low"));
+
+        assert!(Config::from_toml("[priorities]\n\"Very High\" = 5\n").is_err());
+        assert!(Config::from_toml("[priorities]\n7 = 5\n").is_err());
+    }
+
+    #[test]
+    fn test_default_transitions_require_reopen() {
+        let policy = TransitionPolicy::default();
+        assert!(policy.check("a1b2c3d", Status::Todo, Status::Done).is_ok());
+        assert!(policy
+            .check("a1b2c3d", Status::Paused, Status::Cancelled)
+            .is_ok());
+        assert!(policy.check("a1b2c3d", Status::Done, Status::Done).is_ok());
+        assert!(policy.check("a1b2c3d", Status::Done, Status::Todo).is_ok());
+
+        match policy.check("a1b2c3d", Status::Cancelled, Status::InProgress) {
+            Err(WireError::InvalidTransition {
+                from, to, allowed, ..
+            }) => {
+                assert_eq!(from, Status::Cancelled);
+                assert_eq!(to, Status::InProgress);
+                assert_eq!(allowed, vec![Status::Todo]);
+            }
+            other => panic!("expected InvalidTransition, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_transitions_overrides_listed_statuses() {
+        let config = Config::from_toml(
+            "[transitions]\nCANCELLED = [\"TODO\", \"DONE\"]\nTODO = [\"IN_PROGRESS\"]\n",
+        )
+        .unwrap();
+        let policy = &config.transitions;
+        assert_eq!(
+            policy.allowed(Status::Cancelled),
+            vec![Status::Todo, Status::Done]
+        );
+        assert_eq!(policy.allowed(Status::Todo), vec![Status::InProgress]);
+        assert!(policy.check("a1b2c3d", Status::Todo, Status::Done).is_err());
+        // Unlisted statuses keep the defaults
+        assert_eq!(policy.allowed(Status::Done), vec![Status::Todo]);
+
+        assert!(Config::from_toml("[transitions]\nDONE = [\"ARCHIVED\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_title_policy_defaults_only_trim() {
+        let policy = TitlePolicy::default();
+        assert_eq!(policy.normalize("  Fix  the 🐛  ").unwrap(), "Fix  the 🐛");
+        assert_eq!(
+            policy.normalize("   "),
+            Err(WireConstructionError::EmptyTitle)
+        );
+    }
+
+    #[test]
+    fn test_title_policy_normalizes() {
+        let config = Config::from_toml(
+            "[titles]\ncollapse_whitespace = true\nstrip_emoji = true\nmax_length = 12\n",
+        )
+        .unwrap();
+        let policy = &config.titles;
+
+        assert_eq!(policy.normalize("Fix\t the\n bug").unwrap(), "Fix the bug");
+        assert_eq!(policy.normalize("🚀 Ship it ✅").unwrap(), "Ship it");
+        assert_eq!(policy.normalize("👨‍👩‍👧 Family").unwrap(), "Family");
+        assert_eq!(policy.normalize("Café #1").unwrap(), "Café #1");
+        assert_eq!(
+            policy.normalize("🎉"),
+            Err(WireConstructionError::EmptyTitle)
+        );
+        assert_eq!(
+            policy.normalize("Thirteen char"),
+            Err(WireConstructionError::TitleTooLong {
+                length: 13,
+                max: 12
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_display_settings() {
+        let config =
+            Config::from_toml("format = \"markdown\"\ndefault_priority = 5\ncolor = \"never\"\n")
+                .unwrap();
+        assert_eq!(config.format, Some(Format::Markdown));
+        assert_eq!(config.default_priority, 5);
+        assert_eq!(config.color, ColorChoice::Never);
+
+        assert!(Config::from_toml("format = \"yaml\"").is_err());
+        assert!(Config::from_toml("color = \"sometimes\"").is_err());
+    }
+
+    #[test]
+    fn test_ready_weights() {
+        let weights = ReadyConfig::default();
+        assert_eq!(weights.score(3, 10 * 86_400, 4), 3);
+        assert!(weights.ranks_by_priority());
+        assert!(weights.cancelled_blocks);
+
+        let config = Config::from_toml("[ready]\nage = 2\nunblocks = 5\n").unwrap();
+        assert_eq!(config.ready.priority, 1);
+        assert_eq!(config.ready.score(3, 10 * 86_400 + 5, 4), 3 + 20 + 20);
+        assert!(!config.ready.ranks_by_priority());
+
+        let config = Config::from_toml("[ready]\ncancelled_blocks = false\n").unwrap();
+        assert!(!config.ready.cancelled_blocks);
+        assert!(config.ready.ranks_by_priority());
+    }
+
+    #[test]
+    fn test_parse_duplicates() {
+        let duplicates = Config::default().duplicates;
+        assert_eq!(duplicates.threshold, 80);
+        assert!(!duplicates.warn_on_new);
+
+        let config = Config::from_toml(
+            "[duplicates]
+threshold = 95
+warn_on_new = true
+",
+        )
+        .unwrap();
+        assert_eq!(config.duplicates.threshold, 95);
+        assert!(config.duplicates.warn_on_new);
+
+        assert!(Config::from_toml(
+            "[duplicates]
+threshold = 101
+"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_retention() {
+        assert_eq!(Config::default().retention, RetentionPolicy::default());
+
+        let config =
+            Config::from_toml("[retention]\nhistory = \"90d\"\nbackups = \"2w\"\n").unwrap();
+        let retention = &config.retention;
+        assert_eq!(retention.history.as_ref().unwrap().as_secs(), 90 * 86_400);
+        assert_eq!(retention.backups.as_ref().unwrap().as_secs(), 14 * 86_400);
+        assert!(retention.archived.is_none());
+        // Written back as given
+        assert!(toml::to_string(&config)
+            .unwrap()
+            .contains("history = \"90d\""));
+
+        assert!(Config::from_toml("[retention]\nhistory = \"soon\"\n").is_err());
+        assert!(Config::from_toml("[retention]\ntrash = \"30d\"\n").is_err());
+    }
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::from_file(&temp_dir.path().join(CONFIG_NAME)).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_rejects_unknown_keys_and_statuses() {
+        assert!(Config::from_toml("hide_status = []").is_err());
+        assert!(Config::from_toml(r#"hide_statuses = ["ABANDONED"]"#).is_err());
+    }
+}
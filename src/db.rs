@@ -4,21 +4,196 @@
 //! - Initialization and schema creation
 //! - Wire CRUD operations
 //! - Dependency management with circular dependency detection
+//! - Tagging
 //! - Finding ready-to-work wires
+//! - Aggregate numbers for `wr stats` (in [`stats`])
 //!
 //! The database is stored in `.wires/wires.db` and uses WAL mode for
 //! concurrent access support.
 
 use anyhow::{Context, Result};
-use rusqlite::Connection;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::models::WireError;
 
+pub mod stats;
+
+pub use stats::{accuracy_report, epic_status_stats, status_stats};
+
 const WIRES_DIR: &str = ".wires";
 const DB_NAME: &str = "wires.db";
 
+/// Schema migrations applied on top of the base schema, in order.
+///
+/// The database's `user_version` pragma records how many have been applied.
+/// Append new entries; never edit or reorder existing ones.
+const MIGRATIONS: &[&str] = &[
+    // 1: tags
+    "CREATE TABLE tags (
+        wire_id TEXT NOT NULL,
+        tag TEXT NOT NULL,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE,
+        PRIMARY KEY (wire_id, tag)
+    );
+    CREATE INDEX idx_tags_tag ON tags(tag);",
+    // 2: claims held by agents
+    "CREATE TABLE leases (
+        wire_id TEXT PRIMARY KEY,
+        agent TEXT NOT NULL,
+        claimed_at INTEGER NOT NULL,
+        expires_at INTEGER NOT NULL,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE
+    );",
+    // 3: event log (no foreign key: history outlives deleted wires)
+    "CREATE TABLE events (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        at INTEGER NOT NULL,
+        wire_id TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX idx_events_at ON events(at);
+    CREATE INDEX idx_events_wire_id ON events(wire_id);",
+    // 4: full-text index over titles and descriptions, kept in sync by triggers
+    "CREATE VIRTUAL TABLE wires_fts USING fts5(
+        wire_id UNINDEXED,
+        title,
+        description,
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+    INSERT INTO wires_fts (wire_id, title, description)
+        SELECT id, title, COALESCE(description, '') FROM wires;
+    CREATE TRIGGER wires_fts_insert AFTER INSERT ON wires BEGIN
+        INSERT INTO wires_fts (wire_id, title, description)
+        VALUES (new.id, new.title, COALESCE(new.description, ''));
+    END;
+    CREATE TRIGGER wires_fts_update AFTER UPDATE OF title, description ON wires BEGIN
+        DELETE FROM wires_fts WHERE wire_id = old.id;
+        INSERT INTO wires_fts (wire_id, title, description)
+        VALUES (new.id, new.title, COALESCE(new.description, ''));
+    END;
+    CREATE TRIGGER wires_fts_delete AFTER DELETE ON wires BEGIN
+        DELETE FROM wires_fts WHERE wire_id = old.id;
+    END;",
+    // 5: responses of commands run with an idempotency key
+    "CREATE TABLE idempotency_keys (
+        key TEXT PRIMARY KEY,
+        command TEXT NOT NULL,
+        response TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );",
+    // 6: conditions outside the repo that wires wait on
+    "CREATE TABLE external_blockers (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        reason TEXT NOT NULL,
+        created_at INTEGER NOT NULL,
+        cleared_at INTEGER
+    );
+    CREATE TABLE external_blocks (
+        blocker_id INTEGER NOT NULL,
+        wire_id TEXT NOT NULL,
+        FOREIGN KEY (blocker_id) REFERENCES external_blockers(id) ON DELETE CASCADE,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE,
+        PRIMARY KEY (blocker_id, wire_id)
+    );
+    CREATE INDEX idx_external_blocks_wire_id ON external_blocks(wire_id);",
+    // 7: drop rows orphaned while foreign keys were not enforced
+    "DELETE FROM dependencies
+        WHERE wire_id NOT IN (SELECT id FROM wires)
+        OR depends_on NOT IN (SELECT id FROM wires);
+    DELETE FROM tags WHERE wire_id NOT IN (SELECT id FROM wires);
+    DELETE FROM leases WHERE wire_id NOT IN (SELECT id FROM wires);
+    DELETE FROM external_blocks
+        WHERE wire_id NOT IN (SELECT id FROM wires)
+        OR blocker_id NOT IN (SELECT id FROM external_blockers);",
+    // 8: undo bookkeeping (undone events, and events written by an undo)
+    "ALTER TABLE events ADD COLUMN undone INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE events ADD COLUMN undo_of INTEGER;",
+    // 9: outbox consumers and their acknowledged position in the event log
+    "CREATE TABLE outbox_consumers (
+        consumer TEXT PRIMARY KEY,
+        acked_seq INTEGER NOT NULL DEFAULT 0,
+        updated_at INTEGER NOT NULL
+    );",
+    // 10: finished wires hidden from everyday views
+    "CREATE TABLE archived_wires (
+        wire_id TEXT PRIMARY KEY,
+        archived_at INTEGER NOT NULL,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE
+    );",
+    // 11: IDs handed out by `wr reserve` for wires not created yet
+    "CREATE TABLE reserved_ids (
+        id TEXT PRIMARY KEY,
+        reserved_at INTEGER NOT NULL
+    );",
+    // 12: why a wire was paused, kept while it stays PAUSED
+    "CREATE TABLE pauses (
+        wire_id TEXT PRIMARY KEY,
+        reason TEXT NOT NULL,
+        paused_at INTEGER NOT NULL,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE
+    );",
+    // 13: why a wire was cancelled (see CancelReason)
+    "ALTER TABLE wires ADD COLUMN cancel_reason TEXT;",
+    // 14: what a BLOCKED wire is waiting for
+    "ALTER TABLE wires ADD COLUMN blocked_reason TEXT;",
+    // 15: who a wire is assigned to
+    "ALTER TABLE wires ADD COLUMN assignee TEXT;
+    CREATE INDEX idx_wires_assignee ON wires(assignee);",
+    // 16: wire timestamps in milliseconds, and a creation sequence that
+    // orders wires created in the same millisecond
+    "UPDATE wires SET created_at = created_at * 1000, updated_at = updated_at * 1000;
+    ALTER TABLE wires ADD COLUMN seq INTEGER;
+    UPDATE wires SET seq = rowid;
+    CREATE UNIQUE INDEX idx_wires_seq ON wires(seq);
+    CREATE TRIGGER wires_seq_insert AFTER INSERT ON wires WHEN new.seq IS NULL BEGIN
+        UPDATE wires SET seq = (SELECT COALESCE(MAX(seq), 0) + 1 FROM wires)
+        WHERE rowid = new.rowid;
+    END;",
+    // 17: how far each `wr list --since-last` caller has read
+    "CREATE TABLE list_cursors (
+        name TEXT PRIMARY KEY,
+        read_until INTEGER NOT NULL
+    );",
+    // 18: shell commands that must succeed for a wire to count as ready
+    // under `wr ready --check-preconditions`
+    "CREATE TABLE preconditions (
+        wire_id TEXT PRIMARY KEY,
+        command TEXT NOT NULL,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE
+    );",
+    // 19: how `wr graph` draws a wire (rank group, cluster, color)
+    "CREATE TABLE layout_hints (
+        wire_id TEXT PRIMARY KEY,
+        rank TEXT,
+        cluster TEXT,
+        color TEXT,
+        FOREIGN KEY (wire_id) REFERENCES wires(id) ON DELETE CASCADE
+    );",
+    // 20: what a dependency means (see DependencyKind); only `blocks`
+    // edges hold a wire back
+    "ALTER TABLE dependencies ADD COLUMN kind TEXT NOT NULL DEFAULT 'blocks';",
+    // 21: name the millisecond wire timestamps apart from the other tables'
+    // timestamps, which are in seconds
+    "ALTER TABLE wires RENAME COLUMN created_at TO created_at_ms;
+    ALTER TABLE wires RENAME COLUMN updated_at TO updated_at_ms;",
+];
+
+/// Columns selected for a wire row (aliased as `w`), in the order
+/// `wire_from_row` expects. Tags are aggregated into a sorted,
+/// comma-separated list.
+const WIRE_COLUMNS: &str =
+    "w.id, w.title, w.description, w.status, w.created_at_ms, w.updated_at_ms, w.priority,
+    (SELECT group_concat(tag, ',') FROM (SELECT tag FROM tags WHERE wire_id = w.id ORDER BY tag)),
+    w.cancel_reason, w.blocked_reason, w.assignee";
+
+/// Number of columns in [`WIRE_COLUMNS`]; extra columns selected after them
+/// start at this index
+const WIRE_COLUMN_COUNT: usize = 11;
+
 /// Initializes a new wires database in the specified directory.
 ///
 /// Creates a `.wires/` directory containing a SQLite database with
@@ -53,13 +228,177 @@ pub fn init(path: &Path) -> Result<()> {
     fs::create_dir(&wires_dir).context("Failed to create .wires directory")?;
 
     let db_path = wires_dir.join(DB_NAME);
-    let conn = Connection::open(&db_path).context("Failed to create database")?;
+    let conn = connect(&db_path).context("Failed to create database")?;
 
     create_schema(&conn)?;
+    migrate(&conn)?;
 
     Ok(())
 }
 
+/// Recreates the database at `path`, backing up the existing one.
+///
+/// If `.wires/wires.db` exists it is copied to
+/// `.wires/wires.db.<timestamp>.bak` (a consistent copy, including any
+/// changes still in the write-ahead log) and then replaced by an empty
+/// database with the current schema.
+///
+/// # Returns
+///
+/// The path of the backup, or `None` if there was no database to back up.
+pub fn reinit(path: &Path) -> Result<Option<PathBuf>> {
+    let wires_dir = path.join(WIRES_DIR);
+    let db_path = wires_dir.join(DB_NAME);
+
+    if !wires_dir.exists() {
+        init(path)?;
+        return Ok(None);
+    }
+
+    let backup = if db_path.exists() {
+        let backup_path = wires_dir.join(format!("{}.{}.bak", DB_NAME, crate::time::now()));
+        if backup_path.exists() {
+            anyhow::bail!("Backup {} already exists", backup_path.display());
+        }
+
+        let conn = Connection::open(&db_path).context("Failed to open database")?;
+        conn.execute("VACUUM INTO ?1", [backup_path.to_string_lossy().as_ref()])
+            .context("Failed to back up database")?;
+        drop(conn);
+
+        for suffix in ["", "-wal", "-shm"] {
+            let file = wires_dir.join(format!("{}{}", DB_NAME, suffix));
+            if file.exists() {
+                fs::remove_file(&file)
+                    .with_context(|| format!("Failed to remove {}", file.display()))?;
+            }
+        }
+
+        Some(backup_path)
+    } else {
+        None
+    };
+
+    let conn = connect(&db_path).context("Failed to create database")?;
+    create_schema(&conn)?;
+    migrate(&conn)?;
+
+    Ok(backup)
+}
+
+/// Applies any pending schema migrations to the repository at `path`.
+///
+/// # Returns
+///
+/// The schema version before and after the upgrade; they are equal if the
+/// database was already up to date.
+///
+/// # Errors
+///
+/// Returns [`WireError::NotARepository`] if there is no database at `path`.
+pub fn upgrade(path: &Path) -> Result<(i64, i64)> {
+    let db_path = path.join(WIRES_DIR).join(DB_NAME);
+    if !db_path.exists() {
+        return Err(WireError::NotARepository.into());
+    }
+
+    let conn = connect(&db_path).context("Failed to open database")?;
+    let before: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    migrate(&conn)?;
+    let after: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    Ok((before, after))
+}
+
+/// Schema version this build creates and migrates databases to.
+pub const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// Reads the schema version of the database at `db_path` without migrating it.
+pub fn repo_schema_version(db_path: &Path) -> Result<i64> {
+    let conn = Connection::open_with_flags(db_path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .context("Failed to open database")?;
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// How long [`check_health`] waits for the write lock
+const PING_LOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Checks that the repository can be used, without changing it.
+///
+/// The database must be found and readable, its schema must be one this
+/// build can migrate, and it must accept writes. With `lock`, the write lock
+/// is also taken and released, waiting up to five seconds for other writers.
+/// Failed checks are listed in the report's `problems` rather than returned
+/// as errors, so callers always get the full picture.
+pub fn check_health(lock: bool) -> Result<crate::models::HealthReport> {
+    let mut report = crate::models::HealthReport {
+        ok: false,
+        database: None,
+        schema_version: None,
+        supported_schema_version: SCHEMA_VERSION,
+        writable: false,
+        lock: None,
+        problems: Vec::new(),
+    };
+
+    let db_path = match find_db() {
+        Ok(db_path) => db_path,
+        Err(e) => {
+            report.problems.push(e.to_string());
+            return Ok(report);
+        }
+    };
+    report.database = Some(db_path.display().to_string());
+
+    let conn = match connect(&db_path) {
+        Ok(conn) => conn,
+        Err(e) => {
+            report
+                .problems
+                .push(format!("Failed to open database: {}", e));
+            return Ok(report);
+        }
+    };
+    match conn.pragma_query_value(None, "user_version", |row| row.get::<_, i64>(0)) {
+        Ok(version) => {
+            report.schema_version = Some(version);
+            if version > SCHEMA_VERSION {
+                report.problems.push(format!(
+                    "Schema version {} is newer than this build supports ({}); upgrade wr",
+                    version, SCHEMA_VERSION
+                ));
+            }
+        }
+        Err(e) => report
+            .problems
+            .push(format!("Failed to read database: {}", e)),
+    }
+
+    report.writable = !conn.is_readonly(rusqlite::DatabaseName::Main)?;
+    if !report.writable {
+        report.problems.push("Database is read-only".to_string());
+    }
+
+    if lock {
+        conn.busy_timeout(PING_LOCK_TIMEOUT)?;
+        let started = std::time::Instant::now();
+        let acquired = Transaction::new_unchecked(&conn, TransactionBehavior::Immediate)
+            .and_then(|tx| tx.rollback());
+        if let Err(e) = &acquired {
+            report
+                .problems
+                .push(format!("Failed to take the write lock: {}", e));
+        }
+        report.lock = Some(crate::models::LockCheck {
+            acquired: acquired.is_ok(),
+            wait_ms: started.elapsed().as_millis() as u64,
+        });
+    }
+
+    report.ok = report.problems.is_empty();
+    Ok(report)
+}
+
 /// Create the database schema
 fn create_schema(conn: &Connection) -> Result<()> {
     // Enable WAL mode for concurrent access
@@ -100,18 +439,105 @@ fn create_schema(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Applies any schema migrations the database hasn't seen yet.
+///
+/// Runs inside an immediate transaction and re-checks the version once the
+/// write lock is held, so concurrent invocations don't apply a migration twice.
+fn migrate(conn: &Connection) -> Result<()> {
+    let target = SCHEMA_VERSION;
+
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version >= target {
+        return Ok(());
+    }
+
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version.max(0) as usize) {
+        conn.execute_batch(migration)
+            .with_context(|| format!("Failed to apply schema migration {}", i + 1))?;
+    }
+
+    conn.pragma_update(None, "user_version", target)?;
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// Environment variable naming the repository to use, instead of searching
+/// upward from the current directory
+pub const DIR_ENV: &str = "WIRES_DIR";
+
+/// The repository directory named by `WIRES_DIR`, if it is set.
+pub fn env_dir() -> Option<PathBuf> {
+    std::env::var_os(DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The directory commands work on: `WIRES_DIR` if set, otherwise the
+/// current directory.
+///
+/// # Errors
+///
+/// Returns an error if the current directory can't be determined.
+pub fn base_dir() -> Result<PathBuf> {
+    match env_dir() {
+        Some(dir) => Ok(dir),
+        None => std::env::current_dir().context("Failed to get current directory"),
+    }
+}
+
 /// Finds the wires database by searching up the directory tree.
 ///
 /// Like git, this searches from the current directory upward until it
-/// finds a `.wires/` directory containing the database.
+/// finds a `.wires/` directory containing the database. If `WIRES_DIR` is
+/// set, only that directory is checked.
+///
+/// Once [`use_db`] has chosen a database, returns it without searching.
 ///
 /// # Errors
 ///
 /// Returns an error if no `.wires/` directory is found in any parent directory.
 pub fn find_db() -> Result<PathBuf> {
+    if let Some(db_path) = CHOSEN_DB.get() {
+        return Ok(db_path.clone());
+    }
+    if let Some(dir) = env_dir() {
+        let db_path = dir.join(WIRES_DIR).join(DB_NAME);
+        if !db_path.exists() {
+            return Err(WireError::NotARepository.into());
+        }
+        crate::format::diag(format_args!(
+            "database {} (from {})",
+            db_path.display(),
+            DIR_ENV
+        ));
+        return Ok(db_path);
+    }
+
     let current_dir = std::env::current_dir().context("Failed to get current directory")?;
 
-    find_db_from(&current_dir)
+    let db_path = find_db_from(&current_dir)?;
+    crate::format::diag(format_args!(
+        "database {} (found from {})",
+        db_path.display(),
+        current_dir.display()
+    ));
+    Ok(db_path)
+}
+
+/// The database given to [`use_db`]
+static CHOSEN_DB: OnceLock<PathBuf> = OnceLock::new();
+
+/// Makes [`find_db`], and so [`open`], return `db_path` for the rest of the
+/// process instead of searching again.
+///
+/// Only the first call has any effect. `wr` locates the database once at
+/// startup and hands it over here.
+pub fn use_db(db_path: PathBuf) {
+    let _ = CHOSEN_DB.set(db_path);
 }
 
 /// Find the wires database starting from a specific directory
@@ -136,6 +562,7 @@ fn find_db_from(start: &Path) -> Result<PathBuf> {
 /// Opens a connection to the wires database.
 ///
 /// Searches for the database using [`find_db`], then opens a connection to it.
+/// Set `WIRES_DIR` to open a specific repository instead.
 ///
 /// # Errors
 ///
@@ -149,12 +576,67 @@ fn find_db_from(start: &Path) -> Result<PathBuf> {
 /// let conn = db::open().expect("Not in a wires repository");
 /// ```
 pub fn open() -> Result<Connection> {
-    let db_path = find_db()?;
-    Connection::open(db_path).context("Failed to open database")
+    open_db(&find_db()?)
+}
+
+/// Opens the wires database for the repository containing `start`.
+///
+/// Like [`open`], but searches upward from `start` instead of the current
+/// directory, so one process can read several repositories.
+///
+/// # Errors
+///
+/// Returns an error if no database is found or the connection fails.
+pub fn open_at(start: &Path) -> Result<Connection> {
+    let db_path = find_db_from(start)?;
+    crate::format::diag(format_args!(
+        "database {} (found from {})",
+        db_path.display(),
+        start.display()
+    ));
+    open_db(&db_path)
+}
+
+/// Connect to the database at `db_path` and bring its schema up to date
+fn open_db(db_path: &Path) -> Result<Connection> {
+    let conn = connect(db_path).context("Failed to open database")?;
+    migrate(&conn)?;
+    Ok(conn)
+}
+
+/// Opens a connection with foreign key enforcement turned on.
+///
+/// SQLite leaves foreign keys off by default, per connection, so every
+/// connection the crate hands out goes through here. With enforcement on,
+/// deleting a wire cascades to its dependency, tag, claim and blocker rows,
+/// and rows pointing at missing wires are rejected.
+///
+/// With [`Verbosity::Verbose`](crate::format::Verbosity::Verbose), each
+/// statement is logged with how long it took.
+fn connect(db_path: &Path) -> Result<Connection> {
+    let mut conn = Connection::open(db_path)?;
+    if crate::format::verbosity() == crate::format::Verbosity::Verbose {
+        conn.profile(Some(log_statement));
+    }
+    conn.pragma_update(None, "foreign_keys", true)?;
+    Ok(conn)
+}
+
+/// Logs a statement SQLite finished running, for `--verbose`
+fn log_statement(sql: &str, elapsed: std::time::Duration) {
+    let sql = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+    crate::format::diag(format_args!(
+        "sql {:.3}ms: {}",
+        elapsed.as_secs_f64() * 1000.0,
+        sql
+    ));
 }
 
 /// Inserts a new wire into the database.
 ///
+/// The title is first normalized by the repository's `[titles]` config,
+/// and `wire` is updated to match what was stored.
+///
 /// # Arguments
 ///
 /// * `conn` - Database connection
@@ -162,19 +644,90 @@ pub fn open() -> Result<Connection> {
 ///
 /// # Errors
 ///
-/// Returns an error if the insert fails (e.g., duplicate ID).
-pub fn insert_wire(conn: &Connection, wire: &crate::models::Wire) -> Result<()> {
+/// Returns an error if the title is rejected by `[titles]` or the insert
+/// fails (e.g., duplicate ID).
+pub fn insert_wire(conn: &Connection, wire: &mut crate::models::Wire) -> Result<()> {
+    wire.title = title_policy(conn)?.normalize(&wire.title)?;
+    insert_wire_unchecked(conn, wire)
+}
+
+/// Like [`insert_wire`], but stores the title as given. Undo and import use
+/// this for titles that are already normalized.
+fn insert_wire_unchecked(conn: &Connection, wire: &crate::models::Wire) -> Result<()> {
+    in_transaction(conn, || {
+        insert_wire_row(conn, wire)?;
+        record_event(
+            conn,
+            wire.id.as_str(),
+            crate::models::EventKind::Created,
+            serde_json::to_value(wire)?,
+        )
+    })
+}
+
+/// Allocates `count` fresh wire IDs without creating any wires.
+///
+/// The IDs are unique among existing wires and earlier reservations. Pass
+/// one to [`insert_reserved_wire`] to create the wire later.
+pub fn reserve_ids(conn: &Connection, count: usize) -> Result<Vec<crate::models::WireId>> {
+    in_transaction(conn, || {
+        let now = crate::time::now();
+        let scheme = id_scheme(conn)?;
+        let mut ids = Vec::with_capacity(count);
+        while ids.len() < count {
+            let id = scheme.generate(&format!("reserved-{}", ids.len()));
+            let taken: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM wires WHERE id = ?1)
+                     OR EXISTS(SELECT 1 FROM reserved_ids WHERE id = ?1)",
+                [&id],
+                |row| row.get(0),
+            )?;
+            if taken {
+                continue;
+            }
+            conn.execute(
+                "INSERT INTO reserved_ids (id, reserved_at) VALUES (?1, ?2)",
+                rusqlite::params![&id, now],
+            )?;
+            ids.push(id);
+        }
+        Ok(ids)
+    })
+}
+
+/// Inserts a wire whose ID was allocated by [`reserve_ids`], using up the
+/// reservation.
+///
+/// # Errors
+///
+/// Returns [`WireError::NotReserved`] if `wire.id` isn't an outstanding
+/// reservation (never reserved, or already used).
+pub fn insert_reserved_wire(conn: &Connection, wire: &mut crate::models::Wire) -> Result<()> {
+    in_transaction(conn, || {
+        let removed = conn.execute("DELETE FROM reserved_ids WHERE id = ?1", [&wire.id])?;
+        if removed == 0 {
+            return Err(WireError::NotReserved(wire.id.to_string()).into());
+        }
+        insert_wire(conn, wire)
+    })
+}
+
+fn insert_wire_row(conn: &Connection, wire: &crate::models::Wire) -> Result<()> {
     conn.execute(
-        "INSERT INTO wires (id, title, description, status, created_at, updated_at, priority)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        "INSERT INTO wires (id, title, description, status, created_at_ms, updated_at_ms, priority,
+                            cancel_reason, blocked_reason, assignee)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
         rusqlite::params![
             &wire.id,
             &wire.title,
             wire.description.as_deref().unwrap_or(""),
             wire.status.as_str(),
-            wire.created_at,
-            wire.updated_at,
+            wire.created_at_ms,
+            wire.updated_at_ms,
             wire.priority,
+            wire.cancel_reason.as_ref().map(|r| r.to_string()),
+            wire.blocked_reason,
+            wire.assignee,
         ],
     )?;
     Ok(())
@@ -182,7 +735,7 @@ pub fn insert_wire(conn: &Connection, wire: &crate::models::Wire) -> Result<()>
 
 /// Updates one or more fields of a wire.
 ///
-/// Only fields with `Some` values are updated. The `updated_at` timestamp
+/// Only fields with `Some` values are updated. The `updated_at_ms` timestamp
 /// is automatically set to the current time.
 ///
 /// # Arguments
@@ -193,6 +746,15 @@ pub fn insert_wire(conn: &Connection, wire: &crate::models::Wire) -> Result<()>
 /// * `description` - New description (`Some(Some("desc"))` to set, `Some(None)` to clear)
 /// * `status` - New status
 /// * `priority` - New priority value
+///
+/// A new title is normalized by the repository's `[titles]` config, and a
+/// status change must be allowed by its `[transitions]` config.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist,
+/// [`WireError::InvalidTransition`] if it may not move to `status`, or an
+/// error if `[titles]` rejects the title.
 pub fn update_wire(
     conn: &Connection,
     wire_id: &str,
@@ -201,9 +763,198 @@ pub fn update_wire(
     status: Option<crate::models::Status>,
     priority: Option<i32>,
 ) -> Result<()> {
-    use std::time::{SystemTime, UNIX_EPOCH};
+    let title = match title {
+        Some(title) => Some(title_policy(conn)?.normalize(title)?),
+        None => None,
+    };
+    in_transaction(conn, || {
+        if let Some(to) = status {
+            let Some(wire) = fetch_wire(conn, wire_id)? else {
+                return Err(WireError::WireNotFound(wire_id.to_string()).into());
+            };
+            transition_policy(conn)?.check(wire_id, wire.status, to)?;
+        }
+        update_wire_unchecked(
+            conn,
+            wire_id,
+            title.as_deref(),
+            description,
+            status,
+            priority,
+        )
+    })
+}
+
+/// Moves a wire's priority up by `delta`, or down if it is negative, and
+/// returns its priority before and after.
+///
+/// The change is recorded like any other update, so `wr undo` reverts it.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist.
+pub fn adjust_priority(conn: &Connection, wire_id: &str, delta: i32) -> Result<(i32, i32)> {
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+        let priority = wire.priority.saturating_add(delta);
+        update_wire_unchecked(conn, wire_id, None, None, None, Some(priority))?;
+        Ok((wire.priority, priority))
+    })
+}
+
+/// The config of the repository `conn` belongs to (see
+/// [`crate::config::for_db`])
+fn repo_config(conn: &Connection) -> Result<crate::config::Config> {
+    match conn.path().filter(|path| !path.is_empty()) {
+        Some(path) => crate::config::for_db(Path::new(path)),
+        None => Ok(Default::default()),
+    }
+}
+
+/// The status transitions configured for the repository `conn` belongs to
+fn transition_policy(conn: &Connection) -> Result<crate::config::TransitionPolicy> {
+    Ok(repo_config(conn)?.transitions)
+}
+
+/// The error for a command that only moves wires to `to` from other
+/// statuses than `wire`'s, listing where the transitions configured for the
+/// repository let it go instead
+fn invalid_transition(
+    conn: &Connection,
+    wire: &crate::models::Wire,
+    to: crate::models::Status,
+) -> Result<anyhow::Error> {
+    let mut allowed = transition_policy(conn)?.allowed(wire.status);
+    allowed.retain(|status| *status != to);
+    Ok(WireError::InvalidTransition {
+        wire_id: wire.id.to_string(),
+        from: wire.status,
+        to,
+        allowed,
+    }
+    .into())
+}
+
+/// The title rules configured for the repository `conn` belongs to
+fn title_policy(conn: &Connection) -> Result<crate::config::TitlePolicy> {
+    Ok(repo_config(conn)?.titles)
+}
+
+/// The ID scheme configured for the repository `conn` belongs to
+pub fn id_scheme(conn: &Connection) -> Result<crate::models::IdScheme> {
+    Ok(repo_config(conn)?.id_scheme)
+}
+
+/// Checks the `[permissions]` of the repository whose database is at
+/// `db_path` let `actor` run `operation`, a command path like `tag add`.
+///
+/// Every permission check ends here: `wr` makes it for each command before
+/// running it, and [`authorize`] for changes made through a connection,
+/// such as `WireStore`'s and the TUI's.
+///
+/// # Errors
+///
+/// Returns the error loading the config if it is invalid, so a broken
+/// config denies rather than allows, or [`WireError::PermissionDenied`] if
+/// the permissions don't allow `operation`.
+pub fn authorize_db(db_path: &Path, actor: Option<&str>, operation: &str) -> Result<()> {
+    Ok(crate::config::for_db(db_path)?
+        .permissions
+        .check(actor, operation)?)
+}
+
+/// Like [`authorize_db`], for the repository `conn` belongs to. A database
+/// without a file has no config, so nothing is restricted.
+///
+/// # Errors
+///
+/// See [`authorize_db`].
+pub fn authorize(conn: &Connection, actor: Option<&str>, operation: &str) -> Result<()> {
+    match conn.path().filter(|path| !path.is_empty()) {
+        Some(path) => authorize_db(Path::new(path), actor, operation),
+        None => Ok(()),
+    }
+}
+
+/// Like [`update_wire`], but allows any status change. Undo uses this to
+/// put a wire back the way it was.
+fn update_wire_unchecked(
+    conn: &Connection,
+    wire_id: &str,
+    title: Option<&str>,
+    description: Option<Option<&str>>,
+    status: Option<crate::models::Status>,
+    priority: Option<i32>,
+) -> Result<()> {
+    use serde_json::{json, Map};
+
+    in_transaction(conn, || {
+        let Some(before) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+
+        // Record only the fields whose value actually changes. Empty
+        // descriptions are stored as "" but reported as null.
+        let mut changes = Map::new();
+        if let Some(t) = title.filter(|t| *t != before.title) {
+            changes.insert("title".into(), json!({ "from": before.title, "to": t }));
+        }
+        if let Some(d) = description
+            .map(|d| d.filter(|d| !d.is_empty()))
+            .filter(|d| *d != before.description.as_deref())
+        {
+            changes.insert(
+                "description".into(),
+                json!({ "from": before.description, "to": d }),
+            );
+        }
+        if let Some(st) = status.filter(|st| *st != before.status) {
+            changes.insert("status".into(), json!({ "from": before.status, "to": st }));
+        }
+        if let Some(p) = priority.filter(|p| *p != before.priority) {
+            changes.insert(
+                "priority".into(),
+                json!({ "from": before.priority, "to": p }),
+            );
+        }
+
+        write_wire_update(conn, wire_id, title, description, status, priority)?;
+
+        // Pause notes and cancel and block reasons only describe the
+        // current status
+        if changes.contains_key("status") {
+            conn.execute("DELETE FROM pauses WHERE wire_id = ?1", [wire_id])?;
+            conn.execute(
+                "UPDATE wires SET cancel_reason = NULL, blocked_reason = NULL WHERE id = ?1",
+                [wire_id],
+            )?;
+        }
 
-    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        if !changes.is_empty() {
+            record_event(
+                conn,
+                wire_id,
+                crate::models::EventKind::Updated,
+                changes.into(),
+            )?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Write the given fields of a wire, without recording an event
+fn write_wire_update(
+    conn: &Connection,
+    wire_id: &str,
+    title: Option<&str>,
+    description: Option<Option<&str>>,
+    status: Option<crate::models::Status>,
+    priority: Option<i32>,
+) -> Result<()> {
+    let now = crate::time::now_ms();
 
     let mut query_parts = Vec::new();
 
@@ -227,7 +978,7 @@ pub fn update_wire(
         return Ok(());
     }
 
-    query_parts.push("updated_at = ?");
+    query_parts.push("updated_at_ms = ?");
 
     let query = format!("UPDATE wires SET {} WHERE id = ?", query_parts.join(", "));
 
@@ -288,7 +1039,7 @@ pub fn check_incomplete_dependencies(
         "SELECT w.id, w.title, w.status
          FROM wires w
          JOIN dependencies d ON w.id = d.depends_on
-         WHERE d.wire_id = ?1 AND w.status != 'DONE'",
+         WHERE d.wire_id = ?1 AND d.kind = 'blocks' AND w.status != 'DONE'",
     )?;
 
     let deps = stmt
@@ -307,21 +1058,44 @@ pub fn check_incomplete_dependencies(
 
 /// Map a row to a Wire struct (shared by list_wires, get_wire_with_deps, get_ready_wires)
 fn wire_from_row(row: &rusqlite::Row) -> rusqlite::Result<crate::models::Wire> {
-    use crate::models::{Status, Wire};
+    use crate::models::{Agent, Status, Tag, Wire};
     use std::str::FromStr;
 
     let description: Option<String> = row.get(2)?;
     let description = description.filter(|s| !s.is_empty());
 
+    let tags: Option<String> = row.get(7)?;
+    let tags = tags
+        .map(|t| {
+            t.split(',')
+                .map(|tag| Tag::new(tag).map_err(|_| rusqlite::Error::InvalidQuery))
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let cancel_reason: Option<String> = row.get(8)?;
+    let cancel_reason = cancel_reason
+        .map(|r| r.parse().map_err(|_| rusqlite::Error::InvalidQuery))
+        .transpose()?;
+    let assignee: Option<String> = row.get(10)?;
+    let assignee = assignee
+        .map(|a| Agent::new(&a).map_err(|_| rusqlite::Error::InvalidQuery))
+        .transpose()?;
+
     Ok(Wire {
         id: row.get(0)?,
         title: row.get(1)?,
         description,
         status: Status::from_str(row.get::<_, String>(3)?.as_str())
             .map_err(|_| rusqlite::Error::InvalidQuery)?,
-        created_at: row.get(4)?,
-        updated_at: row.get(5)?,
+        created_at_ms: row.get(4)?,
+        updated_at_ms: row.get(5)?,
         priority: row.get(6)?,
+        tags,
+        cancel_reason,
+        blocked_reason: row.get(9)?,
+        assignee,
     })
 }
 
@@ -357,7 +1131,8 @@ fn fetch_wire_deps(
         "SELECT w.id, w.title, w.status
          FROM wires w
          JOIN dependencies d ON w.id = d.depends_on
-         WHERE d.wire_id = ?1",
+         WHERE d.wire_id = ?1 AND d.kind = 'blocks'
+         ORDER BY d.rowid",
     )?;
 
     let depends_on = stmt
@@ -369,7 +1144,8 @@ fn fetch_wire_deps(
         "SELECT w.id, w.title, w.status
          FROM wires w
          JOIN dependencies d ON w.id = d.wire_id
-         WHERE d.depends_on = ?1",
+         WHERE d.depends_on = ?1 AND d.kind = 'blocks'
+         ORDER BY d.rowid",
     )?;
 
     let blocks = stmt
@@ -379,42 +1155,122 @@ fn fetch_wire_deps(
     Ok((depends_on, blocks))
 }
 
-/// Lists wires, optionally filtered by status.
+/// Gets the non-blocking dependencies of a wire, in the order they were
+/// added. `relates` links count from either end.
+fn fetch_wire_links(conn: &Connection, wire_id: &str) -> Result<Vec<crate::models::Link>> {
+    let mut stmt = conn.prepare(
+        "SELECT w.id, w.title, w.status, d.kind, d.rowid AS seq
+         FROM wires w
+         JOIN dependencies d ON w.id = d.depends_on
+         WHERE d.wire_id = ?1 AND d.kind != 'blocks'
+         UNION ALL
+         SELECT w.id, w.title, w.status, d.kind, d.rowid AS seq
+         FROM wires w
+         JOIN dependencies d ON w.id = d.wire_id
+         WHERE d.depends_on = ?1 AND d.kind = 'relates'
+         ORDER BY seq",
+    )?;
+    let links = stmt
+        .query_map([wire_id], link_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(links)
+}
+
+/// Reads a [`Link`](crate::models::Link) from `id, title, status, kind` columns.
+fn link_from_row(row: &rusqlite::Row) -> rusqlite::Result<crate::models::Link> {
+    Ok(crate::models::Link {
+        wire: dependency_info_from_row(row)?,
+        kind: row.get(3)?,
+    })
+}
+
+/// Which wires [`list_wires`] returns. Every condition given must hold.
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    /// Only wires in one of these statuses; any status when empty
+    pub statuses: Vec<crate::models::Status>,
+    /// Only wires with at least this priority
+    pub priority_min: Option<i32>,
+    /// Only wires whose title contains this text, ignoring ASCII case
+    pub title_contains: Option<String>,
+    /// Only wires carrying this tag
+    pub tag: Option<crate::models::Tag>,
+    /// Only wires assigned to this agent
+    pub assignee: Option<crate::models::Agent>,
+}
+
+impl ListFilter {
+    /// A filter for wires in `status`.
+    pub fn status(status: crate::models::Status) -> Self {
+        ListFilter {
+            statuses: vec![status],
+            ..ListFilter::default()
+        }
+    }
+}
+
+/// Lists the wires matching `filter`.
 ///
 /// # Arguments
 ///
 /// * `conn` - Database connection
-/// * `status_filter` - Optional status to filter by
+/// * `filter` - Conditions the wires must meet
 ///
 /// # Returns
 ///
-/// A vector of wires ordered by creation date (newest first).
-pub fn list_wires(
-    conn: &Connection,
-    status_filter: Option<crate::models::Status>,
-) -> Result<Vec<crate::models::Wire>> {
-    if let Some(status) = status_filter {
-        let mut stmt = conn.prepare(
-            "SELECT id, title, description, status, created_at, updated_at, priority
-             FROM wires WHERE status = ? ORDER BY created_at DESC",
-        )?;
-        let wires = stmt
-            .query_map([status.as_str()], wire_from_row)?
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(wires)
-    } else {
-        let mut stmt = conn.prepare(
-            "SELECT id, title, description, status, created_at, updated_at, priority
-             FROM wires ORDER BY created_at DESC",
-        )?;
-        let wires = stmt
-            .query_map([], wire_from_row)?
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(wires)
+/// A vector of wires ordered newest first. Wires created in the same
+/// millisecond are ordered by when they were inserted.
+pub fn list_wires(conn: &Connection, filter: &ListFilter) -> Result<Vec<crate::models::Wire>> {
+    use rusqlite::types::Value;
+
+    let mut conditions = Vec::new();
+    let mut params: Vec<Value> = Vec::new();
+    if !filter.statuses.is_empty() {
+        conditions.push(format!(
+            "w.status IN ({})",
+            vec!["?"; filter.statuses.len()].join(", ")
+        ));
+        params.extend(
+            filter
+                .statuses
+                .iter()
+                .map(|status| Value::Text(status.as_str().to_string())),
+        );
+    }
+    if let Some(min) = filter.priority_min {
+        conditions.push("w.priority >= ?".to_string());
+        params.push(Value::Integer(min.into()));
     }
+    if let Some(text) = &filter.title_contains {
+        conditions.push("instr(lower(w.title), lower(?)) > 0".to_string());
+        params.push(Value::Text(text.clone()));
+    }
+    if let Some(tag) = &filter.tag {
+        conditions
+            .push("EXISTS (SELECT 1 FROM tags t WHERE t.wire_id = w.id AND t.tag = ?)".to_string());
+        params.push(Value::Text(tag.as_str().to_string()));
+    }
+    if let Some(assignee) = &filter.assignee {
+        conditions.push("w.assignee = ?".to_string());
+        params.push(Value::Text(assignee.as_str().to_string()));
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM wires w {} ORDER BY w.created_at_ms DESC, w.seq DESC",
+        WIRE_COLUMNS, where_clause
+    ))?;
+    let wires = stmt
+        .query_map(rusqlite::params_from_iter(params), wire_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(wires)
 }
 
-/// Lists wires with their dependency information, optionally filtered by status.
+/// Lists the wires matching `filter` with their dependency information.
 ///
 /// Similar to `list_wires` but returns full `WireWithDeps` objects including
 /// dependency relationships.
@@ -422,59 +1278,245 @@ pub fn list_wires(
 /// # Arguments
 ///
 /// * `conn` - Database connection
-/// * `status_filter` - Optional status to filter by
+/// * `filter` - Conditions the wires must meet
 ///
 /// # Returns
 ///
 /// A vector of wires with dependencies, ordered by creation date (newest first).
 pub fn list_wires_with_deps(
     conn: &Connection,
-    status_filter: Option<crate::models::Status>,
+    filter: &ListFilter,
 ) -> Result<Vec<crate::models::WireWithDeps>> {
-    use crate::models::WireWithDeps;
+    use crate::models::{DependencyInfo, DependencyKind, Link, Status, WireWithDeps};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    let wires = list_wires(conn, filter)?;
+
+    // Each relation is read once for the whole list rather than per wire
+    let info = |row: &rusqlite::Row, at: usize| -> rusqlite::Result<DependencyInfo> {
+        Ok(DependencyInfo {
+            id: row.get(at)?,
+            title: row.get(at + 1)?,
+            status: Status::from_str(row.get::<_, String>(at + 2)?.as_str())
+                .map_err(|_| rusqlite::Error::InvalidQuery)?,
+        })
+    };
+    let mut depends_on: HashMap<String, Vec<DependencyInfo>> = HashMap::new();
+    let mut blocks: HashMap<String, Vec<DependencyInfo>> = HashMap::new();
+    let mut links: HashMap<String, Vec<Link>> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.title, a.status, b.id, b.title, b.status, d.kind
+         FROM dependencies d
+         JOIN wires a ON a.id = d.wire_id
+         JOIN wires b ON b.id = d.depends_on
+         ORDER BY d.rowid",
+    )?;
+    let edges = stmt.query_map([], |row| {
+        Ok((
+            info(row, 0)?,
+            info(row, 3)?,
+            row.get::<_, DependencyKind>(6)?,
+        ))
+    })?;
+    for edge in edges {
+        let (wire, dependency, kind) = edge?;
+        if !kind.is_blocking() {
+            if kind == DependencyKind::Relates {
+                links
+                    .entry(dependency.id.to_string())
+                    .or_default()
+                    .push(Link {
+                        wire: wire.clone(),
+                        kind,
+                    });
+            }
+            links.entry(wire.id.to_string()).or_default().push(Link {
+                wire: dependency,
+                kind,
+            });
+            continue;
+        }
+        blocks
+            .entry(dependency.id.to_string())
+            .or_default()
+            .push(wire.clone());
+        depends_on
+            .entry(wire.id.to_string())
+            .or_default()
+            .push(dependency);
+    }
+
+    let blockers = list_external_blockers(conn, None)?;
 
-    let wires = list_wires(conn, status_filter)?;
+    let mut pauses = list_pause_notes(conn)?;
 
-    wires
+    Ok(wires
         .into_iter()
         .map(|wire| {
-            let (depends_on, blocks) = fetch_wire_deps(conn, wire.id.as_str())?;
-            Ok(WireWithDeps {
+            let id = wire.id.as_str();
+            WireWithDeps {
+                depends_on: depends_on.remove(id).unwrap_or_default(),
+                blocks: blocks.remove(id).unwrap_or_default(),
+                blocked_on: blockers
+                    .iter()
+                    .filter(|b| b.wires.contains(&wire.id))
+                    .cloned()
+                    .collect(),
+                paused: pauses.remove(&wire.id),
+                links: links.remove(id).unwrap_or_default(),
                 wire,
-                depends_on,
-                blocks,
-            })
+            }
         })
-        .collect()
+        .collect())
 }
 
-/// Gets a wire with its full dependency information.
-///
-/// Returns the wire along with lists of wires it depends on and wires that depend on it.
-///
-/// # Arguments
-///
-/// * `conn` - Database connection
-/// * `wire_id` - ID of the wire to fetch
+/// Which wires [`filter_wires`] selects, as `wr list` and bulk `wr tag` do.
+#[derive(Debug, Clone, Default)]
+pub struct WireFilter {
+    /// Only wires in one of these statuses; any status when empty
+    pub statuses: Vec<crate::models::Status>,
+    /// Only wires with at least this priority
+    pub priority_min: Option<i32>,
+    /// Only wires whose title contains this text, ignoring ASCII case
+    pub title_contains: Option<String>,
+    /// Only wires carrying this tag
+    pub tag: Option<crate::models::Tag>,
+    /// Only wires assigned to this agent
+    pub assignee: Option<crate::models::Agent>,
+    /// Only wires last updated at least this many seconds ago
+    pub older_than: Option<i64>,
+    /// Only wires nothing depends on
+    pub top_level: bool,
+    /// Only wires that depend on nothing
+    pub leaves: bool,
+    /// Include archived wires
+    pub include_archived: bool,
+    /// Statuses left out unless `statuses` asks for one of them
+    pub hidden_statuses: Vec<crate::models::Status>,
+}
+
+/// Lists the wires matching `filter`, with their dependency information.
 ///
-/// # Errors
+/// # Returns
 ///
-/// Returns an error if the wire is not found.
-pub fn get_wire_with_deps(conn: &Connection, wire_id: &str) -> Result<crate::models::WireWithDeps> {
-    use crate::models::WireWithDeps;
-
-    let mut stmt = conn.prepare(
-        "SELECT id, title, description, status, created_at, updated_at, priority
-         FROM wires WHERE id = ?1",
+/// Matching wires, ordered as [`list_wires_with_deps`] orders them.
+pub fn filter_wires(
+    conn: &Connection,
+    filter: &WireFilter,
+) -> Result<Vec<crate::models::WireWithDeps>> {
+    let mut wires = list_wires_with_deps(
+        conn,
+        &ListFilter {
+            statuses: filter.statuses.clone(),
+            priority_min: filter.priority_min,
+            title_contains: filter.title_contains.clone(),
+            tag: filter.tag.clone(),
+            assignee: filter.assignee.clone(),
+        },
     )?;
 
-    let wire = stmt.query_row([wire_id], wire_from_row)?;
+    if let Some(older_than) = filter.older_than {
+        let cutoff = crate::time::now_ms().saturating_sub(older_than.saturating_mul(1000));
+        wires.retain(|wd| wd.wire.updated_at_ms <= cutoff);
+    }
+
+    // Plan structure: nothing depends on top-level wires, leaves depend on nothing
+    if filter.top_level {
+        wires.retain(|wd| wd.blocks.is_empty());
+    }
+    if filter.leaves {
+        wires.retain(|wd| wd.depends_on.is_empty());
+    }
+
+    // An explicit status filter always wins over hidden statuses
+    if filter.statuses.is_empty() {
+        wires.retain(|wd| !filter.hidden_statuses.contains(&wd.wire.status));
+    }
+
+    if !filter.include_archived {
+        let archived = list_archived_ids(conn)?;
+        wires.retain(|wd| !archived.contains(&wd.wire.id));
+    }
+
+    Ok(wires)
+}
+
+/// Lists the wires matching `filter` that were created or updated since
+/// the last call with the same `cursor`, and moves the cursor forward.
+///
+/// The first call with a cursor returns every matching wire. Each call
+/// covers changes up to the millisecond it ran in, exclusive, so a change
+/// made in that same millisecond is returned by the next call rather than
+/// lost. Reading and moving the cursor happen in one transaction.
+///
+/// # Returns
+///
+/// Matching wires, ordered as [`filter_wires`] orders them.
+pub fn filter_wires_since_last(
+    conn: &Connection,
+    filter: &WireFilter,
+    cursor: &crate::models::Consumer,
+) -> Result<Vec<crate::models::WireWithDeps>> {
+    use rusqlite::OptionalExtension;
+
+    in_transaction(conn, || {
+        let since: i64 = conn
+            .query_row(
+                "SELECT read_until FROM list_cursors WHERE name = ?1",
+                [cursor.as_str()],
+                |row| row.get(0),
+            )
+            .optional()?
+            .unwrap_or(0);
+        let until = crate::time::now_ms();
+
+        let mut wires = filter_wires(conn, filter)?;
+        wires.retain(|wd| (since..until).contains(&wd.wire.updated_at_ms));
+
+        conn.execute(
+            "INSERT INTO list_cursors (name, read_until) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET read_until = MAX(read_until, excluded.read_until)",
+            rusqlite::params![cursor.as_str(), until],
+        )?;
+        Ok(wires)
+    })
+}
+
+/// Gets a single wire, or `None` if it doesn't exist.
+pub fn get_wire(conn: &Connection, wire_id: &str) -> Result<Option<crate::models::Wire>> {
+    fetch_wire(conn, wire_id)
+}
+
+/// Gets a wire with its full dependency information.
+///
+/// Returns the wire along with lists of wires it depends on and wires that depend on it.
+///
+/// # Arguments
+///
+/// * `conn` - Database connection
+/// * `wire_id` - ID of the wire to fetch
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire is not found.
+pub fn get_wire_with_deps(conn: &Connection, wire_id: &str) -> Result<crate::models::WireWithDeps> {
+    use crate::models::WireWithDeps;
+
+    let wire =
+        fetch_wire(conn, wire_id)?.ok_or_else(|| WireError::WireNotFound(wire_id.to_string()))?;
     let (depends_on, blocks) = fetch_wire_deps(conn, wire_id)?;
+    let blocked_on = list_external_blockers(conn, Some(wire_id))?;
+    let paused = get_pause_note(conn, wire_id)?;
+    let links = fetch_wire_links(conn, wire_id)?;
 
     Ok(WireWithDeps {
         wire,
         depends_on,
         blocks,
+        blocked_on,
+        paused,
+        links,
     })
 }
 
@@ -484,64 +1526,70 @@ fn would_create_cycle(
     wire_id: &str,
     depends_on: &str,
 ) -> Result<Option<Vec<String>>> {
-    use std::collections::{HashSet, VecDeque};
-
-    // If wire depends on itself, that's a cycle
-    if wire_id == depends_on {
-        return Ok(Some(vec![wire_id.to_string(), wire_id.to_string()]));
-    }
-
-    // DFS to check if we can reach wire_id starting from depends_on
-    let mut visited = HashSet::new();
-    let mut stack = VecDeque::new();
-    let mut parent_map: std::collections::HashMap<String, String> =
-        std::collections::HashMap::new();
-
-    stack.push_back(depends_on.to_string());
-
-    while let Some(current) = stack.pop_back() {
-        if visited.contains(&current) {
-            continue;
-        }
-
-        visited.insert(current.clone());
-
-        // If we reached the original wire, we found a cycle
-        if current == wire_id {
-            // Reconstruct the cycle path
-            let mut path = vec![wire_id.to_string()];
-            let mut node = depends_on.to_string();
-
-            while node != wire_id {
-                path.push(node.clone());
-                if let Some(parent) = parent_map.get(&node) {
-                    node = parent.clone();
-                } else {
-                    break;
-                }
-            }
+    let mut stmt =
+        conn.prepare("SELECT depends_on FROM dependencies WHERE wire_id = ?1 AND kind = 'blocks'")?;
+    crate::graph::cycle_path(wire_id, depends_on, |current| {
+        stmt.query_map([current], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()
+    })
+    .map_err(Into::into)
+}
 
-            path.push(wire_id.to_string());
-            path.reverse();
-            return Ok(Some(path));
-        }
+/// Prefix that turns an ID argument into a title search.
+pub const TITLE_PREFIX: &str = "title:";
 
-        // Get all wires that current depends on
-        let mut stmt = conn.prepare("SELECT depends_on FROM dependencies WHERE wire_id = ?1")?;
+/// Resolves a user-supplied wire reference to a wire ID.
+///
+/// A reference of the form `title:<substring>` is matched case-insensitively
+/// against the titles of open (`TODO`, `IN_PROGRESS`, `PAUSED` or `BLOCKED`)
+/// wires and must identify exactly one. A valid wire ID is returned
+/// normalized, so a ULID may be typed in lowercase; anything else is returned
+/// unchanged and treated as an ID by the caller.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if no open wire matches, or
+/// [`WireError::AmbiguousWire`] listing the candidates if several do.
+///
+/// # Example
+///
+/// ```no_run
+/// use wr::db;
+///
+/// let conn = db::open().expect("Failed to open database");
+/// let id = db::resolve_wire_id(&conn, "title:parser panics").unwrap();
+/// ```
+pub fn resolve_wire_id(conn: &Connection, reference: &str) -> Result<String> {
+    let Some(query) = reference.strip_prefix(TITLE_PREFIX) else {
+        return Ok(crate::models::WireId::new(reference)
+            .map(|id| id.to_string())
+            .unwrap_or_else(|_| reference.to_string()));
+    };
+
+    let query = query.trim();
+    if query.is_empty() {
+        anyhow::bail!("Empty title search: use {}<text>", TITLE_PREFIX);
+    }
 
-        let deps: Vec<String> = stmt
-            .query_map([&current], |row| row.get(0))?
-            .collect::<Result<Vec<_>, _>>()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, title, status FROM wires
+         WHERE status IN ('TODO', 'IN_PROGRESS', 'PAUSED', 'BLOCKED')
+         AND instr(lower(title), lower(?1)) > 0
+         ORDER BY priority DESC, created_at_ms ASC",
+    )?;
+    let mut candidates = stmt
+        .query_map([query], dependency_info_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
-        for dep in deps {
-            if !visited.contains(&dep) {
-                parent_map.insert(dep.clone(), current.clone());
-                stack.push_back(dep);
-            }
+    match candidates.len() {
+        0 => Err(WireError::WireNotFound(reference.to_string()).into()),
+        1 => Ok(candidates.remove(0).id.as_str().to_string()),
+        _ => Err(WireError::AmbiguousWire {
+            query: reference.to_string(),
+            candidates,
         }
+        .into()),
     }
-
-    Ok(None)
 }
 
 /// Adds a dependency between two wires.
@@ -561,39 +1609,163 @@ fn would_create_cycle(
 /// - Either wire does not exist
 /// - The dependency would create a circular dependency
 pub fn add_dependency(conn: &Connection, wire_id: &str, depends_on: &str) -> Result<()> {
-    // Check if both wires exist
-    let wire_exists: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM wires WHERE id = ?1",
-        [wire_id],
-        |row| row.get(0),
-    )?;
+    add_dependency_with_kind(
+        conn,
+        wire_id,
+        depends_on,
+        crate::models::DependencyKind::Blocks,
+    )
+}
 
-    if wire_exists == 0 {
-        return Err(WireError::WireNotFound(wire_id.to_string()).into());
-    }
+/// Adds a dependency of the given kind between two wires, or changes the
+/// kind of an existing one.
+///
+/// Only [`DependencyKind::Blocks`](crate::models::DependencyKind::Blocks)
+/// edges are checked for cycles, since only they hold wires back.
+///
+/// # Errors
+///
+/// Returns an error if either wire does not exist, `wire_id` and
+/// `depends_on` are the same wire, or a blocking dependency would create
+/// a circular dependency.
+pub fn add_dependency_with_kind(
+    conn: &Connection,
+    wire_id: &str,
+    depends_on: &str,
+    kind: crate::models::DependencyKind,
+) -> Result<()> {
+    use rusqlite::OptionalExtension;
+
+    // Check and insert in one write transaction, so two connections can't
+    // each add half of a cycle
+    in_transaction(conn, || {
+        for id in [wire_id, depends_on] {
+            if !wire_exists(conn, id)? {
+                return Err(WireError::WireNotFound(id.to_string()).into());
+            }
+        }
+        if wire_id == depends_on && !kind.is_blocking() {
+            anyhow::bail!("Wire {} can't depend on itself", wire_id);
+        }
 
-    let depends_on_exists: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM wires WHERE id = ?1",
-        [depends_on],
-        |row| row.get(0),
-    )?;
+        let current: Option<crate::models::DependencyKind> = conn
+            .query_row(
+                "SELECT kind FROM dependencies WHERE wire_id = ?1 AND depends_on = ?2",
+                [wire_id, depends_on],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if current == Some(kind) {
+            return Ok(());
+        }
 
-    if depends_on_exists == 0 {
-        return Err(WireError::WireNotFound(depends_on.to_string()).into());
-    }
+        let cycle = if kind.is_blocking() {
+            would_create_cycle(conn, wire_id, depends_on)?
+        } else {
+            None
+        };
+        if let Some(cycle) = cycle {
+            let cycle = cycle
+                .iter()
+                .map(|id| {
+                    conn.query_row(
+                        "SELECT id, title, status FROM wires WHERE id = ?1",
+                        [id],
+                        dependency_info_from_row,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            return Err(WireError::CircularDependency(cycle).into());
+        }
 
-    // Check for circular dependency
-    if let Some(cycle) = would_create_cycle(conn, wire_id, depends_on)? {
-        return Err(WireError::CircularDependency(cycle).into());
-    }
+        conn.execute(
+            "INSERT INTO dependencies (wire_id, depends_on, kind) VALUES (?1, ?2, ?3)
+             ON CONFLICT (wire_id, depends_on) DO UPDATE SET kind = excluded.kind",
+            rusqlite::params![wire_id, depends_on, kind],
+        )?;
 
-    // Add the dependency
-    conn.execute(
-        "INSERT OR IGNORE INTO dependencies (wire_id, depends_on) VALUES (?1, ?2)",
-        [wire_id, depends_on],
-    )?;
+        // A changed kind records the old one, so `wr undo` can put it back
+        let mut data = dependency_event_data(depends_on, kind);
+        if let Some(current) = current {
+            data["from"] = serde_json::json!(current);
+        }
+        record_event(conn, wire_id, crate::models::EventKind::DepAdded, data)?;
 
-    Ok(())
+        Ok(())
+    })
+}
+
+/// Links two wires as related: a symmetric `relates` dependency that never
+/// holds either wire back.
+///
+/// Linking wires that are already related, in either direction, does
+/// nothing.
+///
+/// # Errors
+///
+/// Returns an error if either wire does not exist, the two are the same
+/// wire, or a dependency of another kind already joins them.
+pub fn link_wires(conn: &Connection, wire_id: &str, related: &str) -> Result<()> {
+    use crate::models::DependencyKind;
+
+    in_transaction(conn, || {
+        let mut stmt = conn.prepare(
+            "SELECT kind FROM dependencies
+             WHERE (wire_id = ?1 AND depends_on = ?2) OR (wire_id = ?2 AND depends_on = ?1)",
+        )?;
+        let kinds = stmt
+            .query_map([wire_id, related], |row| row.get::<_, DependencyKind>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(kind) = kinds.iter().find(|k| **k != DependencyKind::Relates) {
+            anyhow::bail!(
+                "Wires {} and {} are already joined by a {} dependency; use `wr dep --kind` to change it",
+                wire_id,
+                related,
+                kind
+            );
+        }
+        if !kinds.is_empty() {
+            return Ok(());
+        }
+
+        add_dependency_with_kind(conn, wire_id, related, DependencyKind::Relates)
+    })
+}
+
+/// Removes the `relates` link between two wires, whichever way it was
+/// added. Other kinds of dependency between them are left alone.
+pub fn unlink_wires(conn: &Connection, wire_id: &str, related: &str) -> Result<()> {
+    use crate::models::DependencyKind;
+    use rusqlite::OptionalExtension;
+
+    in_transaction(conn, || {
+        for (from, to) in [(wire_id, related), (related, wire_id)] {
+            let kind: Option<DependencyKind> = conn
+                .query_row(
+                    "SELECT kind FROM dependencies WHERE wire_id = ?1 AND depends_on = ?2",
+                    [from, to],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if kind == Some(DependencyKind::Relates) {
+                remove_dependency(conn, from, to)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Event data naming the other end of a dependency, and its kind unless
+/// it is blocking.
+fn dependency_event_data(
+    depends_on: &str,
+    kind: crate::models::DependencyKind,
+) -> serde_json::Value {
+    let mut data = serde_json::json!({ "depends_on": depends_on });
+    if !kind.is_blocking() {
+        data["kind"] = serde_json::json!(kind);
+    }
+    data
 }
 
 /// Removes a dependency between two wires.
@@ -604,396 +1776,4994 @@ pub fn add_dependency(conn: &Connection, wire_id: &str, depends_on: &str) -> Res
 /// * `wire_id` - The wire that has the dependency
 /// * `depends_on` - The wire it depends on
 pub fn remove_dependency(conn: &Connection, wire_id: &str, depends_on: &str) -> Result<()> {
-    conn.execute(
-        "DELETE FROM dependencies WHERE wire_id = ?1 AND depends_on = ?2",
-        [wire_id, depends_on],
-    )?;
+    use rusqlite::OptionalExtension;
+
+    in_transaction(conn, || {
+        let kind: Option<crate::models::DependencyKind> = conn
+            .query_row(
+                "DELETE FROM dependencies WHERE wire_id = ?1 AND depends_on = ?2 RETURNING kind",
+                [wire_id, depends_on],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if let Some(kind) = kind {
+            record_event(
+                conn,
+                wire_id,
+                crate::models::EventKind::DepRemoved,
+                dependency_event_data(depends_on, kind),
+            )?;
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
-/// Gets wires that are ready to work on.
+/// Moves every incoming dependency edge from one wire to another: each
+/// wire that depended on `from` depends on `to` instead.
 ///
-/// A wire is ready if:
-/// - Its status is `TODO` or `IN_PROGRESS`
-/// - All wires it depends on have status `DONE`
+/// Dependents that already depend on `to` just lose their edge to `from`.
+/// All edges move in one transaction.
 ///
-/// Results are sorted by:
-/// 1. Status (`IN_PROGRESS` first, then `TODO`)
-/// 2. Priority (higher priority first)
+/// # Returns
 ///
-/// This is the primary function for AI agents to determine what to work on next.
+/// The IDs of the dependents that were moved, sorted.
 ///
-/// # Example
+/// # Errors
 ///
-/// ```no_run
-/// use wr::db;
+/// Returns [`WireError::WireNotFound`] if either wire doesn't exist, or
+/// [`WireError::CircularDependency`] if a moved edge would close a cycle
+/// (including `to` depending on `from`). Nothing is moved in that case.
+pub fn transfer_dependents(
+    conn: &Connection,
+    from: &str,
+    to: &str,
+) -> Result<Vec<crate::models::WireId>> {
+    in_transaction(conn, || {
+        for id in [from, to] {
+            if !wire_exists(conn, id)? {
+                return Err(WireError::WireNotFound(id.to_string()).into());
+            }
+        }
+        if from == to {
+            anyhow::bail!("Can't move dependents of {} onto itself", from);
+        }
+
+        let (_, dependents) = fetch_wire_deps(conn, from)?;
+        let mut moved: Vec<_> = dependents.into_iter().map(|d| d.id).collect();
+        moved.sort();
+        for dependent in &moved {
+            remove_dependency(conn, dependent.as_str(), from)?;
+            add_dependency(conn, dependent.as_str(), to)?;
+        }
+        Ok(moved)
+    })
+}
+
+/// Replaces `old` with `new`: `old`'s dependents are moved to `new` (see
+/// [`transfer_dependents`]) and `old` is cancelled as superseded by `new`.
 ///
-/// let conn = db::open().expect("Failed to open database");
-/// let ready = db::get_ready_wires(&conn).expect("Failed to get ready wires");
+/// # Returns
 ///
-/// if let Some(next) = ready.first() {
-///     println!("Next task: {} - {}", next.id, next.title);
-/// }
-/// ```
-pub fn get_ready_wires(conn: &Connection) -> Result<Vec<crate::models::Wire>> {
-    let query = "
-        SELECT w.id, w.title, w.description, w.status, w.created_at, w.updated_at, w.priority
-        FROM wires w
-        WHERE w.status IN ('TODO', 'IN_PROGRESS')
-        AND NOT EXISTS (
-            SELECT 1 FROM dependencies d
-            JOIN wires dep ON d.depends_on = dep.id
-            WHERE d.wire_id = w.id
-            AND dep.status != 'DONE'
-        )
-        ORDER BY
-            CASE w.status
-                WHEN 'IN_PROGRESS' THEN 0
-                WHEN 'TODO' THEN 1
-            END,
-            w.priority DESC
-    ";
-
-    let mut stmt = conn.prepare(query)?;
+/// The IDs of the dependents that were moved, sorted.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if either wire doesn't exist,
+/// [`WireError::InvalidTransition`] if `old` is DONE, or
+/// [`WireError::CircularDependency`] if moving the dependents would create a
+/// cycle. Nothing changes in that case.
+pub fn supersede_wire(
+    conn: &Connection,
+    old: &str,
+    new: &str,
+) -> Result<Vec<crate::models::WireId>> {
+    use crate::models::{CancelReason, Status, WireId};
+
+    in_transaction(conn, || {
+        if let Some(wire) = fetch_wire(conn, old)?.filter(|w| w.status == Status::Done) {
+            return Err(invalid_transition(conn, &wire, Status::Cancelled)?);
+        }
+        let moved = transfer_dependents(conn, old, new)?;
+        let reason = CancelReason::SupersededBy(WireId::new(new)?);
+        cancel_wire(conn, old, Some(&reason))?;
+        Ok(moved)
+    })
+}
+
+/// Gets every wire `wire_id` depends on, directly or through other wires.
+///
+/// Each wire appears once, at the depth of its shortest path. Results are
+/// ordered by depth, then ID.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn get_transitive_dependencies(
+    conn: &Connection,
+    wire_id: &str,
+) -> Result<Vec<crate::models::TransitiveDependency>> {
+    transitive(conn, wire_id, "wire_id", "depends_on")
+}
+
+/// Gets every wire that depends on `wire_id`, directly or through other
+/// wires: everything it blocks.
+///
+/// Each wire appears once, at the depth of its shortest path. Results are
+/// ordered by depth, then ID.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn get_transitive_dependents(
+    conn: &Connection,
+    wire_id: &str,
+) -> Result<Vec<crate::models::TransitiveDependency>> {
+    transitive(conn, wire_id, "depends_on", "wire_id")
+}
+
+/// Walks the dependency graph from `wire_id` with a recursive CTE, following
+/// edges from column `from` to column `to`.
+fn transitive(
+    conn: &Connection,
+    wire_id: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<crate::models::TransitiveDependency>> {
+    use crate::models::TransitiveDependency;
+
+    if !wire_exists(conn, wire_id)? {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    }
+
+    // Blocking dependencies can't form cycles, so the recursion always ends
+    let sql = format!(
+        "WITH RECURSIVE reach(id, depth) AS (
+             SELECT {to}, 1 FROM dependencies WHERE {from} = ?1 AND kind = 'blocks'
+             UNION
+             SELECT d.{to}, r.depth + 1
+             FROM dependencies d JOIN reach r ON d.{from} = r.id
+             WHERE d.kind = 'blocks'
+         )
+         SELECT w.id, w.title, w.status, MIN(r.depth) AS depth
+         FROM reach r JOIN wires w ON w.id = r.id
+         GROUP BY w.id
+         ORDER BY depth, w.id",
+        from = from,
+        to = to
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
     let wires = stmt
-        .query_map([], wire_from_row)?
+        .query_map([wire_id], |row| {
+            Ok(TransitiveDependency {
+                wire: dependency_info_from_row(row)?,
+                depth: row.get(3)?,
+            })
+        })?
         .collect::<Result<Vec<_>, _>>()?;
-
     Ok(wires)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+/// Lists every dependency relationship in the database.
+///
+/// # Returns
+///
+/// A vector of [`Dependency`](crate::models::Dependency) edges of every
+/// kind, where each `wire_id` depends on `depends_on`, sorted by
+/// `(wire_id, depends_on)`.
+pub fn list_dependencies(conn: &Connection) -> Result<Vec<crate::models::Dependency>> {
+    let mut stmt = conn.prepare(
+        "SELECT wire_id, depends_on, kind FROM dependencies ORDER BY wire_id, depends_on",
+    )?;
+    let deps = stmt
+        .query_map([], dependency_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
 
-    #[test]
-    fn test_init_creates_directory_and_database() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
+    Ok(deps)
+}
 
-        init(path).unwrap();
+/// A dependency from a row of `wire_id, depends_on, kind`
+fn dependency_from_row(row: &rusqlite::Row) -> rusqlite::Result<crate::models::Dependency> {
+    Ok(crate::models::Dependency {
+        wire_id: row.get(0)?,
+        depends_on: row.get(1)?,
+        kind: row.get(2)?,
+    })
+}
 
-        assert!(path.join(WIRES_DIR).exists());
-        assert!(path.join(WIRES_DIR).join(DB_NAME).exists());
-    }
+/// Reconciles the blocking dependencies with a desired set of edges.
+///
+/// Blocking edges present in the database but missing from `desired` are
+/// removed, and edges in `desired` that don't exist yet are added as
+/// blocking (with the same existence and cycle checks as
+/// [`add_dependency`]). Dependencies of other kinds are left alone, unless
+/// `desired` turns one into a blocking edge. All changes happen in a
+/// single transaction, so a failure leaves the graph untouched.
+///
+/// # Arguments
+///
+/// * `conn` - Database connection
+/// * `desired` - The complete set of blocking edges the graph should contain
+/// * `dry_run` - Compute and validate the changes, then roll them back
+///
+/// # Errors
+///
+/// Returns an error if a referenced wire does not exist or an added edge
+/// would create a circular dependency.
+pub fn sync_dependencies(
+    conn: &Connection,
+    desired: &[crate::models::Dependency],
+    dry_run: bool,
+) -> Result<crate::models::DependencyChanges> {
+    use crate::models::DependencyChanges;
+    use std::collections::HashSet;
 
-    #[test]
-    fn test_init_fails_if_already_initialized() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
 
-        init(path).unwrap();
-        let result = init(path);
+    let current: Vec<_> = list_dependencies(conn)?
+        .into_iter()
+        .filter(|d| d.kind.is_blocking())
+        .collect();
+    let current_set: HashSet<(&str, &str)> = current
+        .iter()
+        .map(|d| (d.wire_id.as_str(), d.depends_on.as_str()))
+        .collect();
+    let desired_set: HashSet<(&str, &str)> = desired
+        .iter()
+        .map(|d| (d.wire_id.as_str(), d.depends_on.as_str()))
+        .collect();
+
+    let removed: Vec<_> = current
+        .iter()
+        .filter(|d| !desired_set.contains(&(d.wire_id.as_str(), d.depends_on.as_str())))
+        .cloned()
+        .collect();
+
+    // Keep input order for additions, skipping duplicates
+    let mut seen = HashSet::new();
+    let added: Vec<_> = desired
+        .iter()
+        .filter(|d| !current_set.contains(&(d.wire_id.as_str(), d.depends_on.as_str())))
+        .filter(|d| seen.insert((d.wire_id.as_str(), d.depends_on.as_str())))
+        .cloned()
+        .collect();
+
+    // Remove first so edges being replaced can't cause false cycle reports
+    for dep in &removed {
+        remove_dependency(conn, dep.wire_id.as_str(), dep.depends_on.as_str())?;
+    }
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("already initialized"));
+    for dep in &added {
+        add_dependency(conn, dep.wire_id.as_str(), dep.depends_on.as_str())?;
     }
 
-    #[test]
-    fn test_schema_creation() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
+    if !dry_run {
+        tx.commit()?;
+    }
 
-        init(path).unwrap();
+    Ok(DependencyChanges { added, removed })
+}
+
+/// Rescales the priorities of all open wires to an even spread.
+///
+/// Open wires (`TODO`, `IN_PROGRESS`, `PAUSED` and `BLOCKED`) are ranked by
+/// their current priority, with ties broken by age (older wires rank higher)
+/// and then ID, and reassigned `step`, `2 * step`, `3 * step`, ... from the
+/// lowest rank up. Relative order is preserved, and wires that previously shared a
+/// priority get distinct values.
+///
+/// # Arguments
+///
+/// * `conn` - Database connection
+/// * `step` - Gap between consecutive priorities (must be positive)
+/// * `dry_run` - Compute the changes without writing them
+///
+/// # Returns
+///
+/// The wires whose priority changed, highest new priority first.
+pub fn renumber_priorities(
+    conn: &Connection,
+    step: i32,
+    dry_run: bool,
+) -> Result<Vec<crate::models::PriorityChange>> {
+    use crate::models::{PriorityChange, WireId};
+
+    if step <= 0 {
+        anyhow::bail!("Step must be positive, got {}", step);
+    }
+
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, title, priority FROM wires
+         WHERE status IN ('TODO', 'IN_PROGRESS', 'PAUSED', 'BLOCKED')
+         ORDER BY priority ASC, created_at_ms DESC, id DESC",
+    )?;
+    let open: Vec<(String, String, i32)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    let count = i32::try_from(open.len())?;
+    if count.checked_mul(step).is_none() {
+        anyhow::bail!("Step {} is too large for {} open wires", step, count);
+    }
+
+    let now = crate::time::now_ms();
+    let mut changes = Vec::new();
+
+    for (rank, (id, title, priority)) in open.into_iter().enumerate() {
+        let new_priority = (rank as i32 + 1) * step;
+        if new_priority == priority {
+            continue;
+        }
+
+        conn.execute(
+            "UPDATE wires SET priority = ?1, updated_at_ms = ?2 WHERE id = ?3",
+            rusqlite::params![new_priority, now, id],
+        )?;
+        record_event(
+            conn,
+            &id,
+            crate::models::EventKind::Updated,
+            serde_json::json!({ "priority": { "from": priority, "to": new_priority } }),
+        )?;
+
+        changes.push(PriorityChange {
+            id: WireId::from_trusted(id),
+            title,
+            from: priority,
+            to: new_priority,
+        });
+    }
+
+    if !dry_run {
+        tx.commit()?;
+    }
+
+    changes.reverse();
+    Ok(changes)
+}
+
+/// Attaches a tag to a wire.
+///
+/// Tagging is idempotent; the wire's `updated_at_ms` is only bumped when the
+/// tag is newly added.
+///
+/// # Returns
+///
+/// `true` if the tag was added, `false` if the wire already had it.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist.
+pub fn add_tag(conn: &Connection, wire_id: &str, tag: &crate::models::Tag) -> Result<bool> {
+    if !wire_exists(conn, wire_id)? {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    }
+
+    in_transaction(conn, || {
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO tags (wire_id, tag) VALUES (?1, ?2)",
+            rusqlite::params![wire_id, tag],
+        )?;
+
+        if inserted > 0 {
+            touch_wire(conn, wire_id)?;
+            record_event(
+                conn,
+                wire_id,
+                crate::models::EventKind::Tagged,
+                serde_json::json!({ "tag": tag }),
+            )?;
+        }
+
+        Ok(inserted > 0)
+    })
+}
+
+/// Removes a tag from a wire.
+///
+/// # Returns
+///
+/// `true` if the tag was removed, `false` if the wire didn't have it.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist.
+pub fn remove_tag(conn: &Connection, wire_id: &str, tag: &crate::models::Tag) -> Result<bool> {
+    if !wire_exists(conn, wire_id)? {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    }
+
+    in_transaction(conn, || {
+        let deleted = conn.execute(
+            "DELETE FROM tags WHERE wire_id = ?1 AND tag = ?2",
+            rusqlite::params![wire_id, tag],
+        )?;
+
+        if deleted > 0 {
+            touch_wire(conn, wire_id)?;
+            record_event(
+                conn,
+                wire_id,
+                crate::models::EventKind::Untagged,
+                serde_json::json!({ "tag": tag }),
+            )?;
+        }
+
+        Ok(deleted > 0)
+    })
+}
+
+/// Saves a wire's title, description and tags as edited with `wr edit`,
+/// all or nothing.
+///
+/// Tags missing from `tags` are removed and new ones added. Unchanged
+/// fields are left alone, so an edit that changes nothing records nothing.
+///
+/// # Returns
+///
+/// `true` if anything changed.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn edit_wire(
+    conn: &Connection,
+    wire_id: &str,
+    edited: &crate::edit::WireBuffer,
+) -> Result<bool> {
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+
+        let title = title_policy(conn)?.normalize(&edited.title)?;
+        let mut changed = false;
+        if title != wire.title || edited.description != wire.description {
+            update_wire(
+                conn,
+                wire_id,
+                Some(&title),
+                Some(edited.description.as_deref()),
+                None,
+                None,
+            )?;
+            changed = true;
+        }
+        for tag in wire.tags.iter().filter(|tag| !edited.tags.contains(tag)) {
+            changed |= remove_tag(conn, wire_id, tag)?;
+        }
+        for tag in edited.tags.iter().filter(|tag| !wire.tags.contains(tag)) {
+            changed |= add_tag(conn, wire_id, tag)?;
+        }
+
+        Ok(changed)
+    })
+}
+
+/// Attaches a tag to many wires at once, all or nothing.
+///
+/// # Returns
+///
+/// The wires that didn't already have the tag.
+///
+/// # Errors
+///
+/// Returns an error if any of the wires does not exist.
+pub fn add_tag_to_all(
+    conn: &Connection,
+    wire_ids: &[crate::models::WireId],
+    tag: &crate::models::Tag,
+) -> Result<Vec<crate::models::WireId>> {
+    in_transaction(conn, || {
+        let mut added = Vec::new();
+        for wire_id in wire_ids {
+            if add_tag(conn, wire_id.as_str(), tag)? {
+                added.push(wire_id.clone());
+            }
+        }
+        Ok(added)
+    })
+}
+
+/// Removes a tag from many wires at once, all or nothing.
+///
+/// # Returns
+///
+/// The wires that had the tag.
+///
+/// # Errors
+///
+/// Returns an error if any of the wires does not exist.
+pub fn remove_tag_from_all(
+    conn: &Connection,
+    wire_ids: &[crate::models::WireId],
+    tag: &crate::models::Tag,
+) -> Result<Vec<crate::models::WireId>> {
+    in_transaction(conn, || {
+        let mut removed = Vec::new();
+        for wire_id in wire_ids {
+            if remove_tag(conn, wire_id.as_str(), tag)? {
+                removed.push(wire_id.clone());
+            }
+        }
+        Ok(removed)
+    })
+}
+
+/// Lists every tag in use along with the number of wires carrying it.
+///
+/// # Returns
+///
+/// `(tag, count)` pairs sorted alphabetically by tag.
+pub fn list_tags(conn: &Connection) -> Result<Vec<(crate::models::Tag, i64)>> {
+    use crate::models::Tag;
+
+    let mut stmt = conn.prepare("SELECT tag, COUNT(*) FROM tags GROUP BY tag ORDER BY tag")?;
+    let tags = stmt
+        .query_map([], |row| {
+            let tag: String = row.get(0)?;
+            let tag = Tag::new(&tag).map_err(|_| rusqlite::Error::InvalidQuery)?;
+            Ok((tag, row.get(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(tags)
+}
+
+/// Finds TODO wires not updated for at least `older_than` seconds, grouped
+/// by tag, each with a suggested [`StaleAction`](crate::models::StaleAction).
+pub fn stale_report(conn: &Connection, older_than: i64) -> Result<crate::models::StaleReport> {
+    use crate::models::{StaleAction, StaleGroup, StaleReport, StaleWire, Tag};
+    use std::collections::BTreeMap;
+
+    let now = crate::time::now_ms();
+    let query = format!(
+        "SELECT {}, (SELECT COUNT(*) FROM dependencies d
+                     JOIN wires dependent ON dependent.id = d.wire_id
+                     WHERE d.depends_on = w.id AND d.kind = 'blocks'
+                     AND dependent.status NOT IN ('DONE', 'CANCELLED'))
+         FROM wires w
+         WHERE w.status = 'TODO' AND w.updated_at_ms <= ?1
+         ORDER BY w.updated_at_ms, w.id",
+        WIRE_COLUMNS
+    );
+    let mut stmt = conn.prepare(&query)?;
+    let wires = stmt
+        .query_map(
+            [now.saturating_sub(older_than.saturating_mul(1000))],
+            |row| {
+                let wire = wire_from_row(row)?;
+                let waiting: usize = row.get(WIRE_COLUMN_COUNT)?;
+                let age = (now - wire.updated_at_ms).max(0) / 1000;
+                let action = StaleAction::suggest(age, older_than, waiting);
+                Ok(StaleWire {
+                    command: action.command(&wire),
+                    wire,
+                    age,
+                    waiting,
+                    action,
+                })
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let count = wires.len();
+    let mut by_tag: BTreeMap<Tag, Vec<StaleWire>> = BTreeMap::new();
+    let mut untagged = Vec::new();
+    for stale in wires {
+        for tag in &stale.wire.tags {
+            by_tag.entry(tag.clone()).or_default().push(stale.clone());
+        }
+        if stale.wire.tags.is_empty() {
+            untagged.push(stale);
+        }
+    }
+
+    let mut groups: Vec<StaleGroup> = by_tag
+        .into_iter()
+        .map(|(tag, wires)| StaleGroup {
+            tag: Some(tag),
+            wires,
+        })
+        .collect();
+    if !untagged.is_empty() {
+        groups.push(StaleGroup {
+            tag: None,
+            wires: untagged,
+        });
+    }
+
+    Ok(StaleReport {
+        older_than,
+        count,
+        groups,
+    })
+}
+
+/// Claims a wire for an agent for `ttl_secs` seconds.
+///
+/// Claiming a wire the agent already holds renews the lease. A lease held by
+/// another agent blocks the claim until it expires.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist or another agent holds an
+/// active lease on it.
+pub fn claim_wire(
+    conn: &Connection,
+    wire_id: &str,
+    agent: &crate::models::Agent,
+    ttl_secs: i64,
+) -> Result<crate::models::Lease> {
+    use crate::models::{Lease, WireId};
+
+    in_transaction(conn, || {
+        if !wire_exists(conn, wire_id)? {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        }
+
+        let now = crate::time::now();
+        if let Some(lease) = get_lease(conn, wire_id)? {
+            if &lease.agent != agent && lease.expires_at > now {
+                return Err(WireError::AlreadyClaimed(lease).into());
+            }
+        }
+
+        let lease = Lease {
+            wire_id: WireId::new(wire_id)?,
+            agent: agent.clone(),
+            claimed_at: now,
+            expires_at: now.saturating_add(ttl_secs),
+        };
+
+        conn.execute(
+            "INSERT OR REPLACE INTO leases (wire_id, agent, claimed_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![
+                lease.wire_id,
+                lease.agent,
+                lease.claimed_at,
+                lease.expires_at
+            ],
+        )?;
+
+        record_event(
+            conn,
+            wire_id,
+            crate::models::EventKind::Claimed,
+            serde_json::json!({ "agent": lease.agent, "expires_at": lease.expires_at }),
+        )?;
+
+        Ok(lease)
+    })
+}
+
+/// Picks the best ready wire nobody is working on, claims it for `agent`
+/// (if given) and sets it to IN_PROGRESS, all in one transaction.
+///
+/// "Best" is the first wire [`get_ready_wires_filtered`] returns for a
+/// claimable filter: highest priority first, or as ranked by the config's
+/// [ready weights](crate::config::ReadyConfig), with its IN_PROGRESS
+/// policy applied. Because the pick and the start happen together,
+/// concurrent callers never get the same wire.
+///
+/// # Returns
+///
+/// The started wire, or `None` if nothing is ready.
+///
+/// # Errors
+///
+/// Returns any error from [`start_wire`], such as
+/// [`WireError::InProgressLimit`]; nothing is claimed in that case.
+pub fn next_wire(
+    conn: &Connection,
+    agent: Option<&crate::models::Agent>,
+    ttl_secs: i64,
+    config: &crate::config::Config,
+) -> Result<Option<crate::models::NextWire>> {
+    use crate::models::NextWire;
+
+    in_transaction(conn, || {
+        let filter = ReadyFilter {
+            claimable: true,
+            agent: agent.cloned(),
+            ready: config.ready.clone(),
+            ..Default::default()
+        };
+        let Some(candidate) = get_ready_wires_filtered(conn, &filter)?.into_iter().next() else {
+            return Ok(None);
+        };
+        let wire_id = candidate.id.as_str();
+
+        let lease = agent
+            .map(|agent| claim_wire(conn, wire_id, agent, ttl_secs))
+            .transpose()?;
+        let paused = start_wire(conn, wire_id, agent, &config.in_progress)?;
+        let wire = fetch_wire(conn, wire_id)?.unwrap_or(candidate);
+
+        Ok(Some(NextWire {
+            wire,
+            lease,
+            paused,
+        }))
+    })
+}
+
+/// Picks the ready wires `filter` selects among those nobody is working
+/// on, claims each for `agent` and sets it to IN_PROGRESS, all in one
+/// transaction.
+///
+/// This is [`next_wire`] for several wires at once: concurrent callers
+/// never get the same wire. Without a `filter.limit` only one wire is
+/// taken, as with [`next_wire`]. When the config limits how many wires an
+/// agent may have IN_PROGRESS, no more than that many are taken, so
+/// starting one wire of the batch never pauses another.
+///
+/// # Returns
+///
+/// The started wires, best first; empty if nothing is ready.
+///
+/// # Errors
+///
+/// Returns any error from [`start_wire`], such as
+/// [`WireError::InProgressLimit`]; nothing is claimed in that case.
+pub fn claim_ready_wires(
+    conn: &Connection,
+    filter: &ReadyFilter,
+    agent: &crate::models::Agent,
+    ttl_secs: i64,
+    config: &crate::config::Config,
+) -> Result<Vec<crate::models::NextWire>> {
+    use crate::models::NextWire;
+
+    in_transaction(conn, || {
+        let filter = ReadyFilter {
+            claimable: true,
+            agent: Some(agent.clone()),
+            limit: Some(
+                filter
+                    .limit
+                    .unwrap_or(1)
+                    .min(config.in_progress.limit.map_or(usize::MAX, |max| max.get())),
+            ),
+            ..filter.clone()
+        };
+
+        let mut started = Vec::new();
+        for candidate in get_ready_wires_filtered(conn, &filter)? {
+            let wire_id = candidate.id.as_str();
+            let lease = claim_wire(conn, wire_id, agent, ttl_secs)?;
+            let paused = start_wire(conn, wire_id, Some(agent), &config.in_progress)?;
+            let wire = fetch_wire(conn, wire_id)?.unwrap_or(candidate);
+            started.push(NextWire {
+                wire,
+                lease: Some(lease),
+                paused,
+            });
+        }
+        Ok(started)
+    })
+}
+
+/// Sets a wire to PAUSED, noting why if a reason is given.
+///
+/// Pausing a paused wire replaces its note. The note is kept until the
+/// wire's status changes again.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist, or
+/// [`WireError::InvalidTransition`] if it is already DONE or CANCELLED.
+pub fn pause_wire(conn: &Connection, wire_id: &str, reason: Option<&str>) -> Result<()> {
+    use crate::models::Status;
+
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+        if !wire.status.is_blocking() {
+            return Err(invalid_transition(conn, &wire, Status::Paused)?);
+        }
+
+        update_wire(conn, wire_id, None, None, Some(Status::Paused), None)?;
+        match reason.map(str::trim).filter(|r| !r.is_empty()) {
+            Some(reason) => conn.execute(
+                "INSERT OR REPLACE INTO pauses (wire_id, reason, paused_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![wire_id, reason, crate::time::now()],
+            )?,
+            None => conn.execute("DELETE FROM pauses WHERE wire_id = ?1", [wire_id])?,
+        };
+        Ok(())
+    })
+}
+
+/// Sets a wire to CANCELLED, recording why.
+///
+/// Cancelling an already cancelled wire replaces its reason.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire, or the wire it is
+/// superseded by, doesn't exist, or an error if it would supersede itself.
+pub fn cancel_wire(
+    conn: &Connection,
+    wire_id: &str,
+    reason: Option<&crate::models::CancelReason>,
+) -> Result<()> {
+    use crate::models::CancelReason;
+
+    in_transaction(conn, || {
+        if !wire_exists(conn, wire_id)? {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        }
+        if let Some(CancelReason::SupersededBy(by)) = reason {
+            if by.as_str() == wire_id {
+                anyhow::bail!("Wire {} can't be superseded by itself", wire_id);
+            }
+            if !wire_exists(conn, by.as_str())? {
+                return Err(WireError::WireNotFound(by.to_string()).into());
+            }
+        }
+        update_wire(
+            conn,
+            wire_id,
+            None,
+            None,
+            Some(crate::models::Status::Cancelled),
+            None,
+        )?;
+        conn.execute(
+            "UPDATE wires SET cancel_reason = ?2 WHERE id = ?1",
+            rusqlite::params![wire_id, reason.map(|r| r.to_string())],
+        )?;
+        Ok(())
+    })
+}
+
+/// Sets a PAUSED wire back to IN_PROGRESS, applying the same `policy` as
+/// [`start_wire`].
+///
+/// # Returns
+///
+/// The wires paused to make room for it.
+///
+/// # Errors
+///
+/// Returns [`WireError::InvalidTransition`] if the wire isn't PAUSED, or
+/// any error from [`start_wire`].
+pub fn resume_wire(
+    conn: &Connection,
+    wire_id: &str,
+    agent: Option<&crate::models::Agent>,
+    policy: &crate::config::InProgressPolicy,
+) -> Result<Vec<crate::models::Wire>> {
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+        if wire.status != crate::models::Status::Paused {
+            return Err(invalid_transition(
+                conn,
+                &wire,
+                crate::models::Status::InProgress,
+            )?);
+        }
+        start_wire(conn, wire_id, agent, policy)
+    })
+}
+
+/// Sets a wire to BLOCKED on something outside the dependency graph.
+///
+/// Blocking a blocked wire replaces its reason. The reason is cleared when
+/// the wire's status changes again.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist,
+/// [`WireError::InvalidTransition`] if it is already DONE or CANCELLED, or an
+/// error if `reason` is empty.
+pub fn block_wire(conn: &Connection, wire_id: &str, reason: &str) -> Result<()> {
+    use crate::models::Status;
+
+    let reason = reason.trim();
+    if reason.is_empty() {
+        anyhow::bail!("Block reason cannot be empty");
+    }
+
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+        if !wire.status.is_blocking() {
+            return Err(invalid_transition(conn, &wire, Status::Blocked)?);
+        }
+
+        update_wire(conn, wire_id, None, None, Some(Status::Blocked), None)?;
+        conn.execute(
+            "UPDATE wires SET blocked_reason = ?2 WHERE id = ?1",
+            rusqlite::params![wire_id, reason],
+        )?;
+        Ok(())
+    })
+}
+
+/// Sets a BLOCKED wire back to TODO, so it shows up in `wr ready` again.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist, or
+/// [`WireError::InvalidTransition`] if it isn't BLOCKED.
+pub fn unblock_wire(conn: &Connection, wire_id: &str) -> Result<()> {
+    use crate::models::Status;
+
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+        if wire.status != Status::Blocked {
+            return Err(invalid_transition(conn, &wire, Status::Todo)?);
+        }
+        update_wire(conn, wire_id, None, None, Some(Status::Todo), None)
+    })
+}
+
+/// Assigns a wire to `assignee`, or unassigns it when `None`.
+///
+/// Records an [`Assigned`](crate::models::EventKind::Assigned) event if the
+/// assignee changes, so `wr undo` can restore the previous one.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn assign_wire(
+    conn: &Connection,
+    wire_id: &str,
+    assignee: Option<&crate::models::Agent>,
+) -> Result<()> {
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+        if wire.assignee.as_ref() == assignee {
+            return Ok(());
+        }
+
+        conn.execute(
+            "UPDATE wires SET assignee = ?2, updated_at_ms = ?3 WHERE id = ?1",
+            rusqlite::params![wire_id, assignee, crate::time::now_ms()],
+        )?;
+        record_event(
+            conn,
+            wire_id,
+            crate::models::EventKind::Assigned,
+            serde_json::json!({ "from": wire.assignee, "to": assignee }),
+        )
+    })
+}
+
+/// Returns why a wire was paused, if it is PAUSED with a note.
+pub fn get_pause_note(
+    conn: &Connection,
+    wire_id: &str,
+) -> Result<Option<crate::models::PauseNote>> {
+    use rusqlite::OptionalExtension;
+
+    Ok(conn
+        .query_row(
+            "SELECT reason, paused_at FROM pauses WHERE wire_id = ?1",
+            [wire_id],
+            |row| {
+                Ok(crate::models::PauseNote {
+                    reason: row.get(0)?,
+                    paused_at: row.get(1)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+/// Pause notes of every wire that has one, by wire ID.
+pub fn list_pause_notes(
+    conn: &Connection,
+) -> Result<std::collections::HashMap<crate::models::WireId, crate::models::PauseNote>> {
+    let mut stmt = conn.prepare("SELECT wire_id, reason, paused_at FROM pauses")?;
+    let notes = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                crate::models::PauseNote {
+                    reason: row.get(1)?,
+                    paused_at: row.get(2)?,
+                },
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(notes)
+}
+
+/// Replaces a wire's pause note with `note`, or removes it when `None`,
+/// keeping the note's own `paused_at`
+fn set_pause_note(
+    conn: &Connection,
+    wire_id: &str,
+    note: Option<&crate::models::PauseNote>,
+) -> Result<()> {
+    match note {
+        Some(note) => conn.execute(
+            "INSERT OR REPLACE INTO pauses (wire_id, reason, paused_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![wire_id, note.reason, note.paused_at],
+        )?,
+        None => conn.execute("DELETE FROM pauses WHERE wire_id = ?1", [wire_id])?,
+    };
+    Ok(())
+}
+
+/// Sets the command that must succeed for a wire to count as ready under
+/// [`ReadyFilter::check_preconditions`], replacing any earlier one. `None`
+/// removes it.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn set_precondition(conn: &Connection, wire_id: &str, command: Option<&str>) -> Result<()> {
+    in_transaction(conn, || {
+        if !wire_exists(conn, wire_id)? {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        }
+
+        match command {
+            Some(command) => conn.execute(
+                "INSERT INTO preconditions (wire_id, command) VALUES (?1, ?2)
+                 ON CONFLICT (wire_id) DO UPDATE SET command = excluded.command",
+                rusqlite::params![wire_id, command],
+            )?,
+            None => conn.execute("DELETE FROM preconditions WHERE wire_id = ?1", [wire_id])?,
+        };
+        Ok(())
+    })
+}
+
+/// Returns a wire's precondition command, if it has one.
+pub fn get_precondition(conn: &Connection, wire_id: &str) -> Result<Option<String>> {
+    use rusqlite::OptionalExtension;
+
+    Ok(conn
+        .query_row(
+            "SELECT command FROM preconditions WHERE wire_id = ?1",
+            [wire_id],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Replaces a wire's layout hints; empty hints remove them.
+///
+/// Records a [`LayoutChanged`](crate::models::EventKind::LayoutChanged)
+/// event with the old and new hints if they differ.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn set_layout_hints(
+    conn: &Connection,
+    wire_id: &str,
+    hints: &crate::models::LayoutHints,
+) -> Result<()> {
+    in_transaction(conn, || {
+        if !wire_exists(conn, wire_id)? {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        }
+        let old = get_layout_hints(conn, wire_id)?;
+        if old == *hints {
+            return Ok(());
+        }
+
+        if hints.is_empty() {
+            conn.execute("DELETE FROM layout_hints WHERE wire_id = ?1", [wire_id])?;
+        } else {
+            conn.execute(
+                "INSERT INTO layout_hints (wire_id, rank, cluster, color) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (wire_id) DO UPDATE SET
+                     rank = excluded.rank, cluster = excluded.cluster, color = excluded.color",
+                rusqlite::params![wire_id, hints.rank, hints.cluster, hints.color],
+            )?;
+        }
+        record_event(
+            conn,
+            wire_id,
+            crate::models::EventKind::LayoutChanged,
+            serde_json::json!({"from": old, "to": hints}),
+        )
+    })
+}
+
+/// A wire's layout hints; empty if it has none.
+pub fn get_layout_hints(conn: &Connection, wire_id: &str) -> Result<crate::models::LayoutHints> {
+    use rusqlite::OptionalExtension;
+    let hints = conn
+        .query_row(
+            "SELECT rank, cluster, color FROM layout_hints WHERE wire_id = ?1",
+            [wire_id],
+            |row| {
+                Ok(crate::models::LayoutHints {
+                    rank: row.get(0)?,
+                    cluster: row.get(1)?,
+                    color: row.get(2)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(hints.unwrap_or_default())
+}
+
+/// Layout hints of every wire that has any, by wire ID.
+pub fn list_layout_hints(
+    conn: &Connection,
+) -> Result<std::collections::HashMap<crate::models::WireId, crate::models::LayoutHints>> {
+    let mut stmt = conn.prepare("SELECT wire_id, rank, cluster, color FROM layout_hints")?;
+    let hints = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                crate::models::LayoutHints {
+                    rank: row.get(1)?,
+                    cluster: row.get(2)?,
+                    color: row.get(3)?,
+                },
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(hints)
+}
+
+/// Precondition commands of every wire that has one, by wire ID.
+pub fn list_preconditions(
+    conn: &Connection,
+) -> Result<std::collections::HashMap<crate::models::WireId, String>> {
+    let mut stmt = conn.prepare("SELECT wire_id, command FROM preconditions")?;
+    let commands = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    Ok(commands)
+}
+
+/// Runs a precondition command with `sh -c` in the repository directory
+/// (the one containing `.wires/`), and reports whether it exited with
+/// status 0. Its output is discarded.
+///
+/// # Errors
+///
+/// Returns an error if the shell can't be started.
+pub fn precondition_holds(conn: &Connection, command: &str) -> Result<bool> {
+    let repo_dir = conn
+        .path()
+        .map(Path::new)
+        .and_then(Path::parent)
+        .and_then(Path::parent)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .map_or_else(base_dir, Ok)?;
+
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(repo_dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .with_context(|| format!("Failed to run precondition '{}'", command))?;
+    Ok(status.success())
+}
+
+/// Lists the IN_PROGRESS wires belonging to `agent`: those it holds an
+/// active claim on, or with no agent, those nobody has claimed. Least
+/// recently updated first.
+pub fn in_progress_wires(
+    conn: &Connection,
+    agent: Option<&crate::models::Agent>,
+) -> Result<Vec<crate::models::Wire>> {
+    let holder = if agent.is_some() {
+        "EXISTS (SELECT 1 FROM leases l
+                 WHERE l.wire_id = w.id AND l.expires_at > ?1 AND l.agent = ?2)"
+    } else {
+        "NOT EXISTS (SELECT 1 FROM leases l
+                     WHERE l.wire_id = w.id AND l.expires_at > ?1 AND ?2 IS NULL)"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM wires w
+         WHERE w.status = 'IN_PROGRESS' AND {}
+         ORDER BY w.updated_at_ms ASC, w.id ASC",
+        WIRE_COLUMNS, holder
+    ))?;
+    let wires = stmt
+        .query_map(rusqlite::params![crate::time::now(), agent], wire_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(wires)
+}
+
+/// How long the claim [`start_wire`] takes for an agent lasts, in seconds,
+/// when the agent doesn't hold one already
+pub const START_CLAIM_TTL_SECS: i64 = 30 * 60;
+
+/// Sets a wire to IN_PROGRESS on behalf of `agent`, enforcing `policy`.
+///
+/// If the agent already has as many IN_PROGRESS wires as the policy allows,
+/// its least recently updated ones are paused to make room (with a note
+/// naming the wire that displaced them), or the start is refused.
+///
+/// An agent's IN_PROGRESS wires are the ones it holds a claim on, so an
+/// agent that doesn't hold one on the wire yet claims it for
+/// [`START_CLAIM_TTL_SECS`] in the same transaction.
+///
+/// # Returns
+///
+/// The wires that were paused.
+///
+/// # Errors
+///
+/// Returns [`WireError::InProgressLimit`] if the agent is at its limit and
+/// the policy says to reject, or [`WireError::AlreadyClaimed`] if another
+/// agent holds an active claim on the wire.
+pub fn start_wire(
+    conn: &Connection,
+    wire_id: &str,
+    agent: Option<&crate::models::Agent>,
+    policy: &crate::config::InProgressPolicy,
+) -> Result<Vec<crate::models::Wire>> {
+    use crate::config::WhenFull;
+    use crate::models::{DependencyInfo, Status};
+
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+
+        let mut paused = Vec::new();
+        let limit = policy.limit.filter(|_| wire.status != Status::InProgress);
+        if let Some(limit) = limit {
+            let current = in_progress_wires(conn, agent)?;
+            if current.len() >= limit.get() {
+                let excess = current.len() + 1 - limit.get();
+                match policy.when_full {
+                    WhenFull::Reject => {
+                        return Err(WireError::InProgressLimit {
+                            limit: limit.get(),
+                            wires: current
+                                .into_iter()
+                                .map(|w| DependencyInfo {
+                                    id: w.id,
+                                    title: w.title,
+                                    status: w.status,
+                                })
+                                .collect(),
+                        }
+                        .into())
+                    }
+                    WhenFull::Pause => {
+                        let reason = format!("Started {} \"{}\" instead", wire.id, wire.title);
+                        for mut other in current.into_iter().take(excess) {
+                            pause_wire(conn, other.id.as_str(), Some(&reason))?;
+                            other.status = Status::Paused;
+                            paused.push(other);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(agent) = agent {
+            let now = crate::time::now();
+            let held = get_lease(conn, wire_id)?
+                .is_some_and(|lease| &lease.agent == agent && lease.expires_at > now);
+            if !held {
+                claim_wire(conn, wire_id, agent, START_CLAIM_TTL_SECS)?;
+            }
+        }
+
+        update_wire(conn, wire_id, None, None, Some(Status::InProgress), None)?;
+        Ok(paused)
+    })
+}
+
+/// Releases the claim on a wire.
+///
+/// When `agent` is given, only that agent's lease is released; releasing a
+/// wire held by someone else is an error. Without `agent`, any lease is
+/// dropped.
+///
+/// # Returns
+///
+/// `true` if a lease was removed, `false` if the wire wasn't claimed.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist or is actively claimed by a
+/// different agent.
+pub fn release_wire(
+    conn: &Connection,
+    wire_id: &str,
+    agent: Option<&crate::models::Agent>,
+) -> Result<bool> {
+    if !wire_exists(conn, wire_id)? {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    }
+
+    if let (Some(agent), Some(lease)) = (agent, get_lease(conn, wire_id)?) {
+        if &lease.agent != agent && lease.expires_at > crate::time::now() {
+            return Err(WireError::NotClaimedBy {
+                wire_id: wire_id.to_string(),
+                agent: agent.clone(),
+            }
+            .into());
+        }
+    }
+
+    in_transaction(conn, || {
+        let Some(lease) = get_lease(conn, wire_id)? else {
+            return Ok(false);
+        };
+
+        conn.execute("DELETE FROM leases WHERE wire_id = ?1", [wire_id])?;
+        record_event(
+            conn,
+            wire_id,
+            crate::models::EventKind::Released,
+            serde_json::json!({ "agent": lease.agent }),
+        )?;
+
+        Ok(true)
+    })
+}
+
+/// Gets the lease on a wire, if any, including expired ones.
+pub fn get_lease(conn: &Connection, wire_id: &str) -> Result<Option<crate::models::Lease>> {
+    use crate::models::{Agent, Lease};
+    use rusqlite::OptionalExtension;
+
+    let lease = conn
+        .query_row(
+            "SELECT wire_id, agent, claimed_at, expires_at FROM leases WHERE wire_id = ?1",
+            [wire_id],
+            |row| {
+                let agent: String = row.get(1)?;
+                Ok(Lease {
+                    wire_id: row.get(0)?,
+                    agent: Agent::new(&agent).map_err(|_| rusqlite::Error::InvalidQuery)?,
+                    claimed_at: row.get(2)?,
+                    expires_at: row.get(3)?,
+                })
+            },
+        )
+        .optional()?;
+
+    Ok(lease)
+}
+
+/// Blocks wires on a condition outside the repository.
+///
+/// An uncleared blocker with exactly the same `reason` is reused, so several
+/// wires can wait on one shared condition and be released together by
+/// [`clear_external_blocker`]. Blocked wires are left out of `wr ready`.
+///
+/// # Errors
+///
+/// Returns an error if `reason` is empty or any wire doesn't exist.
+pub fn add_external_blocker(
+    conn: &Connection,
+    reason: &str,
+    wire_ids: &[String],
+) -> Result<crate::models::ExternalBlocker> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        anyhow::bail!("Blocker reason cannot be empty");
+    }
+
+    in_transaction(conn, || {
+        let id = active_blocker_id(conn, reason)?;
+
+        for wire_id in wire_ids {
+            if !wire_exists(conn, wire_id)? {
+                return Err(WireError::WireNotFound(wire_id.clone()).into());
+            }
+            let inserted = conn.execute(
+                "INSERT OR IGNORE INTO external_blocks (blocker_id, wire_id) VALUES (?1, ?2)",
+                rusqlite::params![id, wire_id],
+            )?;
+            if inserted > 0 {
+                touch_wire(conn, wire_id)?;
+                record_event(
+                    conn,
+                    wire_id,
+                    crate::models::EventKind::Blocked,
+                    serde_json::json!({ "blocker": id, "reason": reason }),
+                )?;
+            }
+        }
+
+        get_external_blocker(conn, id)
+    })
+}
+
+/// The uncleared blocker with exactly `reason`, created if there is none
+fn active_blocker_id(conn: &Connection, reason: &str) -> Result<i64> {
+    use rusqlite::OptionalExtension;
+
+    let existing: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM external_blockers
+             WHERE reason = ?1 AND cleared_at IS NULL
+             ORDER BY id LIMIT 1",
+            [reason],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match existing {
+        Some(id) => Ok(id),
+        None => {
+            conn.execute(
+                "INSERT INTO external_blockers (reason, created_at) VALUES (?1, ?2)",
+                rusqlite::params![reason, crate::time::now()],
+            )?;
+            Ok(conn.last_insert_rowid())
+        }
+    }
+}
+
+/// Clears an external blocker, releasing every wire waiting on it.
+///
+/// `reference` is the blocker's number or its exact reason.
+///
+/// # Errors
+///
+/// Returns an error if no uncleared blocker matches `reference`.
+pub fn clear_external_blocker(
+    conn: &Connection,
+    reference: &str,
+) -> Result<crate::models::ExternalBlocker> {
+    use rusqlite::OptionalExtension;
+
+    in_transaction(conn, || {
+        let id: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM external_blockers
+                 WHERE cleared_at IS NULL AND (CAST(id AS TEXT) = ?1 OR reason = ?1)
+                 ORDER BY id LIMIT 1",
+                [reference.trim()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(id) = id else {
+            anyhow::bail!("No active external blocker matches '{}'", reference.trim());
+        };
+
+        conn.execute(
+            "UPDATE external_blockers SET cleared_at = ?1 WHERE id = ?2",
+            rusqlite::params![crate::time::now(), id],
+        )?;
+
+        let blocker = get_external_blocker(conn, id)?;
+        for wire_id in &blocker.wires {
+            touch_wire(conn, wire_id.as_str())?;
+            record_event(
+                conn,
+                wire_id.as_str(),
+                crate::models::EventKind::Unblocked,
+                serde_json::json!({ "blocker": id, "reason": blocker.reason }),
+            )?;
+        }
+
+        Ok(blocker)
+    })
+}
+
+/// Lists uncleared external blockers, oldest first.
+///
+/// With `wire_id`, only the blockers that wire is waiting on.
+pub fn list_external_blockers(
+    conn: &Connection,
+    wire_id: Option<&str>,
+) -> Result<Vec<crate::models::ExternalBlocker>> {
+    let mut stmt = conn.prepare(
+        "SELECT b.id FROM external_blockers b
+         WHERE b.cleared_at IS NULL
+         AND (?1 IS NULL OR EXISTS (
+             SELECT 1 FROM external_blocks eb WHERE eb.blocker_id = b.id AND eb.wire_id = ?1
+         ))
+         ORDER BY b.id",
+    )?;
+    let ids = stmt
+        .query_map([wire_id], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    ids.into_iter()
+        .map(|id| get_external_blocker(conn, id))
+        .collect()
+}
+
+fn get_external_blocker(conn: &Connection, id: i64) -> Result<crate::models::ExternalBlocker> {
+    let (reason, created_at, cleared_at) = conn.query_row(
+        "SELECT reason, created_at, cleared_at FROM external_blockers WHERE id = ?1",
+        [id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+
+    let mut stmt =
+        conn.prepare("SELECT wire_id FROM external_blocks WHERE blocker_id = ?1 ORDER BY wire_id")?;
+    let wires = stmt
+        .query_map([id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(crate::models::ExternalBlocker {
+        id,
+        reason,
+        created_at,
+        cleared_at,
+        wires,
+    })
+}
+
+/// Deletes a wire along with its dependencies, tags and claim.
+///
+/// The `deleted` event keeps a snapshot of the wire, its blocking
+/// dependency edges and its other links, so history remains readable after
+/// the wire is gone.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist.
+pub fn delete_wire(conn: &Connection, wire_id: &str) -> Result<()> {
+    in_transaction(conn, || {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.to_string()).into());
+        };
+
+        // The edges go with the wire (foreign keys cascade), so take them
+        // out first to keep them in the event
+        let mut stmt = conn.prepare(
+            "DELETE FROM dependencies WHERE wire_id = ?1 OR depends_on = ?1
+             RETURNING wire_id, depends_on, kind",
+        )?;
+        let removed = stmt
+            .query_map([wire_id], dependency_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        let (blocking, mut links): (Vec<_>, Vec<_>) =
+            removed.into_iter().partition(|d| d.kind.is_blocking());
+        links.sort_by(|a, b| (&a.wire_id, &a.depends_on).cmp(&(&b.wire_id, &b.depends_on)));
+        let mut depends_on = Vec::new();
+        let mut dependents = Vec::new();
+        for dep in blocking {
+            if dep.wire_id.as_str() == wire_id {
+                depends_on.push(dep.depends_on.to_string());
+            } else {
+                dependents.push(dep.wire_id.to_string());
+            }
+        }
+        depends_on.sort();
+        dependents.sort();
+
+        // Tags, claim, blockers, pause, precondition, layout and archive
+        // entry cascade
+        conn.execute("DELETE FROM wires WHERE id = ?1", [wire_id])?;
+
+        record_event(
+            conn,
+            wire_id,
+            crate::models::EventKind::Deleted,
+            serde_json::json!({
+                "wire": wire,
+                "depends_on": depends_on,
+                "dependents": dependents,
+                "links": links,
+            }),
+        )
+    })
+}
+
+/// Which wires [`archive_wires`] moves to the archive.
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveFilter {
+    /// Specific wires to archive; each must be DONE or CANCELLED
+    pub wire_ids: Vec<String>,
+    /// Archive every DONE or CANCELLED wire
+    pub all_finished: bool,
+    /// Only wires last updated at least this many seconds ago
+    pub older_than: Option<i64>,
+}
+
+/// Archives finished wires, hiding them from `wr list` and `wr graph` unless
+/// archived wires are asked for.
+///
+/// Archived wires keep their dependencies and history, and `wr show` still
+/// finds them. Wires that are already archived are skipped.
+///
+/// # Returns
+///
+/// The wires that were (or, with `dry_run`, would be) archived.
+///
+/// # Errors
+///
+/// Returns an error if an explicitly named wire doesn't exist or isn't
+/// DONE or CANCELLED.
+pub fn archive_wires(
+    conn: &Connection,
+    filter: &ArchiveFilter,
+    dry_run: bool,
+) -> Result<Vec<crate::models::Wire>> {
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+
+    let now = crate::time::now();
+    let cutoff = filter
+        .older_than
+        .map(|secs| crate::time::now_ms() - secs.saturating_mul(1000));
+    let archived = list_archived_ids(conn)?;
+
+    let mut candidates = Vec::new();
+    for wire_id in &filter.wire_ids {
+        let Some(wire) = fetch_wire(conn, wire_id)? else {
+            return Err(WireError::WireNotFound(wire_id.clone()).into());
+        };
+        if wire.status.is_blocking() {
+            anyhow::bail!(
+                "Wire {} is {}; only DONE or CANCELLED wires can be archived",
+                wire.id,
+                wire.status.as_str()
+            );
+        }
+        candidates.push(wire);
+    }
+    if filter.all_finished {
+        candidates.extend(
+            list_wires(conn, &ListFilter::default())?
+                .into_iter()
+                .filter(|w| !w.status.is_blocking()),
+        );
+    }
+
+    let mut wires = Vec::new();
+    for wire in candidates {
+        if archived.contains(&wire.id)
+            || wires.iter().any(|w: &crate::models::Wire| w.id == wire.id)
+            || cutoff.is_some_and(|cutoff| wire.updated_at_ms > cutoff)
+        {
+            continue;
+        }
+        insert_archived(conn, wire.id.as_str(), now)?;
+        wires.push(wire);
+    }
+
+    if dry_run {
+        tx.rollback()?;
+    } else {
+        tx.commit()?;
+    }
+
+    Ok(wires)
+}
+
+/// Add a wire to the archive and record it
+fn insert_archived(conn: &Connection, wire_id: &str, at: i64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO archived_wires (wire_id, archived_at) VALUES (?1, ?2)",
+        rusqlite::params![wire_id, at],
+    )?;
+    record_event(
+        conn,
+        wire_id,
+        crate::models::EventKind::Archived,
+        serde_json::json!({}),
+    )
+}
+
+/// Returns a wire from the archive to everyday views.
+///
+/// # Returns
+///
+/// `true` if the wire was archived, `false` if it wasn't.
+pub fn unarchive_wire(conn: &Connection, wire_id: &str) -> Result<bool> {
+    if !wire_exists(conn, wire_id)? {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    }
+
+    in_transaction(conn, || {
+        let removed = conn.execute("DELETE FROM archived_wires WHERE wire_id = ?1", [wire_id])?;
+        if removed > 0 {
+            record_event(
+                conn,
+                wire_id,
+                crate::models::EventKind::Unarchived,
+                serde_json::json!({}),
+            )?;
+        }
+        Ok(removed > 0)
+    })
+}
+
+/// Archives the wire or returns it from the archive, unless it already is
+/// where `archived` says
+fn set_archived(conn: &Connection, wire_id: &str, archived: bool) -> Result<()> {
+    let is_archived: bool = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM archived_wires WHERE wire_id = ?1)",
+        [wire_id],
+        |row| row.get(0),
+    )?;
+    if archived && !is_archived {
+        insert_archived(conn, wire_id, crate::time::now())?;
+    } else if !archived && is_archived {
+        unarchive_wire(conn, wire_id)?;
+    }
+    Ok(())
+}
+
+/// IDs of every archived wire.
+pub fn list_archived_ids(
+    conn: &Connection,
+) -> Result<std::collections::HashSet<crate::models::WireId>> {
+    let mut stmt = conn.prepare("SELECT wire_id FROM archived_wires")?;
+    let ids = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(ids)
+}
+
+/// Deletes bookkeeping older than the windows in `policy`, as `wr maintain`.
+///
+/// - history: events older than the window, except ones an outbox consumer
+///   hasn't acknowledged yet
+/// - archived: wires archived longer ago than the window (each leaves a
+///   `deleted` event, like `wr rm`)
+/// - outbox: consumers that haven't pulled or acknowledged within the window
+/// - backups: `wires.db.<timestamp>.bak` files left by `wr init --force`
+/// - idempotency keys: saved responses older than the window
+///
+/// Expired outbox consumers are dropped before history is pruned, so they
+/// no longer hold events back. Kinds without a window are left alone.
+///
+/// # Returns
+///
+/// What was (or, with `dry_run`, would be) removed.
+pub fn apply_retention(
+    conn: &Connection,
+    policy: &crate::config::RetentionPolicy,
+    dry_run: bool,
+) -> Result<crate::models::RetentionReport> {
+    let now = crate::time::now();
+    let cutoff = |window: &Option<crate::config::RetentionWindow>| {
+        window.as_ref().map(|w| now.saturating_sub(w.as_secs()))
+    };
+
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    let mut report = crate::models::RetentionReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    if let Some(cutoff) = cutoff(&policy.outbox) {
+        let mut stmt =
+            conn.prepare("DELETE FROM outbox_consumers WHERE updated_at <= ?1 RETURNING consumer")?;
+        report.outbox_consumers = stmt
+            .query_map([cutoff], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        report.outbox_consumers.sort();
+    }
+
+    if let Some(cutoff) = cutoff(&policy.history) {
+        report.events = conn.execute(
+            "DELETE FROM events WHERE at <= ?1
+             AND seq <= COALESCE((SELECT MIN(acked_seq) FROM outbox_consumers), seq)",
+            [cutoff],
+        )?;
+    }
+
+    if let Some(cutoff) = cutoff(&policy.archived) {
+        let mut stmt = conn.prepare(
+            "SELECT wire_id FROM archived_wires WHERE archived_at <= ?1 ORDER BY wire_id",
+        )?;
+        let ids = stmt
+            .query_map([cutoff], |row| row.get(0))?
+            .collect::<Result<Vec<crate::models::WireId>, _>>()?;
+        for wire_id in &ids {
+            delete_wire(conn, wire_id.as_str())?;
+        }
+        report.archived_wires = ids;
+    }
+
+    if let Some(cutoff) = cutoff(&policy.idempotency_keys) {
+        report.idempotency_keys = conn.execute(
+            "DELETE FROM idempotency_keys WHERE created_at <= ?1",
+            [cutoff],
+        )?;
+    }
+
+    if let (Some(cutoff), Some(wires_dir)) = (
+        cutoff(&policy.backups),
+        conn.path().map(Path::new).and_then(Path::parent),
+    ) {
+        report.backups = expired_backups(wires_dir, cutoff)?;
+    }
+
+    if dry_run {
+        tx.rollback()?;
+        return Ok(report);
+    }
+    tx.commit()?;
+
+    if let Some(wires_dir) = conn.path().map(Path::new).and_then(Path::parent) {
+        for name in &report.backups {
+            let file = wires_dir.join(name);
+            fs::remove_file(&file)
+                .with_context(|| format!("Failed to remove {}", file.display()))?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Names of the `wr init --force` backups in `wires_dir` taken at or before
+/// `cutoff` (seconds since the epoch), oldest first
+fn expired_backups(wires_dir: &Path, cutoff: i64) -> Result<Vec<String>> {
+    let prefix = format!("{}.", DB_NAME);
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(wires_dir)? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        let taken_at = name
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".bak"))
+            .and_then(|ts| ts.parse::<i64>().ok());
+        if let Some(taken_at) = taken_at.filter(|&at| at <= cutoff) {
+            backups.push((taken_at, name));
+        }
+    }
+    backups.sort();
+    Ok(backups.into_iter().map(|(_, name)| name).collect())
+}
+
+/// Searches wire titles and descriptions.
+///
+/// Each whitespace-separated word in `query` must appear in the wire (as a
+/// word prefix, case- and accent-insensitive). Matches in the title count
+/// ten times as much as matches in the description.
+///
+/// # Returns
+///
+/// Up to `limit` matching wires with their relevance score, best first.
+pub fn search_wires(
+    conn: &Connection,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<crate::models::SearchHit>> {
+    use crate::models::SearchHit;
+
+    // Quote every term so FTS5 operators in user input are matched literally
+    let match_expr = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if match_expr.is_empty() {
+        anyhow::bail!("Search query cannot be empty");
+    }
+
+    let sql = format!(
+        "SELECT {}, -bm25(wires_fts, 0.0, 10.0, 1.0) AS score
+         FROM wires_fts
+         JOIN wires w ON w.id = wires_fts.wire_id
+         WHERE wires_fts MATCH ?1
+         ORDER BY score DESC, w.priority DESC
+         LIMIT ?2",
+        WIRE_COLUMNS
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let hits = stmt
+        .query_map(rusqlite::params![match_expr, limit as i64], |row| {
+            Ok(SearchHit {
+                wire: wire_from_row(row)?,
+                score: row.get(WIRE_COLUMN_COUNT)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(hits)
+}
+
+/// Runs a mutating command at most once per idempotency key.
+///
+/// Without a key, `f` simply runs. With a key, the first call runs `f` and
+/// stores its JSON response in the same transaction as its changes; later
+/// calls with the same key skip `f` and return the stored response, so a
+/// retried command has no further effect.
+///
+/// # Errors
+///
+/// Returns [`WireError::IdempotencyKeyReused`] if the key was previously
+/// used for a different command, or any error from `f` (in which case the
+/// key is not recorded and the command can be retried).
+pub fn with_idempotency_key(
+    conn: &Connection,
+    key: Option<&crate::models::IdempotencyKey>,
+    command: &str,
+    f: impl FnOnce() -> Result<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    use rusqlite::OptionalExtension;
+
+    let Some(key) = key else {
+        return f();
+    };
+
+    in_transaction(conn, || {
+        let previous: Option<(String, String)> = conn
+            .query_row(
+                "SELECT command, response FROM idempotency_keys WHERE key = ?1",
+                [key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        if let Some((previous_command, response)) = previous {
+            if previous_command != command {
+                return Err(WireError::IdempotencyKeyReused {
+                    key: key.to_string(),
+                    command: previous_command,
+                }
+                .into());
+            }
+            return Ok(serde_json::from_str(&response)?);
+        }
+
+        let response = f()?;
+        conn.execute(
+            "INSERT INTO idempotency_keys (key, command, response, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![key, command, response.to_string(), crate::time::now()],
+        )?;
+
+        Ok(response)
+    })
+}
+
+/// Exports every wire, archived ones included, with its layout hints,
+/// external blockers, pause note and precondition, and every dependency as
+/// a [`Snapshot`](crate::models::Snapshot).
+pub fn export_snapshot(conn: &Connection) -> Result<crate::models::Snapshot> {
+    use crate::models::{Snapshot, SnapshotWire};
+    use std::collections::HashMap;
+
+    let mut layouts = list_layout_hints(conn)?;
+    let mut blockers: HashMap<crate::models::WireId, Vec<String>> = HashMap::new();
+    for blocker in list_external_blockers(conn, None)? {
+        for wire_id in blocker.wires {
+            blockers
+                .entry(wire_id)
+                .or_default()
+                .push(blocker.reason.clone());
+        }
+    }
+    let archived = list_archived_ids(conn)?;
+    let mut pauses = list_pause_notes(conn)?;
+    let mut preconditions = list_preconditions(conn)?;
+    let mut wires: Vec<SnapshotWire> = list_wires(conn, &ListFilter::default())?
+        .into_iter()
+        .map(|wire| SnapshotWire {
+            layout: layouts.remove(&wire.id).unwrap_or_default(),
+            blockers: blockers.remove(&wire.id).unwrap_or_default(),
+            archived: archived.contains(&wire.id),
+            paused: pauses.remove(&wire.id),
+            precondition: preconditions.remove(&wire.id),
+            wire,
+        })
+        .collect();
+    wires.sort_by(|a, b| a.wire.id.cmp(&b.wire.id));
+
+    let dependencies = list_dependencies(conn)?;
+
+    Ok(Snapshot {
+        version: Snapshot::VERSION,
+        wires,
+        dependencies,
+        manifest: None,
+    })
+}
+
+/// Loads a [`Snapshot`](crate::models::Snapshot) into the database.
+///
+/// Wires keep their IDs, statuses, priorities, tags, layout hints, external
+/// blockers, pause notes, preconditions and timestamps exactly. Archived wires are archived again, as of
+/// the import.
+/// IDs are kept whatever their [`IdScheme`](crate::models::IdScheme), so a
+/// repository can hold wires of several schemes; only new wires follow its
+/// own. With `replace`, all existing wires are deleted first; otherwise the
+/// snapshot is added alongside them. Everything happens in one transaction.
+///
+/// # Errors
+///
+/// Returns [`WireError::ImportConflicts`] listing every snapshot wire whose
+/// ID already exists (without `replace`; see [`merge_snapshot`] to settle
+/// them instead), or an error if the snapshot version is unsupported or a
+/// dependency refers to a missing wire or would create a cycle. Nothing is
+/// imported in that case.
+pub fn import_snapshot(
+    conn: &Connection,
+    snapshot: &crate::models::Snapshot,
+    replace: bool,
+) -> Result<()> {
+    import_wires(conn, snapshot, replace, None).map(|_| ())
+}
+
+/// Loads a [`Snapshot`](crate::models::Snapshot) alongside the existing
+/// wires, letting `resolve` pick which copy to keep of each wire that
+/// exists in both with different content.
+///
+/// Wires that exist with the same content are left alone, layout hints
+/// included. Keeping the incoming copy overwrites the local wire's fields,
+/// tags and layout hints, recording the change in the event log. Dependencies from the snapshot are added
+/// to the ones already there.
+///
+/// # Returns
+///
+/// The conflicts, each with the side that was kept.
+///
+/// # Errors
+///
+/// Returns [`WireError::ImportConflicts`] with the conflicts `resolve`
+/// returned `None` for, or the errors of [`import_snapshot`]. Nothing is
+/// imported in that case.
+pub fn merge_snapshot(
+    conn: &Connection,
+    snapshot: &crate::models::Snapshot,
+    mut resolve: impl FnMut(&crate::models::WireConflict) -> Option<crate::models::ConflictSide>,
+) -> Result<Vec<crate::models::WireConflict>> {
+    import_wires(conn, snapshot, false, Some(&mut resolve))
+}
+
+/// The snapshot wires that exist locally with different content, as
+/// [`merge_snapshot`] would find them.
+pub fn snapshot_conflicts(
+    conn: &Connection,
+    snapshot: &crate::models::Snapshot,
+) -> Result<Vec<crate::models::WireConflict>> {
+    use crate::models::WireConflict;
+
+    let mut conflicts = Vec::new();
+    for crate::models::SnapshotWire { wire, .. } in snapshot_wires(conn, snapshot)? {
+        if let Some(local) = fetch_wire(conn, wire.id.as_str())? {
+            let conflict = WireConflict::between(&local, &wire);
+            if !conflict.fields.is_empty() {
+                conflicts.push(conflict);
+            }
+        }
+    }
+    Ok(conflicts)
+}
+
+/// The wires of `snapshot` as this build stores them, with titles
+/// normalized by the repository's `[titles]` config
+fn snapshot_wires(
+    conn: &Connection,
+    snapshot: &crate::models::Snapshot,
+) -> Result<Vec<crate::models::SnapshotWire>> {
+    use crate::models::Snapshot;
+
+    // Version 1 differs only in storing wire timestamps in seconds
+    let scale = match snapshot.version {
+        1 => 1000,
+        2 | Snapshot::VERSION => 1,
+        version => anyhow::bail!(
+            "Unsupported snapshot version {} (expected {})",
+            version,
+            Snapshot::VERSION
+        ),
+    };
+    let titles = title_policy(conn)?;
+    snapshot
+        .wires
+        .iter()
+        .map(|entry| {
+            let mut entry = entry.clone();
+            let wire = &mut entry.wire;
+            wire.title = titles
+                .normalize(&wire.title)
+                .map_err(|e| anyhow::anyhow!("Wire {}: {}", wire.id, e))?;
+            wire.created_at_ms *= scale;
+            wire.updated_at_ms *= scale;
+            Ok(entry)
+        })
+        .collect()
+}
+
+/// Picks the copy to keep of a conflicting wire, or `None` to give up
+type ResolveConflict<'a> =
+    dyn FnMut(&crate::models::WireConflict) -> Option<crate::models::ConflictSide> + 'a;
+
+/// Shared body of [`import_snapshot`] (no `resolve`: every existing ID is a
+/// conflict) and [`merge_snapshot`]
+fn import_wires(
+    conn: &Connection,
+    snapshot: &crate::models::Snapshot,
+    replace: bool,
+    mut resolve: Option<&mut ResolveConflict<'_>>,
+) -> Result<Vec<crate::models::WireConflict>> {
+    use crate::models::{ConflictSide, WireConflict};
+
+    let wires = snapshot_wires(conn, snapshot)?;
+
+    in_transaction(conn, || {
+        if replace {
+            let mut stmt = conn.prepare("SELECT id FROM wires ORDER BY id")?;
+            let ids: Vec<String> = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for id in ids {
+                delete_wire(conn, &id)?;
+            }
+        }
+
+        let mut resolved = Vec::new();
+        let mut unresolved = Vec::new();
+        for crate::models::SnapshotWire {
+            wire,
+            layout,
+            blockers,
+            archived,
+            paused,
+            precondition,
+        } in &wires
+        {
+            let Some(local) = fetch_wire(conn, wire.id.as_str())? else {
+                insert_wire_unchecked(conn, wire)?;
+                set_tags(conn, wire)?;
+                set_layout_hints(conn, wire.id.as_str(), layout)?;
+                set_external_blockers(conn, wire.id.as_str(), blockers)?;
+                set_archived(conn, wire.id.as_str(), *archived)?;
+                set_pause_note(conn, wire.id.as_str(), paused.as_ref())?;
+                set_precondition(conn, wire.id.as_str(), precondition.as_deref())?;
+                continue;
+            };
+
+            let mut conflict = WireConflict::between(&local, wire);
+            let Some(resolve) = resolve.as_mut() else {
+                unresolved.push(conflict);
+                continue;
+            };
+            if conflict.fields.is_empty() {
+                continue;
+            }
+            match resolve(&conflict) {
+                Some(side) => {
+                    if side == ConflictSide::Theirs {
+                        overwrite_wire(conn, wire)?;
+                        set_layout_hints(conn, wire.id.as_str(), layout)?;
+                        set_external_blockers(conn, wire.id.as_str(), blockers)?;
+                        set_archived(conn, wire.id.as_str(), *archived)?;
+                        set_pause_note(conn, wire.id.as_str(), paused.as_ref())?;
+                        set_precondition(conn, wire.id.as_str(), precondition.as_deref())?;
+                    }
+                    conflict.resolution = Some(side);
+                    resolved.push(conflict);
+                }
+                None => unresolved.push(conflict),
+            }
+        }
+        if !unresolved.is_empty() {
+            return Err(WireError::ImportConflicts(unresolved).into());
+        }
+
+        for dep in &snapshot.dependencies {
+            add_dependency_with_kind(
+                conn,
+                dep.wire_id.as_str(),
+                dep.depends_on.as_str(),
+                dep.kind,
+            )?;
+        }
+
+        Ok(resolved)
+    })
+}
+
+/// Makes the stored wire wait on exactly the active external blockers with
+/// `reasons`, reusing or creating them as [`add_external_blocker`] does.
+/// Unlike it, the wire's timestamps are left alone.
+fn set_external_blockers(conn: &Connection, wire_id: &str, reasons: &[String]) -> Result<()> {
+    conn.execute(
+        "DELETE FROM external_blocks WHERE wire_id = ?1 AND blocker_id IN (
+             SELECT id FROM external_blockers WHERE cleared_at IS NULL
+         )",
+        [wire_id],
+    )?;
+    for reason in reasons {
+        let id = active_blocker_id(conn, reason)?;
+        conn.execute(
+            "INSERT OR IGNORE INTO external_blocks (blocker_id, wire_id) VALUES (?1, ?2)",
+            rusqlite::params![id, wire_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Replaces the tags of the stored wire with `wire`'s
+fn set_tags(conn: &Connection, wire: &crate::models::Wire) -> Result<()> {
+    conn.execute("DELETE FROM tags WHERE wire_id = ?1", [&wire.id])?;
+    for tag in &wire.tags {
+        conn.execute(
+            "INSERT OR IGNORE INTO tags (wire_id, tag) VALUES (?1, ?2)",
+            rusqlite::params![wire.id, tag],
+        )?;
+    }
+    Ok(())
+}
+
+/// Overwrites an existing wire with every field of `wire`, keeping its
+/// dependencies. Title, description, status and priority changes are
+/// recorded like any other update.
+fn overwrite_wire(conn: &Connection, wire: &crate::models::Wire) -> Result<()> {
+    let id = wire.id.as_str();
+    update_wire_unchecked(
+        conn,
+        id,
+        Some(&wire.title),
+        Some(wire.description.as_deref()),
+        Some(wire.status),
+        Some(wire.priority),
+    )?;
+    conn.execute(
+        "UPDATE wires SET cancel_reason = ?2, blocked_reason = ?3, assignee = ?4, updated_at_ms = ?5
+         WHERE id = ?1",
+        rusqlite::params![
+            id,
+            wire.cancel_reason.as_ref().map(|r| r.to_string()),
+            wire.blocked_reason,
+            wire.assignee,
+            wire.updated_at_ms
+        ],
+    )?;
+    set_tags(conn, wire)
+}
+
+/// Creates every wire in `plan`, with its tags and dependencies, in one transaction.
+/// Titles are normalized by the repository's `[titles]` config.
+///
+/// # Returns
+///
+/// The created wires, in plan order.
+///
+/// # Errors
+///
+/// Returns an error (and creates nothing) if the plan doesn't
+/// [resolve](crate::plan::Plan::resolve) or a wire is invalid.
+pub fn apply_plan(conn: &Connection, plan: &crate::plan::Plan) -> Result<Vec<crate::models::Wire>> {
+    use crate::models::Wire;
+
+    let edges = plan.resolve()?;
+
+    let config = repo_config(conn)?;
+    in_transaction(conn, || {
+        let mut wires = Vec::with_capacity(plan.wires.len());
+        for item in &plan.wires {
+            let title = config.titles.normalize(&item.title)?;
+            let mut wire = Wire::new(&title, item.description.as_deref(), item.priority)?;
+            wire.id = config.id_scheme.generate(&wire.title);
+            insert_wire_unchecked(conn, &wire)?;
+            for tag in &item.tags {
+                add_tag(conn, wire.id.as_str(), tag)?;
+            }
+            wire.tags = item.tags.clone();
+            wire.tags.sort();
+            wire.tags.dedup();
+            wires.push(wire);
+        }
+
+        for (i, deps) in edges.iter().enumerate() {
+            for &dep in deps {
+                add_dependency(conn, wires[i].id.as_str(), wires[dep].id.as_str())?;
+            }
+        }
+
+        Ok(wires)
+    })
+}
+
+/// Wires and dependency edges added by [`generate_fixture`].
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    /// The generated wires, oldest first. Each wire only depends on wires
+    /// before it.
+    pub ids: Vec<crate::models::WireId>,
+    /// Number of dependency edges added
+    pub edges: usize,
+}
+
+/// Fills the database with `wires` synthetic wires and `edges` random
+/// dependencies between them, for benchmarking at scale.
+///
+/// The same `seed` always produces the same graph. Statuses, priorities and
+/// ages are mixed roughly like a long-lived repository: most wires are
+/// TODO or DONE, with a few IN_PROGRESS, PAUSED and CANCELLED. Edges always
+/// point from a newer wire to an older one, so the graph is acyclic without
+/// checking each edge. No events are recorded, and existing wires are left
+/// alone.
+///
+/// # Errors
+///
+/// Returns an error if `edges` is more than `wires` can hold.
+pub fn generate_fixture(
+    conn: &Connection,
+    wires: usize,
+    edges: usize,
+    seed: u64,
+) -> Result<Fixture> {
+    use crate::models::{Status, WireId};
+    use sha2::{Digest, Sha256};
+
+    let max_edges = wires.saturating_mul(wires.saturating_sub(1)) / 2;
+    if edges > max_edges {
+        anyhow::bail!("{} wires can have at most {} edges", wires, max_edges);
+    }
+
+    // splitmix64: small, fast and good enough for test data
+    let mut state = seed;
+    let mut next = |bound: u64| -> u64 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        (z ^ (z >> 31)) % bound
+    };
+
+    let now = crate::time::now_ms();
+    in_transaction(conn, || {
+        let mut insert_wire = conn.prepare(
+            "INSERT OR IGNORE INTO wires (id, title, description, status, created_at_ms,
+                                          updated_at_ms, priority)
+             VALUES (?1, ?2, '', ?3, ?4, ?4, ?5)",
+        )?;
+        let mut ids = Vec::with_capacity(wires);
+        for i in 0..wires {
+            let status = match next(100) {
+                0..=44 => Status::Todo,
+                45..=84 => Status::Done,
+                85..=92 => Status::InProgress,
+                93..=95 => Status::Paused,
+                _ => Status::Cancelled,
+            };
+            let age = i64::try_from(next(365 * 86_400_000))?;
+            let priority = i32::try_from(next(5))?;
+            let title = format!("Fixture wire {}", i);
+
+            // Seven hex digits collide now and then at this scale; rehash
+            for attempt in 0u32.. {
+                let hash = Sha256::digest(format!("{}:{}:{}", seed, i, attempt));
+                let id = WireId::from_trusted(format!("{:x}", hash)[..7].to_string());
+                let inserted = insert_wire.execute(rusqlite::params![
+                    id,
+                    title,
+                    status.as_str(),
+                    now - age,
+                    priority
+                ])?;
+                if inserted > 0 {
+                    ids.push(id);
+                    break;
+                }
+            }
+        }
+
+        let mut insert_edge = conn
+            .prepare("INSERT OR IGNORE INTO dependencies (wire_id, depends_on) VALUES (?1, ?2)")?;
+        let mut added = 0;
+        while added < edges {
+            let from = next(wires as u64 - 1) as usize + 1;
+            let to = next(from as u64) as usize;
+            added += insert_edge.execute([&ids[from], &ids[to]])?;
+        }
+
+        Ok(Fixture { ids, edges: added })
+    })
+}
+
+/// Filters for [`list_events`].
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    /// Only events at or after this Unix timestamp
+    pub since: Option<i64>,
+    /// Only events for this wire
+    pub wire_id: Option<String>,
+    /// Only events with a sequence number greater than this
+    pub after: Option<i64>,
+}
+
+/// Lists recorded events in the order they happened.
+pub fn list_events(conn: &Connection, filter: &EventFilter) -> Result<Vec<crate::models::Event>> {
+    let mut stmt = conn.prepare(
+        "SELECT seq, at, wire_id, kind, data FROM events
+         WHERE (?1 IS NULL OR at >= ?1)
+         AND (?2 IS NULL OR wire_id = ?2)
+         AND (?3 IS NULL OR seq > ?3)
+         ORDER BY seq",
+    )?;
+    let events = stmt
+        .query_map(
+            rusqlite::params![filter.since, filter.wire_id, filter.after],
+            event_from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(events)
+}
+
+/// Sequence number of the most recent event, or 0 if none were recorded.
+pub fn last_event_seq(conn: &Connection) -> Result<i64> {
+    Ok(
+        conn.query_row("SELECT COALESCE(MAX(seq), 0) FROM events", [], |row| {
+            row.get(0)
+        })?,
+    )
+}
+
+/// Returns events `consumer` hasn't acknowledged yet, oldest first.
+///
+/// The event log doubles as a write-ahead outbox: every mutation appends its
+/// event in the same transaction as the change, so nothing can be committed
+/// without its event. Pulling doesn't move the consumer's position; call
+/// [`ack_outbox`] once the events are handled, so a consumer that crashes
+/// (or was offline) receives them again. Unknown consumers are registered at
+/// the start of the log, and every pull counts as activity, so a consumer
+/// that keeps pulling isn't dropped by the `outbox` retention window.
+pub fn pull_outbox(
+    conn: &Connection,
+    consumer: &crate::models::Consumer,
+    limit: usize,
+) -> Result<Vec<crate::models::Event>> {
+    in_transaction(conn, || {
+        conn.execute(
+            "INSERT INTO outbox_consumers (consumer, acked_seq, updated_at)
+             VALUES (?1, 0, ?2)
+             ON CONFLICT (consumer) DO UPDATE SET updated_at = excluded.updated_at",
+            rusqlite::params![consumer, crate::time::now()],
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT seq, at, wire_id, kind, data FROM events
+             WHERE seq > (SELECT acked_seq FROM outbox_consumers WHERE consumer = ?1)
+             ORDER BY seq
+             LIMIT ?2",
+        )?;
+        let events = stmt
+            .query_map(
+                rusqlite::params![consumer, i64::try_from(limit)?],
+                event_from_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events)
+    })
+}
+
+/// Acknowledges every event up to and including `seq` for `consumer`.
+///
+/// Acknowledging an older position than the current one is a no-op, so
+/// retried acks are safe.
+///
+/// # Returns
+///
+/// The consumer's position after the acknowledgement.
+///
+/// # Errors
+///
+/// Returns an error if the consumer has never pulled or `seq` is beyond the
+/// last recorded event.
+pub fn ack_outbox(conn: &Connection, consumer: &crate::models::Consumer, seq: i64) -> Result<i64> {
+    use rusqlite::OptionalExtension;
+
+    in_transaction(conn, || {
+        let Some(acked): Option<i64> = conn
+            .query_row(
+                "SELECT acked_seq FROM outbox_consumers WHERE consumer = ?1",
+                [consumer],
+                |row| row.get(0),
+            )
+            .optional()?
+        else {
+            anyhow::bail!("Unknown outbox consumer '{}' (pull first)", consumer);
+        };
+
+        let last: i64 = conn.query_row("SELECT COALESCE(MAX(seq), 0) FROM events", [], |row| {
+            row.get(0)
+        })?;
+        if seq > last {
+            anyhow::bail!(
+                "Cannot acknowledge event {}: the last event is {}",
+                seq,
+                last
+            );
+        }
+        if seq <= acked {
+            return Ok(acked);
+        }
+
+        conn.execute(
+            "UPDATE outbox_consumers SET acked_seq = ?1, updated_at = ?2 WHERE consumer = ?3",
+            rusqlite::params![seq, crate::time::now(), consumer],
+        )?;
+        Ok(seq)
+    })
+}
+
+/// Lists outbox consumers with their position and backlog.
+pub fn list_outbox_cursors(conn: &Connection) -> Result<Vec<crate::models::OutboxCursor>> {
+    use crate::models::{Consumer, OutboxCursor};
+
+    let mut stmt = conn.prepare(
+        "SELECT c.consumer, c.acked_seq,
+                (SELECT COUNT(*) FROM events e WHERE e.seq > c.acked_seq),
+                c.updated_at
+         FROM outbox_consumers c
+         ORDER BY c.consumer",
+    )?;
+    let cursors = stmt
+        .query_map([], |row| {
+            let consumer: String = row.get(0)?;
+            Ok(OutboxCursor {
+                consumer: Consumer::new(&consumer).map_err(|_| rusqlite::Error::InvalidQuery)?,
+                acked_seq: row.get(1)?,
+                pending: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cursors)
+}
+
+/// Map a `seq, at, wire_id, kind, data` row to an Event
+fn event_from_row(row: &rusqlite::Row) -> rusqlite::Result<crate::models::Event> {
+    use crate::models::{Event, EventKind};
+    use std::str::FromStr;
+
+    let kind: String = row.get(3)?;
+    let data: String = row.get(4)?;
+    Ok(Event {
+        seq: row.get(0)?,
+        at: row.get(1)?,
+        wire_id: row.get(2)?,
+        kind: EventKind::from_str(&kind).map_err(|_| rusqlite::Error::InvalidQuery)?,
+        data: serde_json::from_str(&data).map_err(|_| rusqlite::Error::InvalidQuery)?,
+    })
+}
+
+/// Reverses the last `count` recorded changes, newest first.
+///
+/// Each change is undone by applying its inverse (deleting a created wire,
+/// restoring a deleted one with its tags and dependencies, setting updated
+/// fields back, and so on), so the reversal shows up in the event log too.
+/// Undone events and the events an undo writes are skipped by later undos.
+///
+/// # Returns
+///
+/// The events that were undone, most recent first.
+///
+/// # Errors
+///
+/// Returns an error, undoing nothing, if there are fewer than `count`
+/// changes to undo or one of them can no longer be reversed (e.g. the wire
+/// it touched has since been deleted).
+pub fn undo(conn: &Connection, count: usize) -> Result<Vec<crate::models::Event>> {
+    use rusqlite::OptionalExtension;
+
+    in_transaction(conn, || {
+        let mut undone = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let event = conn
+                .query_row(
+                    "SELECT seq, at, wire_id, kind, data FROM events
+                     WHERE undone = 0 AND undo_of IS NULL
+                     ORDER BY seq DESC LIMIT 1",
+                    [],
+                    event_from_row,
+                )
+                .optional()?;
+            let Some(event) = event else {
+                if undone.is_empty() {
+                    anyhow::bail!("Nothing to undo");
+                }
+                anyhow::bail!(
+                    "Only {} change(s) can be undone, {} requested",
+                    undone.len(),
+                    count
+                );
+            };
+
+            let last_seq: i64 =
+                conn.query_row("SELECT MAX(seq) FROM events", [], |row| row.get(0))?;
+
+            revert_event(conn, &event)
+                .with_context(|| format!("Cannot undo event {} ({})", event.seq, event.kind))?;
+
+            conn.execute(
+                "UPDATE events SET undo_of = ?1 WHERE seq > ?2",
+                [event.seq, last_seq],
+            )?;
+            conn.execute("UPDATE events SET undone = 1 WHERE seq = ?1", [event.seq])?;
+
+            undone.push(event);
+        }
+
+        Ok(undone)
+    })
+}
+
+/// Apply the inverse of a recorded change
+fn revert_event(conn: &Connection, event: &crate::models::Event) -> Result<()> {
+    use crate::models::{Agent, Dependency, DependencyKind, EventKind, Status, Tag, Wire};
+    use serde_json::Value;
+
+    let wire_id = event.wire_id.as_str();
+    let data = &event.data;
+    let field = |name: &str| -> Result<&str> {
+        data[name]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Event is missing '{}'", name))
+    };
+    // Dependency events only record kinds other than blocks
+    let kind = |name: &str| -> Result<DependencyKind> {
+        match data.get(name) {
+            Some(kind) => Ok(serde_json::from_value(kind.clone())?),
+            None => Ok(DependencyKind::Blocks),
+        }
+    };
+    let require_wire = || -> Result<()> {
+        if wire_exists(conn, wire_id)? {
+            Ok(())
+        } else {
+            Err(WireError::WireNotFound(wire_id.to_string()).into())
+        }
+    };
+
+    match event.kind {
+        EventKind::Created => {
+            if wire_exists(conn, wire_id)? {
+                delete_wire(conn, wire_id)?;
+            }
+        }
+        EventKind::Updated => {
+            require_wire()?;
+            let from = |name: &str| data.get(name).map(|change| &change["from"]);
+
+            let title = from("title").and_then(Value::as_str);
+            let description = from("description").map(Value::as_str);
+            let status = from("status")
+                .and_then(Value::as_str)
+                .map(str::parse::<Status>)
+                .transpose()
+                .map_err(anyhow::Error::msg)?;
+            let priority = from("priority")
+                .and_then(Value::as_i64)
+                .map(i32::try_from)
+                .transpose()?;
+
+            update_wire_unchecked(conn, wire_id, title, description, status, priority)?;
+        }
+        EventKind::Deleted => {
+            if wire_exists(conn, wire_id)? {
+                anyhow::bail!("Wire {} exists again", wire_id);
+            }
+            let wire: Wire = serde_json::from_value(data["wire"].clone())?;
+            insert_wire_unchecked(conn, &wire)?;
+            for tag in &wire.tags {
+                conn.execute(
+                    "INSERT OR IGNORE INTO tags (wire_id, tag) VALUES (?1, ?2)",
+                    rusqlite::params![wire.id, tag],
+                )?;
+            }
+
+            // Reconnect the edges whose other end still exists
+            let ids = |name: &str| -> Vec<String> {
+                data[name]
+                    .as_array()
+                    .map(|ids| {
+                        ids.iter()
+                            .filter_map(|id| id.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            for depends_on in ids("depends_on") {
+                if wire_exists(conn, &depends_on)? {
+                    add_dependency(conn, wire_id, &depends_on)?;
+                }
+            }
+            for dependent in ids("dependents") {
+                if wire_exists(conn, &dependent)? {
+                    add_dependency(conn, &dependent, wire_id)?;
+                }
+            }
+            let links: Vec<Dependency> = match data.get("links") {
+                Some(links) => serde_json::from_value(links.clone())?,
+                None => Vec::new(),
+            };
+            for link in links {
+                if wire_exists(conn, link.wire_id.as_str())?
+                    && wire_exists(conn, link.depends_on.as_str())?
+                {
+                    add_dependency_with_kind(
+                        conn,
+                        link.wire_id.as_str(),
+                        link.depends_on.as_str(),
+                        link.kind,
+                    )?;
+                }
+            }
+        }
+        EventKind::DepAdded => {
+            let depends_on = field("depends_on")?;
+            if data.get("from").is_some() {
+                add_dependency_with_kind(conn, wire_id, depends_on, kind("from")?)?;
+            } else {
+                remove_dependency(conn, wire_id, depends_on)?;
+            }
+        }
+        EventKind::DepRemoved => {
+            add_dependency_with_kind(conn, wire_id, field("depends_on")?, kind("kind")?)?
+        }
+        EventKind::Tagged => {
+            require_wire()?;
+            remove_tag(conn, wire_id, &Tag::new(field("tag")?)?)?;
+        }
+        EventKind::Untagged => {
+            add_tag(conn, wire_id, &Tag::new(field("tag")?)?)?;
+        }
+        EventKind::Claimed => {
+            let agent = Agent::new(field("agent")?)?;
+            if get_lease(conn, wire_id)?.is_some_and(|lease| lease.agent == agent) {
+                release_wire(conn, wire_id, Some(&agent))?;
+            }
+        }
+        EventKind::Released | EventKind::HandedOff => {
+            anyhow::bail!("Released claims can't be restored; claim the wire again instead")
+        }
+        EventKind::Blocked => {
+            let blocker = data["blocker"].as_i64().unwrap_or_default();
+            let removed = conn.execute(
+                "DELETE FROM external_blocks WHERE blocker_id = ?1 AND wire_id = ?2",
+                rusqlite::params![blocker, wire_id],
+            )?;
+            if removed > 0 {
+                record_event(conn, wire_id, EventKind::Unblocked, data.clone())?;
+            }
+        }
+        EventKind::Archived => {
+            if wire_exists(conn, wire_id)? {
+                unarchive_wire(conn, wire_id)?;
+            }
+        }
+        EventKind::Unarchived => {
+            require_wire()?;
+            insert_archived(conn, wire_id, crate::time::now())?;
+        }
+        EventKind::Assigned => {
+            require_wire()?;
+            let from = data["from"].as_str().map(Agent::new).transpose()?;
+            assign_wire(conn, wire_id, from.as_ref())?;
+        }
+        EventKind::LayoutChanged => {
+            require_wire()?;
+            let from = serde_json::from_value(data["from"].clone())?;
+            set_layout_hints(conn, wire_id, &from)?;
+        }
+        EventKind::Unblocked => {
+            require_wire()?;
+            let blocker = data["blocker"].as_i64().unwrap_or_default();
+            conn.execute(
+                "UPDATE external_blockers SET cleared_at = NULL WHERE id = ?1",
+                [blocker],
+            )?;
+            record_event(conn, wire_id, EventKind::Blocked, data.clone())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Append an entry to the event log
+fn record_event(
+    conn: &Connection,
+    wire_id: &str,
+    kind: crate::models::EventKind,
+    data: serde_json::Value,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO events (at, wire_id, kind, data) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![crate::time::now(), wire_id, kind.as_str(), data.to_string()],
+    )?;
+    Ok(())
+}
+
+/// Run `f` inside an immediate transaction, or directly if the caller
+/// already opened one
+fn in_transaction<T>(conn: &Connection, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    if !conn.is_autocommit() {
+        return f();
+    }
+
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    let value = f()?;
+    tx.commit()?;
+    Ok(value)
+}
+
+/// Fetch a single wire, if it exists
+fn fetch_wire(conn: &Connection, wire_id: &str) -> Result<Option<crate::models::Wire>> {
+    use rusqlite::OptionalExtension;
+
+    let query = format!("SELECT {} FROM wires w WHERE w.id = ?1", WIRE_COLUMNS);
+    let wire = conn
+        .query_row(&query, [wire_id], wire_from_row)
+        .optional()?;
+    Ok(wire)
+}
+
+/// Check whether a wire with the given ID exists
+fn wire_exists(conn: &Connection, wire_id: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM wires WHERE id = ?1",
+        [wire_id],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Sets a wire's `updated_at_ms` to the current time, without changing
+/// anything else, so it no longer looks stale.
+///
+/// No event is recorded: there is nothing for `wr undo` to restore.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist.
+pub fn touch_wire(conn: &Connection, wire_id: &str) -> Result<()> {
+    let touched = conn.execute(
+        "UPDATE wires SET updated_at_ms = ?1 WHERE id = ?2",
+        rusqlite::params![crate::time::now_ms(), wire_id],
+    )?;
+    if touched == 0 {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    }
+    Ok(())
+}
+
+/// Gets wires that are ready to work on.
+///
+/// A wire is ready if:
+/// - Its status is `TODO` or `IN_PROGRESS` (paused wires wait to be resumed)
+/// - All wires it depends on are `DONE`, or `CANCELLED` when
+///   `ready.cancelled_blocks` is off (see [`ReadyFilter::ready`])
+/// - No agent holds an active claim on it (see [`claim_wire`])
+///
+/// Results are sorted by:
+/// 1. Status (`IN_PROGRESS` first, then `TODO`)
+/// 2. Priority (higher priority first), or the score from the weights in
+///    [`ReadyFilter::ready`]
+///
+/// This is the primary function for AI agents to determine what to work on next.
+///
+/// # Example
+///
+/// ```no_run
+/// use wr::db;
+///
+/// let conn = db::open().expect("Failed to open database");
+/// let ready = db::get_ready_wires(&conn).expect("Failed to get ready wires");
+///
+/// if let Some(next) = ready.first() {
+///     println!("Next task: {} - {}", next.id, next.title);
+/// }
+/// ```
+pub fn get_ready_wires(conn: &Connection) -> Result<Vec<crate::models::Wire>> {
+    get_ready_wires_filtered(conn, &ReadyFilter::default())
+}
+
+/// A counter that changes whenever another connection commits to the
+/// database, for noticing changes without comparing contents.
+///
+/// Only meaningful compared with earlier values from the same connection.
+pub fn data_version(conn: &Connection) -> Result<i64> {
+    Ok(conn.pragma_query_value(None, "data_version", |row| row.get(0))?)
+}
+
+/// Options narrowing the set of wires returned by [`get_ready_wires_filtered`].
+#[derive(Debug, Clone, Default)]
+pub struct ReadyFilter {
+    /// Only wires nobody is working on yet: excludes `IN_PROGRESS` wires,
+    /// which belong to whichever agent started them
+    pub claimable: bool,
+    /// The agent asking. Wires it has claimed stay visible; wires with an
+    /// active claim by anyone else are always hidden.
+    pub agent: Option<crate::models::Agent>,
+    /// Only wires assigned to this agent
+    pub assignee: Option<crate::models::Agent>,
+    /// Only wires carrying this tag
+    pub tag: Option<crate::models::Tag>,
+    /// How wires of the same status are ranked, and whether CANCELLED
+    /// dependencies hold wires back
+    pub ready: crate::config::ReadyConfig,
+    /// Run each remaining wire's precondition command (see
+    /// [`set_precondition`]) and leave out wires whose command fails
+    pub check_preconditions: bool,
+    /// Only this wire and the wires it depends on, directly or not
+    pub epic: Option<String>,
+    /// At most this many wires, the best first
+    pub limit: Option<usize>,
+}
+
+/// Gets ready wires, narrowed by `filter`.
+///
+/// With a default filter this is identical to [`get_ready_wires`].
+pub fn get_ready_wires_filtered(
+    conn: &Connection,
+    filter: &ReadyFilter,
+) -> Result<Vec<crate::models::Wire>> {
+    let statuses = if filter.claimable {
+        "'TODO'"
+    } else {
+        "'TODO', 'IN_PROGRESS'"
+    };
+
+    if let Some(epic) = &filter.epic {
+        if !wire_exists(conn, epic)? {
+            return Err(WireError::WireNotFound(epic.clone()).into());
+        }
+    }
+
+    let query = format!(
+        "WITH RECURSIVE {}
+         SELECT {}
+         FROM wires w
+         WHERE w.status IN ({})
+         AND NOT EXISTS (
+             SELECT 1 FROM dependencies d
+             JOIN wires dep ON d.depends_on = dep.id
+             WHERE d.wire_id = w.id AND d.kind = 'blocks'
+             AND dep.status NOT IN ({})
+         )
+         AND NOT EXISTS (
+             SELECT 1 FROM leases l
+             WHERE l.wire_id = w.id
+             AND l.expires_at > ?1
+             AND l.agent IS NOT ?2
+         )
+         AND NOT EXISTS (
+             SELECT 1 FROM external_blocks eb
+             JOIN external_blockers b ON eb.blocker_id = b.id
+             WHERE eb.wire_id = w.id
+             AND b.cleared_at IS NULL
+         )
+         AND (?3 IS NULL OR w.assignee = ?3)
+         AND (?4 IS NULL OR w.id IN (SELECT id FROM subtree))
+         AND (?5 IS NULL OR EXISTS (SELECT 1 FROM tags t WHERE t.wire_id = w.id AND t.tag = ?5))
+         ORDER BY
+             CASE w.status
+                 WHEN 'IN_PROGRESS' THEN 0
+                 WHEN 'TODO' THEN 1
+             END,
+             w.priority DESC",
+        subtree_cte("?4"),
+        WIRE_COLUMNS,
+        statuses,
+        if filter.ready.cancelled_blocks {
+            "'DONE'"
+        } else {
+            "'DONE', 'CANCELLED'"
+        }
+    );
+
+    let now = crate::time::now();
+    let now_ms = crate::time::now_ms();
+    let mut stmt = conn.prepare(&query)?;
+    let mut wires = stmt
+        .query_map(
+            rusqlite::params![now, filter.agent, filter.assignee, filter.epic, filter.tag],
+            wire_from_row,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !filter.ready.ranks_by_priority() {
+        let unblocks = open_dependent_counts(conn)?;
+        wires.sort_by_cached_key(|w| {
+            let waiting = unblocks.get(w.id.as_str()).copied().unwrap_or(0);
+            let score = filter
+                .ready
+                .score(w.priority, (now_ms - w.created_at_ms) / 1000, waiting);
+            (
+                w.status != crate::models::Status::InProgress,
+                std::cmp::Reverse(score),
+            )
+        });
+    }
+
+    if filter.check_preconditions {
+        let mut held = Vec::with_capacity(wires.len());
+        for wire in wires {
+            match get_precondition(conn, wire.id.as_str())? {
+                Some(command) if !precondition_holds(conn, &command)? => {}
+                _ => held.push(wire),
+            }
+        }
+        wires = held;
+    }
+
+    if let Some(limit) = filter.limit {
+        wires.truncate(limit);
+    }
+
+    Ok(wires)
+}
+
+/// A `subtree(id)` common table expression: the wire bound to `param` and
+/// every wire it blocks on, directly or not. Use after `WITH RECURSIVE`.
+fn subtree_cte(param: &str) -> String {
+    format!(
+        "subtree(id) AS (
+             SELECT {param}
+             UNION
+             SELECT d.depends_on FROM dependencies d JOIN subtree s ON d.wire_id = s.id
+             WHERE d.kind = 'blocks'
+         )"
+    )
+}
+
+/// Progress of an epic: the wire `epic_id` and everything it depends on.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn epic_progress(conn: &Connection, epic_id: &str) -> Result<crate::models::EpicProgress> {
+    use crate::models::Status;
+
+    let Some(epic) = fetch_wire(conn, epic_id)? else {
+        return Err(WireError::WireNotFound(epic_id.to_string()).into());
+    };
+
+    let status = epic_status_stats(conn, epic_id)?;
+    let count = |status_of: Status| {
+        status
+            .rows
+            .iter()
+            .find(|row| row.status == status_of)
+            .map_or(0, |row| row.count)
+    };
+    let done = count(Status::Done);
+    let counted = status.total.count - count(Status::Cancelled);
+    let complete = if counted == 0 {
+        0.0
+    } else {
+        (done as f64 * 1000.0 / counted as f64).round() / 10.0
+    };
+
+    let filter = ReadyFilter {
+        epic: Some(epic_id.to_string()),
+        ..Default::default()
+    };
+    let ready = get_ready_wires_filtered(conn, &filter)?;
+
+    Ok(crate::models::EpicProgress {
+        epic,
+        status,
+        complete,
+        ready,
+    })
+}
+
+/// For each wire, how many open (not DONE or CANCELLED) wires depend on it
+fn open_dependent_counts(conn: &Connection) -> Result<std::collections::HashMap<String, i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT d.depends_on, COUNT(*)
+         FROM dependencies d
+         JOIN wires w ON d.wire_id = w.id
+         WHERE d.kind = 'blocks' AND w.status IN ('TODO', 'IN_PROGRESS', 'PAUSED', 'BLOCKED')
+         GROUP BY d.depends_on",
+    )?;
+    let counts = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    Ok(counts)
+}
+
+/// A wire's `estimate:<n>` tag value, or 1 if it has none.
+fn estimate(wire: &crate::models::Wire) -> u64 {
+    wire.tags
+        .iter()
+        .find_map(|tag| tag.as_str().strip_prefix(ESTIMATE_TAG_PREFIX)?.parse().ok())
+        .unwrap_or(1)
+}
+
+/// For every node, the weight of the heaviest chain ending there (its own
+/// weight plus its heaviest dependency chain) and the dependency that chain
+/// continues through.
+///
+/// `deps[i]` lists the nodes `i` depends on. Edges that would close a cycle
+/// are ignored.
+fn longest_chains(deps: &[Vec<usize>], weights: &[u64]) -> Vec<(u64, Option<usize>)> {
+    fn visit(
+        node: usize,
+        deps: &[Vec<usize>],
+        weights: &[u64],
+        best: &mut [Option<(u64, Option<usize>)>],
+        active: &mut [bool],
+    ) -> u64 {
+        if let Some((weight, _)) = best[node] {
+            return weight;
+        }
+        active[node] = true;
+        let mut heaviest: Option<(u64, usize)> = None;
+        for &dep in &deps[node] {
+            if active[dep] {
+                continue;
+            }
+            let weight = visit(dep, deps, weights, best, active);
+            if heaviest.is_none_or(|(w, _)| weight > w) {
+                heaviest = Some((weight, dep));
+            }
+        }
+        active[node] = false;
+        let total = weights[node] + heaviest.map_or(0, |(w, _)| w);
+        best[node] = Some((total, heaviest.map(|(_, dep)| dep)));
+        total
+    }
+
+    let mut best = vec![None; deps.len()];
+    let mut active = vec![false; deps.len()];
+    for node in 0..deps.len() {
+        visit(node, deps, weights, &mut best, &mut active);
+    }
+    best.into_iter().map(|b| b.unwrap_or((0, None))).collect()
+}
+
+/// Prefix of tags holding a wire's estimate for [`critical_path`], as in
+/// `estimate:3`.
+pub const ESTIMATE_TAG_PREFIX: &str = "estimate:";
+
+/// Finds the longest chain of unfinished (TODO, IN_PROGRESS, PAUSED or BLOCKED)
+/// wires linked by dependencies.
+///
+/// Each wire counts as 1, or with `weighted` as its `estimate:<n>` tag (1 if
+/// it has none). Among equally long chains the one ending in the
+/// highest-priority, oldest wire wins.
+pub fn critical_path(conn: &Connection, weighted: bool) -> Result<crate::models::CriticalPath> {
+    use crate::models::{CriticalPath, Wire};
+    use std::collections::HashMap;
+
+    let mut open: Vec<Wire> = list_wires(conn, &ListFilter::default())?
+        .into_iter()
+        .filter(|w| w.status.is_blocking())
+        .collect();
+    open.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then(a.created_at_ms.cmp(&b.created_at_ms))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let index: HashMap<&str, usize> = open
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (w.id.as_str(), i))
+        .collect();
+    let mut deps = vec![Vec::new(); open.len()];
+    for dep in list_dependencies(conn)?
+        .into_iter()
+        .filter(|d| d.kind.is_blocking())
+    {
+        if let (Some(&from), Some(&to)) = (
+            index.get(dep.wire_id.as_str()),
+            index.get(dep.depends_on.as_str()),
+        ) {
+            deps[from].push(to);
+        }
+    }
+    for list in &mut deps {
+        list.sort_unstable();
+    }
+
+    let weights: Vec<u64> = open
+        .iter()
+        .map(|w| if weighted { estimate(w) } else { 1 })
+        .collect();
+
+    let chains = longest_chains(&deps, &weights);
+    // Wires are in priority order, so the first of equal chains wins
+    let Some(end) = (0..chains.len()).reduce(|best, i| {
+        if chains[i].0 > chains[best].0 {
+            i
+        } else {
+            best
+        }
+    }) else {
+        return Ok(CriticalPath {
+            weight: 0,
+            wires: vec![],
+        });
+    };
+
+    // Walk from the last wire back to the first one to start
+    let mut chain = vec![end];
+    let mut node = end;
+    while let Some(next) = chains[node].1 {
+        chain.push(next);
+        node = next;
+    }
+    chain.reverse();
+
+    let weight = chains[end].0;
+    let mut slots: Vec<Option<Wire>> = open.into_iter().map(Some).collect();
+    let wires = chain.into_iter().filter_map(|i| slots[i].take()).collect();
+
+    Ok(CriticalPath { weight, wires })
+}
+
+/// Builds the transitive dependency tree rooted at a wire: what it depends
+/// on, or with `reverse`, what it blocks.
+///
+/// A wire reachable along several paths is expanded the first time it
+/// appears and marked [`repeated`](crate::models::DependencyTree::repeated)
+/// afterwards, keeping the tree linear in the size of the graph.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn dependency_tree(
+    conn: &Connection,
+    wire_id: &str,
+    reverse: bool,
+) -> Result<crate::models::DependencyTree> {
+    use crate::models::{DependencyInfo, DependencyTree};
+    use std::collections::HashSet;
+
+    fn expand(
+        conn: &Connection,
+        wire: DependencyInfo,
+        reverse: bool,
+        expanded: &mut HashSet<String>,
+    ) -> Result<DependencyTree> {
+        if !expanded.insert(wire.id.to_string()) {
+            return Ok(DependencyTree {
+                wire,
+                repeated: true,
+                children: vec![],
+            });
+        }
+
+        let (depends_on, blocks) = fetch_wire_deps(conn, wire.id.as_str())?;
+        let mut next = if reverse { blocks } else { depends_on };
+        next.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let children = next
+            .into_iter()
+            .map(|child| expand(conn, child, reverse, expanded))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DependencyTree {
+            wire,
+            repeated: false,
+            children,
+        })
+    }
+
+    let Some(root) = fetch_wire(conn, wire_id)? else {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    };
+    let root = DependencyInfo {
+        id: root.id,
+        title: root.title,
+        status: root.status,
+    };
+    expand(conn, root, reverse, &mut HashSet::new())
+}
+
+/// Explains why a wire is or isn't in `wr ready` for `agent`.
+///
+/// Walks the wire's dependencies transitively, following only unfinished
+/// ones (anything not DONE holds up its dependents, as in
+/// [`get_ready_wires`]). A dependency reachable along several paths is
+/// explained once and marked
+/// [`repeated`](crate::models::Blocker::repeated) afterwards.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn readiness(
+    conn: &Connection,
+    wire_id: &str,
+    agent: Option<&crate::models::Agent>,
+) -> Result<crate::models::Readiness> {
+    use crate::models::{Blocker, DependencyInfo, Readiness, Status};
+    use std::collections::HashSet;
+
+    fn blockers(
+        conn: &Connection,
+        wire_id: &str,
+        explained: &mut HashSet<String>,
+    ) -> Result<Vec<Blocker>> {
+        let (mut depends_on, _) = fetch_wire_deps(conn, wire_id)?;
+        depends_on.retain(|dep| dep.status != Status::Done);
+        depends_on.sort_by(|a, b| a.id.cmp(&b.id));
+
+        depends_on
+            .into_iter()
+            .map(|wire| {
+                if !explained.insert(wire.id.to_string()) {
+                    return Ok(Blocker {
+                        wire,
+                        repeated: true,
+                        blocked_on: vec![],
+                        blockers: vec![],
+                    });
+                }
+                let blocked_on = list_external_blockers(conn, Some(wire.id.as_str()))?;
+                let blockers = blockers(conn, wire.id.as_str(), explained)?;
+                Ok(Blocker {
+                    wire,
+                    repeated: false,
+                    blocked_on,
+                    blockers,
+                })
+            })
+            .collect()
+    }
+
+    let Some(wire) = fetch_wire(conn, wire_id)? else {
+        return Err(WireError::WireNotFound(wire_id.to_string()).into());
+    };
+
+    let claimed = get_lease(conn, wire_id)?
+        .filter(|lease| lease.expires_at > crate::time::now() && Some(&lease.agent) != agent);
+    let blocked_on = list_external_blockers(conn, Some(wire_id))?;
+    let blockers = blockers(conn, wire_id, &mut HashSet::from([wire_id.to_string()]))?;
+
+    let mut readiness = Readiness {
+        wire: DependencyInfo {
+            id: wire.id,
+            title: wire.title,
+            status: wire.status,
+        },
+        ready: false,
+        claimed,
+        blocked_on,
+        blockers,
+    };
+    readiness.ready = !readiness.status_blocks()
+        && readiness.claimed.is_none()
+        && readiness.blocked_on.is_empty()
+        && readiness.blockers.is_empty();
+    Ok(readiness)
+}
+
+/// Assembles everything known about a wire for `wr doc`: its fields and
+/// direct dependencies, its claim, whether it is ready for `agent`, every
+/// wire upstream of it and its history.
+///
+/// # Errors
+///
+/// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+pub fn dossier(
+    conn: &Connection,
+    wire_id: &str,
+    agent: Option<&crate::models::Agent>,
+) -> Result<crate::models::Dossier> {
+    let wire = get_wire_with_deps(conn, wire_id)?;
+    let lease = get_lease(conn, wire_id)?.filter(|lease| lease.expires_at > crate::time::now());
+    let readiness = readiness(conn, wire_id, agent)?;
+    let upstream = get_transitive_dependencies(conn, wire_id)?
+        .into_iter()
+        .filter_map(|dep| fetch_wire(conn, dep.wire.id.as_str()).transpose())
+        .collect::<Result<Vec<_>>>()?;
+    let history = list_events(
+        conn,
+        &EventFilter {
+            wire_id: Some(wire_id.to_string()),
+            ..Default::default()
+        },
+    )?;
+
+    Ok(crate::models::Dossier {
+        wire,
+        lease,
+        readiness,
+        upstream,
+        history,
+    })
+}
+
+/// Hands a wire off mid-task: releases its claim, records the handoff
+/// (with `note`, if given) in the history, and returns its dossier with the
+/// work left and every handoff note so far.
+///
+/// # Errors
+///
+/// Returns an error if the wire does not exist or is actively claimed by an
+/// agent other than `agent`.
+pub fn handoff(
+    conn: &Connection,
+    wire_id: &str,
+    agent: Option<&crate::models::Agent>,
+    note: Option<&str>,
+) -> Result<crate::models::Handoff> {
+    use crate::models::{EventKind, HandoffNote};
+
+    let released = in_transaction(conn, || {
+        let lease = get_lease(conn, wire_id)?.filter(|lease| lease.expires_at > crate::time::now());
+        release_wire(conn, wire_id, agent)?;
+        let from = agent.or(lease.as_ref().map(|lease| &lease.agent));
+        record_event(
+            conn,
+            wire_id,
+            EventKind::HandedOff,
+            serde_json::json!({ "agent": from, "note": note }),
+        )?;
+        Ok(lease)
+    })?;
+
+    let dossier = dossier(conn, wire_id, None)?;
+    let remaining = dossier
+        .upstream
+        .iter()
+        .filter(|wire| {
+            !matches!(
+                wire.status,
+                crate::models::Status::Done | crate::models::Status::Cancelled
+            )
+        })
+        .cloned()
+        .collect();
+    let notes = dossier
+        .history
+        .iter()
+        .filter(|event| event.kind == EventKind::HandedOff)
+        .filter_map(|event| {
+            Some(HandoffNote {
+                at: event.at,
+                agent: event.data["agent"].as_str().map(str::to_string),
+                note: event.data["note"].as_str()?.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(crate::models::Handoff {
+        dossier,
+        remaining,
+        notes,
+        released,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_init_creates_directory_and_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        init(path).unwrap();
+
+        assert!(path.join(WIRES_DIR).exists());
+        assert!(path.join(WIRES_DIR).join(DB_NAME).exists());
+    }
+
+    #[test]
+    fn test_init_fails_if_already_initialized() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        init(path).unwrap();
+        let result = init(path);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("already initialized"));
+    }
+
+    #[test]
+    fn test_schema_creation() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        init(path).unwrap();
 
         let db_path = path.join(WIRES_DIR).join(DB_NAME);
         let conn = Connection::open(db_path).unwrap();
 
-        // Check that tables exist
-        let mut stmt = conn
-            .prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")
+        // Check that tables exist
+        let mut stmt = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")
+            .unwrap();
+        let tables: Vec<String> = stmt
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<String>, _>>()
+            .unwrap();
+
+        assert!(tables.contains(&"wires".to_string()));
+        assert!(tables.contains(&"dependencies".to_string()));
+
+        // Check WAL mode
+        let journal_mode: String = conn
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode.to_uppercase(), "WAL");
+    }
+
+    #[test]
+    fn test_init_applies_all_migrations() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let version: i64 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn test_migrate_upgrades_base_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let conn = Connection::open(temp_dir.path().join(DB_NAME)).unwrap();
+
+        // A database created before migrations existed
+        create_schema(&conn).unwrap();
+        migrate(&conn).unwrap();
+
+        let tags_exists: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='tags'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(tags_exists, 1);
+
+        // Running again is a no-op
+        migrate(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_find_db_current_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        init(path).unwrap();
+
+        // Change to temp directory
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(path).unwrap();
+
+        let result = find_db();
+
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().ends_with(DB_NAME));
+    }
+
+    #[test]
+    fn test_find_db_parent_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        init(path).unwrap();
+
+        // Create subdirectory
+        let sub_dir = path.join("subdir");
+        fs::create_dir(&sub_dir).unwrap();
+
+        let result = find_db_from(&sub_dir);
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().ends_with(DB_NAME));
+    }
+
+    #[test]
+    fn test_find_db_not_found() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        let result = find_db_from(path);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Not a wires repository"));
+    }
+
+    // Helper to set up a test database with schema
+    fn setup_test_db() -> (TempDir, Connection) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        init(path).unwrap();
+        let db_path = path.join(WIRES_DIR).join(DB_NAME);
+        let conn = connect(&db_path).unwrap();
+        (temp_dir, conn)
+    }
+
+    // Helper to insert a test wire
+    fn insert_test_wire(conn: &Connection, id: &str) {
+        conn.execute(
+            "INSERT INTO wires (id, title, status, created_at_ms, updated_at_ms, priority)
+             VALUES (?1, ?2, 'TODO', 0, 0, 0)",
+            [id, &format!("Wire {}", id)],
+        )
+        .unwrap();
+    }
+
+    // Helper to insert a dependency
+    fn insert_test_dep(conn: &Connection, wire_id: &str, depends_on: &str) {
+        conn.execute(
+            "INSERT INTO dependencies (wire_id, depends_on) VALUES (?1, ?2)",
+            [wire_id, depends_on],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_cycle_detection_self_reference() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+
+        let result = would_create_cycle(&conn, "a", "a").unwrap();
+
+        assert!(result.is_some());
+        let cycle = result.unwrap();
+        assert_eq!(cycle, vec!["a", "a"]);
+    }
+
+    #[test]
+    fn test_cycle_detection_direct_cycle() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+
+        // a depends on b
+        insert_test_dep(&conn, "a", "b");
+
+        // Would b -> a create a cycle? Yes: b -> a -> b
+        let result = would_create_cycle(&conn, "b", "a").unwrap();
+
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn test_cycle_detection_indirect_cycle() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+        insert_test_wire(&conn, "c");
+
+        // Chain: a -> b -> c
+        insert_test_dep(&conn, "a", "b");
+        insert_test_dep(&conn, "b", "c");
+
+        // Would c -> a create a cycle? Yes: c -> a -> b -> c
+        let result = would_create_cycle(&conn, "c", "a").unwrap();
+
+        assert!(result.is_some());
+    }
+
+    #[test]
+    fn test_non_blocking_dependencies_skip_ready_and_cycle_checks() {
+        use crate::models::DependencyKind;
+
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+
+        add_dependency_with_kind(&conn, "a", "b", DependencyKind::Relates).unwrap();
+        add_dependency_with_kind(&conn, "b", "a", DependencyKind::Duplicates).unwrap();
+        let mut ready: Vec<String> = get_ready_wires(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id.to_string())
+            .collect();
+        ready.sort();
+        assert_eq!(ready, vec!["a", "b"]);
+
+        let wire = get_wire_with_deps(&conn, "a").unwrap();
+        assert!(wire.depends_on.is_empty());
+        assert_eq!(wire.links.len(), 1);
+        assert_eq!(wire.links[0].kind, DependencyKind::Relates);
+
+        // Turning a link into a blocking edge is checked for cycles
+        assert!(add_dependency(&conn, "a", "b").is_ok());
+        assert!(add_dependency(&conn, "b", "a").is_err());
+        assert!(add_dependency_with_kind(&conn, "a", "a", DependencyKind::Parent).is_err());
+    }
+
+    #[test]
+    fn test_adjust_priority_records_an_undoable_update() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "aaaaaaa");
+
+        assert_eq!(adjust_priority(&conn, "aaaaaaa", 2).unwrap(), (0, 2));
+        assert_eq!(adjust_priority(&conn, "aaaaaaa", -5).unwrap(), (2, -3));
+        assert!(adjust_priority(&conn, "bbbbbbb", 1).is_err());
+
+        undo(&conn, 1).unwrap();
+        assert_eq!(get_wire(&conn, "aaaaaaa").unwrap().unwrap().priority, 2);
+    }
+
+    #[test]
+    fn test_linked_wires_see_each_other() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+
+        link_wires(&conn, "a", "b").unwrap();
+        link_wires(&conn, "b", "a").unwrap();
+        assert_eq!(list_dependencies(&conn).unwrap().len(), 1);
+
+        let linked = |wire: &crate::models::WireWithDeps| -> Vec<String> {
+            wire.links.iter().map(|l| l.wire.id.to_string()).collect()
+        };
+        assert_eq!(linked(&get_wire_with_deps(&conn, "b").unwrap()), vec!["a"]);
+        let all = list_wires_with_deps(&conn, &ListFilter::default()).unwrap();
+        for wire in &all {
+            let other = if wire.wire.id.as_str() == "a" {
+                "b"
+            } else {
+                "a"
+            };
+            assert_eq!(linked(wire), vec![other]);
+        }
+
+        unlink_wires(&conn, "b", "a").unwrap();
+        assert!(list_dependencies(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_undo_restores_previous_dependency_kind() {
+        use crate::models::DependencyKind;
+
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "aaaaaaa");
+        insert_test_wire(&conn, "bbbbbbb");
+        let kinds = |conn: &Connection| -> Vec<DependencyKind> {
+            list_dependencies(conn)
+                .unwrap()
+                .into_iter()
+                .map(|d| d.kind)
+                .collect()
+        };
+
+        add_dependency_with_kind(&conn, "aaaaaaa", "bbbbbbb", DependencyKind::Parent).unwrap();
+        add_dependency(&conn, "aaaaaaa", "bbbbbbb").unwrap();
+        assert_eq!(kinds(&conn), vec![DependencyKind::Blocks]);
+
+        undo(&conn, 1).unwrap();
+        assert_eq!(kinds(&conn), vec![DependencyKind::Parent]);
+
+        remove_dependency(&conn, "aaaaaaa", "bbbbbbb").unwrap();
+        undo(&conn, 1).unwrap();
+        assert_eq!(kinds(&conn), vec![DependencyKind::Parent]);
+
+        delete_wire(&conn, "bbbbbbb").unwrap();
+        undo(&conn, 1).unwrap();
+        assert_eq!(kinds(&conn), vec![DependencyKind::Parent]);
+    }
+
+    #[test]
+    fn test_undo_restores_previous_layout_hints() {
+        use crate::models::{EventKind, LayoutHints};
+
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "aaaaaaa");
+        let backend = LayoutHints {
+            cluster: Some("Backend".to_string()),
+            ..Default::default()
+        };
+        let ranked = LayoutHints {
+            rank: Some("top".to_string()),
+            ..backend.clone()
+        };
+
+        set_layout_hints(&conn, "aaaaaaa", &backend).unwrap();
+        set_layout_hints(&conn, "aaaaaaa", &ranked).unwrap();
+        set_layout_hints(&conn, "aaaaaaa", &ranked).unwrap();
+        let changes = list_events(&conn, &EventFilter::default())
+            .unwrap()
+            .iter()
+            .filter(|e| e.kind == EventKind::LayoutChanged)
+            .count();
+        assert_eq!(changes, 2);
+
+        undo(&conn, 1).unwrap();
+        assert_eq!(get_layout_hints(&conn, "aaaaaaa").unwrap(), backend);
+        undo(&conn, 1).unwrap();
+        assert!(get_layout_hints(&conn, "aaaaaaa").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cycle_detection_no_cycle() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+        insert_test_wire(&conn, "c");
+
+        // a -> b (no chain to c)
+        insert_test_dep(&conn, "a", "b");
+
+        // Would c -> a create a cycle? No, there's no path from a to c
+        let result = would_create_cycle(&conn, "c", "a").unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_cycle_detection_diamond_allowed() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+        insert_test_wire(&conn, "c");
+        insert_test_wire(&conn, "d");
+
+        // Diamond: d -> b, d -> c, b -> a, c -> a
+        insert_test_dep(&conn, "d", "b");
+        insert_test_dep(&conn, "d", "c");
+        insert_test_dep(&conn, "b", "a");
+        insert_test_dep(&conn, "c", "a");
+
+        // This is a valid DAG (diamond shape), not a cycle
+        // Adding another dep from d to a should be allowed
+        let result = would_create_cycle(&conn, "d", "a").unwrap();
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_fetch_wire_deps_empty() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+
+        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+
+        assert!(depends_on.is_empty());
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn test_fetch_wire_deps_with_dependencies() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+        insert_test_wire(&conn, "b2c3d4e");
+        insert_test_dep(&conn, "a1b2c3d", "b2c3d4e"); // a depends on b
+
+        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+
+        assert_eq!(depends_on.len(), 1);
+        assert_eq!(depends_on[0].id.as_str(), "b2c3d4e");
+        assert!(blocks.is_empty());
+    }
+
+    #[test]
+    fn test_fetch_wire_deps_with_blocks() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+        insert_test_wire(&conn, "b2c3d4e");
+        insert_test_dep(&conn, "b2c3d4e", "a1b2c3d"); // b depends on a, so a blocks b
+
+        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+
+        assert!(depends_on.is_empty());
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id.as_str(), "b2c3d4e");
+    }
+
+    #[test]
+    fn test_fetch_wire_deps_both_directions() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+        insert_test_wire(&conn, "b2c3d4e");
+        insert_test_wire(&conn, "c3d4e5f");
+
+        // a depends on b, c depends on a
+        insert_test_dep(&conn, "a1b2c3d", "b2c3d4e");
+        insert_test_dep(&conn, "c3d4e5f", "a1b2c3d");
+
+        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+
+        assert_eq!(depends_on.len(), 1);
+        assert_eq!(depends_on[0].id.as_str(), "b2c3d4e");
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id.as_str(), "c3d4e5f");
+    }
+
+    #[test]
+    fn test_list_wires_with_deps_empty() {
+        let (_temp_dir, conn) = setup_test_db();
+
+        let result = list_wires_with_deps(&conn, &ListFilter::default()).unwrap();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_list_wires_with_deps_includes_dependencies() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+        insert_test_wire(&conn, "b2c3d4e");
+        insert_test_dep(&conn, "a1b2c3d", "b2c3d4e");
+
+        let result = list_wires_with_deps(&conn, &ListFilter::default()).unwrap();
+
+        assert_eq!(result.len(), 2);
+
+        // Find the wire that has a dependency
+        let wire_a = result
+            .iter()
+            .find(|w| w.wire.id.as_str() == "a1b2c3d")
             .unwrap();
-        let tables: Vec<String> = stmt
-            .query_map([], |row| row.get(0))
+        assert_eq!(wire_a.depends_on.len(), 1);
+        assert_eq!(wire_a.depends_on[0].id.as_str(), "b2c3d4e");
+
+        // Find the wire that is depended on
+        let wire_b = result
+            .iter()
+            .find(|w| w.wire.id.as_str() == "b2c3d4e")
+            .unwrap();
+        assert_eq!(wire_b.blocks.len(), 1);
+        assert_eq!(wire_b.blocks[0].id.as_str(), "a1b2c3d");
+    }
+
+    #[test]
+    fn test_add_tag_is_idempotent_and_sorted() {
+        use crate::models::Tag;
+
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+
+        let tests = Tag::new("tests").unwrap();
+        let backend = Tag::new("backend").unwrap();
+
+        assert!(add_tag(&conn, "a1b2c3d", &tests).unwrap());
+        assert!(!add_tag(&conn, "a1b2c3d", &tests).unwrap());
+        assert!(add_tag(&conn, "a1b2c3d", &backend).unwrap());
+
+        let wire = get_wire_with_deps(&conn, "a1b2c3d").unwrap().wire;
+        assert_eq!(wire.tags, vec![backend.clone(), tests.clone()]);
+
+        assert!(remove_tag(&conn, "a1b2c3d", &tests).unwrap());
+        let wire = get_wire_with_deps(&conn, "a1b2c3d").unwrap().wire;
+        assert_eq!(wire.tags, vec![backend]);
+    }
+
+    #[test]
+    fn test_add_tag_missing_wire() {
+        let (_temp_dir, conn) = setup_test_db();
+        let tag = crate::models::Tag::new("backend").unwrap();
+
+        let result = add_tag(&conn, "a1b2c3d", &tag);
+        assert!(result.unwrap_err().to_string().contains("Wire not found"));
+    }
+
+    #[test]
+    fn test_renumber_priorities_breaks_ties_by_age() {
+        let (_temp_dir, conn) = setup_test_db();
+        for (id, priority, created_at_ms, status) in [
+            ("aaaaaaa", 5, 100, "TODO"),
+            ("bbbbbbb", 5, 50, "IN_PROGRESS"),
+            ("ccccccc", 1, 10, "TODO"),
+            ("ddddddd", 9, 10, "DONE"),
+        ] {
+            conn.execute(
+                "INSERT INTO wires (id, title, status, created_at_ms, updated_at_ms, priority)
+                 VALUES (?1, ?1, ?2, ?3, ?3, ?4)",
+                rusqlite::params![id, status, created_at_ms, priority],
+            )
+            .unwrap();
+        }
+
+        let changes = renumber_priorities(&conn, 10, false).unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].id.as_str(), "bbbbbbb");
+        assert_eq!((changes[0].from, changes[0].to), (5, 30));
+
+        let priority = |id: &str| -> i32 {
+            conn.query_row("SELECT priority FROM wires WHERE id = ?1", [id], |r| {
+                r.get(0)
+            })
             .unwrap()
-            .collect::<Result<Vec<String>, _>>()
+        };
+        assert_eq!(priority("bbbbbbb"), 30);
+        assert_eq!(priority("aaaaaaa"), 20);
+        assert_eq!(priority("ccccccc"), 10);
+        // Closed wires are left alone
+        assert_eq!(priority("ddddddd"), 9);
+
+        // Already normalized: nothing to do
+        assert!(renumber_priorities(&conn, 10, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_renumber_priorities_dry_run() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+
+        let changes = renumber_priorities(&conn, 10, true).unwrap();
+        assert_eq!(changes.len(), 1);
+
+        let priority: i32 = conn
+            .query_row("SELECT priority FROM wires WHERE id = 'a1b2c3d'", [], |r| {
+                r.get(0)
+            })
             .unwrap();
+        assert_eq!(priority, 0);
 
-        assert!(tables.contains(&"wires".to_string()));
-        assert!(tables.contains(&"dependencies".to_string()));
+        assert!(renumber_priorities(&conn, 0, false).is_err());
+    }
 
-        // Check WAL mode
-        let journal_mode: String = conn
-            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+    #[test]
+    fn test_expired_lease_can_be_taken_over() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
+        let first = crate::models::Agent::new("agent-1").unwrap();
+        let second = crate::models::Agent::new("agent-2").unwrap();
+
+        // A zero TTL lapses immediately
+        claim_wire(&conn, "a1b2c3d", &first, 0).unwrap();
+        let filter = ReadyFilter {
+            agent: Some(second.clone()),
+            ..Default::default()
+        };
+        assert_eq!(get_ready_wires_filtered(&conn, &filter).unwrap().len(), 1);
+
+        let lease = claim_wire(&conn, "a1b2c3d", &second, 60).unwrap();
+        assert_eq!(lease.agent, second);
+        assert!(claim_wire(&conn, "a1b2c3d", &first, 60).is_err());
+    }
+
+    #[test]
+    fn test_resolve_wire_id_by_title() {
+        let (_temp_dir, conn) = setup_test_db();
+        for (id, title, status) in [
+            ("aaaaaaa", "Fix parser panics", "TODO"),
+            ("bbbbbbb", "Parser docs", "DONE"),
+            ("ccccccc", "Refactor lexer", "IN_PROGRESS"),
+        ] {
+            conn.execute(
+                "INSERT INTO wires (id, title, status, created_at_ms, updated_at_ms, priority)
+                 VALUES (?1, ?2, ?3, 0, 0, 0)",
+                [id, title, status],
+            )
             .unwrap();
-        assert_eq!(journal_mode.to_uppercase(), "WAL");
+        }
+
+        // Plain IDs pass through untouched
+        assert_eq!(resolve_wire_id(&conn, "bbbbbbb").unwrap(), "bbbbbbb");
+        // Only open wires are searched, case-insensitively
+        assert_eq!(resolve_wire_id(&conn, "title:PARSER").unwrap(), "aaaaaaa");
+        assert!(resolve_wire_id(&conn, "title:docs")
+            .unwrap_err()
+            .to_string()
+            .contains("Wire not found"));
+
+        let err = resolve_wire_id(&conn, "title:e").unwrap_err();
+        match err.downcast_ref::<WireError>() {
+            Some(WireError::AmbiguousWire { candidates, .. }) => {
+                assert_eq!(candidates.len(), 2)
+            }
+            other => panic!("expected AmbiguousWire, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_find_db_current_directory() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
+    fn test_list_wires_with_deps_respects_status_filter() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a1b2c3d");
 
-        init(path).unwrap();
+        // Change wire to DONE
+        conn.execute("UPDATE wires SET status = 'DONE' WHERE id = 'a1b2c3d'", [])
+            .unwrap();
 
-        // Change to temp directory
-        let original_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(path).unwrap();
+        // Filter by TODO should return empty
+        let todo_result =
+            list_wires_with_deps(&conn, &ListFilter::status(crate::models::Status::Todo)).unwrap();
+        assert!(todo_result.is_empty());
 
-        let result = find_db();
+        // Filter by DONE should return the wire
+        let done_result =
+            list_wires_with_deps(&conn, &ListFilter::status(crate::models::Status::Done)).unwrap();
+        assert_eq!(done_result.len(), 1);
+    }
 
-        std::env::set_current_dir(original_dir).unwrap();
+    #[test]
+    fn test_list_wires_combines_filter_conditions() {
+        use crate::models::Status;
+
+        let (_temp_dir, conn) = setup_test_db();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        conn.execute_batch(
+            "UPDATE wires SET title = 'Fix AUTH flow', priority = 5 WHERE id = 'aaaaaaa';
+             UPDATE wires SET title = 'auth docs', priority = 1 WHERE id = 'bbbbbbb';
+             UPDATE wires SET title = 'Auth retries', priority = 3, status = 'IN_PROGRESS'
+                 WHERE id = 'ccccccc';
+             UPDATE wires SET title = 'Auth cleanup', priority = 9, status = 'DONE'
+                 WHERE id = 'ddddddd';",
+        )
+        .unwrap();
+
+        let ids = |filter: &ListFilter| {
+            let mut ids: Vec<String> = list_wires(&conn, filter)
+                .unwrap()
+                .into_iter()
+                .map(|w| w.id.to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            ids(&ListFilter {
+                statuses: vec![Status::Todo, Status::InProgress],
+                priority_min: Some(3),
+                title_contains: Some("auth".to_string()),
+                ..Default::default()
+            }),
+            ["aaaaaaa", "ccccccc"]
+        );
+        assert_eq!(
+            ids(&ListFilter {
+                title_contains: Some("DOCS".to_string()),
+                ..Default::default()
+            }),
+            ["bbbbbbb"]
+        );
+        assert_eq!(ids(&ListFilter::default()).len(), 4);
+
+        let backend = crate::models::Tag::new("backend").unwrap();
+        let alice = crate::models::Agent::new("alice").unwrap();
+        add_tag(&conn, "aaaaaaa", &backend).unwrap();
+        add_tag(&conn, "bbbbbbb", &backend).unwrap();
+        assign_wire(&conn, "bbbbbbb", Some(&alice)).unwrap();
+        assign_wire(&conn, "ccccccc", Some(&alice)).unwrap();
+        assert_eq!(
+            ids(&ListFilter {
+                tag: Some(backend.clone()),
+                ..Default::default()
+            }),
+            ["aaaaaaa", "bbbbbbb"]
+        );
+        assert_eq!(
+            ids(&ListFilter {
+                tag: Some(backend.clone()),
+                assignee: Some(alice.clone()),
+                ..Default::default()
+            }),
+            ["bbbbbbb"]
+        );
+
+        let ready = |filter: &ReadyFilter| {
+            let mut ids: Vec<String> = get_ready_wires_filtered(&conn, filter)
+                .unwrap()
+                .into_iter()
+                .map(|w| w.id.to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(
+            ready(&ReadyFilter {
+                tag: Some(backend),
+                assignee: Some(alice),
+                ..Default::default()
+            }),
+            ["bbbbbbb"]
+        );
+    }
+
+    #[test]
+    fn test_external_blocker_is_shared_and_cleared() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+
+        let first = add_external_blocker(&conn, "API key", &["a".to_string()]).unwrap();
+        let second = add_external_blocker(&conn, " API key ", &["b".to_string()]).unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.wires.len(), 2);
+        assert!(get_ready_wires(&conn).unwrap().is_empty());
+
+        let cleared = clear_external_blocker(&conn, "API key").unwrap();
+        assert!(cleared.cleared_at.is_some());
+        assert_eq!(get_ready_wires(&conn).unwrap().len(), 2);
+        assert!(list_external_blockers(&conn, None).unwrap().is_empty());
+
+        // A cleared condition isn't reused
+        let third = add_external_blocker(&conn, "API key", &["a".to_string()]).unwrap();
+        assert_ne!(third.id, first.id);
+        assert!(clear_external_blocker(&conn, &first.id.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_connections_enforce_foreign_keys() {
+        let (temp_dir, _) = setup_test_db();
+        let conn = open_at(temp_dir.path()).unwrap();
+        let enabled: bool = conn
+            .pragma_query_value(None, "foreign_keys", |row| row.get(0))
+            .unwrap();
+        assert!(enabled);
+
+        insert_test_wire(&conn, "a");
+        assert!(conn
+            .execute(
+                "INSERT INTO dependencies (wire_id, depends_on) VALUES ('a', 'missing')",
+                [],
+            )
+            .is_err());
+        assert!(conn
+            .execute(
+                "INSERT INTO tags (wire_id, tag) VALUES ('missing', 'x')",
+                []
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_deleting_wire_row_cascades() {
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "a");
+        insert_test_wire(&conn, "b");
+        insert_test_dep(&conn, "a", "b");
+        add_tag(&conn, "b", &crate::models::Tag::new("x").unwrap()).unwrap();
+        add_external_blocker(&conn, "later", &["b".to_string()]).unwrap();
+
+        // Bypass delete_wire: the schema alone must not leave orphans
+        conn.execute("DELETE FROM wires WHERE id = 'b'", [])
+            .unwrap();
 
-        assert!(result.is_ok());
-        assert!(result.unwrap().ends_with(DB_NAME));
+        for table in ["dependencies", "tags", "external_blocks"] {
+            let count: i64 = conn
+                .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                    row.get(0)
+                })
+                .unwrap();
+            assert_eq!(count, 0, "orphaned rows in {}", table);
+        }
     }
 
     #[test]
-    fn test_find_db_parent_directory() {
+    fn test_migration_removes_existing_orphans() {
         let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
+        let conn = Connection::open(temp_dir.path().join(DB_NAME)).unwrap();
+        create_schema(&conn).unwrap();
 
-        init(path).unwrap();
+        // Written without enforcement, as connections could be before
+        conn.pragma_update(None, "foreign_keys", false).unwrap();
+        conn.execute(
+            "INSERT INTO wires (id, title, status, created_at, updated_at)
+             VALUES ('a', 'Wire a', 'TODO', 0, 0)",
+            [],
+        )
+        .unwrap();
+        insert_test_dep(&conn, "a", "gone");
+        insert_test_dep(&conn, "gone", "a");
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
 
-        // Create subdirectory
-        let sub_dir = path.join("subdir");
-        fs::create_dir(&sub_dir).unwrap();
+        migrate(&conn).unwrap();
 
-        let result = find_db_from(&sub_dir);
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM dependencies", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
 
-        assert!(result.is_ok());
-        assert!(result.unwrap().ends_with(DB_NAME));
+    #[test]
+    fn test_status_stats_totals_and_sort() {
+        use crate::models::{StatsSort, Status};
+
+        let (_temp_dir, conn) = setup_test_db();
+        for id in ["a", "b", "c"] {
+            insert_test_wire(&conn, id);
+        }
+        conn.execute("UPDATE wires SET status = 'DONE' WHERE id = 'c'", [])
+            .unwrap();
+
+        let mut stats = status_stats(&conn).unwrap();
+        assert_eq!(stats.rows.len(), 6);
+        assert_eq!(stats.rows[0].status, Status::Todo);
+        assert_eq!(stats.rows[0].count, 2);
+        assert_eq!(stats.rows[0].percent, 66.7);
+        assert_eq!(stats.rows[1].oldest_age, None);
+        assert_eq!(stats.total.count, 3);
+        assert_eq!(stats.total.percent, 100.0);
+        assert!(stats.total.oldest_age.is_some());
+
+        stats.sort_by(StatsSort::Count);
+        let order: Vec<_> = stats.rows.iter().map(|row| row.status).collect();
+        assert_eq!(
+            order,
+            vec![
+                Status::Todo,
+                Status::Done,
+                Status::InProgress,
+                Status::Paused,
+                Status::Blocked,
+                Status::Cancelled
+            ]
+        );
     }
 
     #[test]
-    fn test_find_db_not_found() {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
+    fn test_reserved_ids_are_unique_and_single_use() {
+        use crate::models::Wire;
 
-        let result = find_db_from(path);
+        let (_temp_dir, conn) = setup_test_db();
+        let ids = reserve_ids(&conn, 5).unwrap();
+        let unique: std::collections::HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), 5);
+
+        let mut wire = Wire::new("Reserved", None, 0).unwrap();
+        wire.id = ids[0].clone();
+        insert_reserved_wire(&conn, &mut wire).unwrap();
+        assert!(wire_exists(&conn, ids[0].as_str()).unwrap());
+
+        let err = insert_reserved_wire(&conn, &mut wire).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<WireError>(),
+            Some(WireError::NotReserved(_))
+        ));
+    }
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Not a wires repository"));
+    #[test]
+    fn test_longest_chains_prefers_heaviest() {
+        // 0 <- 1 <- 2, and 3 (heavy) <- 2
+        let deps = vec![vec![], vec![0], vec![1, 3], vec![]];
+        let chains = longest_chains(&deps, &[1, 1, 1, 1]);
+        assert_eq!(chains[2], (3, Some(1)));
+
+        let chains = longest_chains(&deps, &[1, 1, 1, 5]);
+        assert_eq!(chains[2], (6, Some(3)));
     }
 
-    // Helper to set up a test database with schema
-    fn setup_test_db() -> (TempDir, Connection) {
-        let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path();
-        init(path).unwrap();
-        let db_path = path.join(WIRES_DIR).join(DB_NAME);
-        let conn = Connection::open(db_path).unwrap();
-        (temp_dir, conn)
+    #[test]
+    fn test_critical_path_skips_finished_wires() {
+        let (_temp_dir, conn) = setup_test_db();
+        for id in ["a", "b", "c", "d"] {
+            insert_test_wire(&conn, id);
+        }
+        insert_test_dep(&conn, "b", "a");
+        insert_test_dep(&conn, "c", "b");
+        insert_test_dep(&conn, "d", "c");
+        conn.execute("UPDATE wires SET status = 'DONE' WHERE id = 'a'", [])
+            .unwrap();
+
+        let path = critical_path(&conn, false).unwrap();
+        let ids: Vec<&str> = path.wires.iter().map(|w| w.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c", "d"]);
+        assert_eq!(path.weight, 3);
     }
 
-    // Helper to insert a test wire
-    fn insert_test_wire(conn: &Connection, id: &str) {
+    #[test]
+    fn test_start_wire_pauses_least_recently_updated() {
+        use crate::config::InProgressPolicy;
+        use crate::models::Status;
+        use std::num::NonZeroUsize;
+
+        let (_temp_dir, conn) = setup_test_db();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc"] {
+            insert_test_wire(&conn, id);
+        }
         conn.execute(
-            "INSERT INTO wires (id, title, status, created_at, updated_at, priority)
-             VALUES (?1, ?2, 'TODO', 0, 0, 0)",
-            [id, &format!("Wire {}", id)],
+            "UPDATE wires SET status = 'IN_PROGRESS', updated_at_ms = 10 WHERE id = 'aaaaaaa'",
+            [],
         )
         .unwrap();
-    }
-
-    // Helper to insert a dependency
-    fn insert_test_dep(conn: &Connection, wire_id: &str, depends_on: &str) {
         conn.execute(
-            "INSERT INTO dependencies (wire_id, depends_on) VALUES (?1, ?2)",
-            [wire_id, depends_on],
+            "UPDATE wires SET status = 'IN_PROGRESS', updated_at_ms = 20 WHERE id = 'bbbbbbb'",
+            [],
         )
         .unwrap();
+
+        let policy = InProgressPolicy {
+            limit: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+        let paused = start_wire(&conn, "ccccccc", None, &policy).unwrap();
+        assert_eq!(paused.len(), 1);
+        assert_eq!(paused[0].id.as_str(), "aaaaaaa");
+        assert!(get_pause_note(&conn, "aaaaaaa").unwrap().is_some());
+
+        // Leaving PAUSED drops the note
+        update_wire(&conn, "aaaaaaa", None, None, Some(Status::Todo), None).unwrap();
+        assert!(get_pause_note(&conn, "aaaaaaa").unwrap().is_none());
     }
 
     #[test]
-    fn test_cycle_detection_self_reference() {
+    fn test_claim_ready_wires_takes_one_by_default_and_stays_within_limit() {
+        use crate::config::{Config, InProgressPolicy};
+        use crate::models::{Agent, Status};
+        use std::num::NonZeroUsize;
+
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a");
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        let agent = Agent::new("alice").unwrap();
+        let mut config = Config::default();
 
-        let result = would_create_cycle(&conn, "a", "a").unwrap();
+        let started =
+            claim_ready_wires(&conn, &ReadyFilter::default(), &agent, 60, &config).unwrap();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].wire.status, Status::InProgress);
+        assert_eq!(started[0].lease.as_ref().unwrap().agent, agent);
 
-        assert!(result.is_some());
-        let cycle = result.unwrap();
-        assert_eq!(cycle, vec!["a", "a"]);
+        let first = started[0].wire.id.clone();
+        conn.execute(
+            "UPDATE wires SET updated_at_ms = 0 WHERE id = ?1",
+            [first.as_str()],
+        )
+        .unwrap();
+
+        config.in_progress = InProgressPolicy {
+            limit: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+        let filter = ReadyFilter {
+            limit: Some(3),
+            ..Default::default()
+        };
+        let started = claim_ready_wires(&conn, &filter, &agent, 60, &config).unwrap();
+        assert_eq!(started.len(), 2);
+        // The earlier wire is paused to make room, not one of the batch
+        let paused: Vec<_> = started.iter().flat_map(|next| &next.paused).collect();
+        assert_eq!(paused.len(), 1);
+        assert_eq!(paused[0].id, first);
+        let in_progress = list_wires(&conn, &ListFilter::status(Status::InProgress)).unwrap();
+        assert_eq!(in_progress.len(), 2);
     }
 
     #[test]
-    fn test_cycle_detection_direct_cycle() {
-        let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a");
-        insert_test_wire(&conn, "b");
-
-        // a depends on b
-        insert_test_dep(&conn, "a", "b");
+    fn test_cancel_wire_records_reason() {
+        use crate::models::{CancelReason, Status, WireId};
 
-        // Would b -> a create a cycle? Yes: b -> a -> b
-        let result = would_create_cycle(&conn, "b", "a").unwrap();
-
-        assert!(result.is_some());
+        let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "aaaaaaa");
+        insert_test_wire(&conn, "bbbbbbb");
+
+        let reason = CancelReason::SupersededBy(WireId::new("bbbbbbb").unwrap());
+        cancel_wire(&conn, "aaaaaaa", Some(&reason)).unwrap();
+        let wire = fetch_wire(&conn, "aaaaaaa").unwrap().unwrap();
+        assert_eq!(wire.status, Status::Cancelled);
+        assert_eq!(wire.cancel_reason, Some(reason));
+
+        let missing = CancelReason::SupersededBy(WireId::new("ccccccc").unwrap());
+        assert!(cancel_wire(&conn, "bbbbbbb", Some(&missing)).is_err());
+        assert_eq!(
+            fetch_wire(&conn, "bbbbbbb").unwrap().unwrap().status,
+            Status::Todo
+        );
     }
 
     #[test]
-    fn test_cycle_detection_indirect_cycle() {
+    fn test_dependency_tree_marks_repeated_wires() {
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a");
-        insert_test_wire(&conn, "b");
-        insert_test_wire(&conn, "c");
+        // aaaaaaa -> bbbbbbb -> ddddddd, aaaaaaa -> ccccccc -> ddddddd
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        insert_test_dep(&conn, "aaaaaaa", "bbbbbbb");
+        insert_test_dep(&conn, "aaaaaaa", "ccccccc");
+        insert_test_dep(&conn, "bbbbbbb", "ddddddd");
+        insert_test_dep(&conn, "ccccccc", "ddddddd");
+
+        let tree = dependency_tree(&conn, "aaaaaaa", false).unwrap();
+        assert_eq!(tree.children.len(), 2);
+        let first = &tree.children[0];
+        assert_eq!(first.wire.id.as_str(), "bbbbbbb");
+        assert!(!first.children[0].repeated);
+        let second = &tree.children[1];
+        assert_eq!(second.wire.id.as_str(), "ccccccc");
+        assert!(second.children[0].repeated);
+        assert!(second.children[0].children.is_empty());
+
+        let reversed = dependency_tree(&conn, "ddddddd", true).unwrap();
+        let ids: Vec<&str> = reversed
+            .children
+            .iter()
+            .map(|c| c.wire.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["bbbbbbb", "ccccccc"]);
+        assert_eq!(reversed.children[0].children[0].wire.id.as_str(), "aaaaaaa");
+    }
 
-        // Chain: a -> b -> c
-        insert_test_dep(&conn, "a", "b");
-        insert_test_dep(&conn, "b", "c");
+    #[test]
+    fn test_supersede_moves_dependents_and_cancels() {
+        use crate::models::{CancelReason, Status};
 
-        // Would c -> a create a cycle? Yes: c -> a -> b -> c
-        let result = would_create_cycle(&conn, "c", "a").unwrap();
+        let (_temp_dir, conn) = setup_test_db();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        // ccccccc and ddddddd wait on aaaaaaa; ddddddd already waits on bbbbbbb
+        insert_test_dep(&conn, "ccccccc", "aaaaaaa");
+        insert_test_dep(&conn, "ddddddd", "aaaaaaa");
+        insert_test_dep(&conn, "ddddddd", "bbbbbbb");
 
-        assert!(result.is_some());
+        let moved = supersede_wire(&conn, "aaaaaaa", "bbbbbbb").unwrap();
+        let moved: Vec<&str> = moved.iter().map(|id| id.as_str()).collect();
+        assert_eq!(moved, vec!["ccccccc", "ddddddd"]);
+
+        let (_, blocks) = fetch_wire_deps(&conn, "aaaaaaa").unwrap();
+        assert!(blocks.is_empty());
+        let (_, blocks) = fetch_wire_deps(&conn, "bbbbbbb").unwrap();
+        assert_eq!(blocks.len(), 2);
+
+        let old = fetch_wire(&conn, "aaaaaaa").unwrap().unwrap();
+        assert_eq!(old.status, Status::Cancelled);
+        assert!(matches!(
+            old.cancel_reason,
+            Some(CancelReason::SupersededBy(id)) if id.as_str() == "bbbbbbb"
+        ));
     }
 
     #[test]
-    fn test_cycle_detection_no_cycle() {
+    fn test_transfer_dependents_rolls_back_on_cycle() {
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a");
-        insert_test_wire(&conn, "b");
-        insert_test_wire(&conn, "c");
-
-        // a -> b (no chain to c)
-        insert_test_dep(&conn, "a", "b");
-
-        // Would c -> a create a cycle? No, there's no path from a to c
-        let result = would_create_cycle(&conn, "c", "a").unwrap();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc"] {
+            insert_test_wire(&conn, id);
+        }
+        insert_test_dep(&conn, "bbbbbbb", "aaaaaaa");
+        insert_test_dep(&conn, "ccccccc", "aaaaaaa");
+        // Moving bbbbbbb's edge onto ccccccc is fine, but ccccccc can't depend on itself
+        assert!(transfer_dependents(&conn, "aaaaaaa", "ccccccc").is_err());
 
-        assert!(result.is_none());
+        let (_, blocks) = fetch_wire_deps(&conn, "aaaaaaa").unwrap();
+        assert_eq!(blocks.len(), 2);
     }
 
     #[test]
-    fn test_cycle_detection_diamond_allowed() {
+    fn test_readiness_matches_ready_wires() {
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a");
-        insert_test_wire(&conn, "b");
-        insert_test_wire(&conn, "c");
-        insert_test_wire(&conn, "d");
-
-        // Diamond: d -> b, d -> c, b -> a, c -> a
-        insert_test_dep(&conn, "d", "b");
-        insert_test_dep(&conn, "d", "c");
-        insert_test_dep(&conn, "b", "a");
-        insert_test_dep(&conn, "c", "a");
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc"] {
+            insert_test_wire(&conn, id);
+        }
+        // aaaaaaa -> bbbbbbb -> ccccccc (DONE)
+        insert_test_dep(&conn, "aaaaaaa", "bbbbbbb");
+        insert_test_dep(&conn, "bbbbbbb", "ccccccc");
+        conn.execute("UPDATE wires SET status = 'DONE' WHERE id = 'ccccccc'", [])
+            .unwrap();
 
-        // This is a valid DAG (diamond shape), not a cycle
-        // Adding another dep from d to a should be allowed
-        let result = would_create_cycle(&conn, "d", "a").unwrap();
+        let ready: Vec<String> = get_ready_wires(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id.to_string())
+            .collect();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc"] {
+            let why = readiness(&conn, id, None).unwrap();
+            assert_eq!(why.ready, ready.contains(&id.to_string()), "{}", id);
+        }
 
-        assert!(result.is_none());
+        let why = readiness(&conn, "aaaaaaa", None).unwrap();
+        assert_eq!(why.blockers.len(), 1);
+        assert_eq!(why.blockers[0].wire.id.as_str(), "bbbbbbb");
+        // DONE dependencies don't hold anything up
+        assert!(why.blockers[0].blockers.is_empty());
     }
 
     #[test]
-    fn test_fetch_wire_deps_empty() {
+    fn test_transitive_dependencies_use_shortest_depth() {
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a1b2c3d");
-
-        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        // aaaaaaa -> bbbbbbb -> ccccccc -> ddddddd, and aaaaaaa -> ccccccc
+        insert_test_dep(&conn, "aaaaaaa", "bbbbbbb");
+        insert_test_dep(&conn, "bbbbbbb", "ccccccc");
+        insert_test_dep(&conn, "ccccccc", "ddddddd");
+        insert_test_dep(&conn, "aaaaaaa", "ccccccc");
 
-        assert!(depends_on.is_empty());
-        assert!(blocks.is_empty());
+        let deps: Vec<(String, u32)> = get_transitive_dependencies(&conn, "aaaaaaa")
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.wire.id.to_string(), d.depth))
+            .collect();
+        assert_eq!(
+            deps,
+            vec![
+                ("bbbbbbb".to_string(), 1),
+                ("ccccccc".to_string(), 1),
+                ("ddddddd".to_string(), 2),
+            ]
+        );
+
+        let dependents: Vec<(String, u32)> = get_transitive_dependents(&conn, "ddddddd")
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.wire.id.to_string(), d.depth))
+            .collect();
+        assert_eq!(
+            dependents,
+            vec![
+                ("ccccccc".to_string(), 1),
+                ("aaaaaaa".to_string(), 2),
+                ("bbbbbbb".to_string(), 2),
+            ]
+        );
+
+        assert!(get_transitive_dependencies(&conn, "ddddddd")
+            .unwrap()
+            .is_empty());
+        assert!(get_transitive_dependents(&conn, "eeeeeee").is_err());
     }
 
     #[test]
-    fn test_fetch_wire_deps_with_dependencies() {
+    fn test_stale_report_groups_and_suggests() {
+        use crate::models::StaleAction;
+
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a1b2c3d");
-        insert_test_wire(&conn, "b2c3d4e");
-        insert_test_dep(&conn, "a1b2c3d", "b2c3d4e"); // a depends on b
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        let day = 24 * 60 * 60;
+        let now = crate::time::now_ms();
+        for (id, age) in [
+            ("aaaaaaa", 40),
+            ("bbbbbbb", 100),
+            ("ccccccc", 1),
+            ("ddddddd", 40),
+        ] {
+            conn.execute(
+                "UPDATE wires SET updated_at_ms = ?2 WHERE id = ?1",
+                rusqlite::params![id, now - age * day * 1000],
+            )
+            .unwrap();
+        }
+        // ccccccc is fresh but waits on ddddddd
+        insert_test_dep(&conn, "ccccccc", "ddddddd");
+        conn.execute(
+            "INSERT INTO tags (wire_id, tag) VALUES ('aaaaaaa', 'backend')",
+            [],
+        )
+        .unwrap();
 
-        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+        let report = stale_report(&conn, 30 * day).unwrap();
+        assert_eq!(report.count, 3);
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.groups[0].tag.as_ref().unwrap().as_str(), "backend");
+        assert_eq!(report.groups[0].wires[0].action, StaleAction::Defer);
 
-        assert_eq!(depends_on.len(), 1);
-        assert_eq!(depends_on[0].id.as_str(), "b2c3d4e");
-        assert!(blocks.is_empty());
+        let untagged = &report.groups[1];
+        assert!(untagged.tag.is_none());
+        let actions: Vec<(&str, StaleAction)> = untagged
+            .wires
+            .iter()
+            .map(|s| (s.wire.id.as_str(), s.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("bbbbbbb", StaleAction::Cancel),
+                ("ddddddd", StaleAction::Bump)
+            ]
+        );
     }
 
     #[test]
-    fn test_fetch_wire_deps_with_blocks() {
+    fn test_filter_wires() {
+        use crate::models::{Status, Tag, WireId};
+        let id = |s: &str| WireId::new(s).unwrap();
+
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a1b2c3d");
-        insert_test_wire(&conn, "b2c3d4e");
-        insert_test_dep(&conn, "b2c3d4e", "a1b2c3d"); // b depends on a, so a blocks b
+        insert_test_wire(&conn, "aaaaaaa");
+        insert_test_wire(&conn, "bbbbbbb");
+        insert_test_wire(&conn, "ccccccc");
+        insert_test_dep(&conn, "aaaaaaa", "bbbbbbb");
+        conn.execute(
+            "UPDATE wires SET status = 'CANCELLED' WHERE id = 'ccccccc'",
+            [],
+        )
+        .unwrap();
+        let now = crate::time::now_ms();
+        conn.execute(
+            "UPDATE wires SET updated_at_ms = ?1 WHERE id = 'bbbbbbb'",
+            [now],
+        )
+        .unwrap();
+        let backend = Tag::new("backend").unwrap();
+
+        let ids = |filter: &WireFilter| {
+            let mut ids: Vec<String> = filter_wires(&conn, filter)
+                .unwrap()
+                .into_iter()
+                .map(|wd| wd.wire.id.to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids(&WireFilter::default()).len(), 3);
+        assert_eq!(
+            ids(&WireFilter {
+                leaves: true,
+                older_than: Some(60),
+                ..Default::default()
+            }),
+            ["ccccccc"]
+        );
+
+        // Tagging bumps updated_at_ms, so the age check comes first
+        let added = add_tag_to_all(&conn, &[id("aaaaaaa"), id("ccccccc")], &backend).unwrap();
+        assert_eq!(added.len(), 2);
+        let hiding = WireFilter {
+            hidden_statuses: vec![Status::Cancelled],
+            ..Default::default()
+        };
+        assert_eq!(ids(&hiding), ["aaaaaaa", "bbbbbbb"]);
+        assert_eq!(
+            ids(&WireFilter {
+                statuses: vec![Status::Cancelled],
+                ..hiding.clone()
+            }),
+            ["ccccccc"]
+        );
+        assert_eq!(
+            ids(&WireFilter {
+                tag: Some(backend.clone()),
+                top_level: true,
+                ..hiding.clone()
+            }),
+            ["aaaaaaa"]
+        );
+
+        let removed =
+            remove_tag_from_all(&conn, &[id("aaaaaaa"), id("bbbbbbb")], &backend).unwrap();
+        assert_eq!(removed, vec![id("aaaaaaa")]);
+    }
 
-        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+    #[test]
+    fn test_list_wires_with_deps_matches_single_lookups() {
+        let (_temp_dir, conn) = setup_test_db();
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        insert_test_dep(&conn, "aaaaaaa", "bbbbbbb");
+        insert_test_dep(&conn, "aaaaaaa", "ccccccc");
+        insert_test_dep(&conn, "bbbbbbb", "ccccccc");
+        add_external_blocker(&conn, "Vendor API key", &["ccccccc".to_string()]).unwrap();
+        pause_wire(&conn, "ddddddd", Some("Waiting on design")).unwrap();
+
+        let listed = list_wires_with_deps(&conn, &ListFilter::default()).unwrap();
+        assert_eq!(listed.len(), 4);
+        for wire in listed {
+            let single = get_wire_with_deps(&conn, wire.wire.id.as_str()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&wire).unwrap(),
+                serde_json::to_value(&single).unwrap(),
+                "{}",
+                wire.wire.id
+            );
+        }
 
-        assert!(depends_on.is_empty());
-        assert_eq!(blocks.len(), 1);
-        assert_eq!(blocks[0].id.as_str(), "b2c3d4e");
+        assert!(matches!(
+            get_wire_with_deps(&conn, "eeeeeee")
+                .unwrap_err()
+                .downcast_ref::<WireError>(),
+            Some(WireError::WireNotFound(_))
+        ));
     }
 
     #[test]
-    fn test_fetch_wire_deps_both_directions() {
+    fn test_generate_fixture_is_acyclic_and_repeatable() {
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a1b2c3d");
-        insert_test_wire(&conn, "b2c3d4e");
-        insert_test_wire(&conn, "c3d4e5f");
-
-        // a depends on b, c depends on a
-        insert_test_dep(&conn, "a1b2c3d", "b2c3d4e");
-        insert_test_dep(&conn, "c3d4e5f", "a1b2c3d");
+        let fixture = generate_fixture(&conn, 200, 600, 7).unwrap();
+        assert_eq!(fixture.ids.len(), 200);
+        assert_eq!(fixture.edges, 600);
+        assert_eq!(list_dependencies(&conn).unwrap().len(), 600);
+
+        // Edges only point at older wires
+        let index: std::collections::HashMap<_, _> = fixture
+            .ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.clone(), i))
+            .collect();
+        for dep in list_dependencies(&conn).unwrap() {
+            assert!(index[&dep.wire_id] > index[&dep.depends_on]);
+        }
 
-        let (depends_on, blocks) = fetch_wire_deps(&conn, "a1b2c3d").unwrap();
+        let (_other_dir, other) = setup_test_db();
+        let again = generate_fixture(&other, 200, 600, 7).unwrap();
+        assert_eq!(again.ids, fixture.ids);
 
-        assert_eq!(depends_on.len(), 1);
-        assert_eq!(depends_on[0].id.as_str(), "b2c3d4e");
-        assert_eq!(blocks.len(), 1);
-        assert_eq!(blocks[0].id.as_str(), "c3d4e5f");
+        assert!(generate_fixture(&conn, 3, 4, 0).is_err());
     }
 
     #[test]
-    fn test_list_wires_with_deps_empty() {
+    fn test_assign_wire_filters_ready_and_undoes() {
         let (_temp_dir, conn) = setup_test_db();
+        insert_test_wire(&conn, "aaaaaaa");
+        insert_test_wire(&conn, "bbbbbbb");
+        let alice = crate::models::Agent::new("alice").unwrap();
+
+        assign_wire(&conn, "aaaaaaa", Some(&alice)).unwrap();
+        // Reassigning to the same agent records nothing
+        assign_wire(&conn, "aaaaaaa", Some(&alice)).unwrap();
+        let assigned = list_events(&conn, &EventFilter::default())
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.kind == crate::models::EventKind::Assigned)
+            .count();
+        assert_eq!(assigned, 1);
+
+        let filter = ReadyFilter {
+            assignee: Some(alice.clone()),
+            ..Default::default()
+        };
+        let ready = get_ready_wires_filtered(&conn, &filter).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].assignee, Some(alice));
+        assert_eq!(get_ready_wires(&conn).unwrap().len(), 2);
+
+        undo(&conn, 1).unwrap();
+        assert_eq!(get_wire(&conn, "aaaaaaa").unwrap().unwrap().assignee, None);
+
+        assert!(assign_wire(&conn, "ccccccc", None).is_err());
+    }
 
-        let result = list_wires_with_deps(&conn, None).unwrap();
+    #[test]
+    fn test_list_wires_breaks_timestamp_ties_by_creation_order() {
+        let (_temp_dir, conn) = setup_test_db();
+        // All three share created_at_ms = 0, as wires made in the same ms would
+        insert_test_wire(&conn, "bbbbbbb");
+        insert_test_wire(&conn, "aaaaaaa");
+        insert_test_wire(&conn, "ccccccc");
 
-        assert!(result.is_empty());
+        let ids: Vec<String> = list_wires(&conn, &ListFilter::default())
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id.to_string())
+            .collect();
+        assert_eq!(ids, ["ccccccc", "aaaaaaa", "bbbbbbb"]);
     }
 
     #[test]
-    fn test_list_wires_with_deps_includes_dependencies() {
+    fn test_stats_throughput_cycle_time_and_blocked() {
+        use crate::models::Status;
+
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a1b2c3d");
-        insert_test_wire(&conn, "b2c3d4e");
-        insert_test_dep(&conn, "a1b2c3d", "b2c3d4e");
+        for id in ["aaaaaaa", "bbbbbbb", "ccccccc", "ddddddd"] {
+            insert_test_wire(&conn, id);
+        }
+        assert_eq!(stats::cycle_time(&conn).unwrap(), None);
 
-        let result = list_wires_with_deps(&conn, None).unwrap();
+        update_wire(&conn, "aaaaaaa", None, None, Some(Status::InProgress), None).unwrap();
+        update_wire(&conn, "aaaaaaa", None, None, Some(Status::Done), None).unwrap();
+        // Finished long ago, per its events
+        update_wire(&conn, "bbbbbbb", None, None, Some(Status::Done), None).unwrap();
+        conn.execute(
+            "UPDATE events SET at = at - 30 * 86400 WHERE wire_id = 'bbbbbbb'",
+            [],
+        )
+        .unwrap();
+        update_wire(&conn, "ccccccc", None, None, Some(Status::Blocked), None).unwrap();
+        insert_test_dep(&conn, "ddddddd", "ccccccc");
+        add_external_blocker(&conn, "API key", &["ddddddd".to_string()]).unwrap();
+
+        let stats = stats::stats(&conn).unwrap();
+        assert_eq!(stats.status.total.count, 4);
+        assert_eq!(stats.throughput.day, 1);
+        assert_eq!(stats.throughput.week, 1);
+        assert!(stats.cycle_time.is_some());
+        assert_eq!(stats.blocked.status, 1);
+        assert_eq!(stats.blocked.dependencies, 1);
+        assert_eq!(stats.blocked.external, 1);
+    }
 
-        assert_eq!(result.len(), 2);
+    #[test]
+    fn test_burndown_replays_events_by_day() {
+        use crate::models::Status;
 
-        // Find the wire that has a dependency
-        let wire_a = result
-            .iter()
-            .find(|w| w.wire.id.as_str() == "a1b2c3d")
+        let (_temp_dir, conn) = setup_test_db();
+        let day = 24 * 60 * 60;
+        for title in ["A", "B"] {
+            insert_wire(
+                &conn,
+                &mut crate::models::Wire::new(title, None, 0).unwrap(),
+            )
             .unwrap();
-        assert_eq!(wire_a.depends_on.len(), 1);
-        assert_eq!(wire_a.depends_on[0].id.as_str(), "b2c3d4e");
+        }
+        let ids: Vec<String> = list_wires(&conn, &ListFilter::default())
+            .unwrap()
+            .into_iter()
+            .map(|w| w.id.to_string())
+            .collect();
+        update_wire(&conn, &ids[0], None, None, Some(Status::Done), None).unwrap();
+        // Created three days ago, one finished yesterday
+        conn.execute(
+            "UPDATE events SET at = at - 3 * ?1 WHERE kind = 'created'",
+            [day],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE events SET at = at - ?1 WHERE kind = 'updated'",
+            [day],
+        )
+        .unwrap();
 
-        // Find the wire that is depended on
-        let wire_b = result
-            .iter()
-            .find(|w| w.wire.id.as_str() == "b2c3d4e")
+        let days = stats::burndown(&conn, crate::time::now() - 4 * day).unwrap();
+        let counts: Vec<_> = days.iter().map(|d| (d.open, d.done, d.completed)).collect();
+        assert_eq!(
+            counts,
+            [(0, 0, 0), (2, 0, 0), (2, 0, 0), (1, 1, 1), (1, 1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_title_policy_applies_to_every_insert_and_update() {
+        let (temp_dir, conn) = setup_test_db();
+        std::fs::write(
+            crate::config::config_path(&temp_dir.path().join(WIRES_DIR).join(DB_NAME)),
+            "[titles]\ncollapse_whitespace = true\nmax_length = 20\n",
+        )
+        .unwrap();
+
+        let mut wire = crate::models::Wire::new(" Ship   the\trelease ", None, 0).unwrap();
+        insert_wire(&conn, &mut wire).unwrap();
+        assert_eq!(wire.title, "Ship the release");
+        assert_eq!(
+            get_wire(&conn, wire.id.as_str()).unwrap().unwrap().title,
+            "Ship the release"
+        );
+
+        update_wire(&conn, wire.id.as_str(), Some("Ship  it"), None, None, None).unwrap();
+        assert_eq!(
+            get_wire(&conn, wire.id.as_str()).unwrap().unwrap().title,
+            "Ship it"
+        );
+        assert!(update_wire(
+            &conn,
+            wire.id.as_str(),
+            Some("A title that is far too long"),
+            None,
+            None,
+            None
+        )
+        .is_err());
+
+        let plan = crate::plan::Plan::from_jsonl("{\"title\": \"Plan   this\"}\n").unwrap();
+        assert_eq!(apply_plan(&conn, &plan).unwrap()[0].title, "Plan this");
+        let plan = crate::plan::Plan::from_jsonl("{\"title\": \"A title that is far too long\"}\n")
             .unwrap();
-        assert_eq!(wire_b.blocks.len(), 1);
-        assert_eq!(wire_b.blocks[0].id.as_str(), "a1b2c3d");
+        assert!(apply_plan(&conn, &plan).is_err());
     }
 
     #[test]
-    fn test_list_wires_with_deps_respects_status_filter() {
+    fn test_pulling_keeps_outbox_consumer_alive() {
         let (_temp_dir, conn) = setup_test_db();
-        insert_test_wire(&conn, "a1b2c3d");
+        let consumer = crate::models::Consumer::new("indexer").unwrap();
+        let policy: crate::config::RetentionPolicy = toml::from_str("outbox = \"7d\"").unwrap();
 
-        // Change wire to DONE
-        conn.execute("UPDATE wires SET status = 'DONE' WHERE id = 'a1b2c3d'", [])
+        pull_outbox(&conn, &consumer, 10).unwrap();
+        let stale = crate::time::now() - 10 * 24 * 60 * 60;
+        conn.execute("UPDATE outbox_consumers SET updated_at = ?1", [stale])
             .unwrap();
 
-        // Filter by TODO should return empty
-        let todo_result = list_wires_with_deps(&conn, Some(crate::models::Status::Todo)).unwrap();
-        assert!(todo_result.is_empty());
+        // Still pulling, never acknowledging: not stale
+        pull_outbox(&conn, &consumer, 10).unwrap();
+        let report = apply_retention(&conn, &policy, false).unwrap();
+        assert!(report.outbox_consumers.is_empty());
 
-        // Filter by DONE should return the wire
-        let done_result = list_wires_with_deps(&conn, Some(crate::models::Status::Done)).unwrap();
-        assert_eq!(done_result.len(), 1);
+        conn.execute("UPDATE outbox_consumers SET updated_at = ?1", [stale])
+            .unwrap();
+        let report = apply_retention(&conn, &policy, false).unwrap();
+        assert_eq!(report.outbox_consumers, ["indexer"]);
     }
 }
@@ -0,0 +1,398 @@
+//! Aggregate numbers for `wr stats`: wires by status, throughput, cycle
+//! time and blocked wires; the daily series behind `wr burndown`; and the
+//! estimate accuracy behind `wr report --accuracy`.
+//!
+//! A wire's finish time is its last status change to `DONE` in the event
+//! log, and its start time its first change to `IN_PROGRESS`. Wires with
+//! no such events (imported, or done without being started) fall back to
+//! their `updated_at_ms` and `created_at_ms`.
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::models::{
+    Accuracy, AccuracyGroup, AccuracyReport, BlockedCounts, BurndownDay, Stats, Status,
+    StatusStats, Throughput,
+};
+use std::collections::{BTreeMap, HashMap};
+
+const DAY: i64 = 24 * 60 * 60;
+
+/// `DONE` wires with their start and finish times, in seconds
+const FINISHED: &str = "WITH finished AS (
+    SELECT
+        w.id,
+        COALESCE(
+            (SELECT MAX(e.at) FROM events e
+             WHERE e.wire_id = w.id AND e.kind = 'updated' AND e.undone = 0
+             AND json_extract(e.data, '$.status.to') = 'DONE'),
+            w.updated_at_ms / 1000
+        ) AS done_at,
+        COALESCE(
+            (SELECT MIN(e.at) FROM events e
+             WHERE e.wire_id = w.id AND e.kind = 'updated' AND e.undone = 0
+             AND json_extract(e.data, '$.status.to') = 'IN_PROGRESS'),
+            w.created_at_ms / 1000
+        ) AS started_at
+    FROM wires w
+    WHERE w.status = 'DONE'
+)";
+
+/// Everything `wr stats` reports.
+pub fn stats(conn: &Connection) -> Result<Stats> {
+    Ok(Stats {
+        status: status_stats(conn)?,
+        throughput: throughput(conn)?,
+        cycle_time: cycle_time(conn)?,
+        blocked: blocked_counts(conn)?,
+    })
+}
+
+/// Counts wires by status, with percentages, the age of the stalest wire in
+/// each status, and a totals row.
+///
+/// Every status gets a row, in status order, even when it has no wires.
+pub fn status_stats(conn: &Connection) -> Result<StatusStats> {
+    count_by_status(
+        conn,
+        "SELECT status, COUNT(*), MIN(updated_at_ms) FROM wires GROUP BY status",
+        [],
+    )
+}
+
+/// [`status_stats`] over the subtree of `epic_id` (see [`subtree`]).
+pub fn epic_status_stats(conn: &Connection, epic_id: &str) -> Result<StatusStats> {
+    count_by_status(
+        conn,
+        &format!(
+            "WITH RECURSIVE {}
+             SELECT status, COUNT(*), MIN(updated_at_ms) FROM wires
+             WHERE id IN (SELECT id FROM subtree)
+             GROUP BY status",
+            super::subtree_cte("?1")
+        ),
+        [epic_id],
+    )
+}
+
+/// Builds [`StatusStats`] from a `status, count, oldest updated_at_ms` query
+fn count_by_status(
+    conn: &Connection,
+    query: &str,
+    params: impl rusqlite::Params,
+) -> Result<StatusStats> {
+    use crate::models::{StatsTotal, StatusCount};
+    use clap::ValueEnum;
+    use std::collections::HashMap;
+
+    let now = crate::time::now_ms();
+    let mut stmt = conn.prepare(query)?;
+    let counts = stmt
+        .query_map(params, |row| {
+            let status: String = row.get(0)?;
+            let status = status
+                .parse::<Status>()
+                .map_err(|_| rusqlite::Error::InvalidQuery)?;
+            Ok((status, (row.get(1)?, row.get(2)?)))
+        })?
+        .collect::<Result<HashMap<Status, (i64, i64)>, _>>()?;
+
+    let total: i64 = counts.values().map(|(count, _)| count).sum();
+    let percent = |count: i64| {
+        if total == 0 {
+            0.0
+        } else {
+            (count as f64 * 1000.0 / total as f64).round() / 10.0
+        }
+    };
+
+    let rows: Vec<StatusCount> = Status::value_variants()
+        .iter()
+        .map(|&status| {
+            let (count, oldest) = counts.get(&status).copied().unwrap_or((0, now));
+            StatusCount {
+                status,
+                count,
+                percent: percent(count),
+                oldest_age: (count > 0).then(|| (now - oldest).max(0) / 1000),
+            }
+        })
+        .collect();
+
+    let total = StatsTotal {
+        count: total,
+        percent: percent(total),
+        oldest_age: rows.iter().filter_map(|row| row.oldest_age).max(),
+    };
+
+    Ok(StatusStats { rows, total })
+}
+
+/// Counts the wires finished in the last day and the last week.
+pub fn throughput(conn: &Connection) -> Result<Throughput> {
+    let now = crate::time::now();
+    let (day, week) = conn.query_row(
+        &format!(
+            "{} SELECT COUNT(CASE WHEN done_at >= ?1 THEN 1 END),
+                       COUNT(CASE WHEN done_at >= ?2 THEN 1 END)
+                FROM finished",
+            FINISHED
+        ),
+        [now - DAY, now - 7 * DAY],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    Ok(Throughput { day, week })
+}
+
+/// Average seconds from start to finish over every `DONE` wire, or `None`
+/// if nothing is done yet.
+pub fn cycle_time(conn: &Connection) -> Result<Option<i64>> {
+    let average: Option<f64> = conn.query_row(
+        &format!(
+            "{} SELECT AVG(MAX(done_at - started_at, 0)) FROM finished",
+            FINISHED
+        ),
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(average.map(|secs| secs.round() as i64))
+}
+
+/// Compares the `estimate:<n>` tags of `DONE` wires with how long each
+/// took from start to finish, overall and by tag, epic and actor.
+pub fn accuracy_report(conn: &Connection) -> Result<AccuracyReport> {
+    // Every finished wire with its time taken and actor
+    let mut stmt = conn.prepare(&format!(
+        "{} SELECT f.id, MAX(f.done_at - f.started_at, 0),
+                COALESCE(
+                    (SELECT json_extract(e.data, '$.agent') FROM events e
+                     WHERE e.wire_id = f.id AND e.kind = 'claimed' AND e.undone = 0
+                     ORDER BY e.seq DESC LIMIT 1),
+                    w.assignee
+                )
+         FROM finished f JOIN wires w ON w.id = f.id",
+        FINISHED
+    ))?;
+    let finished: Vec<(String, i64, Option<String>)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    let mut stmt = conn.prepare("SELECT wire_id, tag FROM tags ORDER BY tag")?;
+    for row in stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))? {
+        let (wire_id, tag): (String, String) = row?;
+        tags.entry(wire_id).or_default().push(tag);
+    }
+    let estimate = |wire_id: &str| {
+        tags.get(wire_id)?
+            .iter()
+            .find_map(|tag| tag.strip_prefix(super::ESTIMATE_TAG_PREFIX)?.parse().ok())
+            .filter(|&points: &u64| points > 0)
+    };
+
+    let mut overall = Accuracy::default();
+    let mut by_tag: BTreeMap<String, Accuracy> = BTreeMap::new();
+    let mut by_actor: BTreeMap<Option<String>, Accuracy> = BTreeMap::new();
+    let mut measured: HashMap<&str, (u64, i64)> = HashMap::new();
+    for (wire_id, actual, actor) in &finished {
+        let Some(points) = estimate(wire_id) else {
+            continue;
+        };
+        overall.add(points, *actual);
+        for tag in tags.get(wire_id).into_iter().flatten() {
+            if !tag.starts_with(super::ESTIMATE_TAG_PREFIX) {
+                by_tag.entry(tag.clone()).or_default().add(points, *actual);
+            }
+        }
+        by_actor
+            .entry(actor.clone())
+            .or_default()
+            .add(points, *actual);
+        measured.insert(wire_id, (points, *actual));
+    }
+
+    let mut by_epic = Vec::new();
+    for (epic, title) in top_level_wires(conn)? {
+        let mut accuracy = Accuracy::default();
+        for wire_id in subtree(conn, &epic)? {
+            if let Some((points, actual)) = measured.get(wire_id.as_str()) {
+                accuracy.add(*points, *actual);
+            }
+        }
+        if accuracy.wires > 0 {
+            by_epic.push((Some(epic), Some(title), accuracy));
+        }
+    }
+
+    let rate = |accuracy: &Accuracy| accuracy.actual as f64 / accuracy.estimate as f64;
+    let group =
+        |(name, title, accuracy): (Option<String>, Option<String>, Accuracy)| AccuracyGroup {
+            variance: ((rate(&accuracy) / rate(&overall) - 1.0) * 1000.0).round() / 10.0,
+            name,
+            title,
+            accuracy,
+        };
+    let by_tag = by_tag
+        .into_iter()
+        .map(|(tag, accuracy)| group((Some(tag), None, accuracy)))
+        .collect();
+    let by_epic = by_epic.into_iter().map(group).collect();
+    // `None` sorts first in a BTreeMap; wires without an actor go last
+    let (unknown, known): (Vec<_>, Vec<_>) =
+        by_actor.into_iter().partition(|(actor, _)| actor.is_none());
+    let by_actor = known
+        .into_iter()
+        .chain(unknown)
+        .map(|(actor, accuracy)| group((actor, None, accuracy)))
+        .collect();
+
+    Ok(AccuracyReport {
+        overall,
+        by_tag,
+        by_epic,
+        by_actor,
+    })
+}
+
+/// Wires with dependencies that no other wire depends on, with their
+/// titles, ordered by ID
+fn top_level_wires(conn: &Connection) -> Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT w.id, w.title FROM wires w
+         WHERE EXISTS (SELECT 1 FROM dependencies d WHERE d.wire_id = w.id AND d.kind = 'blocks')
+         AND NOT EXISTS (
+             SELECT 1 FROM dependencies d WHERE d.depends_on = w.id AND d.kind = 'blocks'
+         )
+         ORDER BY w.id",
+    )?;
+    let wires = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    Ok(wires)
+}
+
+/// IDs of an epic's subtree: `epic_id` itself and every wire it depends on,
+/// directly or not, as `wr ready --epic` scopes it
+fn subtree(conn: &Connection, epic_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "WITH RECURSIVE {} SELECT id FROM subtree",
+        super::subtree_cte("?1")
+    ))?;
+    let ids = stmt
+        .query_map([epic_id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(ids)
+}
+
+/// Counts `BLOCKED` wires, and open wires held up by dependencies or
+/// external blockers.
+///
+/// A wire waiting on both a dependency and an external blocker counts
+/// toward each. As in `wr ready`, a CANCELLED dependency only holds a wire
+/// up while the repository's `ready.cancelled_blocks` is on.
+pub fn blocked_counts(conn: &Connection) -> Result<BlockedCounts> {
+    let cancelled_blocks = super::repo_config(conn)?.ready.cancelled_blocks;
+    let blocked = conn.query_row(
+        "SELECT
+            COUNT(CASE WHEN w.status = 'BLOCKED' THEN 1 END),
+            COUNT(CASE WHEN w.status IN ('TODO', 'IN_PROGRESS') AND EXISTS (
+                SELECT 1 FROM dependencies d JOIN wires dep ON dep.id = d.depends_on
+                WHERE d.wire_id = w.id AND d.kind = 'blocks' AND dep.status != 'DONE'
+                AND (?1 OR dep.status != 'CANCELLED'))
+                THEN 1 END),
+            COUNT(CASE WHEN w.status IN ('TODO', 'IN_PROGRESS') AND EXISTS (
+                SELECT 1 FROM external_blocks eb
+                JOIN external_blockers b ON b.id = eb.blocker_id
+                WHERE eb.wire_id = w.id AND b.cleared_at IS NULL) THEN 1 END)
+         FROM wires w",
+        [cancelled_blocks],
+        |row| {
+            Ok(BlockedCounts {
+                status: row.get(0)?,
+                dependencies: row.get(1)?,
+                external: row.get(2)?,
+            })
+        },
+    )?;
+    Ok(blocked)
+}
+
+/// Open and done wire counts at the end of each UTC day from the day
+/// containing `since` through today, replayed from the event log.
+///
+/// Wires created before the event log existed count from their
+/// `created_at_ms`, in their current status.
+pub fn burndown(conn: &Connection, since: i64) -> Result<Vec<BurndownDay>> {
+    use crate::models::EventKind;
+    use std::collections::{HashMap, HashSet};
+
+    // (at, wire, status it moved to, or None once deleted)
+    let mut changes: Vec<(i64, String, Option<Status>)> = Vec::new();
+    let mut logged = HashSet::new();
+    for event in super::list_events(conn, &Default::default())? {
+        let wire_id = event.wire_id.to_string();
+        let status = match event.kind {
+            EventKind::Created => {
+                logged.insert(wire_id.clone());
+                Some(event.data["status"].as_str().unwrap_or("TODO"))
+            }
+            EventKind::Updated => event.data["status"]["to"].as_str(),
+            EventKind::Deleted => {
+                changes.push((event.at, wire_id, None));
+                continue;
+            }
+            _ => None,
+        };
+        if let Some(status) = status {
+            let status = status.parse::<Status>().map_err(anyhow::Error::msg)?;
+            changes.push((event.at, wire_id, Some(status)));
+        }
+    }
+    for wire in super::list_wires(conn, &super::ListFilter::default())? {
+        if !logged.contains(wire.id.as_str()) {
+            changes.push((
+                wire.created_at_ms / 1000,
+                wire.id.to_string(),
+                Some(wire.status),
+            ));
+        }
+    }
+    changes.sort_by_key(|(at, _, _)| *at);
+
+    let is_open = |status: Status| !matches!(status, Status::Done | Status::Cancelled);
+    let mut statuses: HashMap<String, Status> = HashMap::new();
+    let (mut open, mut done) = (0i64, 0i64);
+    let mut changes = changes.into_iter().peekable();
+    let mut days = Vec::new();
+
+    let today = crate::time::now().div_euclid(DAY);
+    for day in since.div_euclid(DAY)..=today {
+        let end = (day + 1) * DAY;
+        let mut completed = 0;
+        while let Some((at, wire_id, status)) = changes.next_if(|(at, _, _)| *at < end) {
+            let before = match status {
+                Some(status) => statuses.insert(wire_id, status),
+                None => statuses.remove(&wire_id),
+            };
+            for (status, delta) in [(before, -1), (status, 1)] {
+                match status {
+                    Some(Status::Done) => done += delta,
+                    Some(status) if is_open(status) => open += delta,
+                    _ => {}
+                }
+            }
+            // Changes before `since` only set the starting counts
+            if status == Some(Status::Done) && before != Some(Status::Done) && at >= end - DAY {
+                completed += 1;
+            }
+        }
+        days.push(BurndownDay {
+            date: crate::time::format_utc(day * DAY)[..10].to_string(),
+            open,
+            done,
+            completed,
+        });
+    }
+
+    Ok(days)
+}
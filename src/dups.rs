@@ -0,0 +1,174 @@
+//! Likely duplicate wires, found by comparing normalized titles.
+//!
+//! Titles are lowercased and split into words of letters and digits, then
+//! compared by the trigrams they share, as PostgreSQL's `pg_trgm` does:
+//! each word is padded with two spaces in front and one behind, and the
+//! similarity of two titles is the share of their distinct trigrams they
+//! have in common. It is reported as a whole percentage, 100 for titles
+//! with the same words and 0 for titles with no trigram in common.
+
+use crate::models::{DependencyInfo, Wire};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Two wires whose titles are at least as similar as the threshold asked for.
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicatePair {
+    /// The older of the two wires
+    pub first: DependencyInfo,
+    /// The newer of the two wires
+    pub second: DependencyInfo,
+    /// How alike the titles are, in percent
+    pub similarity: u8,
+}
+
+/// Lowercases `title` and reduces it to its words of letters and digits,
+/// separated by single spaces.
+pub fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The distinct trigrams of a title's words, each padded like `pg_trgm`
+fn trigrams(title: &str) -> BTreeSet<[char; 3]> {
+    let mut trigrams = BTreeSet::new();
+    for word in normalize_title(title).split(' ').filter(|w| !w.is_empty()) {
+        let padded: Vec<char> = "  ".chars().chain(word.chars()).chain([' ']).collect();
+        trigrams.extend(padded.windows(3).map(|w| [w[0], w[1], w[2]]));
+    }
+    trigrams
+}
+
+/// Share of trigrams two sets have in common, in percent
+fn overlap(a: &BTreeSet<[char; 3]>, b: &BTreeSet<[char; 3]>) -> u8 {
+    let shared = a.intersection(b).count();
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        return 0;
+    }
+    (shared * 100 / total) as u8
+}
+
+/// How alike two titles are, in percent.
+pub fn similarity(a: &str, b: &str) -> u8 {
+    overlap(&trigrams(a), &trigrams(b))
+}
+
+/// Every pair of `wires` whose titles are at least `threshold` percent
+/// alike, most similar first.
+///
+/// Within a pair the older wire comes first; pairs equally alike are
+/// ordered by the first and then the second wire's ID.
+pub fn find_duplicates(wires: &[Wire], threshold: u8) -> Vec<DuplicatePair> {
+    let mut wires: Vec<&Wire> = wires.iter().collect();
+    wires.sort_by(|a, b| a.created_at_ms.cmp(&b.created_at_ms).then(a.id.cmp(&b.id)));
+    let grams: Vec<_> = wires.iter().map(|w| trigrams(&w.title)).collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..wires.len() {
+        for j in i + 1..wires.len() {
+            let similarity = overlap(&grams[i], &grams[j]);
+            if similarity >= threshold && similarity > 0 {
+                pairs.push(DuplicatePair {
+                    first: info(wires[i]),
+                    second: info(wires[j]),
+                    similarity,
+                });
+            }
+        }
+    }
+
+    pairs.sort_by(|a, b| {
+        b.similarity
+            .cmp(&a.similarity)
+            .then_with(|| a.first.id.cmp(&b.first.id))
+            .then_with(|| a.second.id.cmp(&b.second.id))
+    });
+    pairs
+}
+
+/// The wires whose titles are at least `threshold` percent alike to
+/// `title`, with their similarity, most similar first.
+pub fn similar_to<'a>(title: &str, wires: &'a [Wire], threshold: u8) -> Vec<(&'a Wire, u8)> {
+    let target = trigrams(title);
+    let mut similar: Vec<_> = wires
+        .iter()
+        .map(|wire| (wire, overlap(&target, &trigrams(&wire.title))))
+        .filter(|(_, similarity)| *similarity >= threshold && *similarity > 0)
+        .collect();
+    similar.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.id.cmp(&b.0.id)));
+    similar
+}
+
+fn info(wire: &Wire) -> DependencyInfo {
+    DependencyInfo {
+        id: wire.id.clone(),
+        title: wire.title.clone(),
+        status: wire.status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wire(title: &str, created_at_ms: i64) -> Wire {
+        let mut wire = Wire::new(title, None, 0).unwrap();
+        wire.created_at_ms = created_at_ms;
+        wire
+    }
+
+    #[test]
+    fn test_normalize_title() {
+        assert_eq!(
+            normalize_title("  Fix: parser   PANIC (again!) "),
+            "fix parser panic again"
+        );
+        assert_eq!(normalize_title("--"), "");
+    }
+
+    #[test]
+    fn test_similarity_ignores_case_and_punctuation() {
+        assert_eq!(similarity("Fix parser panic", "fix: parser panic!"), 100);
+        assert_eq!(similarity("Fix parser panic", "Write release notes"), 0);
+        assert_eq!(similarity("", "anything"), 0);
+
+        let close = similarity("Fix parser panic", "Fix parser panics");
+        let far = similarity("Fix parser panic", "Fix lexer bug");
+        assert!(close >= 80, "{}", close);
+        assert!(far < close, "{} vs {}", far, close);
+    }
+
+    #[test]
+    fn test_find_duplicates_orders_pairs() {
+        let wires = vec![
+            wire("Add login page", 3),
+            wire("Fix parser panic", 1),
+            wire("Fix the parser panic", 2),
+            wire("add login page!", 4),
+        ];
+
+        let pairs = find_duplicates(&wires, 60);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].similarity, 100);
+        assert_eq!(pairs[0].first.title, "Add login page");
+        assert_eq!(pairs[0].second.title, "add login page!");
+        assert_eq!(pairs[1].first.title, "Fix parser panic");
+        assert!(pairs[1].similarity < 100);
+
+        assert!(find_duplicates(&wires, 101).is_empty());
+    }
+
+    #[test]
+    fn test_similar_to() {
+        let wires = vec![wire("Fix parser panic", 1), wire("Write docs", 2)];
+        let similar = similar_to("fix parser panic", &wires, 80);
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].0.title, "Fix parser panic");
+        assert_eq!(similar[0].1, 100);
+    }
+}
@@ -0,0 +1,183 @@
+//! The text `wr edit` opens in `$EDITOR`: a wire's title and tags as TOML
+//! front matter between `+++` lines, followed by its description as plain
+//! Markdown.
+//!
+//! ```text
+//! +++
+//! title = "Fix parser panic"
+//! tags = ["bug", "parser"]
+//! +++
+//!
+//! The parser panics on empty input.
+//!
+//! Steps to reproduce: ...
+//! ```
+//!
+//! Everything after the closing `+++` is the description, trimmed; leaving
+//! it empty clears the description.
+
+use crate::models::{Tag, Wire};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Line opening and closing the front matter
+const FENCE: &str = "+++";
+
+/// A wire's editable fields, as read from or written to an edit buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireBuffer {
+    pub title: String,
+    pub tags: Vec<Tag>,
+    pub description: Option<String>,
+}
+
+/// The front matter's fields
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FrontMatter {
+    title: String,
+    #[serde(default)]
+    tags: Vec<Tag>,
+}
+
+impl WireBuffer {
+    /// The editable fields of `wire`.
+    pub fn from_wire(wire: &Wire) -> Self {
+        WireBuffer {
+            title: wire.title.clone(),
+            tags: wire.tags.clone(),
+            description: wire.description.clone(),
+        }
+    }
+
+    /// Renders the buffer as front matter followed by the description.
+    pub fn render(&self) -> String {
+        let front = FrontMatter {
+            title: self.title.clone(),
+            tags: self.tags.clone(),
+        };
+        let front = toml::to_string(&front).expect("front matter serializes");
+        format!(
+            "{fence}\n{front}{fence}\n\n{}\n",
+            self.description.as_deref().unwrap_or_default(),
+            fence = FENCE,
+        )
+    }
+
+    /// Parses an edited buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditError::MissingFrontMatter`] if the text doesn't start
+    /// with a `+++` block, or [`EditError::InvalidFrontMatter`] if the block
+    /// isn't valid TOML with a non-empty `title`.
+    pub fn parse(text: &str) -> Result<Self, EditError> {
+        let text = text.trim_start();
+        let rest = text
+            .strip_prefix(FENCE)
+            .and_then(|rest| rest.strip_prefix('\n').or(rest.strip_prefix("\r\n")))
+            .ok_or(EditError::MissingFrontMatter)?;
+
+        let mut front_len = None;
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            if line.trim_end() == FENCE {
+                front_len = Some(offset);
+                break;
+            }
+            offset += line.len();
+        }
+        let front_len = front_len.ok_or(EditError::MissingFrontMatter)?;
+        let (front, body) = rest.split_at(front_len);
+        let body = body[FENCE.len()..].trim();
+
+        let front: FrontMatter =
+            toml::from_str(front).map_err(|e| EditError::InvalidFrontMatter(e.to_string()))?;
+        if front.title.trim().is_empty() {
+            return Err(EditError::InvalidFrontMatter(
+                "title must not be empty".to_string(),
+            ));
+        }
+
+        let mut tags = front.tags;
+        tags.sort();
+        tags.dedup();
+
+        Ok(WireBuffer {
+            title: front.title.trim().to_string(),
+            tags,
+            description: Some(body.to_string()).filter(|d| !d.is_empty()),
+        })
+    }
+}
+
+/// Problems reading an edited buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// The text doesn't start with a `+++` front matter block
+    MissingFrontMatter,
+    /// The front matter isn't valid TOML, or lacks a title
+    InvalidFrontMatter(String),
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::MissingFrontMatter => write!(
+                f,
+                "Edited text must start with a front matter block between {} lines",
+                FENCE
+            ),
+            EditError::InvalidFrontMatter(msg) => write!(f, "Invalid front matter: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer() -> WireBuffer {
+        WireBuffer {
+            title: "Fix \"parser\" panic".to_string(),
+            tags: vec![Tag::new("bug").unwrap(), Tag::new("parser").unwrap()],
+            description: Some("First paragraph.\n\n+++ not a fence\n\nLast.".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_render_parse_roundtrip() {
+        let buffer = buffer();
+        assert_eq!(WireBuffer::parse(&buffer.render()).unwrap(), buffer);
+    }
+
+    #[test]
+    fn test_parse_empty_description_and_missing_tags() {
+        let parsed = WireBuffer::parse("+++\ntitle = \"Short\"\n+++\n\n  \n").unwrap();
+        assert_eq!(parsed.title, "Short");
+        assert!(parsed.tags.is_empty());
+        assert_eq!(parsed.description, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            WireBuffer::parse("title = \"x\"\n"),
+            Err(EditError::MissingFrontMatter)
+        );
+        assert_eq!(
+            WireBuffer::parse("+++\ntitle = \"x\"\n"),
+            Err(EditError::MissingFrontMatter)
+        );
+        assert!(matches!(
+            WireBuffer::parse("+++\ntitle = \"\"\n+++\n"),
+            Err(EditError::InvalidFrontMatter(_))
+        ));
+        assert!(matches!(
+            WireBuffer::parse("+++\ntitle = \"x\"\nowner = \"me\"\n+++\n"),
+            Err(EditError::InvalidFrontMatter(_))
+        ));
+    }
+}
@@ -1,29 +1,89 @@
 //! Output formatting utilities.
 //!
-//! This module handles output formatting for wires, supporting both:
+//! This module handles output formatting for wires, supporting:
 //! - **JSON** - Machine-readable format for programmatic use
+//! - **JSON Lines** - One JSON value per line, for streaming (never auto-selected)
 //! - **Table** - Human-readable format for terminal display
+//! - **Markdown** - Checklists for pasting into PRs and docs (never auto-selected)
+//! - **CSV/TSV** - Spreadsheet rows, for wire lists only ([`ListFormat`])
 //!
 //! The format is auto-detected based on whether stdout is a TTY:
 //! - TTY → table format
 //! - Piped/redirected → JSON format
 //!
-//! Users can override with `--format json` or `--format table`.
+//! Users can override with `--format json` or `--format table`, and
+//! repositories can pick a different terminal format with
+//! [`set_terminal_default`]. JSON is compact unless [`set_pretty`] asks
+//! for it indented.
 
+use crate::config::{PriorityLevels, ReadyConfig};
 use clap::ValueEnum;
 use owo_colors::{OwoColorize, Stream};
+use serde::{Deserialize, Serialize};
 use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+
+/// Format [`Format::resolve`] picks on a terminal, if not table
+static TERMINAL_DEFAULT: OnceLock<Format> = OnceLock::new();
+
+/// Sets the format used on a terminal when none is asked for.
+///
+/// Only the first call has any effect. Piped output is always JSON.
+pub fn set_terminal_default(format: Format) {
+    let _ = TERMINAL_DEFAULT.set(format);
+}
+
+/// Priority names used in place of numbers, if set
+static PRIORITY_LEVELS: OnceLock<PriorityLevels> = OnceLock::new();
+
+/// Sets the priority levels whose names are shown instead of numbers.
+///
+/// Only the first call has any effect. JSON output always has the number.
+pub fn set_priority_levels(levels: PriorityLevels) {
+    let _ = PRIORITY_LEVELS.set(levels);
+}
+
+/// Whether CANCELLED dependencies are shown as blockers, if set
+static CANCELLED_BLOCKS: OnceLock<bool> = OnceLock::new();
+
+/// Sets whether a CANCELLED dependency is listed among a wire's blockers,
+/// matching `ready.cancelled_blocks` so tables agree with `wr ready`.
+///
+/// Only the first call has any effect.
+pub fn set_cancelled_blocks(cancelled_blocks: bool) {
+    let _ = CANCELLED_BLOCKS.set(cancelled_blocks);
+}
+
+/// Whether a dependency with `status` is shown as blocking its dependent.
+fn blocks_dependents(status: crate::models::Status) -> bool {
+    let cancelled_blocks = CANCELLED_BLOCKS.get_or_init(|| ReadyConfig::default().cancelled_blocks);
+    status.blocks_dependents(*cancelled_blocks)
+}
+
+/// A priority's level name, or the number if no level has it.
+fn format_priority(priority: i32) -> String {
+    let levels = PRIORITY_LEVELS.get_or_init(PriorityLevels::default);
+    match levels.name(priority) {
+        Some(name) => name.to_string(),
+        None => priority.to_string(),
+    }
+}
 
 /// Output format options.
 ///
 /// The format determines how wires are displayed to the user.
 /// Implements [`ValueEnum`] for direct use with clap CLI arguments.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Format {
     /// JSON output for programmatic parsing
     Json,
     /// Human-readable table format
     Table,
+    /// Markdown checklist
+    Markdown,
+    /// JSON Lines: each item of a list on its own line
+    Jsonl,
 }
 
 impl Format {
@@ -36,16 +96,263 @@ impl Format {
     /// # Returns
     ///
     /// - `Some(format)` → uses the specified format
-    /// - `None` → auto-detects based on stdout TTY status (table for TTY, json for pipes)
+    /// - `None` → auto-detects based on stdout TTY status (table, or the
+    ///   [terminal default](set_terminal_default), for TTY; json for pipes)
     pub fn resolve(format: Option<Format>) -> Self {
         format.unwrap_or_else(|| {
             if io::stdout().is_terminal() {
-                Format::Table
+                TERMINAL_DEFAULT.get().copied().unwrap_or(Format::Table)
             } else {
                 Format::Json
             }
         })
     }
+
+    /// Whether the output is JSON, as a document or as lines.
+    pub fn is_json(self) -> bool {
+        matches!(self, Format::Json | Format::Jsonl)
+    }
+}
+
+/// Output formats for commands that print a list of wires (`wr list`,
+/// `wr ready`): every [`Format`], plus delimited rows for spreadsheets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    /// JSON output for programmatic parsing
+    Json,
+    /// JSON Lines: each wire on its own line
+    Jsonl,
+    /// Human-readable table format
+    Table,
+    /// Markdown checklist
+    Markdown,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+impl ListFormat {
+    /// Returns the format asked for, or the one [`Format::resolve`] picks.
+    pub fn resolve(format: Option<ListFormat>) -> Self {
+        format.unwrap_or_else(|| Format::resolve(None).into())
+    }
+
+    /// The general format, or `None` for CSV and TSV.
+    pub fn general(self) -> Option<Format> {
+        match self {
+            ListFormat::Json => Some(Format::Json),
+            ListFormat::Jsonl => Some(Format::Jsonl),
+            ListFormat::Table => Some(Format::Table),
+            ListFormat::Markdown => Some(Format::Markdown),
+            ListFormat::Csv | ListFormat::Tsv => None,
+        }
+    }
+
+    /// The field delimiter of CSV and TSV, `None` for other formats.
+    pub fn delimiter(self) -> Option<u8> {
+        match self {
+            ListFormat::Csv => Some(b','),
+            ListFormat::Tsv => Some(b'\t'),
+            _ => None,
+        }
+    }
+}
+
+impl From<Format> for ListFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Json => ListFormat::Json,
+            Format::Jsonl => ListFormat::Jsonl,
+            Format::Table => ListFormat::Table,
+            Format::Markdown => ListFormat::Markdown,
+        }
+    }
+}
+
+/// A wire field that `--columns` can pick and `--template` can name.
+///
+/// Every entry of [`WIRE_COLUMNS`] is selectable in both, so a field added
+/// there shows up everywhere at once.
+#[derive(Debug)]
+pub struct WireColumn {
+    /// Name in `--columns`, `{{name}}` and the CSV header
+    pub name: &'static str,
+    /// Whether table output right-aligns the values
+    numeric: bool,
+    value: fn(&crate::models::Wire) -> String,
+}
+
+impl WireColumn {
+    /// The wire's value, as text. Missing values (no description, no
+    /// assignee) are empty; tags are comma-separated.
+    pub fn value(&self, wire: &crate::models::Wire) -> String {
+        (self.value)(wire)
+    }
+}
+
+impl PartialEq for WireColumn {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for WireColumn {}
+
+/// Every wire column, in the order `wr list --help` lists them.
+pub const WIRE_COLUMNS: &[WireColumn] = &[
+    WireColumn {
+        name: "id",
+        numeric: false,
+        value: |wire| wire.id.to_string(),
+    },
+    WireColumn {
+        name: "title",
+        numeric: false,
+        value: |wire| wire.title.clone(),
+    },
+    WireColumn {
+        name: "description",
+        numeric: false,
+        value: |wire| wire.description.clone().unwrap_or_default(),
+    },
+    WireColumn {
+        name: "status",
+        numeric: false,
+        value: |wire| wire.status.as_str().to_string(),
+    },
+    WireColumn {
+        name: "priority",
+        numeric: true,
+        value: |wire| wire.priority.to_string(),
+    },
+    WireColumn {
+        name: "tags",
+        numeric: false,
+        value: |wire| {
+            wire.tags
+                .iter()
+                .map(|tag| tag.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        },
+    },
+    WireColumn {
+        name: "assignee",
+        numeric: false,
+        value: |wire| {
+            wire.assignee
+                .as_ref()
+                .map(|a| a.to_string())
+                .unwrap_or_default()
+        },
+    },
+    WireColumn {
+        name: "age",
+        numeric: true,
+        value: |wire| crate::time::format_age((crate::time::now_ms() - wire.created_at_ms) / 1000),
+    },
+    WireColumn {
+        name: "created_at",
+        numeric: true,
+        value: |wire| wire.created_at_ms.to_string(),
+    },
+    WireColumn {
+        name: "updated_at",
+        numeric: true,
+        value: |wire| wire.updated_at_ms.to_string(),
+    },
+    WireColumn {
+        name: "cancel_reason",
+        numeric: false,
+        value: |wire| {
+            wire.cancel_reason
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or_default()
+        },
+    },
+    WireColumn {
+        name: "blocked_reason",
+        numeric: false,
+        value: |wire| wire.blocked_reason.clone().unwrap_or_default(),
+    },
+];
+
+/// The column called `name`, if there is one.
+pub fn wire_column(name: &str) -> Option<&'static WireColumn> {
+    WIRE_COLUMNS.iter().find(|column| column.name == name)
+}
+
+/// Formats wires as an aligned table of the chosen columns under a header
+/// row of their names in capitals.
+///
+/// Returns "No wires found." if the list is empty.
+pub fn format_wire_columns_table<'a>(
+    wires: impl IntoIterator<Item = &'a crate::models::Wire>,
+    columns: &crate::template::Columns,
+) -> String {
+    let header: Vec<String> = columns
+        .names()
+        .iter()
+        .map(|name| name.to_uppercase())
+        .collect();
+    let rows: Vec<Vec<String>> = wires.into_iter().map(|w| columns.values(w)).collect();
+    if rows.is_empty() {
+        return "No wires found.\n".to_string();
+    }
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(columns.columns())
+            .zip(&widths)
+            .map(|((cell, column), &width)| {
+                let padding = " ".repeat(width - cell.chars().count());
+                if column.numeric {
+                    padding + cell
+                } else {
+                    cell.clone() + &padding
+                }
+            })
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Formats wires as delimited rows (CSV with `,`, TSV with a tab) under a
+/// header row of column names.
+///
+/// Values containing the delimiter, quotes or line breaks are quoted.
+///
+/// # Errors
+///
+/// Returns an error if writing a row fails.
+pub fn format_wire_delimited<'a>(
+    wires: impl IntoIterator<Item = &'a crate::models::Wire>,
+    columns: &crate::template::Columns,
+    delimiter: u8,
+) -> anyhow::Result<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+    writer.write_record(columns.names())?;
+    for wire in wires {
+        writer.write_record(columns.values(wire))?;
+    }
+    Ok(String::from_utf8(writer.into_inner()?)?)
 }
 
 /// Returns a colored status symbol for terminal display.
@@ -64,6 +371,12 @@ fn format_status_symbol(status: crate::models::Status) -> String {
         Status::InProgress => symbol
             .if_supports_color(Stream::Stdout, |text| text.yellow())
             .to_string(),
+        Status::Paused => symbol
+            .if_supports_color(Stream::Stdout, |text| text.blue())
+            .to_string(),
+        Status::Blocked => symbol
+            .if_supports_color(Stream::Stdout, |text| text.magenta())
+            .to_string(),
         Status::Todo => symbol.to_string(),
         Status::Cancelled => symbol
             .if_supports_color(Stream::Stdout, |text| text.red())
@@ -71,37 +384,1167 @@ fn format_status_symbol(status: crate::models::Status) -> String {
     }
 }
 
-/// Formats a list of wires as a table.
+/// Formats tags as space-separated `#tag` labels, dimmed when colors are enabled.
+fn format_tags(tags: &[crate::models::Tag]) -> String {
+    let labels: Vec<String> = tags.iter().map(|t| format!("#{}", t)).collect();
+    labels
+        .join(" ")
+        .if_supports_color(Stream::Stdout, |text| text.dimmed())
+        .to_string()
+}
+
+/// Formats a list of wires as a table.
+///
+/// The table includes status symbol, ID, title, and optional blocker info.
+/// Returns "No wires found." if the list is empty.
+pub fn format_wire_table(wires: &[crate::models::WireWithDeps]) -> String {
+    if wires.is_empty() {
+        return String::from("No wires found.");
+    }
+
+    let mut output = String::new();
+
+    // No header - symbols are self-explanatory
+
+    // Rows
+    for wire_with_deps in wires {
+        let wire = &wire_with_deps.wire;
+        let symbol = format_status_symbol(wire.status);
+
+        // Base line: symbol + id + title
+        output.push_str(&format!("{} {}  {}", symbol, wire.id.as_str(), wire.title));
+
+        if !wire.tags.is_empty() {
+            output.push_str(&format!("  {}", format_tags(&wire.tags)));
+        }
+        if let Some(assignee) = &wire.assignee {
+            output.push_str(&format!("  @{}", assignee));
+        }
+
+        // Add blocker suffix if this wire has blocking dependencies
+        // or is waiting on an external condition
+        let blocker_ids: Vec<_> = wire_with_deps
+            .depends_on
+            .iter()
+            .filter(|dep| blocks_dependents(dep.status))
+            .map(|dep| dep.id.as_str().to_string())
+            .chain(
+                wire_with_deps
+                    .blocked_on
+                    .iter()
+                    .map(|b| format!("\"{}\"", b.reason)),
+            )
+            .collect();
+
+        if !blocker_ids.is_empty() {
+            output.push_str(&format!("  ← blocked by {}", blocker_ids.join(", ")));
+        }
+
+        if let Some(pause) = &wire_with_deps.paused {
+            output.push_str(&format!("  ‖ paused: {}", pause.reason));
+        }
+        if let Some(reason) = &wire_with_deps.wire.blocked_reason {
+            output.push_str(&format!("  ⊘ blocked: {}", reason));
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Width of table output when `COLUMNS` doesn't say
+const DEFAULT_WIDTH: usize = 80;
+
+/// Width of the terminal table output is fitted to: `COLUMNS`, or 80.
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Wraps `text` at word boundaries to fit `width` characters.
+///
+/// Each line is wrapped on its own, so paragraph and line breaks stay where
+/// they were. Markdown list items continue under their text rather than
+/// their marker, fenced code blocks are left alone, and a word longer than
+/// the width (usually a URL) gets a line to itself rather than being split.
+pub fn wrap_text(text: &str, width: usize) -> String {
+    const MIN_TEXT_WIDTH: usize = 20;
+
+    let mut output = String::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        if fence {
+            in_code = !in_code;
+        }
+        if fence || in_code || line.chars().count() <= width {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let hanging = indent.chars().count() + list_marker_len(trimmed);
+        let width = width.max(hanging + MIN_TEXT_WIDTH);
+        let mut current = indent.to_string();
+        let mut len = current.chars().count();
+        let mut empty = true;
+        for word in trimmed.split_whitespace() {
+            let word_len = word.chars().count();
+            if !empty && len + 1 + word_len > width {
+                output.push_str(&current);
+                output.push('\n');
+                current = " ".repeat(hanging);
+                len = hanging;
+                empty = true;
+            }
+            if !empty {
+                current.push(' ');
+                len += 1;
+            }
+            current.push_str(word);
+            len += word_len;
+            empty = false;
+        }
+        output.push_str(&current);
+        output.push('\n');
+    }
+    output
+}
+
+/// Length of the Markdown list marker (`- `, `* `, `+ `, `1. ` or `1) `)
+/// starting `line`, or 0 if it isn't a list item.
+fn list_marker_len(line: &str) -> usize {
+    if ["- ", "* ", "+ "].iter().any(|m| line.starts_with(m)) {
+        return 2;
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    if digits > 0 && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        digits + 2
+    } else {
+        0
+    }
+}
+
+/// Formats a wire's details with a compact header.
+///
+/// Shows a single-line header with symbol, ID, title, and priority,
+/// followed by description and dependency information. The description is
+/// [wrapped](wrap_text) to `width` characters.
+pub fn format_wire_detail_table(wire: &crate::models::WireWithDeps, width: usize) -> String {
+    let mut output = String::new();
+
+    let symbol = format_status_symbol(wire.wire.status);
+
+    // Compact header: symbol + id + title + [pri:N]
+    output.push_str(&format!(
+        "{} {}  {}  [pri:{}]\n",
+        symbol,
+        wire.wire.id.as_str(),
+        wire.wire.title,
+        format_priority(wire.wire.priority)
+    ));
+
+    if !wire.wire.tags.is_empty() {
+        output.push_str(&format!("{}\n", format_tags(&wire.wire.tags)));
+    }
+    if let Some(assignee) = &wire.wire.assignee {
+        output.push_str(&format!("Assignee: {}\n", assignee));
+    }
+
+    // Description (if present)
+    if let Some(ref desc) = wire.wire.description {
+        output.push('\n');
+        output.push_str(&wrap_text(desc, width));
+    }
+
+    // Dependencies
+    if !wire.depends_on.is_empty() {
+        output.push_str("\nDepends on:\n");
+        for dep in &wire.depends_on {
+            let dep_symbol = format_status_symbol(dep.status);
+            output.push_str(&format!(
+                "  {} {}  {}\n",
+                dep_symbol,
+                dep.id.as_str(),
+                dep.title
+            ));
+        }
+    }
+
+    // Blocks
+    if !wire.blocks.is_empty() {
+        output.push_str("\nBlocks:\n");
+        for blocker in &wire.blocks {
+            let blocker_symbol = format_status_symbol(blocker.status);
+            output.push_str(&format!(
+                "  {} {}  {}\n",
+                blocker_symbol,
+                blocker.id.as_str(),
+                blocker.title
+            ));
+        }
+    }
+
+    // Related wires
+    let (related, links) = split_related(&wire.links);
+    if !related.is_empty() {
+        output.push_str("\nRelated:\n");
+        for link in related {
+            output.push_str(&format!(
+                "  {} {}  {}\n",
+                format_status_symbol(link.wire.status),
+                link.wire.id.as_str(),
+                link.wire.title
+            ));
+        }
+    }
+
+    // Other non-blocking dependencies
+    if !links.is_empty() {
+        output.push_str("\nLinks:\n");
+        for link in links {
+            output.push_str(&format!(
+                "  {} {}  {}  ({})\n",
+                format_status_symbol(link.wire.status),
+                link.wire.id.as_str(),
+                link.wire.title,
+                link.kind
+            ));
+        }
+    }
+
+    // External conditions
+    if !wire.blocked_on.is_empty() {
+        output.push_str("\nWaiting on:\n");
+        for blocker in &wire.blocked_on {
+            output.push_str(&format!("  #{}  {}\n", blocker.id, blocker.reason));
+        }
+    }
+
+    if let Some(pause) = &wire.paused {
+        output.push_str(&format!("\nPaused: {}\n", pause.reason));
+    }
+
+    if let Some(reason) = &wire.wire.blocked_reason {
+        output.push_str(&format!("\nBlocked: {}\n", reason));
+    }
+
+    if let Some(reason) = &wire.wire.cancel_reason {
+        output.push_str(&format!("\nCancelled: {}\n", reason));
+    }
+
+    output
+}
+
+/// Formats event log entries, one per line, oldest first.
+///
+/// Returns "No events found." if the list is empty.
+pub fn format_event_table(events: &[crate::models::Event]) -> String {
+    if events.is_empty() {
+        return String::from("No events found.");
+    }
+
+    let mut output = String::new();
+
+    for event in events {
+        let when = crate::time::format_utc(event.at);
+        output.push_str(&format!(
+            "{}  {}  {:<11}  {}\n",
+            when.if_supports_color(Stream::Stdout, |text| text.dimmed()),
+            event.wire_id.as_str(),
+            event.kind.as_str(),
+            event.data
+        ));
+    }
+
+    output
+}
+
+/// Formats per-status counts as a table with a totals row, followed by
+/// throughput, cycle time and blocked counts.
+pub fn format_stats_table(stats: &crate::models::Stats) -> String {
+    let mut output = status_stats_table(&stats.status);
+
+    output.push('\n');
+    for (label, value) in stats_summary(stats) {
+        output.push_str(&format!("{:<12} {}\n", label, value));
+    }
+
+    output
+}
+
+/// Formats per-status counts as a table with a totals row.
+fn status_stats_table(rows: &crate::models::StatusStats) -> String {
+    let age = |age: Option<i64>| age.map_or_else(|| "-".to_string(), crate::time::format_age);
+
+    let mut output = format!("{:<12} {:>6} {:>7}  {}\n", "STATUS", "COUNT", "%", "OLDEST");
+    for row in &rows.rows {
+        output.push_str(&format!(
+            "{:<12} {:>6} {:>6.1}%  {}\n",
+            row.status.as_str(),
+            row.count,
+            row.percent,
+            age(row.oldest_age)
+        ));
+    }
+    output.push_str(&format!(
+        "{:<12} {:>6} {:>6.1}%  {}\n",
+        "TOTAL",
+        rows.total.count,
+        rows.total.percent,
+        age(rows.total.oldest_age)
+    ));
+
+    output
+}
+
+/// Formats per-status counts as a Markdown table with a bold totals row,
+/// followed by throughput, cycle time and blocked counts.
+pub fn format_stats_markdown(stats: &crate::models::Stats) -> String {
+    let mut output = status_stats_markdown(&stats.status);
+
+    output.push('\n');
+    for (label, value) in stats_summary(stats) {
+        output.push_str(&format!("- **{}:** {}\n", label, value));
+    }
+
+    output
+}
+
+/// Formats per-status counts as a Markdown table with a bold totals row.
+fn status_stats_markdown(rows: &crate::models::StatusStats) -> String {
+    let age = |age: Option<i64>| age.map_or_else(|| "-".to_string(), crate::time::format_age);
+
+    let mut output = String::from("| Status | Count | % | Oldest |\n|---|---:|---:|---|\n");
+    for row in &rows.rows {
+        output.push_str(&format!(
+            "| {} | {} | {:.1}% | {} |\n",
+            row.status.as_str(),
+            row.count,
+            row.percent,
+            age(row.oldest_age)
+        ));
+    }
+    output.push_str(&format!(
+        "| **Total** | **{}** | **{:.1}%** | **{}** |\n",
+        rows.total.count,
+        rows.total.percent,
+        age(rows.total.oldest_age)
+    ));
+
+    output
+}
+
+/// Formats `wr epic progress`: the epic with its completion, its subtree
+/// by status, and the wires ready to work on.
+pub fn format_epic_progress_table(progress: &crate::models::EpicProgress) -> String {
+    let epic = &progress.epic;
+    let mut output = format!(
+        "{} {}  {}  ({:.1}% complete)\n\n",
+        format_status_symbol(epic.status),
+        epic.id,
+        epic.title,
+        progress.complete
+    );
+    output.push_str(&status_stats_table(&progress.status));
+
+    output.push_str("\nREADY\n");
+    if progress.ready.is_empty() {
+        output.push_str("(none)\n");
+    }
+    for wire in &progress.ready {
+        output.push_str(&format!(
+            "{} {}  {}\n",
+            format_status_symbol(wire.status),
+            wire.id,
+            wire.title
+        ));
+    }
+
+    output
+}
+
+/// Formats `wr epic progress` as Markdown: a heading with the completion,
+/// a status table and a checklist of ready wires.
+pub fn format_epic_progress_markdown(progress: &crate::models::EpicProgress) -> String {
+    let epic = &progress.epic;
+    let mut output = format!(
+        "## `{}` {}\n\n**{:.1}% complete**\n\n",
+        epic.id, epic.title, progress.complete
+    );
+    output.push_str(&status_stats_markdown(&progress.status));
+
+    output.push_str("\n**Ready:**\n\n");
+    if progress.ready.is_empty() {
+        output.push_str("*No wires found.*\n");
+    }
+    for wire in &progress.ready {
+        output.push_str(&markdown_item(&wire.id, &wire.title, wire.status));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// The lines under the `wr stats` table, as (label, value) pairs
+fn stats_summary(stats: &crate::models::Stats) -> [(&'static str, String); 3] {
+    let blocked = &stats.blocked;
+    [
+        (
+            "Throughput",
+            format!(
+                "{} done in the last day, {} in the last week",
+                stats.throughput.day, stats.throughput.week
+            ),
+        ),
+        (
+            "Cycle time",
+            stats
+                .cycle_time
+                .map_or_else(|| "-".to_string(), crate::time::format_age),
+        ),
+        (
+            "Blocked",
+            format!(
+                "{} blocked, {} waiting on dependencies, {} on external blockers",
+                blocked.status, blocked.dependencies, blocked.external
+            ),
+        ),
+    ]
+}
+
+/// Formats `wr board` columns side by side within `width` characters,
+/// each wire as its ID and title, cut short with `…` to fit.
+pub fn format_board_table(columns: &[crate::models::BoardColumn], width: usize) -> String {
+    const GAP: usize = 2;
+    const MIN_COLUMN: usize = 12;
+
+    if columns.is_empty() {
+        return String::new();
+    }
+    let gaps = GAP * (columns.len() - 1);
+    let column_width = (width.saturating_sub(gaps) / columns.len()).max(MIN_COLUMN);
+    let fit = |text: &str| {
+        let mut cell: String = text.chars().take(column_width).collect();
+        if text.chars().count() > column_width {
+            cell.pop();
+            cell.push('…');
+        }
+        let padding = column_width - cell.chars().count();
+        cell + &" ".repeat(padding)
+    };
+    let join = |cells: Vec<String>| {
+        let line = cells.join(&" ".repeat(GAP));
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut output = join(
+        columns
+            .iter()
+            .map(|column| {
+                fit(&format!(
+                    "{} ({})",
+                    column.status.as_str(),
+                    column.wires.len()
+                ))
+                .if_supports_color(Stream::Stdout, |text| text.bold())
+                .to_string()
+            })
+            .collect(),
+    );
+    output.push_str(&join(
+        columns.iter().map(|_| "─".repeat(column_width)).collect(),
+    ));
+
+    let rows = columns.iter().map(|c| c.wires.len()).max().unwrap_or(0);
+    for row in 0..rows {
+        output.push_str(&join(
+            columns
+                .iter()
+                .map(|column| match column.wires.get(row) {
+                    Some(wire) => fit(&format!("{} {}", wire.id, wire.title)),
+                    None => fit(""),
+                })
+                .collect(),
+        ));
+    }
+
+    output
+}
+
+/// Formats `wr board` columns as a Markdown table, one status per column.
+pub fn format_board_markdown(columns: &[crate::models::BoardColumn]) -> String {
+    let cells = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+    let mut output = cells(
+        columns
+            .iter()
+            .map(|column| format!("{} ({})", column.status.as_str(), column.wires.len()))
+            .collect(),
+    );
+    output.push_str(&cells(columns.iter().map(|_| "---".to_string()).collect()));
+
+    let rows = columns.iter().map(|c| c.wires.len()).max().unwrap_or(0);
+    for row in 0..rows {
+        output.push_str(&cells(
+            columns
+                .iter()
+                .map(|column| match column.wires.get(row) {
+                    Some(wire) => format!("`{}` {}", wire.id, wire.title.replace('|', "\\|")),
+                    None => String::new(),
+                })
+                .collect(),
+        ));
+    }
+
+    output
+}
+
+/// Formats `wr burndown` days as columns, oldest first.
+pub fn format_burndown_table(days: &[crate::models::BurndownDay]) -> String {
+    let mut output = format!(
+        "{:<10} {:>6} {:>6} {:>9}\n",
+        "DATE", "OPEN", "DONE", "COMPLETED"
+    );
+    for day in days {
+        output.push_str(&format!(
+            "{:<10} {:>6} {:>6} {:>9}\n",
+            day.date, day.open, day.done, day.completed
+        ));
+    }
+    output
+}
+
+/// Formats a dependency tree with box-drawing connectors, one wire per line.
+///
+/// Wires already shown higher up are marked `(see above)` and not expanded again.
+pub fn format_tree_table(tree: &crate::models::DependencyTree) -> String {
+    fn line(node: &crate::models::DependencyTree) -> String {
+        let mut line = format!(
+            "{} {}  {}",
+            format_status_symbol(node.wire.status),
+            node.wire.id,
+            node.wire.title
+        );
+        if node.repeated {
+            line.push_str(
+                &"  (see above)"
+                    .if_supports_color(Stream::Stdout, |text| text.dimmed())
+                    .to_string(),
+            );
+        }
+        line.push('\n');
+        line
+    }
+
+    fn children(node: &crate::models::DependencyTree, prefix: &str, output: &mut String) {
+        for (i, child) in node.children.iter().enumerate() {
+            let last = i + 1 == node.children.len();
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            output.push_str(prefix);
+            output.push_str(branch);
+            output.push_str(&line(child));
+            children(child, &format!("{}{}", prefix, indent), output);
+        }
+    }
+
+    let mut output = line(tree);
+    children(tree, "", &mut output);
+    output
+}
+
+/// Formats a dependency tree as a nested Markdown checklist.
+pub fn format_tree_markdown(tree: &crate::models::DependencyTree) -> String {
+    fn node(tree: &crate::models::DependencyTree, depth: usize, output: &mut String) {
+        output.push_str(&"  ".repeat(depth));
+        output.push_str(&markdown_item(
+            &tree.wire.id,
+            &tree.wire.title,
+            tree.wire.status,
+        ));
+        if tree.repeated {
+            output.push_str(" *(see above)*");
+        }
+        output.push('\n');
+        for child in &tree.children {
+            node(child, depth + 1, output);
+        }
+    }
+
+    let mut output = String::new();
+    node(tree, 0, &mut output);
+    output
+}
+
+/// Formats a `wr why` report: a verdict line, then what keeps the wire out
+/// of `wr ready`, with unfinished dependencies drawn as a tree.
+pub fn format_readiness_table(why: &crate::models::Readiness) -> String {
+    use crate::models::Blocker;
+
+    fn children(blocker: &Blocker, prefix: &str, output: &mut String) {
+        let lines: Vec<String> = blocker
+            .blocked_on
+            .iter()
+            .map(|b| format!("waiting on #{} \"{}\"\n", b.id, b.reason))
+            .collect();
+        let count = lines.len() + blocker.blockers.len();
+        for (i, line) in lines.into_iter().enumerate() {
+            let branch = if i + 1 == count {
+                "└── "
+            } else {
+                "├── "
+            };
+            output.push_str(&format!("{}{}{}", prefix, branch, line));
+        }
+        let offset = count - blocker.blockers.len();
+        for (i, child) in blocker.blockers.iter().enumerate() {
+            let last = offset + i + 1 == count;
+            let (branch, indent) = if last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            output.push_str(&format!("{}{}{}", prefix, branch, blocker_line(child)));
+            children(child, &format!("{}{}", prefix, indent), output);
+        }
+    }
+
+    fn blocker_line(blocker: &Blocker) -> String {
+        let mut line = format!(
+            "{} {}  {}",
+            format_status_symbol(blocker.wire.status),
+            blocker.wire.id,
+            blocker.wire.title
+        );
+        if blocker.repeated {
+            line.push_str("  (see above)");
+        } else if blocker.wire.status == crate::models::Status::Cancelled {
+            line.push_str("  (cancelled, will never finish)");
+        } else if blocker.blocked_on.is_empty() && blocker.blockers.is_empty() {
+            line.push_str("  (not finished)");
+        }
+        line.push('\n');
+        line
+    }
+
+    let wire = &why.wire;
+    let verdict = if why.ready { "ready" } else { "not ready" };
+    let mut output = format!(
+        "{} {}  {}  — {}\n",
+        format_status_symbol(wire.status),
+        wire.id,
+        wire.title,
+        verdict
+    );
+
+    if why.status_blocks() {
+        output.push_str(&format!("status {} is never ready\n", wire.status.as_str()));
+    }
+    if let Some(lease) = &why.claimed {
+        output.push_str(&format!(
+            "claimed by {} until {}\n",
+            lease.agent,
+            crate::time::format_utc(lease.expires_at)
+        ));
+    }
+    for blocker in &why.blocked_on {
+        output.push_str(&format!(
+            "waiting on #{} \"{}\"\n",
+            blocker.id, blocker.reason
+        ));
+    }
+    if !why.blockers.is_empty() {
+        output.push_str("blocked by:\n");
+        let root = Blocker {
+            wire: why.wire.clone(),
+            repeated: false,
+            blocked_on: vec![],
+            blockers: why.blockers.clone(),
+        };
+        children(&root, "", &mut output);
+    }
+
+    output
+}
+
+/// Formats a `wr why` report as Markdown, with unfinished dependencies as a
+/// nested checklist.
+pub fn format_readiness_markdown(why: &crate::models::Readiness) -> String {
+    use crate::models::Blocker;
+
+    fn node(blocker: &Blocker, depth: usize, output: &mut String) {
+        let indent = "  ".repeat(depth);
+        output.push_str(&indent);
+        output.push_str(&markdown_item(
+            &blocker.wire.id,
+            &blocker.wire.title,
+            blocker.wire.status,
+        ));
+        if blocker.repeated {
+            output.push_str(" *(see above)*");
+        }
+        output.push('\n');
+        for b in &blocker.blocked_on {
+            output.push_str(&format!(
+                "{}  - waiting on *{}* (#{})\n",
+                indent, b.reason, b.id
+            ));
+        }
+        for child in &blocker.blockers {
+            node(child, depth + 1, output);
+        }
+    }
+
+    let wire = &why.wire;
+    let verdict = if why.ready { "ready" } else { "not ready" };
+    let mut output = format!("**`{}` {}** is {}.\n", wire.id, wire.title, verdict);
+
+    if why.status_blocks() {
+        output.push_str(&format!(
+            "\n- Its status, {}, is never ready\n",
+            wire.status.as_str()
+        ));
+    }
+    if let Some(lease) = &why.claimed {
+        output.push_str(&format!(
+            "\n- Claimed by {} until {}\n",
+            lease.agent,
+            crate::time::format_utc(lease.expires_at)
+        ));
+    }
+    for blocker in &why.blocked_on {
+        output.push_str(&format!(
+            "\n- Waiting on *{}* (#{})\n",
+            blocker.reason, blocker.id
+        ));
+    }
+    if !why.blockers.is_empty() {
+        output.push_str("\n**Blocked by:**\n\n");
+        for blocker in &why.blockers {
+            node(blocker, 0, &mut output);
+        }
+    }
+
+    output
+}
+
+/// Formats a stale report as one section per tag, each wire followed by its
+/// suggested action.
+pub fn format_stale_table(report: &crate::models::StaleReport) -> String {
+    if report.groups.is_empty() {
+        return format!(
+            "No TODO wires untouched for {} or more.\n",
+            crate::time::format_age(report.older_than)
+        );
+    }
+
+    let mut output = format!(
+        "{} TODO wire(s) untouched for {} or more\n",
+        report.count,
+        crate::time::format_age(report.older_than)
+    );
+    for group in &report.groups {
+        let heading = match &group.tag {
+            Some(tag) => format_tags(std::slice::from_ref(tag)),
+            None => "(untagged)".to_string(),
+        };
+        output.push_str(&format!("\n{}\n", heading));
+        for stale in &group.wires {
+            output.push_str(&format!(
+                "  {} {}  {}  {}  → {}: {}\n",
+                format_status_symbol(stale.wire.status),
+                stale.wire.id,
+                stale.wire.title,
+                crate::time::format_age(stale.age),
+                stale.action.as_str(),
+                stale.command
+            ));
+        }
+    }
+
+    output
+}
+
+/// Rows of an accuracy report: `(group, wires, points, actual, per point,
+/// variance)`, overall first
+fn accuracy_rows(report: &crate::models::AccuracyReport) -> Vec<[String; 6]> {
+    use crate::time::format_age;
+
+    let row = |group: String, accuracy: &crate::models::Accuracy, variance: String| {
+        [
+            group,
+            accuracy.wires.to_string(),
+            accuracy.estimate.to_string(),
+            format_age(accuracy.actual),
+            format_age(accuracy.per_point.round() as i64),
+            variance,
+        ]
+    };
+    let mut rows = vec![row("overall".to_string(), &report.overall, "-".to_string())];
+    for (kind, groups) in [
+        ("tag", &report.by_tag),
+        ("epic", &report.by_epic),
+        ("actor", &report.by_actor),
+    ] {
+        for group in groups {
+            let mut name = format!("{} {}", kind, group.name.as_deref().unwrap_or("(none)"));
+            if let Some(title) = &group.title {
+                name.push_str(&format!(" {}", title));
+            }
+            rows.push(row(
+                name,
+                &group.accuracy,
+                format!("{:+.1}%", group.variance),
+            ));
+        }
+    }
+    rows
+}
+
+/// Formats `wr report --accuracy` as a table: overall, then by tag, epic
+/// and actor.
+pub fn format_accuracy_table(report: &crate::models::AccuracyReport) -> String {
+    if report.overall.wires == 0 {
+        return "No DONE wires with an estimate:<n> tag.\n".to_string();
+    }
+
+    let rows = accuracy_rows(report);
+    let width = rows
+        .iter()
+        .map(|row| row[0].chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut output = format!(
+        "{:<width$}  {:>5}  {:>6}  {:>6}  {:>9}  {:>8}\n",
+        "GROUP", "WIRES", "POINTS", "ACTUAL", "PER POINT", "VARIANCE"
+    );
+    for [group, wires, points, actual, per_point, variance] in rows {
+        output.push_str(&format!(
+            "{:<width$}  {:>5}  {:>6}  {:>6}  {:>9}  {:>8}\n",
+            group, wires, points, actual, per_point, variance
+        ));
+    }
+    output
+}
+
+/// Formats `wr report --accuracy` as a Markdown table.
+pub fn format_accuracy_markdown(report: &crate::models::AccuracyReport) -> String {
+    if report.overall.wires == 0 {
+        return "*No DONE wires with an estimate:<n> tag.*\n".to_string();
+    }
+
+    let mut output = String::from(
+        "| Group | Wires | Points | Actual | Per point | Variance |\n|---|---:|---:|---:|---:|---:|\n",
+    );
+    for [group, wires, points, actual, per_point, variance] in accuracy_rows(report) {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            group, wires, points, actual, per_point, variance
+        ));
+    }
+    output
+}
+
+/// Formats `wr plan compare` as `+`/`-`/`~` lines for wires, then for
+/// dependencies, then the edit distance.
+pub fn format_plan_diff_table(diff: &crate::plan::PlanDiff) -> String {
+    if diff.is_empty() {
+        return "No differences.\n".to_string();
+    }
+
+    let node = |node: &crate::plan::PlanNode| {
+        if node.name == node.title {
+            node.title.clone()
+        } else {
+            format!("{}  {}", node.name, node.title)
+        }
+    };
+    let mut output = String::new();
+    if !diff.added.is_empty() || !diff.removed.is_empty() || !diff.changed.is_empty() {
+        output.push_str("Wires:\n");
+        for added in &diff.added {
+            output.push_str(&format!("  + {}\n", node(added)));
+        }
+        for removed in &diff.removed {
+            output.push_str(&format!("  - {}\n", node(removed)));
+        }
+        for changed in &diff.changed {
+            output.push_str(&format!(
+                "  ~ {}  ({})\n",
+                node(&changed.node),
+                changed.fields.join(", ")
+            ));
+        }
+    }
+    if !diff.added_edges.is_empty() || !diff.removed_edges.is_empty() {
+        output.push_str("Dependencies:\n");
+        for edge in &diff.added_edges {
+            output.push_str(&format!("  + {} → {}\n", edge.wire, edge.depends_on));
+        }
+        for edge in &diff.removed_edges {
+            output.push_str(&format!("  - {} → {}\n", edge.wire, edge.depends_on));
+        }
+    }
+    output.push_str(&format!("Distance: {}\n", diff.distance));
+    output
+}
+
+/// Formats `wr plan compare` as Markdown lists under a heading.
+pub fn format_plan_diff_markdown(diff: &crate::plan::PlanDiff) -> String {
+    if diff.is_empty() {
+        return "*No differences.*\n".to_string();
+    }
+
+    let node = |node: &crate::plan::PlanNode| {
+        if node.name == node.title {
+            node.title.clone()
+        } else {
+            format!("`{}` {}", node.name, node.title)
+        }
+    };
+    let mut output = format!("### Plan changes (distance {})\n", diff.distance);
+    let mut section = |heading: &str, items: Vec<String>| {
+        if !items.is_empty() {
+            output.push_str(&format!("\n**{}:**\n\n", heading));
+            for item in items {
+                output.push_str(&format!("- {}\n", item));
+            }
+        }
+    };
+    section("Added wires", diff.added.iter().map(node).collect());
+    section("Removed wires", diff.removed.iter().map(node).collect());
+    section(
+        "Changed wires",
+        diff.changed
+            .iter()
+            .map(|c| format!("{} ({})", node(&c.node), c.fields.join(", ")))
+            .collect(),
+    );
+    let edge = |e: &crate::plan::PlanEdge| format!("`{}` → `{}`", e.wire, e.depends_on);
+    section(
+        "Added dependencies",
+        diff.added_edges.iter().map(edge).collect(),
+    );
+    section(
+        "Removed dependencies",
+        diff.removed_edges.iter().map(edge).collect(),
+    );
+    output
+}
+
+/// Formats a stale report as Markdown checklists under a heading per tag.
+pub fn format_stale_markdown(report: &crate::models::StaleReport) -> String {
+    let age = crate::time::format_age(report.older_than);
+    if report.groups.is_empty() {
+        return format!("*No TODO wires untouched for {} or more.*\n", age);
+    }
+
+    let mut output = format!(
+        "**{} TODO wire(s) untouched for {} or more**\n",
+        report.count, age
+    );
+    for group in &report.groups {
+        match &group.tag {
+            Some(tag) => output.push_str(&format!("\n### #{}\n\n", tag)),
+            None => output.push_str("\n### Untagged\n\n"),
+        }
+        for stale in &group.wires {
+            output.push_str(&format!(
+                "{} — {} old, suggest **{}**: `{}`\n",
+                markdown_item(&stale.wire.id, &stale.wire.title, stale.wire.status),
+                crate::time::format_age(stale.age),
+                stale.action.as_str(),
+                stale.command
+            ));
+        }
+    }
+
+    output
+}
+
+/// Escapes text for use inside a double-quoted DOT string.
+///
+/// Backslashes and quotes are escaped so a title can't end the string or
+/// smuggle in DOT's own escapes (`\l`, `\N`, ...). Line breaks become `\n`,
+/// which Graphviz renders as a new line; other control characters are
+/// replaced with U+FFFD. Everything else, including non-ASCII text, passes
+/// through unchanged (DOT files are UTF-8).
+pub fn escape_dot(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => escaped.push_str("\\n"),
+            '\t' => escaped.push(c),
+            c if c.is_control() => escaped.push(char::REPLACEMENT_CHARACTER),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes text for a quoted Mermaid label, using Mermaid's `#name;`
+/// entity codes for characters that would end or break the label.
+///
+/// # Example
+///
+/// ```
+/// use wr::format::escape_mermaid;
+///
+/// assert_eq!(escape_mermaid(r#"Say "hi" <b>"#), "Say #quot;hi#quot; #lt;b#gt;");
+/// ```
+pub fn escape_mermaid(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("#quot;"),
+            '#' => escaped.push_str("#35;"),
+            '<' => escaped.push_str("#lt;"),
+            '>' => escaped.push_str("#gt;"),
+            '\n' | '\r' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escapes text for XML character data and attribute values.
+///
+/// The five special characters become entities. Characters XML 1.0 can't
+/// represent at all, even as references (most C0 controls, U+FFFE and
+/// U+FFFF), are replaced with U+FFFD.
+pub fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            '\u{FFFE}' | '\u{FFFF}' => escaped.push(char::REPLACEMENT_CHARACTER),
+            c if (c as u32) < 0x20 => escaped.push(char::REPLACEMENT_CHARACTER),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the Markdown task-list marker for a status.
+///
+/// Done wires are checked; everything else is unchecked.
+fn markdown_checkbox(status: crate::models::Status) -> &'static str {
+    match status {
+        crate::models::Status::Done => "- [x]",
+        _ => "- [ ]",
+    }
+}
+
+/// Formats a wire or dependency as a single checklist item.
+/// Splits a wire's links into `relates` links and the rest, keeping order
+fn split_related(
+    links: &[crate::models::Link],
+) -> (Vec<&crate::models::Link>, Vec<&crate::models::Link>) {
+    links
+        .iter()
+        .partition(|link| link.kind == crate::models::DependencyKind::Relates)
+}
+
+fn markdown_item(id: &crate::models::WireId, title: &str, status: crate::models::Status) -> String {
+    use crate::models::Status;
+
+    match status {
+        Status::Cancelled => format!(
+            "{} ~~`{}` {}~~ *(cancelled)*",
+            markdown_checkbox(status),
+            id,
+            title
+        ),
+        Status::InProgress => format!(
+            "{} `{}` {} *(in progress)*",
+            markdown_checkbox(status),
+            id,
+            title
+        ),
+        Status::Paused => format!(
+            "{} `{}` {} *(paused)*",
+            markdown_checkbox(status),
+            id,
+            title
+        ),
+        Status::Blocked => format!(
+            "{} `{}` {} *(blocked)*",
+            markdown_checkbox(status),
+            id,
+            title
+        ),
+        _ => format!("{} `{}` {}", markdown_checkbox(status), id, title),
+    }
+}
+
+/// Formats a list of wires as a Markdown checklist.
 ///
-/// The table includes status symbol, ID, title, and optional blocker info.
-/// Returns "No wires found." if the list is empty.
-pub fn format_wire_table(wires: &[crate::models::WireWithDeps]) -> String {
+/// Each wire becomes `- [ ] `id` Title`, checked when done, with its tags and
+/// any unfinished dependencies noted after the title.
+pub fn format_wire_markdown(wires: &[crate::models::WireWithDeps]) -> String {
     if wires.is_empty() {
-        return String::from("No wires found.");
+        return String::from("*No wires found.*\n");
     }
 
     let mut output = String::new();
 
-    // No header - symbols are self-explanatory
-
-    // Rows
     for wire_with_deps in wires {
         let wire = &wire_with_deps.wire;
-        let symbol = format_status_symbol(wire.status);
+        output.push_str(&markdown_item(&wire.id, &wire.title, wire.status));
 
-        // Base line: symbol + id + title
-        output.push_str(&format!("{} {}  {}", symbol, wire.id.as_str(), wire.title));
+        if !wire.tags.is_empty() {
+            let tags: Vec<String> = wire.tags.iter().map(|t| format!("`#{}`", t)).collect();
+            output.push_str(&format!(" {}", tags.join(" ")));
+        }
+        if let Some(assignee) = &wire.assignee {
+            output.push_str(&format!(" `@{}`", assignee));
+        }
 
-        // Add blocker suffix if this wire has blocking dependencies
-        let blocker_ids: Vec<_> = wire_with_deps
+        let blocker_ids: Vec<String> = wire_with_deps
             .depends_on
             .iter()
-            .filter(|dep| dep.status.is_blocking())
-            .map(|dep| dep.id.as_str())
+            .filter(|dep| blocks_dependents(dep.status))
+            .map(|dep| format!("`{}`", dep.id))
             .collect();
 
         if !blocker_ids.is_empty() {
-            output.push_str(&format!("  ← blocked by {}", blocker_ids.join(", ")));
+            output.push_str(&format!(" — blocked by {}", blocker_ids.join(", ")));
+        }
+
+        if !wire_with_deps.blocked_on.is_empty() {
+            let reasons: Vec<String> = wire_with_deps
+                .blocked_on
+                .iter()
+                .map(|b| format!("*{}*", b.reason))
+                .collect();
+            output.push_str(&format!(" — waiting on {}", reasons.join(", ")));
+        }
+
+        if let Some(pause) = &wire_with_deps.paused {
+            output.push_str(&format!(" — *{}*", pause.reason));
+        }
+        if let Some(reason) = &wire_with_deps.wire.blocked_reason {
+            output.push_str(&format!(" — *{}*", reason));
         }
 
         output.push('\n');
@@ -110,64 +1553,302 @@ pub fn format_wire_table(wires: &[crate::models::WireWithDeps]) -> String {
     output
 }
 
-/// Formats a wire's details with a compact header.
+/// Formats a wire's details as a Markdown section.
 ///
-/// Shows a single-line header with symbol, ID, title, and priority,
-/// followed by description and dependency information.
-pub fn format_wire_detail_table(wire: &crate::models::WireWithDeps) -> String {
+/// Includes a heading, status and priority line, the description, and
+/// checklists of the wires it depends on and blocks.
+pub fn format_wire_detail_markdown(wire: &crate::models::WireWithDeps) -> String {
     let mut output = String::new();
 
-    let symbol = format_status_symbol(wire.wire.status);
+    output.push_str(&format!("### `{}` {}\n\n", wire.wire.id, wire.wire.title));
 
-    // Compact header: symbol + id + title + [pri:N]
     output.push_str(&format!(
-        "{} {}  {}  [pri:{}]\n",
-        symbol,
-        wire.wire.id.as_str(),
-        wire.wire.title,
-        wire.wire.priority
+        "**Status:** {} · **Priority:** {}",
+        wire.wire.status.as_str(),
+        format_priority(wire.wire.priority)
     ));
+    if !wire.wire.tags.is_empty() {
+        let tags: Vec<String> = wire.wire.tags.iter().map(|t| format!("`#{}`", t)).collect();
+        output.push_str(&format!(" · **Tags:** {}", tags.join(" ")));
+    }
+    if let Some(assignee) = &wire.wire.assignee {
+        output.push_str(&format!(" · **Assignee:** {}", assignee));
+    }
+    output.push('\n');
 
-    // Description (if present)
     if let Some(ref desc) = wire.wire.description {
         output.push('\n');
         output.push_str(desc);
         output.push('\n');
     }
 
-    // Dependencies
     if !wire.depends_on.is_empty() {
-        output.push_str("\nDepends on:\n");
+        output.push_str("\n**Depends on:**\n\n");
         for dep in &wire.depends_on {
-            let dep_symbol = format_status_symbol(dep.status);
-            output.push_str(&format!(
-                "  {} {}  {}\n",
-                dep_symbol,
-                dep.id.as_str(),
-                dep.title
-            ));
+            output.push_str(&markdown_item(&dep.id, &dep.title, dep.status));
+            output.push('\n');
         }
     }
 
-    // Blocks
     if !wire.blocks.is_empty() {
-        output.push_str("\nBlocks:\n");
-        for blocker in &wire.blocks {
-            let blocker_symbol = format_status_symbol(blocker.status);
+        output.push_str("\n**Blocks:**\n\n");
+        for blocked in &wire.blocks {
+            output.push_str(&markdown_item(&blocked.id, &blocked.title, blocked.status));
+            output.push('\n');
+        }
+    }
+
+    let (related, links) = split_related(&wire.links);
+    if !related.is_empty() {
+        output.push_str("\n**Related:**\n\n");
+        for link in related {
+            output.push_str(&markdown_item(
+                &link.wire.id,
+                &link.wire.title,
+                link.wire.status,
+            ));
+            output.push('\n');
+        }
+    }
+
+    if !links.is_empty() {
+        output.push_str("\n**Links:**\n\n");
+        for link in links {
+            output.push_str(&markdown_item(
+                &link.wire.id,
+                &link.wire.title,
+                link.wire.status,
+            ));
+            output.push_str(&format!(" *({})*\n", link.kind));
+        }
+    }
+
+    if !wire.blocked_on.is_empty() {
+        output.push_str("\n**Waiting on:**\n\n");
+        for blocker in &wire.blocked_on {
             output.push_str(&format!(
-                "  {} {}  {}\n",
-                blocker_symbol,
-                blocker.id.as_str(),
-                blocker.title
+                "- [ ] {} *(external, #{})*\n",
+                blocker.reason, blocker.id
             ));
         }
     }
 
+    if let Some(pause) = &wire.paused {
+        output.push_str(&format!("\n**Paused:** {}\n", pause.reason));
+    }
+
+    if let Some(reason) = &wire.wire.blocked_reason {
+        output.push_str(&format!("\n**Blocked:** {}\n", reason));
+    }
+
+    if let Some(reason) = &wire.wire.cancel_reason {
+        output.push_str(&format!("\n**Cancelled:** {}\n", reason));
+    }
+
+    output
+}
+
+/// Formats a `wr doc` dossier as one Markdown document: the wire's details,
+/// its claim and readiness, the upstream wires it builds on with their
+/// descriptions, and its history.
+pub fn format_dossier_markdown(dossier: &crate::models::Dossier) -> String {
+    let mut output = format_wire_detail_markdown(&dossier.wire);
+
+    if let Some(lease) = &dossier.lease {
+        output.push_str(&format!(
+            "\n**Claimed by:** {} until {}\n",
+            lease.agent,
+            crate::time::format_utc(lease.expires_at)
+        ));
+    }
+
+    output.push_str("\n#### Readiness\n\n");
+    output.push_str(&format_readiness_markdown(&dossier.readiness));
+
+    if !dossier.upstream.is_empty() {
+        output.push_str("\n#### Upstream\n");
+        for wire in &dossier.upstream {
+            output.push('\n');
+            output.push_str(&markdown_item(&wire.id, &wire.title, wire.status));
+            output.push('\n');
+            if let Some(desc) = &wire.description {
+                for line in desc.lines() {
+                    output.push_str(&format!("  > {}\n", line));
+                }
+            }
+        }
+    }
+
+    output.push_str("\n#### History\n\n");
+    if dossier.history.is_empty() {
+        output.push_str("No events recorded.\n");
+    }
+    for event in &dossier.history {
+        output.push_str(&format!(
+            "- {} {}\n",
+            crate::time::format_utc(event.at),
+            describe_event(event)
+        ));
+    }
+
     output
 }
 
+/// One-line summary of an event: changed fields as `from → to`, or the
+/// kind and its details.
+fn describe_event(event: &crate::models::Event) -> String {
+    use crate::models::EventKind;
+
+    let value = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    match (&event.kind, event.data.as_object()) {
+        (EventKind::Created | EventKind::Deleted, _) => event.kind.as_str().to_string(),
+        (EventKind::Updated, Some(fields)) => {
+            let changes: Vec<String> = fields
+                .iter()
+                .filter(|(_, change)| change.get("to").is_some())
+                .map(|(field, change)| {
+                    format!(
+                        "{}: {} → {}",
+                        field,
+                        value(&change["from"]),
+                        value(&change["to"])
+                    )
+                })
+                .collect();
+            format!("updated {}", changes.join(", "))
+        }
+        _ => format!("{} {}", event.kind.as_str(), event.data),
+    }
+}
+
+/// The request echoed around every JSON result by `--envelope`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Envelope {
+    /// Subcommand path, e.g. `done` or `tag add`
+    pub command: String,
+    /// Arguments given on the command line, by name
+    pub args: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Envelope {
+    /// `{"ok": true, "command", "args", "data"}`
+    pub fn wrap<'a, T: Serialize>(&'a self, data: &'a T) -> impl Serialize + 'a {
+        #[derive(Serialize)]
+        struct Wrapped<'a, T> {
+            ok: bool,
+            #[serde(flatten)]
+            request: &'a Envelope,
+            data: &'a T,
+        }
+        Wrapped {
+            ok: true,
+            request: self,
+            data,
+        }
+    }
+
+    /// `{"ok": false, "command", "args", ...error}`, for an error object
+    pub fn wrap_error<'a>(
+        &'a self,
+        error: &'a serde_json::Map<String, serde_json::Value>,
+    ) -> impl Serialize + 'a {
+        #[derive(Serialize)]
+        struct Wrapped<'a> {
+            ok: bool,
+            #[serde(flatten)]
+            request: &'a Envelope,
+            #[serde(flatten)]
+            error: &'a serde_json::Map<String, serde_json::Value>,
+        }
+        Wrapped {
+            ok: false,
+            request: self,
+            error,
+        }
+    }
+}
+
+/// Envelope [`print_json`] wraps results in, if any
+static ENVELOPE: OnceLock<Envelope> = OnceLock::new();
+
+/// Wraps every result [`print_json`] prints in `envelope`.
+///
+/// Only the first call has any effect.
+pub fn set_envelope(envelope: Envelope) {
+    let _ = ENVELOPE.set(envelope);
+}
+
+/// The envelope results are wrapped in, if one was set.
+pub fn envelope() -> Option<&'static Envelope> {
+    ENVELOPE.get()
+}
+
+/// How much `wr` prints besides command results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Only essential output: the new ID from `wr new`, nothing from other
+    /// changes and no warnings
+    Quiet,
+    #[default]
+    Normal,
+    /// Diagnostics on stderr too: the database chosen and SQL timings
+    Verbose,
+}
+
+/// Verbosity set by `--quiet` or `--verbose`
+static VERBOSITY: OnceLock<Verbosity> = OnceLock::new();
+
+/// Sets how much `wr` prints for the rest of the process.
+///
+/// Only the first call has any effect.
+pub fn set_verbosity(verbosity: Verbosity) {
+    let _ = VERBOSITY.set(verbosity);
+}
+
+/// The verbosity set by [`set_verbosity`], or [`Verbosity::Normal`].
+pub fn verbosity() -> Verbosity {
+    VERBOSITY.get().copied().unwrap_or_default()
+}
+
+/// Whether non-essential output should be left out.
+pub fn is_quiet() -> bool {
+    verbosity() == Verbosity::Quiet
+}
+
+/// Prints a diagnostic line to stderr when the verbosity is
+/// [`Verbosity::Verbose`].
+pub fn diag(message: impl std::fmt::Display) {
+    if verbosity() == Verbosity::Verbose {
+        eprintln!("wr: {}", message);
+    }
+}
+
+/// Prints a warning line to stderr unless the verbosity is
+/// [`Verbosity::Quiet`].
+pub fn warn(message: impl std::fmt::Display) {
+    if !is_quiet() {
+        eprintln!("wr: warning: {}", message);
+    }
+}
+
+/// Whether [`print_json`] indents its output
+static PRETTY: OnceLock<bool> = OnceLock::new();
+
+/// Makes [`print_json`] print indented, multi-line JSON.
+///
+/// Only the first call has any effect. JSON Lines stay one value per line.
+pub fn set_pretty(pretty: bool) {
+    let _ = PRETTY.set(pretty);
+}
+
 /// Prints data as JSON to stdout.
 ///
+/// With an [envelope](set_envelope), the data is wrapped in it. Output is
+/// compact unless [`set_pretty`] was called.
+///
 /// # Arguments
 ///
 /// * `data` - Any serializable data
@@ -176,14 +1857,71 @@ pub fn format_wire_detail_table(wire: &crate::models::WireWithDeps) -> String {
 ///
 /// Returns an error if JSON serialization fails.
 pub fn print_json<T: serde::Serialize>(data: &T) -> anyhow::Result<()> {
-    println!("{}", serde_json::to_string(data)?);
+    let pretty = PRETTY.get().copied().unwrap_or(false);
+    let text = match (envelope(), pretty) {
+        (Some(envelope), false) => serde_json::to_string(&envelope.wrap(data))?,
+        (Some(envelope), true) => serde_json::to_string_pretty(&envelope.wrap(data))?,
+        (None, false) => serde_json::to_string(data)?,
+        (None, true) => serde_json::to_string_pretty(data)?,
+    };
+    println!("{}", text);
+    Ok(())
+}
+
+/// Prints the outcome of a change like [`print_json`], or nothing when
+/// [quiet](is_quiet), leaving the exit code as the result.
+pub fn print_result<T: serde::Serialize>(data: &T) -> anyhow::Result<()> {
+    if is_quiet() {
+        return Ok(());
+    }
+    print_json(data)
+}
+
+/// Prints data as JSON Lines: each item of an array on its own line, and
+/// anything else on a single line.
+///
+/// With an [envelope](set_envelope) the whole result is one enveloped line,
+/// as [`print_json`] would print it without [`set_pretty`].
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails.
+pub fn print_jsonl<T: serde::Serialize>(data: &T) -> anyhow::Result<()> {
+    use std::io::Write;
+
+    let value = serde_json::to_value(data)?;
+    let mut out = io::stdout().lock();
+    match (envelope(), value) {
+        (Some(envelope), value) => {
+            writeln!(out, "{}", serde_json::to_string(&envelope.wrap(&value))?)?
+        }
+        (None, serde_json::Value::Array(items)) => {
+            for item in items {
+                writeln!(out, "{}", item)?;
+            }
+        }
+        (None, value) => writeln!(out, "{}", value)?,
+    }
     Ok(())
 }
 
+/// Prints data as [`print_jsonl`] for [`Format::Jsonl`], else as [`print_json`].
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails.
+pub fn print_json_as<T: serde::Serialize>(data: &T, format: Format) -> anyhow::Result<()> {
+    match format {
+        Format::Jsonl => print_jsonl(data),
+        _ => print_json(data),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{DependencyInfo, Status, Wire, WireId, WireWithDeps};
+    use crate::models::{Agent, DependencyInfo, Status, Tag, Wire, WireId, WireWithDeps};
+    use proptest::prelude::*;
 
     fn make_test_wire(id: &str, title: &str, status: Status) -> Wire {
         Wire {
@@ -191,9 +1929,13 @@ mod tests {
             title: title.to_string(),
             description: None,
             status,
-            created_at: 0,
-            updated_at: 0,
+            created_at_ms: 0,
+            updated_at_ms: 0,
             priority: 0,
+            tags: vec![],
+            cancel_reason: None,
+            blocked_reason: None,
+            assignee: None,
         }
     }
 
@@ -214,6 +1956,54 @@ mod tests {
         assert!(format_status_symbol(Status::Cancelled).contains(Status::Cancelled.symbol()));
     }
 
+    #[test]
+    fn test_format_wire_markdown_checklist() {
+        let mut tagged = make_test_wire("b2c3d4e", "Add API", Status::Todo);
+        tagged.tags = vec![Tag::new("backend").unwrap()];
+        let wires = vec![
+            WireWithDeps::from(make_test_wire("a1b2c3d", "Setup DB", Status::Done)),
+            WireWithDeps {
+                wire: tagged,
+                depends_on: vec![make_test_dep("c3d4e5f", "Auth", Status::InProgress)],
+                blocks: vec![],
+                blocked_on: vec![],
+                paused: None,
+                links: vec![],
+            },
+            WireWithDeps::from(make_test_wire("d4e5f6a", "Old idea", Status::Cancelled)),
+        ];
+
+        assert_eq!(
+            format_wire_markdown(&wires),
+            "- [x] `a1b2c3d` Setup DB\n\
+             - [ ] `b2c3d4e` Add API `#backend` — blocked by `c3d4e5f`\n\
+             - [ ] ~~`d4e5f6a` Old idea~~ *(cancelled)*\n"
+        );
+    }
+
+    #[test]
+    fn test_format_wire_detail_markdown() {
+        let mut wire = make_test_wire("a1b2c3d", "Add API", Status::InProgress);
+        wire.description = Some("REST endpoints".to_string());
+        wire.priority = 2;
+        let detail = WireWithDeps {
+            wire,
+            depends_on: vec![make_test_dep("b2c3d4e", "Setup DB", Status::Done)],
+            blocks: vec![make_test_dep("c3d4e5f", "Frontend", Status::Todo)],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
+        };
+
+        let output = format_wire_detail_markdown(&detail);
+        assert!(output.starts_with(
+            "### `a1b2c3d` Add API\n\n**Status:** IN_PROGRESS · **Priority:** high\n"
+        ));
+        assert!(output.contains("\nREST endpoints\n"));
+        assert!(output.contains("**Depends on:**\n\n- [x] `b2c3d4e` Setup DB\n"));
+        assert!(output.contains("**Blocks:**\n\n- [ ] `c3d4e5f` Frontend\n"));
+    }
+
     #[test]
     fn test_format_wire_table_empty() {
         let wires = vec![];
@@ -228,6 +2018,9 @@ mod tests {
             wire,
             depends_on: vec![],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
         let output = format_wire_table(&[wire_with_deps]);
 
@@ -244,6 +2037,9 @@ mod tests {
             wire,
             depends_on: vec![dep],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
         let output = format_wire_table(&[wire_with_deps]);
 
@@ -259,6 +2055,9 @@ mod tests {
             wire,
             depends_on: vec![dep],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
         let output = format_wire_table(&[wire_with_deps]);
 
@@ -267,18 +2066,22 @@ mod tests {
     }
 
     #[test]
-    fn test_format_wire_table_no_blocker_for_cancelled_deps() {
-        let wire = make_test_wire("a1b2c3d", "Unblocked wire", Status::Todo);
+    fn test_format_wire_table_cancelled_deps_block_by_default() {
+        // As in `wr ready`, unless `ready.cancelled_blocks` is turned off
+        let wire = make_test_wire("a1b2c3d", "Waiting wire", Status::Todo);
         let dep = make_test_dep("b2c3d4e", "Cancelled blocker", Status::Cancelled);
         let wire_with_deps = WireWithDeps {
             wire,
             depends_on: vec![dep],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
         let output = format_wire_table(&[wire_with_deps]);
 
-        assert!(output.contains("Unblocked wire"));
-        assert!(!output.contains("← blocked by"));
+        assert!(output.contains("Waiting wire"));
+        assert!(output.contains("← blocked by b2c3d4e"));
     }
 
     #[test]
@@ -290,32 +2093,95 @@ mod tests {
             wire,
             depends_on: vec![dep1, dep2],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
         let output = format_wire_table(&[wire_with_deps]);
 
         assert!(output.contains("← blocked by b2c3d4e, c3d4e5f"));
     }
 
+    #[test]
+    fn test_format_wire_table_shows_assignee() {
+        let wire = Wire {
+            assignee: Some(Agent::new("alice").unwrap()),
+            ..make_test_wire("a1b2c3d", "Assigned wire", Status::Todo)
+        };
+        let output = format_wire_table(&[WireWithDeps::from(wire)]);
+
+        assert!(output.contains("Assigned wire  @alice"));
+    }
+
+    #[test]
+    fn test_format_wire_table_shows_tags() {
+        let wire = Wire {
+            tags: vec![Tag::new("backend").unwrap(), Tag::new("tests").unwrap()],
+            ..make_test_wire("a1b2c3d", "Tagged wire", Status::Todo)
+        };
+        let output = format_wire_table(&[WireWithDeps::from(wire)]);
+
+        assert!(output.contains("#backend #tests"));
+    }
+
+    #[test]
+    fn test_wire_columns_have_unique_names() {
+        for (i, column) in WIRE_COLUMNS.iter().enumerate() {
+            assert_eq!(wire_column(column.name), Some(&WIRE_COLUMNS[i]));
+        }
+    }
+
+    #[test]
+    fn test_format_wire_columns_table() {
+        let mut first = make_test_wire("a1b2c3d", "First", Status::Todo);
+        first.priority = 10;
+        first.assignee = Some("agent-1".parse().unwrap());
+        let second = make_test_wire("b2c3d4e", "Second wire", Status::Done);
+        let columns: crate::template::Columns = "id,priority,title,assignee".parse().unwrap();
+
+        assert_eq!(
+            format_wire_columns_table([&first, &second], &columns),
+            "ID       PRIORITY  TITLE        ASSIGNEE\n\
+             a1b2c3d        10  First        agent-1\n\
+             b2c3d4e         0  Second wire\n"
+        );
+        assert_eq!(format_wire_columns_table([], &columns), "No wires found.\n");
+    }
+
     #[test]
     fn test_format_wire_detail_table_compact_header() {
         let wire = make_test_wire("a1b2c3d", "Test wire", Status::InProgress);
         let wire_with_deps = WireWithDeps {
             wire: Wire {
-                priority: 2,
+                priority: 7,
                 ..wire
             },
             depends_on: vec![],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
-        let output = format_wire_detail_table(&wire_with_deps);
+        let output = format_wire_detail_table(&wire_with_deps, 80);
 
         // Should have compact header with symbol, id, title, priority
         assert!(output.contains("a1b2c3d"));
         assert!(output.contains("Test wire"));
-        assert!(output.contains("[pri:2]"));
+        assert!(output.contains("[pri:7]"));
         assert!(output.contains(Status::InProgress.symbol()));
     }
 
+    #[test]
+    fn test_format_wire_detail_table_names_priority() {
+        let wire = Wire {
+            priority: 2,
+            ..make_test_wire("a1b2c3d", "Test wire", Status::Todo)
+        };
+        let output = format_wire_detail_table(&WireWithDeps::from(wire), 80);
+
+        assert!(output.contains("[pri:high]"));
+    }
+
     #[test]
     fn test_format_wire_detail_table_with_description() {
         let wire = Wire {
@@ -326,12 +2192,63 @@ mod tests {
             wire,
             depends_on: vec![],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
-        let output = format_wire_detail_table(&wire_with_deps);
+        let output = format_wire_detail_table(&wire_with_deps, 80);
 
         assert!(output.contains("Test description"));
     }
 
+    #[test]
+    fn test_format_wire_detail_table_wraps_description() {
+        let wire = Wire {
+            description: Some("word ".repeat(30).trim_end().to_string()),
+            ..make_test_wire("a1b2c3d", "Test wire", Status::Todo)
+        };
+        let output = format_wire_detail_table(&WireWithDeps::from(wire), 40);
+
+        let description: Vec<_> = output.lines().skip(2).collect();
+        assert_eq!(description.len(), 4);
+        assert!(description.iter().all(|line| line.len() <= 40));
+    }
+
+    #[test]
+    fn test_wrap_text_keeps_paragraphs_and_short_lines() {
+        let text = "First paragraph that is rather too long to fit.\n\nShort line\nAnother";
+        assert_eq!(
+            wrap_text(text, 24),
+            "First paragraph that is\nrather too long to fit.\n\nShort line\nAnother\n"
+        );
+    }
+
+    #[test]
+    fn test_wrap_text_indents_list_continuations() {
+        let text =
+            "- a bullet item that needs wrapping here\n  12. numbered item that also wraps around";
+        assert_eq!(
+            wrap_text(text, 24),
+            "- a bullet item that\n  needs wrapping here\n  12. numbered item that\n      also wraps around\n"
+        );
+    }
+
+    #[test]
+    fn test_wrap_text_leaves_code_and_long_words() {
+        let url = format!("https://example.com/{}", "x".repeat(40));
+        let text = format!(
+            "```\nlet a_long_line_of_code = 1; // stays as it is\n```\nsee {} now",
+            url
+        );
+        assert_eq!(
+            wrap_text(&text, 30),
+            format!(
+                "```\nlet a_long_line_of_code = 1; // stays as it is\n```\nsee\n{}\nnow\n",
+                url
+            )
+        );
+    }
+
     #[test]
     fn test_format_wire_detail_table_with_dependencies() {
         let wire = make_test_wire("a1b2c3d", "Test wire", Status::Todo);
@@ -340,8 +2257,11 @@ mod tests {
             wire,
             depends_on: vec![dep],
             blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
-        let output = format_wire_detail_table(&wire_with_deps);
+        let output = format_wire_detail_table(&wire_with_deps, 80);
 
         assert!(output.contains("Depends on:"));
         assert!(output.contains("b2c3d4e"));
@@ -349,6 +2269,48 @@ mod tests {
         assert!(output.contains(Status::Done.symbol()));
     }
 
+    #[test]
+    fn test_format_wire_detail_lists_links_with_kind() {
+        use crate::models::{DependencyKind, Link};
+
+        let wire_with_deps = WireWithDeps {
+            links: vec![Link {
+                wire: make_test_dep("b2c3d4e", "Older report", Status::Todo),
+                kind: DependencyKind::Duplicates,
+            }],
+            ..WireWithDeps::from(make_test_wire("a1b2c3d", "Test wire", Status::Todo))
+        };
+
+        let table = format_wire_detail_table(&wire_with_deps, 80);
+        assert!(table.contains("Links:\n"));
+        assert!(table.contains("b2c3d4e  Older report  (duplicates)"));
+        assert!(!table.contains("Depends on:"));
+
+        let markdown = format_wire_detail_markdown(&wire_with_deps);
+        assert!(markdown.contains("**Links:**\n\n- [ ] `b2c3d4e` Older report *(duplicates)*\n"));
+    }
+
+    #[test]
+    fn test_format_wire_detail_lists_related_wires_separately() {
+        use crate::models::{DependencyKind, Link};
+
+        let wire_with_deps = WireWithDeps {
+            links: vec![Link {
+                wire: make_test_dep("b2c3d4e", "Context", Status::Done),
+                kind: DependencyKind::Relates,
+            }],
+            ..WireWithDeps::from(make_test_wire("a1b2c3d", "Test wire", Status::Todo))
+        };
+
+        let table = format_wire_detail_table(&wire_with_deps, 80);
+        assert!(table.contains("Related:\n"));
+        assert!(table.contains("b2c3d4e  Context\n"));
+        assert!(!table.contains("Links:"));
+
+        let markdown = format_wire_detail_markdown(&wire_with_deps);
+        assert!(markdown.contains("**Related:**\n\n- [x] `b2c3d4e` Context\n"));
+    }
+
     #[test]
     fn test_format_wire_detail_table_with_blocks() {
         let wire = make_test_wire("a1b2c3d", "Test wire", Status::Done);
@@ -357,11 +2319,114 @@ mod tests {
             wire,
             depends_on: vec![],
             blocks: vec![blocker],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
         };
-        let output = format_wire_detail_table(&wire_with_deps);
+        let output = format_wire_detail_table(&wire_with_deps, 80);
 
         assert!(output.contains("Blocks:"));
         assert!(output.contains("b2c3d4e"));
         assert!(output.contains("Blocked task"));
     }
+
+    /// Reads a DOT quoted-string body, failing on anything that would end
+    /// the string early or break the line.
+    fn unescape_dot(s: &str) -> Result<String, String> {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some(c @ ('\\' | '"')) => out.push(c),
+                    other => return Err(format!("stray escape \\{:?}", other)),
+                },
+                '"' => return Err("unescaped quote".into()),
+                '\n' | '\r' => return Err("raw line break".into()),
+                c => out.push(c),
+            }
+        }
+        Ok(out)
+    }
+
+    fn unescape_xml(s: &str) -> Result<String, String> {
+        let mut out = String::new();
+        let mut rest = s;
+        while let Some(c) = rest.chars().next() {
+            if c == '&' {
+                let entity = ["&amp;", "&lt;", "&gt;", "&quot;", "&apos;"]
+                    .into_iter()
+                    .find(|e| rest.starts_with(e))
+                    .ok_or("bare ampersand")?;
+                out.push_str(&html_char(entity));
+                rest = &rest[entity.len()..];
+            } else {
+                if matches!(c, '<' | '>' | '"' | '\'') {
+                    return Err(format!("unescaped {}", c));
+                }
+                if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') {
+                    return Err(format!("invalid XML character {:?}", c));
+                }
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        Ok(out)
+    }
+
+    fn html_char(entity: &str) -> String {
+        match entity {
+            "&amp;" => "&",
+            "&lt;" => "<",
+            "&gt;" => ">",
+            "&quot;" => "\"",
+            _ => "'",
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn test_escape_dot_hostile_title() {
+        let title = "a\"];\n\"x\" -> \"y\" [label=\"\\l{}[]é";
+        assert_eq!(
+            escape_dot(title),
+            "a\\\"];\\n\\\"x\\\" -> \\\"y\\\" [label=\\\"\\\\l{}[]é"
+        );
+        assert_eq!(escape_dot("line\r\nbreak\u{7}"), "line\\nbreak\u{FFFD}");
+    }
+
+    #[test]
+    fn test_escape_xml_replaces_invalid_characters() {
+        assert_eq!(escape_xml("<a & 'b'>"), "&lt;a &amp; &apos;b&apos;&gt;");
+        assert_eq!(escape_xml("bell\u{7}"), "bell\u{FFFD}");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_escape_dot_stays_inside_the_string(title in any_text()) {
+            let escaped = escape_dot(&title);
+            prop_assert!(unescape_dot(&escaped).is_ok(), "{:?}", escaped);
+        }
+
+        #[test]
+        fn prop_escape_dot_round_trips_printable_text(title in "[^\\p{Cc}]*(\n[^\\p{Cc}]*)*") {
+            prop_assert_eq!(unescape_dot(&escape_dot(&title)).unwrap(), title);
+        }
+
+        #[test]
+        fn prop_escape_xml_is_well_formed(title in any_text()) {
+            let escaped = escape_xml(&title);
+            let unescaped = unescape_xml(&escaped);
+            prop_assert!(unescaped.is_ok(), "{:?}", escaped);
+            if !title.chars().any(|c| (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r')) {
+                prop_assert_eq!(unescaped.unwrap(), title);
+            }
+        }
+    }
+
+    fn any_text() -> impl Strategy<Value = String> {
+        proptest::collection::vec(any::<char>(), 0..64)
+            .prop_map(|chars| chars.into_iter().collect())
+    }
 }
@@ -0,0 +1,218 @@
+//! Readiness and cycle rules for wires already in memory.
+//!
+//! Nothing here touches the database, so these rules are available without
+//! the `sqlite` feature, e.g. in a wasm32 build behind a web dashboard or a
+//! plan editor. [`db`](crate::db) runs its cycle check through
+//! [`cycle_path`], and `wr ready` lists exactly the wires [`is_ready`]
+//! accepts, apart from claims, which aren't part of [`WireWithDeps`].
+//!
+//! ```
+//! use wr::graph;
+//! use wr::models::{DependencyInfo, Status, Wire, WireWithDeps};
+//!
+//! let schema = Wire::new("Design schema", None, 1).unwrap();
+//! let mut api = WireWithDeps::from(Wire::new("Build API", None, 0).unwrap());
+//! api.depends_on.push(DependencyInfo {
+//!     id: schema.id.clone(),
+//!     title: schema.title.clone(),
+//!     status: Status::Todo,
+//! });
+//! let wires = vec![WireWithDeps::from(schema), api];
+//!
+//! let ready: Vec<_> = graph::ready(&wires).iter().map(|w| w.title.as_str()).collect();
+//! assert_eq!(ready, ["Design schema"]);
+//!
+//! let (api, schema) = (wires[1].wire.id.as_str(), wires[0].wire.id.as_str());
+//! assert!(graph::find_cycle(&wires, schema, api).is_some());
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+
+use crate::models::{Status, Wire, WireWithDeps};
+
+/// Whether `wire` belongs in `wr ready`: it is `TODO` or `IN_PROGRESS`,
+/// every dependency is `DONE`, and no external blocker is uncleared.
+pub fn is_ready(wire: &WireWithDeps) -> bool {
+    matches!(wire.wire.status, Status::Todo | Status::InProgress)
+        && wire.depends_on.iter().all(|dep| dep.status == Status::Done)
+        && wire.blocked_on.iter().all(|b| b.cleared_at.is_some())
+}
+
+/// The ready wires among `wires`, in `wr ready` order: `IN_PROGRESS` before
+/// `TODO`, then highest priority first.
+pub fn ready(wires: &[WireWithDeps]) -> Vec<&Wire> {
+    let mut ready: Vec<&Wire> = wires
+        .iter()
+        .filter(|w| is_ready(w))
+        .map(|w| &w.wire)
+        .collect();
+    ready.sort_by_key(|w| {
+        (
+            w.status != Status::InProgress,
+            std::cmp::Reverse(w.priority),
+        )
+    });
+    ready
+}
+
+/// The cycle that making `wire_id` depend on `depends_on` would close, if
+/// any, among `wires` and their dependencies.
+///
+/// The cycle starts and ends with `wire_id`, like
+/// [`WireError::CircularDependency`](crate::models::WireError::CircularDependency).
+pub fn find_cycle(wires: &[WireWithDeps], wire_id: &str, depends_on: &str) -> Option<Vec<String>> {
+    let edges: HashMap<&str, Vec<String>> = wires
+        .iter()
+        .map(|w| {
+            let deps = w.depends_on.iter().map(|d| d.id.to_string()).collect();
+            (w.wire.id.as_str(), deps)
+        })
+        .collect();
+    cycle_path(wire_id, depends_on, |id| {
+        Ok::<_, Infallible>(edges.get(id).cloned().unwrap_or_default())
+    })
+    .unwrap_or_else(|never| match never {})
+}
+
+/// The cycle that making `wire_id` depend on `depends_on` would close, with
+/// each wire's dependencies looked up by `dependencies`.
+///
+/// Searches depth-first from `depends_on` for `wire_id`, asking for the
+/// dependencies of each wire at most once.
+///
+/// # Errors
+///
+/// Returns the first error `dependencies` returns.
+pub fn cycle_path<E>(
+    wire_id: &str,
+    depends_on: &str,
+    mut dependencies: impl FnMut(&str) -> Result<Vec<String>, E>,
+) -> Result<Option<Vec<String>>, E> {
+    // If wire depends on itself, that's a cycle
+    if wire_id == depends_on {
+        return Ok(Some(vec![wire_id.to_string(), wire_id.to_string()]));
+    }
+
+    let mut visited = HashSet::new();
+    let mut stack = vec![depends_on.to_string()];
+    let mut parent_map: HashMap<String, String> = HashMap::new();
+
+    while let Some(current) = stack.pop() {
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+
+        // If we reached the original wire, we found a cycle: walk back to
+        // `depends_on` through the wires that led here
+        if current == wire_id {
+            let mut path = vec![wire_id.to_string()];
+            let mut node = wire_id;
+            while node != depends_on {
+                match parent_map.get(node) {
+                    Some(parent) => node = parent,
+                    None => break,
+                }
+                path.push(node.to_string());
+            }
+            path.push(wire_id.to_string());
+            path.reverse();
+            return Ok(Some(path));
+        }
+
+        for dep in dependencies(&current)? {
+            if !visited.contains(&dep) {
+                parent_map.insert(dep.clone(), current.clone());
+                stack.push(dep);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DependencyInfo, ExternalBlocker};
+
+    fn wire(title: &str, status: Status, priority: i32) -> WireWithDeps {
+        let mut wire = Wire::new(title, None, priority).unwrap();
+        wire.status = status;
+        WireWithDeps::from(wire)
+    }
+
+    fn depend(wire: &mut WireWithDeps, on: &WireWithDeps) {
+        wire.depends_on.push(DependencyInfo {
+            id: on.wire.id.clone(),
+            title: on.wire.title.clone(),
+            status: on.wire.status,
+        });
+    }
+
+    #[test]
+    fn test_is_ready_needs_done_dependencies() {
+        let done = wire("Done", Status::Done, 0);
+        let cancelled = wire("Cancelled", Status::Cancelled, 0);
+        let mut a = wire("A", Status::Todo, 0);
+        depend(&mut a, &done);
+        assert!(is_ready(&a));
+
+        depend(&mut a, &cancelled);
+        assert!(!is_ready(&a));
+        assert!(!is_ready(&wire("Paused", Status::Paused, 0)));
+    }
+
+    #[test]
+    fn test_is_ready_waits_for_external_blockers() {
+        let mut a = wire("A", Status::Todo, 0);
+        a.blocked_on.push(ExternalBlocker {
+            id: 1,
+            reason: "API key".to_string(),
+            created_at: 0,
+            cleared_at: None,
+            wires: vec![a.wire.id.clone()],
+        });
+        assert!(!is_ready(&a));
+
+        a.blocked_on[0].cleared_at = Some(1);
+        assert!(is_ready(&a));
+    }
+
+    #[test]
+    fn test_ready_orders_in_progress_then_priority() {
+        let wires = vec![
+            wire("Low", Status::Todo, 0),
+            wire("High", Status::Todo, 5),
+            wire("Started", Status::InProgress, 1),
+            wire("Finished", Status::Done, 9),
+        ];
+        let titles: Vec<_> = ready(&wires).iter().map(|w| w.title.as_str()).collect();
+        assert_eq!(titles, ["Started", "High", "Low"]);
+    }
+
+    #[test]
+    fn test_find_cycle_returns_path() {
+        let a = wire("A", Status::Todo, 0);
+        let mut b = wire("B", Status::Todo, 0);
+        let mut c = wire("C", Status::Todo, 0);
+        depend(&mut b, &a);
+        depend(&mut c, &b);
+        let (a_id, b_id, c_id) = (
+            a.wire.id.to_string(),
+            b.wire.id.to_string(),
+            c.wire.id.to_string(),
+        );
+        let wires = vec![a, b, c];
+
+        assert_eq!(
+            find_cycle(&wires, &a_id, &c_id),
+            Some(vec![a_id.clone(), c_id.clone(), b_id, a_id.clone()])
+        );
+        assert_eq!(find_cycle(&wires, &c_id, &a_id), None);
+        assert_eq!(
+            find_cycle(&wires, &a_id, &a_id),
+            Some(vec![a_id.clone(), a_id])
+        );
+    }
+}
@@ -16,9 +16,21 @@
 //!
 //! ## Modules
 //!
+//! - `async_store` - `AsyncWireStore`, an async [`WireStore`](store::WireStore) for tokio
+//!   (requires the `async` feature)
+//! - [`config`] - Repository settings from `.wires/config.toml`
 //! - [`db`] - Database operations (init, open, CRUD, dependencies)
+//!   (requires the `sqlite` feature, on by default)
+//! - [`dups`] - Likely duplicate wires, by title similarity
+//! - [`edit`] - The text buffer `wr edit` opens in `$EDITOR`
+//! - [`graph`] - Readiness and cycle rules for wires in memory
 //! - [`models`] - Data structures (Wire, Status, WireWithDeps)
 //! - [`mod@format`] - Output formatting (JSON, tables, TTY detection)
+//! - [`plan`] - Plan files and built-in templates for creating wire batches
+//! - [`store`] - [`WireStore`](store::WireStore), a typed handle for embedding wires
+//!   (requires the `sqlite` feature)
+//! - [`template`] - `{var}` expansion for titles and descriptions, and `--template` output
+//! - [`time`] - Timestamps and duration parsing (`30m`, `7d`)
 //!
 //! ## Example
 //!
@@ -35,19 +47,30 @@
 //! }
 //! ```
 
+#[cfg(feature = "async")]
+pub mod async_store;
+pub mod config;
+#[cfg(feature = "sqlite")]
 pub mod db;
+pub mod dups;
+pub mod edit;
 pub mod format;
+pub mod graph;
 pub mod models;
+pub mod plan;
+#[cfg(feature = "sqlite")]
+pub mod store;
+pub mod template;
+pub mod time;
 
-use models::WireId;
-use sha2::{Digest, Sha256};
-use std::time::{SystemTime, UNIX_EPOCH};
+use models::{IdScheme, WireId};
 
-/// Generates a unique [`WireId`] from a title.
+/// Generates a unique [`WireId`] from a title, in the default
+/// [`IdScheme::Hex`] scheme.
 ///
 /// The ID is derived from a SHA-256 hash of the title combined with
 /// the current timestamp in nanoseconds, ensuring uniqueness even
-/// for identical titles.
+/// for identical titles. Use [`IdScheme::generate`] for the other schemes.
 ///
 /// # Arguments
 ///
@@ -64,17 +87,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 /// assert_eq!(id.as_str().len(), 7);
 /// ```
 pub fn generate_id(title: &str) -> WireId {
-    let timestamp_nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_nanos();
-
-    let input = format!("{}{}", title, timestamp_nanos);
-    let hash = Sha256::digest(input.as_bytes());
-    let hex = format!("{:x}", hash);
-
-    // Safe: SHA-256 hex output is always valid hex characters
-    WireId::from_trusted(hex[..7].to_string())
+    IdScheme::Hex.generate(title)
 }
 
 #[cfg(test)]
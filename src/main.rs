@@ -1,8 +1,26 @@
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{ArgAction, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::ArgValueCandidates;
+use commands::agent_help::AgentHelpFormat;
+use commands::burndown::BurndownFormat;
+use commands::completions::CompletionShell;
+use commands::doc::DocFormat;
+use commands::export::ExportFormat;
+use commands::list::Filters;
+use commands::log::LogFormat;
+use commands::ready::ReadyFilters;
 use serde_json::json;
 use std::io::IsTerminal;
-use wr::format::Format;
-use wr::models::Status;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use wr::config::ColorChoice;
+use wr::format::{Envelope, Format, ListFormat, Verbosity};
+use wr::models::{
+    Agent, Consumer, DependencyKind, EventKind, IdempotencyKey, ImportStrategy, LayoutHints,
+    PriorityArg, StatsSort, Status, Tag, WireError, WireId,
+};
+use wr::template::{Columns, OutputTemplate, TemplateVar};
+use wr::time::{parse_duration, parse_since};
 
 mod commands;
 
@@ -11,6 +29,26 @@ mod commands;
 #[command(version)]
 #[command(about = "Lightweight local task tracker optimized for AI coding agents", long_about = None)]
 struct Cli {
+    /// Repository directory to use, instead of searching upward from the current directory
+    #[arg(long, global = true, env = "WIRES_DIR", value_name = "PATH")]
+    dir: Option<PathBuf>,
+
+    /// Wrap JSON results as {"ok", "command", "args", "data"}, and errors as {"ok": false, ...}
+    #[arg(long, global = true)]
+    envelope: bool,
+
+    /// Indent JSON output across several lines
+    #[arg(long, global = true)]
+    pretty: bool,
+
+    /// Print only essential output: the ID from `new`, nothing from other changes
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log diagnostics to stderr: the database chosen and each SQL statement's timing
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -18,153 +56,1568 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a new wires repository
-    Init,
+    Init {
+        /// Recreate the database, keeping a backup of the old one
+        #[arg(long, conflicts_with = "upgrade")]
+        force: bool,
+        /// Apply pending schema migrations to an existing repository
+        #[arg(long)]
+        upgrade: bool,
+        /// Populate the new repository from a plan file or built-in template (feature, bugfix)
+        #[arg(long, value_name = "PLAN", conflicts_with = "upgrade")]
+        template: Option<String>,
+    },
     /// Create a new wire
     New {
         /// Wire title
-        title: String,
-        /// Wire description
+        #[arg(required_unless_present = "stdin")]
+        title: Option<String>,
+        /// Wire description; `-` reads it from stdin
         #[arg(short, long)]
         description: Option<String>,
-        /// Priority (default: 0)
-        #[arg(short, long, default_value = "0")]
-        priority: i32,
+        /// Read the description from this file (`-` for stdin)
+        #[arg(long, value_name = "PATH", conflicts_with = "description")]
+        description_file: Option<PathBuf>,
+        /// Priority: a number, or a level name like high, med or low
+        /// (default: 0, or `default_priority` in the config)
+        #[arg(short, long, allow_negative_numbers = true)]
+        priority: Option<PriorityArg>,
+        /// Template variable expanded into `{name}` placeholders (repeatable)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<TemplateVar>,
+        /// Use an ID allocated earlier by `wr reserve`
+        #[arg(long)]
+        id: Option<WireId>,
+        /// Retrying with the same key returns the original result instead of
+        /// creating another wire
+        #[arg(long)]
+        idempotency_key: Option<IdempotencyKey>,
+        /// Print the complete resulting wire, including dependencies
+        #[arg(long)]
+        full: bool,
+        /// Create many wires at once from JSON Lines on stdin, one
+        /// `{"title", "description", "priority", "key", "deps"}` object (or
+        /// bare title, like `"Write tests" after:"Parse input"`) per line
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "title", "description", "description_file", "priority", "vars", "id", "full"
+            ]
+        )]
+        stdin: bool,
     },
     /// List wires
     List {
-        /// Filter by status (todo, in-progress, done, cancelled)
+        #[command(flatten)]
+        filters: Filters,
+        /// Only wires created or updated since the last `--since-last` call
+        /// with the same cursor
+        #[arg(long)]
+        since_last: bool,
+        /// Cursor for --since-last, one per polling caller [default: default]
+        #[arg(long, requires = "since_last")]
+        cursor: Option<Consumer>,
+        /// Print each wire as this text, e.g. '{{id}} {{status}} {{title}}'
+        #[arg(long, conflicts_with = "format")]
+        template: Option<OutputTemplate>,
+        /// Columns for table, csv and tsv output, e.g. 'id,title,priority,age' [csv default: id,title,status,priority,tags,assignee]
+        #[arg(long, value_name = "FIELDS")]
+        columns: Option<Columns>,
+        /// Output format (json, jsonl, table, markdown, csv, tsv). Auto-detects based on TTY.
         #[arg(short, long, value_enum)]
-        status: Option<Status>,
-        /// Output format (json, table). Auto-detects based on TTY.
+        format: Option<ListFormat>,
+    },
+    /// Full-text search over titles and descriptions
+    Search {
+        /// Words to search for (all must match, as word prefixes)
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
         #[arg(short, long, value_enum)]
         format: Option<Format>,
     },
     /// Show wire details
     Show {
         /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         id: String,
-        /// Output format (json, table). Auto-detects based on TTY.
+        /// Print each wire as this text, e.g. '{{id}} {{status}} {{title}}'
+        #[arg(long, conflicts_with = "format")]
+        template: Option<OutputTemplate>,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
         #[arg(short, long, value_enum)]
         format: Option<Format>,
     },
+    /// Edit a wire's title, tags and description in $EDITOR
+    Edit {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+    },
     /// Update wire fields
     Update {
         /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         id: String,
         /// New title
         #[arg(long)]
         title: Option<String>,
-        /// New description
+        /// New description; `-` reads it from stdin
         #[arg(long)]
         description: Option<String>,
-        /// New status (todo, in-progress, done, cancelled)
+        /// Read the new description from this file (`-` for stdin)
+        #[arg(long, value_name = "PATH", conflicts_with = "description")]
+        description_file: Option<PathBuf>,
+        /// New status (todo, in-progress, paused, blocked, done, cancelled)
         #[arg(long, value_enum)]
         status: Option<Status>,
-        /// New priority
+        /// New priority: a number, or a level name like high, med or low
+        #[arg(long, allow_negative_numbers = true)]
+        priority: Option<PriorityArg>,
+        /// Print the complete resulting wire, including dependencies
         #[arg(long)]
-        priority: Option<i32>,
+        full: bool,
     },
     /// Set wire status to IN_PROGRESS
     Start {
         /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Agent starting the wire; claims it for 30m unless the agent holds a
+        /// claim already, and counts it toward the `[in_progress]` limit
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+        /// Print the complete resulting wire, including dependencies
+        #[arg(long)]
+        full: bool,
+    },
+    /// Set a wire aside (PAUSED) without finishing it
+    Pause {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Why the wire is parked, shown by `wr show` and `wr list`
+        #[arg(short, long)]
+        reason: Option<String>,
+    },
+    /// Set a PAUSED wire back to IN_PROGRESS
+    Resume {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Agent resuming the wire, for the `[in_progress]` limit in the config
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+    },
+    /// Set wire status to BLOCKED, waiting on something outside the graph
+    Block {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// What the wire is waiting for, shown by `wr blocked`, `wr show` and `wr list`
+        #[arg(short, long)]
+        reason: String,
+    },
+    /// Set a BLOCKED wire back to TODO
+    Unblock {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+    },
+    /// List BLOCKED wires with what they are waiting for
+    Blocked {
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Mark a wire as looked at, without changing it, so it no longer looks stale
+    Touch {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         id: String,
     },
+    /// Raise a wire's priority by some steps
+    Bump {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// How many steps to raise it (the sign is optional: 2 and +2 are the same)
+        #[arg(default_value_t = 1, allow_negative_numbers = true)]
+        #[arg(value_parser = commands::bump::parse_raise)]
+        steps: i32,
+    },
+    /// Lower a wire's priority by some steps
+    Drop {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// How many steps to lower it (the sign is optional: 2 and -2 are the same)
+        #[arg(default_value_t = 1, allow_negative_numbers = true)]
+        #[arg(value_parser = commands::bump::parse_lower)]
+        steps: i32,
+    },
     /// Set wire status to DONE
     Done {
         /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         id: String,
+        /// Print the complete resulting wire, including dependencies
+        #[arg(long)]
+        full: bool,
     },
     /// Set wire status to CANCELLED
     Cancel {
         /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         id: String,
+        /// Why: duplicate, obsolete, wontfix or superseded-by:<ID>
+        #[arg(short, long, value_name = "REASON")]
+        reason: Option<String>,
+        /// Print the complete resulting wire, including dependencies
+        #[arg(long)]
+        full: bool,
+    },
+    /// Make every wire that depends on one wire depend on another instead
+    Rewire {
+        /// Wire whose dependents move
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        from: String,
+        /// Wire they depend on afterwards
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        to: String,
+    },
+    /// Replace a wire: cancel it and move its dependents to the new one
+    Supersede {
+        /// Wire being replaced
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        old: String,
+        /// Wire replacing it
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        new: String,
     },
     /// Add a dependency (wire_id depends on depends_on)
     Dep {
         /// Wire ID that has the dependency
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         wire_id: String,
         /// Wire ID that it depends on
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         depends_on: String,
+        /// Kind of dependency; only blocks holds wire_id back
+        #[arg(short, long, value_enum, default_value_t = DependencyKind::Blocks)]
+        kind: DependencyKind,
+        /// Retrying with the same key returns the original result
+        #[arg(long)]
+        idempotency_key: Option<IdempotencyKey>,
+        /// Print the complete resulting wire, including dependencies
+        #[arg(long)]
+        full: bool,
     },
     /// Remove a dependency
     Undep {
         /// Wire ID that has the dependency
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         wire_id: String,
         /// Wire ID that it depends on
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         depends_on: String,
     },
+    /// Mark two wires as related, without either blocking the other
+    Link {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        wire_id: String,
+        /// Wire ID it relates to
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        related: String,
+    },
+    /// Remove a link between two related wires
+    Unlink {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        wire_id: String,
+        /// Wire ID it relates to
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        related: String,
+    },
     /// Find wires ready to work on
     Ready {
-        /// Output format (json, table). Auto-detects based on TTY.
+        #[command(flatten)]
+        filters: ReadyFilters,
+        /// Print each wire as this text, e.g. '{{id}} {{status}} {{title}}'
+        #[arg(long, conflicts_with = "format")]
+        template: Option<OutputTemplate>,
+        /// Keep running, printing the queue again whenever the database changes
+        #[arg(long, conflicts_with = "claim")]
+        watch: bool,
+        /// Claim and start the best wire (or --limit wires) for --agent, in the same transaction that picks them
+        #[arg(long, requires = "agent")]
+        claim: bool,
+        /// How long each claim lasts (e.g. 30m, 2h, 1d)
+        #[arg(long, default_value = "30m", value_parser = parse_duration, requires = "claim")]
+        ttl: Duration,
+        /// Columns for table, csv and tsv output, e.g. 'id,title,priority,age' [csv default: id,title,status,priority,tags,assignee]
+        #[arg(long, value_name = "FIELDS")]
+        columns: Option<Columns>,
+        /// Output format (json, jsonl, table, markdown, csv, tsv). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<ListFormat>,
+    },
+    /// Kanban board: wires in a column per status, side by side
+    Board {
+        /// Only wires with this tag
+        #[arg(short, long)]
+        tag: Option<Tag>,
+        /// Only wires assigned to this agent
+        #[arg(long)]
+        assignee: Option<Agent>,
+        /// Include columns for statuses hidden by `hide_statuses` in the config
+        #[arg(long)]
+        show_hidden: bool,
+        /// Board width in characters [default: $COLUMNS, or 80]
+        #[arg(long)]
+        width: Option<usize>,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
         #[arg(short, long, value_enum)]
         format: Option<Format>,
     },
+    /// Browse and edit wires in an interactive terminal interface
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Include wires with statuses hidden by `hide_statuses` in the config
+        #[arg(long)]
+        show_hidden: bool,
+    },
+    /// Start the highest-priority ready wire nobody is working on, and print it
+    Next {
+        /// Agent taking the wire; it is claimed for them
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+        /// How long the claim lasts (e.g. 30m, 2h, 1d)
+        #[arg(long, default_value = "30m", value_parser = parse_duration)]
+        ttl: Duration,
+    },
+    /// Track an epic: a wire and everything it depends on
+    Epic {
+        #[command(subcommand)]
+        action: EpicAction,
+    },
+    /// Work with plan files
+    Plan {
+        #[command(subcommand)]
+        action: PlanAction,
+    },
+    /// Show everything a wire depends on, level by level
+    Tree {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Show what the wire blocks instead
+        #[arg(long)]
+        reverse: bool,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Explain what keeps a wire out of `wr ready`
+    Why {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Agent asking; its own claims don't count against it
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Everything known about a wire in one document, for an agent's context
+    Doc {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Agent about to work on it; its own claims don't count against it
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+        /// Output format (json, markdown). Markdown on a terminal, JSON when piped.
+        #[arg(short, long, value_enum)]
+        format: Option<DocFormat>,
+    },
+    /// Show the longest chain of unfinished dependencies, first step first
+    CriticalPath {
+        /// Weigh each wire by its `estimate:<n>` tag instead of counting wires
+        #[arg(long)]
+        weighted: bool,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Find likely duplicate wires by comparing their titles
+    Dups {
+        /// Lowest title similarity to report, in percent (default from config, else 80)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(0..=100))]
+        threshold: Option<u8>,
+        /// Compare DONE and CANCELLED wires too, not only open ones
+        #[arg(long)]
+        all: bool,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Claim a wire so other agents' `wr ready` skips it
+    Claim {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Agent taking the claim
+        #[arg(long, env = "WR_AGENT")]
+        agent: Agent,
+        /// How long the claim lasts (e.g. 30m, 2h, 1d)
+        #[arg(long, default_value = "30m", value_parser = parse_duration)]
+        ttl: Duration,
+    },
+    /// Release a claimed wire
+    Release {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Only release if this agent holds the claim
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+    },
+    /// Release a wire mid-task, printing its full context for the next agent
+    Handoff {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Agent handing off; fails if another agent holds the claim
+        #[arg(long, env = "WR_AGENT")]
+        agent: Option<Agent>,
+        /// Note for the next agent: where you stopped, what to try next
+        #[arg(short, long)]
+        note: Option<String>,
+    },
+    /// Assign a wire to an agent or person, for `wr list/ready --assignee`
+    Assign {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Who the wire is for
+        agent: Agent,
+    },
+    /// Remove a wire's assignee
+    Unassign {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+    },
+    /// Block wires on a condition outside the repo (e.g. "waiting for API key")
+    BlockOn {
+        /// The condition being waited on; reuses an active blocker with the same text
+        reason: String,
+        /// Wire IDs
+        #[arg(required = true, num_args = 1..)]
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        ids: Vec<String>,
+    },
+    /// Clear an external condition, releasing every wire waiting on it
+    UnblockExt {
+        /// Blocker number (from `wr blockers`) or its exact reason
+        blocker: String,
+    },
+    /// List active external blockers
+    Blockers {
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Probe commands that must succeed for `wr ready --check-preconditions` to list a wire
+    Precondition {
+        #[command(subcommand)]
+        action: PreconditionAction,
+    },
     /// Delete a wire and its dependencies
     Rm {
         /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
         id: String,
     },
     /// Export dependency graph
+    #[command(args_conflicts_with_subcommands = true)]
     Graph {
-        /// Output format (json)
+        #[command(subcommand)]
+        action: Option<GraphAction>,
+        /// Output format (json, dot, adjacency, jsonl, mermaid)
         #[arg(short, long, default_value = "json")]
         format: String,
+        /// Include archived wires
+        #[arg(long)]
+        include_archived: bool,
+        /// Include statuses hidden by `hide_statuses` in the config
+        #[arg(long)]
+        show_hidden: bool,
+    },
+    /// Hide finished wires from `wr list` and `wr graph`
+    #[command(group = clap::ArgGroup::new("selection").required(true).multiple(true))]
+    Archive {
+        /// Wires to archive (must be DONE or CANCELLED)
+        #[arg(group = "selection", add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        ids: Vec<String>,
+        /// Archive every DONE or CANCELLED wire
+        #[arg(long, group = "selection")]
+        done: bool,
+        /// Only wires finished at least this long ago (e.g. 30d, 12h)
+        #[arg(long, value_parser = parse_duration)]
+        older_than: Option<Duration>,
+        /// Report what would be archived without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Return an archived wire to `wr list` and `wr graph`
+    Unarchive {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+    },
+    /// Prune history, archived wires and backups past the `[retention]` windows
+    Maintain {
+        /// Report what would be removed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Read and change settings in `.wires/config.toml`
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Manage wire tags
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Check that the repository is reachable, compatible and writable
+    Ping {
+        /// Also take and release the write lock, waiting up to 5s for other writers
+        #[arg(long)]
+        lock: bool,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Report this build's version, schema version and optional features
+    Capabilities {
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Print a shell script that completes wr's commands and wire IDs
+    Completions {
+        /// Shell to complete for
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
+    /// Print a compact usage contract for AI agents' system prompts
+    AgentHelp {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "text")]
+        format: AgentHelpFormat,
+    },
+    /// Count wires by status, with throughput, cycle time and blocked counts
+    Stats {
+        /// Sort rows by this column, largest first (default: status order)
+        #[arg(long, value_enum)]
+        sort: Option<StatsSort>,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Open vs done wires per day, replayed from the event log, for progress charts
+    Burndown {
+        /// First day of the series (Unix timestamp, or ago like 2w, 30d)
+        #[arg(long, default_value = "2w", value_parser = parse_since)]
+        since: i64,
+        /// Output format (json, csv, table). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<BurndownFormat>,
+    },
+    /// Backlog hygiene reports
+    #[command(group = clap::ArgGroup::new("report").required(true))]
+    Report {
+        /// TODO wires untouched for a while, grouped by tag, with suggested actions
+        #[arg(long, group = "report")]
+        stale: bool,
+        /// Estimated points vs time taken for DONE wires, by tag, epic and actor
+        #[arg(long, group = "report")]
+        accuracy: bool,
+        /// How long without updates counts as stale (e.g. 14d, 8w)
+        #[arg(long, default_value = "30d", value_parser = parse_duration)]
+        older_than: Duration,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Rescale open-wire priorities to an even spread (10, 20, 30, ...)
+    RenumberPriorities {
+        /// Gap between consecutive priorities
+        #[arg(long, default_value = "10")]
+        step: i32,
+        /// Show the changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the history of changes
+    Log {
+        /// Only events at or after this time (Unix timestamp, or ago like 2h, 7d)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<i64>,
+        /// Only events for this wire
+        #[arg(long, add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        wire: Option<String>,
+        /// Output format (json, ndjson, table). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<LogFormat>,
+    },
+    /// Print events from the history as JSON Lines, optionally waiting for new ones
+    Events {
+        /// Only events after this sequence number, to resume where a reader stopped
+        #[arg(long)]
+        after: Option<i64>,
+        /// Only events at or after this time (Unix timestamp, or ago like 2h, 7d)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<i64>,
+        /// Only events for this wire
+        #[arg(long, add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        wire: Option<String>,
+        /// Only events of this kind, e.g. created, updated, dep_added (repeatable)
+        #[arg(long = "kind", value_name = "KIND")]
+        kinds: Vec<EventKind>,
+        /// Keep running and print events as they are recorded. Without --after or
+        /// --since, starts with the next event.
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Consume the event log from an integration, with acknowledged offsets
+    Outbox {
+        #[command(subcommand)]
+        action: OutboxAction,
+    },
+    /// Reverse the most recent change(s) recorded in the history
+    Undo {
+        /// Number of changes to undo
+        #[arg(default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        count: u16,
+    },
+    /// Allocate wire IDs now and create the wires later with `wr new --id`
+    Reserve {
+        /// Number of IDs to allocate
+        #[arg(default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+        count: u16,
+    },
+    /// Export all wires and dependencies
+    Export {
+        /// Output format (json, graphml)
+        #[arg(short, long, value_enum)]
+        format: ExportFormat,
+        /// Add a manifest of SHA-256 digests that `wr import --check-manifest` checks (json only).
+        /// It catches corruption, not deliberate edits: anyone editing the file can redo it.
+        #[arg(long, alias = "sign")]
+        manifest: bool,
+    },
+    /// Run a read-only command across several repositories
+    Multi {
+        /// Repository directories, comma-separated
+        #[arg(long, env = "WR_ROOTS", value_delimiter = ',', required = true)]
+        roots: Vec<PathBuf>,
+        #[command(subcommand)]
+        action: MultiAction,
+    },
+    /// Load wires and dependencies from `wr export --format json` output
+    Import {
+        /// Snapshot file, or - for stdin
+        path: PathBuf,
+        /// Delete all existing wires first, rebuilding the database from the file
+        #[arg(long)]
+        replace: bool,
+        /// Settle wires that already exist with different content, instead of failing
+        #[arg(long, value_enum, conflicts_with = "replace")]
+        strategy: Option<ImportStrategy>,
+        /// Refuse the file unless its SHA-256 is this hex digest, obtained separately
+        /// (e.g. from `sha256sum` on the exporting machine)
+        #[arg(long, value_name = "SHA256")]
+        verify: Option<String>,
+        /// Refuse the snapshot unless it matches its own manifest from
+        /// `wr export --manifest`, naming each wire that doesn't
+        #[arg(long)]
+        check_manifest: bool,
+    },
+    /// Tools for developing wires itself
+    #[command(hide = true)]
+    Devtool {
+        #[command(subcommand)]
+        action: DevtoolAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevtoolAction {
+    /// Fill the repository with a synthetic graph for benchmarking
+    Gen {
+        /// Number of wires to create
+        #[arg(long, default_value_t = 1000)]
+        wires: usize,
+        /// Number of dependency edges between them
+        #[arg(long, default_value_t = 4000)]
+        edges: usize,
+        /// Seed for the random graph; the same seed gives the same graph
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum GraphAction {
+    /// Reconcile dependencies with a graph read from stdin
+    Apply {
+        /// Validate and report changes without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Set how a wire is drawn in DOT and Mermaid output
+    Layout {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Rank group; wires sharing one are drawn side by side (DOT only)
+        #[arg(long)]
+        rank: Option<String>,
+        /// Cluster label; wires sharing one are boxed together
+        #[arg(long)]
+        cluster: Option<String>,
+        /// Fill color: a name like `salmon` or a hex code like `#ffcc00`
+        #[arg(long, value_parser = LayoutHints::parse_color)]
+        color: Option<String>,
+        /// Remove all hints before applying the given ones
+        #[arg(long)]
+        clear: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum MultiAction {
+    /// List wires from every repository, newest first
+    List {
+        /// Filter by status (todo, in-progress, paused, blocked, done, cancelled)
+        #[arg(short, long, value_enum)]
+        status: Option<Status>,
+        /// Only show wires with this tag
+        #[arg(short, long)]
+        tag: Option<Tag>,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Ready wires from every repository, highest priority first
+    Ready {
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+}
+
+#[derive(Subcommand)]
+enum EpicAction {
+    /// Completion, status counts and ready wires for the epic's subtree
+    Progress {
+        /// Wire ID of the epic
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlanAction {
+    /// Show the wires and dependencies added, removed or changed between two plans
+    Compare {
+        /// Earlier plan file (JSON or JSON Lines) or `wr export` snapshot
+        old: PathBuf,
+        /// Later plan file or snapshot
+        new: PathBuf,
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+}
+
+#[derive(Subcommand)]
+enum OutboxAction {
+    /// Fetch events the consumer hasn't acknowledged (does not advance its position)
+    Pull {
+        /// Consumer name; registered on first pull
+        #[arg(short, long)]
+        consumer: Consumer,
+        /// Maximum number of events
+        #[arg(short = 'n', long, default_value_t = 100)]
+        limit: usize,
+    },
+    /// Mark events up to and including SEQ as handled
+    Ack {
+        /// Consumer name
+        #[arg(short, long)]
+        consumer: Consumer,
+        /// Sequence number of the last handled event
+        seq: i64,
+    },
+    /// Show every consumer's position and backlog
+    Status {
+        /// Output format (json, table). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PreconditionAction {
+    /// Attach a shell command to a wire, replacing any earlier one
+    Set {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+        /// Run with `sh -c` in the repository directory; exit status 0 means it holds
+        command: String,
+    },
+    /// Remove a wire's precondition
+    Clear {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+    },
+    /// Run a wire's precondition now and report whether it holds
+    Check {
+        /// Wire ID
+        #[arg(add = ArgValueCandidates::new(commands::completions::wire_ids))]
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print a setting (e.g. `in_progress.limit`), or all of them, defaults included
+    Get {
+        /// Setting name, dotted for settings in a section
+        key: Option<String>,
+    },
+    /// Change a setting, keeping the rest of the file as written
+    Set {
+        /// Setting name, dotted for settings in a section
+        key: String,
+        /// New value, as TOML (`true`, `10`, `["DONE"]`); bare words are strings
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TagAction {
+    /// Add a tag to a wire, or to every wire matching filters
+    Add {
+        /// Wire ID, or the tag when selecting wires with filters
+        #[arg(
+            value_name = "ID_OR_TAG",
+            add = ArgValueCandidates::new(commands::completions::wire_ids)
+        )]
+        id: String,
+        /// Tag to add
+        #[arg(value_name = "TAG")]
+        name: Option<Tag>,
+        #[command(flatten)]
+        filters: Filters,
+        /// Report which wires would change without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a tag from a wire, or from every wire matching filters
+    Rm {
+        /// Wire ID, or the tag when selecting wires with filters
+        #[arg(
+            value_name = "ID_OR_TAG",
+            add = ArgValueCandidates::new(commands::completions::wire_ids)
+        )]
+        id: String,
+        /// Tag to remove
+        #[arg(value_name = "TAG")]
+        name: Option<Tag>,
+        #[command(flatten)]
+        filters: Filters,
+        /// Report which wires would change without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List tags in use with their wire counts
+    List {
+        /// Output format (json, table, markdown). Auto-detects based on TTY.
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
     },
 }
 
+/// The subcommand path and explicitly given arguments of a parsed command line
+fn request(mut command: &clap::Command, mut matches: &ArgMatches) -> Envelope {
+    let mut path = Vec::new();
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        path.push(name);
+        command = command
+            .find_subcommand(name)
+            .expect("matched subcommands are defined");
+        matches = sub_matches;
+    }
+
+    let mut args = serde_json::Map::new();
+    for arg in command.get_arguments() {
+        let id = arg.get_id().as_str();
+        if matches!(id, "envelope" | "pretty" | "quiet" | "verbose")
+            || !matches!(
+                matches.value_source(id),
+                Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+            )
+        {
+            continue;
+        }
+        let values: Vec<_> = matches
+            .get_raw(id)
+            .into_iter()
+            .flatten()
+            .map(|v| v.to_string_lossy().into_owned())
+            .collect();
+        let value = if !arg.get_action().takes_values() {
+            json!(true)
+        } else if matches!(arg.get_action(), ArgAction::Append) || values.len() > 1 {
+            json!(values)
+        } else {
+            json!(values.into_iter().next())
+        };
+        args.insert(id.to_string(), value);
+    }
+
+    Envelope {
+        command: path.join(" "),
+        args,
+    }
+}
+
 fn main() {
-    let cli = Cli::parse();
+    // When the shell asks for completions (`COMPLETE=<shell>`), answer and exit
+    clap_complete::CompleteEnv::with_factory(Cli::command).complete();
+
+    let command = Cli::command();
+    let matches = command
+        .clone()
+        .try_get_matches()
+        .unwrap_or_else(|e| exit_usage(e));
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| exit_usage(e));
+
+    if cli.envelope {
+        wr::format::set_envelope(request(&command, &matches));
+    }
+    if cli.pretty {
+        wr::format::set_pretty(true);
+    }
+    if cli.quiet {
+        wr::format::set_verbosity(Verbosity::Quiet);
+    } else if cli.verbose {
+        wr::format::set_verbosity(Verbosity::Verbose);
+    }
+
+    // The library reads the repository from the environment, so `--dir`
+    // reaches every command and anything it opens
+    if let Some(dir) = &cli.dir {
+        std::env::set_var(wr::db::DIR_ENV, dir);
+    }
+
+    // `wr ready --cancelled-blocks` overrides the config for its output too
+    if let Commands::Ready { filters, .. } = &cli.command {
+        if let Some(cancelled_blocks) = filters.cancelled_blocks {
+            wr::format::set_cancelled_blocks(cancelled_blocks);
+        }
+    }
+
+    // Locate the repository and read its config once; commands reuse both
+    // rather than searching and reading again. `wr init` is left to set up
+    // its own repository.
+    // The path SQLite reports for the connection, so the database functions
+    // recognize the config as theirs
+    let repo = wr::db::find_db()
+        .ok()
+        .map(|db_path| db_path.canonicalize().unwrap_or(db_path));
+    let config = repo.as_deref().map(wr::config::for_db);
+    if let Some(db_path) = repo
+        .as_ref()
+        .filter(|_| !matches!(cli.command, Commands::Init { .. }))
+    {
+        wr::db::use_db(db_path.clone());
+        if let Some(Ok(config)) = &config {
+            wr::config::use_config(db_path.clone(), config.clone());
+        }
+    }
+
+    // Display settings apply to every command. Outside a repository, or with
+    // an invalid config, they keep their defaults; commands that need the
+    // config report the problem themselves.
+    if let Some(Ok(config)) = config {
+        if let Some(format) = config.format {
+            wr::format::set_terminal_default(format);
+        }
+        wr::format::set_priority_levels(config.priorities);
+        wr::format::set_cancelled_blocks(config.ready.cancelled_blocks);
+        match config.color {
+            ColorChoice::Auto => {}
+            ColorChoice::Always => owo_colors::set_override(true),
+            ColorChoice::Never => owo_colors::set_override(false),
+        }
+    }
+    if let Some(db_path) = &repo {
+        if let Err(e) = authorize(&command, &cli.command, &matches, db_path) {
+            exit_with_error(e);
+        }
+    }
 
     let result = match cli.command {
-        Commands::Init => commands::init::run(),
+        Commands::Init {
+            force,
+            upgrade,
+            template,
+        } => commands::init::run(force, upgrade, template.as_deref()),
         Commands::New {
             title,
             description,
+            description_file,
             priority,
-        } => commands::new::run(&title, description.as_deref(), priority),
-        Commands::List { status, format } => commands::list::run(status, format),
-        Commands::Show { id, format } => commands::show::run(&id, format),
+            vars,
+            id,
+            idempotency_key,
+            full,
+            stdin,
+        } => match title {
+            Some(title) if !stdin => {
+                read_description(description, description_file).and_then(|description| {
+                    commands::new::run(
+                        &title,
+                        description.as_deref(),
+                        priority.as_ref(),
+                        vars.into_iter().collect(),
+                        id.as_ref(),
+                        idempotency_key.as_ref(),
+                        full,
+                    )
+                })
+            }
+            _ => commands::new::batch(idempotency_key.as_ref()),
+        },
+        Commands::List {
+            filters,
+            since_last,
+            cursor,
+            template,
+            columns,
+            format,
+        } => {
+            let cursor =
+                since_last.then(|| cursor.unwrap_or_else(|| Consumer::new("default").unwrap()));
+            commands::list::run(
+                &filters,
+                cursor.as_ref(),
+                template.as_ref(),
+                columns.as_ref(),
+                format,
+            )
+        }
+        Commands::Search {
+            query,
+            limit,
+            format,
+        } => commands::search::run(&query.join(" "), limit, format),
+        Commands::Show {
+            id,
+            template,
+            format,
+        } => commands::show::run(&id, template.as_ref(), format),
+        Commands::Edit { id } => commands::edit::run(&id),
         Commands::Update {
             id,
             title,
             description,
+            description_file,
             status,
             priority,
-        } => commands::update::run(
-            &id,
-            title.as_deref(),
-            description.as_deref(),
-            status,
-            priority,
-        ),
-        Commands::Start { id } => commands::start::run(&id),
-        Commands::Done { id } => commands::done::run(&id),
-        Commands::Cancel { id } => commands::cancel::run(&id),
+            full,
+        } => read_description(description, description_file).and_then(|description| {
+            commands::update::run(
+                &id,
+                title.as_deref(),
+                description.as_deref(),
+                status,
+                priority.as_ref(),
+                full,
+            )
+        }),
+        Commands::Start { id, agent, full } => commands::start::run(&id, agent.as_ref(), full),
+        Commands::Pause { id, reason } => commands::pause::pause(&id, reason.as_deref()),
+        Commands::Resume { id, agent } => commands::pause::resume(&id, agent.as_ref()),
+        Commands::Block { id, reason } => commands::blocked::block(&id, &reason),
+        Commands::Unblock { id } => commands::blocked::unblock(&id),
+        Commands::Blocked { format } => commands::blocked::list(format),
+        Commands::Touch { id } => commands::touch::run(&id),
+        Commands::Bump { id, steps } => commands::bump::run(&id, steps),
+        Commands::Drop { id, steps } => commands::bump::run(&id, -steps),
+        Commands::Done { id, full } => commands::done::run(&id, full),
+        Commands::Cancel { id, reason, full } => {
+            commands::cancel::run(&id, reason.as_deref(), full)
+        }
+        Commands::Rewire { from, to } => commands::rewire::run(&from, &to),
+        Commands::Supersede { old, new } => commands::supersede::run(&old, &new),
         Commands::Dep {
             wire_id,
             depends_on,
-        } => commands::dep::run(&wire_id, &depends_on),
+            kind,
+            idempotency_key,
+            full,
+        } => commands::dep::run(&wire_id, &depends_on, kind, idempotency_key.as_ref(), full),
         Commands::Undep {
             wire_id,
             depends_on,
         } => commands::undep::run(&wire_id, &depends_on),
-        Commands::Ready { format } => commands::ready::run(format),
+        Commands::Link { wire_id, related } => commands::link::run(&wire_id, &related),
+        Commands::Unlink { wire_id, related } => commands::unlink::run(&wire_id, &related),
+        Commands::Ready {
+            filters,
+            template,
+            columns,
+            watch,
+            claim,
+            ttl,
+            format,
+        } => commands::ready::run(
+            &filters,
+            template.as_ref(),
+            columns.as_ref(),
+            claim.then_some(ttl),
+            watch,
+            format,
+        ),
+        Commands::Board {
+            tag,
+            assignee,
+            show_hidden,
+            width,
+            format,
+        } => commands::board::run(tag.as_ref(), assignee.as_ref(), show_hidden, width, format),
+        #[cfg(feature = "tui")]
+        Commands::Tui { show_hidden } => commands::tui::run(show_hidden),
+        Commands::Next { agent, ttl } => commands::next::run(agent.as_ref(), ttl),
+        Commands::Epic { action } => match action {
+            EpicAction::Progress { id, format } => commands::epic::progress(&id, format),
+        },
+        Commands::Plan { action } => match action {
+            PlanAction::Compare { old, new, format } => commands::plan::compare(&old, &new, format),
+        },
+        Commands::Tree {
+            id,
+            reverse,
+            format,
+        } => commands::tree::run(&id, reverse, format),
+        Commands::Why { id, agent, format } => commands::why::run(&id, agent.as_ref(), format),
+        Commands::Doc { id, agent, format } => commands::doc::run(&id, agent.as_ref(), format),
+        Commands::CriticalPath { weighted, format } => {
+            commands::critical_path::run(weighted, format)
+        }
+        Commands::Dups {
+            threshold,
+            all,
+            format,
+        } => commands::dups::run(threshold, all, format),
+        Commands::Claim { id, agent, ttl } => commands::claim::claim(&id, &agent, ttl),
+        Commands::Release { id, agent } => commands::claim::release(&id, agent.as_ref()),
+        Commands::Handoff { id, agent, note } => {
+            commands::handoff::run(&id, agent.as_ref(), note.as_deref())
+        }
+        Commands::Assign { id, agent } => commands::assign::assign(&id, Some(&agent)),
+        Commands::Unassign { id } => commands::assign::assign(&id, None),
+        Commands::BlockOn { reason, ids } => commands::block::block_on(&reason, &ids),
+        Commands::UnblockExt { blocker } => commands::block::unblock(&blocker),
+        Commands::Blockers { format } => commands::block::list(format),
+        Commands::Precondition { action } => match action {
+            PreconditionAction::Set { id, command } => {
+                commands::precondition::set(&id, Some(&command))
+            }
+            PreconditionAction::Clear { id } => commands::precondition::set(&id, None),
+            PreconditionAction::Check { id } => commands::precondition::check(&id),
+        },
         Commands::Rm { id } => commands::rm::run(&id),
-        Commands::Graph { format } => commands::graph::run(Some(&format)),
+        Commands::Graph {
+            action: Some(GraphAction::Apply { dry_run }),
+            ..
+        } => commands::graph::apply(dry_run),
+        Commands::Graph {
+            action:
+                Some(GraphAction::Layout {
+                    id,
+                    rank,
+                    cluster,
+                    color,
+                    clear,
+                }),
+            ..
+        } => commands::graph::layout(&id, rank, cluster, color, clear),
+        Commands::Graph {
+            action: None,
+            format,
+            include_archived,
+            show_hidden,
+        } => commands::graph::run(Some(&format), include_archived, show_hidden),
+        Commands::Archive {
+            ids,
+            done,
+            older_than,
+            dry_run,
+        } => commands::archive::archive(&ids, done, older_than, dry_run),
+        Commands::Unarchive { id } => commands::archive::unarchive(&id),
+        Commands::Maintain { dry_run } => commands::maintain::run(dry_run),
+        Commands::Config { action } => match action {
+            ConfigAction::Get { key } => commands::config::get(key.as_deref()),
+            ConfigAction::Set { key, value } => commands::config::set(&key, &value),
+        },
+        Commands::Tag { action } => match action {
+            TagAction::Add {
+                id,
+                name,
+                filters,
+                dry_run,
+            } => commands::tag::add(&id, name, &filters, dry_run),
+            TagAction::Rm {
+                id,
+                name,
+                filters,
+                dry_run,
+            } => commands::tag::remove(&id, name, &filters, dry_run),
+            TagAction::List { format } => commands::tag::list(format),
+        },
+        Commands::Ping { lock, format } => commands::ping::run(lock, format),
+        Commands::Capabilities { format } => commands::capabilities::run(format),
+        Commands::Completions { shell } => commands::completions::run(shell),
+        Commands::AgentHelp { format } => commands::agent_help::run(&Cli::command(), format),
+        Commands::Stats { sort, format } => commands::stats::run(sort, format),
+        Commands::Burndown { since, format } => commands::burndown::run(since, format),
+        Commands::Report {
+            accuracy: true,
+            format,
+            ..
+        } => commands::report::accuracy(format),
+        Commands::Report {
+            older_than, format, ..
+        } => commands::report::stale(older_than, format),
+        Commands::RenumberPriorities { step, dry_run } => {
+            commands::renumber_priorities::run(step, dry_run)
+        }
+        Commands::Log {
+            since,
+            wire,
+            format,
+        } => commands::log::run(since, wire.as_deref(), format),
+        Commands::Events {
+            after,
+            since,
+            wire,
+            kinds,
+            follow,
+        } => commands::events::run(after, since, wire.as_deref(), &kinds, follow),
+        Commands::Outbox { action } => match action {
+            OutboxAction::Pull { consumer, limit } => commands::outbox::pull(&consumer, limit),
+            OutboxAction::Ack { consumer, seq } => commands::outbox::ack(&consumer, seq),
+            OutboxAction::Status { format } => commands::outbox::status(format),
+        },
+        Commands::Undo { count } => commands::undo::run(count.into()),
+        Commands::Reserve { count } => commands::reserve::run(count.into()),
+        Commands::Export { format, manifest } => commands::export::run(format, manifest),
+        Commands::Import {
+            path,
+            replace,
+            strategy,
+            verify,
+            check_manifest,
+        } => commands::import::run(&path, replace, strategy, verify.as_deref(), check_manifest),
+        Commands::Devtool { action } => match action {
+            DevtoolAction::Gen { wires, edges, seed } => commands::devtool::gen(wires, edges, seed),
+        },
+        Commands::Multi { roots, action } => match action {
+            MultiAction::List {
+                status,
+                tag,
+                format,
+            } => commands::multi::list(&roots, status, tag.as_ref(), format),
+            MultiAction::Ready { format } => commands::multi::ready(&roots, format),
+        },
     };
 
     if let Err(e) = result {
-        let error_msg = e.to_string();
+        exit_with_error(e);
+    }
+}
 
-        if std::io::stderr().is_terminal() {
-            // Human-friendly output for interactive use
-            eprintln!("Error: {}", error_msg);
-        } else {
-            // JSON output for programmatic use
-            let error_json = json!({ "error": error_msg });
-            eprintln!("{}", serde_json::to_string(&error_json).unwrap());
+/// Reports `e` on stderr, as JSON unless a person is watching, and exits
+/// with its status.
+fn exit_with_error(e: anyhow::Error) -> ! {
+    let error_msg = e.to_string();
+    let wire_error = e.downcast_ref::<WireError>();
+
+    if std::io::stderr().is_terminal() && wr::format::envelope().is_none() {
+        // Human-friendly output for interactive use
+        eprintln!("Error: {}", error_msg);
+    } else {
+        // JSON output for programmatic use
+        let code = wire_error.map_or("error", WireError::code);
+        let mut error_json = json!({ "error": error_msg, "code": code });
+        // Attach structured context so callers don't need a follow-up lookup
+        match wire_error {
+            Some(WireError::CircularDependency(cycle)) => error_json["cycle"] = json!(cycle),
+            Some(WireError::AmbiguousWire { candidates, .. }) => {
+                error_json["candidates"] = json!(candidates)
+            }
+            Some(WireError::AlreadyClaimed(lease)) => error_json["lease"] = json!(lease),
+            Some(WireError::InProgressLimit { wires, .. }) => {
+                error_json["in_progress"] = json!(wires)
+            }
+            Some(WireError::InvalidTransition {
+                from, to, allowed, ..
+            }) => {
+                error_json["from"] = json!(from);
+                error_json["to"] = json!(to);
+                error_json["allowed"] = json!(allowed);
+            }
+            Some(WireError::ImportConflicts(conflicts)) => {
+                error_json["conflicts"] = json!(conflicts)
+            }
+            Some(WireError::SnapshotUnverified(problems) | WireError::Unhealthy(problems)) => {
+                error_json["problems"] = json!(problems)
+            }
+            Some(WireError::PermissionDenied { allowed, .. }) => {
+                error_json["allowed"] = json!(allowed)
+            }
+            _ => {}
         }
+        let output = match (wr::format::envelope(), error_json.as_object()) {
+            (Some(envelope), Some(error)) => serde_json::to_string(&envelope.wrap_error(error)),
+            _ => serde_json::to_string(&error_json),
+        };
+        eprintln!("{}", output.unwrap());
+    }
+
+    std::process::exit(wire_error.map_or(1, WireError::exit_code));
+}
 
-        std::process::exit(1);
+impl Commands {
+    /// Whether the command only reads the repository, which `[permissions]`
+    /// never restricts. Every command is listed, so a new one has to say.
+    fn is_read_only(&self) -> bool {
+        match self {
+            // `wr ready --claim` reads like `wr ready` but claims and starts wires
+            Commands::Ready { claim, .. } => !claim,
+            Commands::Epic { action } => match action {
+                EpicAction::Progress { .. } => true,
+            },
+            Commands::Plan { action } => match action {
+                PlanAction::Compare { .. } => true,
+            },
+            Commands::Precondition { action } => match action {
+                PreconditionAction::Check { .. } => true,
+                PreconditionAction::Set { .. } | PreconditionAction::Clear { .. } => false,
+            },
+            Commands::Graph { action, .. } => match action {
+                None => true,
+                Some(GraphAction::Apply { .. } | GraphAction::Layout { .. }) => false,
+            },
+            Commands::Config { action } => match action {
+                ConfigAction::Get { .. } => true,
+                ConfigAction::Set { .. } => false,
+            },
+            Commands::Tag { action } => match action {
+                TagAction::List { .. } => true,
+                TagAction::Add { .. } | TagAction::Rm { .. } => false,
+            },
+            Commands::Outbox { action } => match action {
+                OutboxAction::Pull { .. } | OutboxAction::Status { .. } => true,
+                OutboxAction::Ack { .. } => false,
+            },
+            Commands::Multi { action, .. } => match action {
+                MultiAction::List { .. } | MultiAction::Ready { .. } => true,
+            },
+            Commands::Devtool { action } => match action {
+                DevtoolAction::Gen { .. } => false,
+            },
+            #[cfg(feature = "tui")]
+            Commands::Tui { .. } => false,
+            Commands::AgentHelp { .. }
+            | Commands::Blocked { .. }
+            | Commands::Blockers { .. }
+            | Commands::Board { .. }
+            | Commands::Burndown { .. }
+            | Commands::Capabilities { .. }
+            | Commands::Completions { .. }
+            | Commands::CriticalPath { .. }
+            | Commands::Doc { .. }
+            | Commands::Dups { .. }
+            | Commands::Events { .. }
+            | Commands::Export { .. }
+            | Commands::List { .. }
+            | Commands::Log { .. }
+            | Commands::Ping { .. }
+            | Commands::Report { .. }
+            | Commands::Search { .. }
+            | Commands::Show { .. }
+            | Commands::Stats { .. }
+            | Commands::Tree { .. }
+            | Commands::Why { .. } => true,
+            Commands::Init { .. }
+            | Commands::New { .. }
+            | Commands::Edit { .. }
+            | Commands::Update { .. }
+            | Commands::Start { .. }
+            | Commands::Pause { .. }
+            | Commands::Resume { .. }
+            | Commands::Block { .. }
+            | Commands::Unblock { .. }
+            | Commands::Touch { .. }
+            | Commands::Bump { .. }
+            | Commands::Drop { .. }
+            | Commands::Done { .. }
+            | Commands::Cancel { .. }
+            | Commands::Rewire { .. }
+            | Commands::Supersede { .. }
+            | Commands::Dep { .. }
+            | Commands::Undep { .. }
+            | Commands::Link { .. }
+            | Commands::Unlink { .. }
+            | Commands::Next { .. }
+            | Commands::Claim { .. }
+            | Commands::Release { .. }
+            | Commands::Handoff { .. }
+            | Commands::Assign { .. }
+            | Commands::Unassign { .. }
+            | Commands::BlockOn { .. }
+            | Commands::UnblockExt { .. }
+            | Commands::Rm { .. }
+            | Commands::Archive { .. }
+            | Commands::Unarchive { .. }
+            | Commands::Maintain { .. }
+            | Commands::RenumberPriorities { .. }
+            | Commands::Undo { .. }
+            | Commands::Reserve { .. }
+            | Commands::Import { .. } => false,
+        }
     }
 }
+
+/// Checks the `[permissions]` of the repository at `db_path` let the caller
+/// run the parsed command, through [`wr::db::authorize_db`]. The caller is
+/// the command's `--agent` (which defaults to `WR_AGENT`), or `WR_AGENT`
+/// for commands without one.
+///
+/// A config that can't be loaded refuses every command but reads, so a
+/// mistake in it never turns the rules off.
+fn authorize(
+    command: &clap::Command,
+    parsed: &Commands,
+    matches: &ArgMatches,
+    db_path: &Path,
+) -> anyhow::Result<()> {
+    let permissions = match wr::config::for_db(db_path) {
+        Ok(config) => config.permissions,
+        Err(_) if parsed.is_read_only() => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for (actor, operation) in permissions.rules() {
+        let known = operation == wr::config::Permissions::ANYONE
+            || operation
+                .split(' ')
+                .try_fold(command, |command, name| command.find_subcommand(name))
+                .is_some();
+        if !known {
+            anyhow::bail!(
+                "Unknown command '{}' for '{}' in [permissions]",
+                operation,
+                actor
+            );
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut leaf = matches;
+    while let Some((name, sub_matches)) = leaf.subcommand() {
+        path.push(name);
+        leaf = sub_matches;
+    }
+    let operation = path.join(" ");
+    let operations = match parsed {
+        _ if parsed.is_read_only() => return Ok(()),
+        Commands::Ready { .. } => vec!["claim", "start"],
+        _ => vec![operation.as_str()],
+    };
+
+    let actor = match leaf.try_get_raw("agent") {
+        Ok(agent) => agent
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|agent| agent.to_string_lossy().into_owned()),
+        Err(_) => std::env::var("WR_AGENT").ok(),
+    }
+    .filter(|actor| !actor.is_empty());
+    for operation in operations {
+        wr::db::authorize_db(db_path, actor.as_deref(), operation)?;
+    }
+    Ok(())
+}
+
+/// The description given with `--description` or `--description-file`,
+/// read from stdin for `-` or from the file. Text is kept as written, only
+/// trailing line breaks are dropped.
+fn read_description(
+    description: Option<String>,
+    file: Option<PathBuf>,
+) -> anyhow::Result<Option<String>> {
+    use anyhow::Context;
+    use std::io::Read;
+
+    // `None` reads stdin
+    let path = match (description, file) {
+        (Some(description), _) if description != "-" => return Ok(Some(description)),
+        (Some(_), _) => None,
+        (None, Some(file)) => Some(file).filter(|file| file.as_os_str() != "-"),
+        (None, None) => return Ok(None),
+    };
+    let text = match path {
+        Some(path) => std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read description from {}", path.display()))?,
+        None => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .context("Failed to read description from stdin")?;
+            text
+        }
+    };
+    Ok(Some(text.trim_end_matches(['\n', '\r']).to_string()))
+}
+
+/// Exit status for invalid command-line usage (`EX_USAGE` in sysexits.h),
+/// kept apart from the per-error statuses of [`WireError::exit_code`]
+const EXIT_USAGE: i32 = 64;
+
+/// Prints a command-line parsing error, or `--help`/`--version` output, and exits
+fn exit_usage(e: clap::Error) -> ! {
+    let _ = e.print();
+    std::process::exit(if e.use_stderr() { EXIT_USAGE } else { 0 })
+}
@@ -1,26 +1,34 @@
 //! Data models for wires.
 //!
 //! This module contains the core data structures used throughout the application:
-//! - [`WireId`] - A validated 7-character hexadecimal wire identifier
+//! - [`WireId`] - A validated wire identifier, in one of the [`IdScheme`]s
+//! - [`Tag`] - A validated label for categorizing wires
 //! - [`Status`] - Task status enum (TODO, IN_PROGRESS, DONE, CANCELLED)
 //! - [`Wire`] - A task/item with title, description, status, and priority
 //! - [`WireWithDeps`] - A wire with its dependency relationships
 //! - [`DependencyInfo`] - Summary info about a dependent wire
 
 use clap::ValueEnum;
+#[cfg(feature = "sqlite")]
 use rusqlite::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-/// A validated 7-character hexadecimal wire identifier.
+/// A validated wire identifier.
 ///
-/// Wire IDs are generated from a hash of the title and timestamp,
-/// providing uniqueness while being short enough for human use.
+/// Wire IDs come in one of the [`IdScheme`]s a repository can be configured
+/// with: 7 hex characters (the default, short enough for human use), a
+/// 26-character ULID or a 36-character UUIDv7. The time-based schemes sort
+/// by creation time and are globally unique, for syncing with other systems.
 ///
 /// # Validation
 ///
-/// A valid WireId must be exactly 7 lowercase hexadecimal characters (0-9, a-f).
+/// Any of the three forms is accepted whatever the repository's scheme, so
+/// existing IDs keep working after the scheme changes, and imported wires
+/// keep the IDs they were exported with. The scheme only decides the IDs
+/// the repository generates: for new wires, plans and `wr reserve`. IDs
+/// are normalized: hex and UUIDs to lowercase, ULIDs to uppercase.
 ///
 /// # Example
 ///
@@ -30,11 +38,14 @@ use std::str::FromStr;
 /// let id = WireId::new("a1b2c3d").unwrap();
 /// assert_eq!(id.as_str(), "a1b2c3d");
 ///
+/// let ulid = WireId::new("01hq3v5k8m2n4p6r8t0w2y4a6c").unwrap();
+/// assert_eq!(ulid.as_str(), "01HQ3V5K8M2N4P6R8T0W2Y4A6C");
+///
 /// // Invalid IDs are rejected
 /// assert!(WireId::new("too_long_id").is_err());
 /// assert!(WireId::new("abc").is_err());
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WireId(String);
 
 impl WireId {
@@ -42,22 +53,29 @@ impl WireId {
     ///
     /// # Errors
     ///
-    /// Returns an error if the string is not exactly 7 lowercase hex characters.
+    /// Returns an error if the string is not 7 hex characters, a ULID or a
+    /// UUIDv7.
     pub fn new(s: &str) -> Result<Self, WireIdError> {
-        if s.len() != 7 {
-            return Err(WireIdError::InvalidLength(s.len()));
-        }
-        if !s.chars().all(|c| c.is_ascii_hexdigit()) {
+        let scheme = match s.len() {
+            7 => IdScheme::Hex,
+            26 => IdScheme::Ulid,
+            36 => IdScheme::Uuid7,
+            len => return Err(WireIdError::InvalidLength(len)),
+        };
+        if !scheme.matches(s) {
             return Err(WireIdError::InvalidCharacters);
         }
-        Ok(WireId(s.to_lowercase()))
+        Ok(WireId(match scheme {
+            IdScheme::Ulid => s.to_uppercase(),
+            IdScheme::Hex | IdScheme::Uuid7 => s.to_lowercase(),
+        }))
     }
 
     /// Creates a WireId without validation.
     ///
     /// # Safety
     ///
-    /// The caller must ensure the string is a valid 7-character hex string.
+    /// The caller must ensure the string is a valid, normalized ID.
     /// This is intended for use when reading from the database where data
     /// is known to be valid.
     pub(crate) fn from_trusted(s: String) -> Self {
@@ -70,6 +88,14 @@ impl WireId {
     }
 }
 
+impl FromStr for WireId {
+    type Err = WireIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WireId::new(s)
+    }
+}
+
 impl fmt::Display for WireId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -98,27 +124,145 @@ impl<'de> Deserialize<'de> for WireId {
 /// Error type for invalid wire IDs.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WireIdError {
-    /// ID is not exactly 7 characters
+    /// ID is not 7, 26 or 36 characters
     InvalidLength(usize),
-    /// ID contains non-hexadecimal characters
+    /// ID has the length of one scheme but not its characters
     InvalidCharacters,
 }
 
 impl fmt::Display for WireIdError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            WireIdError::InvalidLength(len) => {
-                write!(f, "Wire ID must be 7 characters, got {}", len)
+            WireIdError::InvalidLength(len) => write!(
+                f,
+                "Wire ID must be 7 hex characters, a 26-character ULID or a 36-character UUIDv7, got {} characters",
+                len
+            ),
+            WireIdError::InvalidCharacters => write!(
+                f,
+                "Wire ID must be 7 hex characters, a ULID (Crockford base32) or a UUIDv7"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WireIdError {}
+
+/// How new wire IDs are generated, set by `id_scheme` in the repository
+/// config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdScheme {
+    /// 7 hex characters from a hash of the title and time
+    #[default]
+    Hex,
+    /// A ULID: 26 Crockford base32 characters, sortable by creation time
+    Ulid,
+    /// A UUIDv7: time-ordered, in the usual hyphenated form
+    Uuid7,
+}
+
+/// The ULID alphabet, which leaves out I, L, O and U
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+impl IdScheme {
+    /// Generates a fresh ID, mixing `seed` (usually the title) into its
+    /// random part.
+    ///
+    /// ```
+    /// use wr::models::{IdScheme, WireId};
+    ///
+    /// let id = IdScheme::Ulid.generate("Fix parser");
+    /// assert_eq!(id.as_str().len(), 26);
+    /// assert_eq!(WireId::new(id.as_str()).unwrap(), id);
+    /// ```
+    pub fn generate(self, seed: &str) -> WireId {
+        use sha2::{Digest, Sha256};
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        // Separates IDs generated within the same clock tick
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards");
+        let input = format!(
+            "{}{}{}",
+            seed,
+            now.as_nanos(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let hash = Sha256::digest(input.as_bytes());
+
+        // 48-bit millisecond timestamp, then 80 bits of hash
+        let mut random = [0u8; 16];
+        random.copy_from_slice(&hash[..16]);
+        let millis = now.as_millis() & ((1 << 48) - 1);
+        let bits = (millis << 80) | (u128::from_be_bytes(random) >> 48);
+
+        // Safe: every branch produces a well-formed ID of its scheme
+        WireId::from_trusted(match self {
+            IdScheme::Hex => format!("{:x}", hash)[..7].to_string(),
+            IdScheme::Ulid => (0..26)
+                .map(|i| CROCKFORD[((bits >> (125 - 5 * i)) & 0x1f) as usize] as char)
+                .collect(),
+            IdScheme::Uuid7 => {
+                let version: u128 = 0x7 << 76;
+                let variant: u128 = 0b10 << 62;
+                let bits = (bits & !(0xf << 76) & !(0b11 << 62)) | version | variant;
+                let hex = format!("{:032x}", bits);
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                )
+            }
+        })
+    }
+
+    /// Whether `id` is in this scheme's form, in either case.
+    pub fn matches(self, id: &str) -> bool {
+        match self {
+            IdScheme::Hex => id.len() == 7 && id.chars().all(|c| c.is_ascii_hexdigit()),
+            IdScheme::Ulid => {
+                // 26 base32 digits hold 130 bits; a ULID is 128, so the first is at most 7
+                id.len() == 26
+                    && id.starts_with(|c: char| ('0'..='7').contains(&c))
+                    && id
+                        .bytes()
+                        .all(|b| CROCKFORD.contains(&b.to_ascii_uppercase()))
             }
-            WireIdError::InvalidCharacters => {
-                write!(f, "Wire ID must contain only hexadecimal characters")
+            IdScheme::Uuid7 => {
+                id.len() == 36
+                    && id.char_indices().all(|(i, c)| match i {
+                        8 | 13 | 18 | 23 => c == '-',
+                        _ => c.is_ascii_hexdigit(),
+                    })
+                    && id.as_bytes()[14] == b'7'
+                    && matches!(
+                        id.as_bytes()[19].to_ascii_lowercase(),
+                        b'8' | b'9' | b'a' | b'b'
+                    )
             }
         }
     }
 }
 
-impl std::error::Error for WireIdError {}
+impl fmt::Display for IdScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IdScheme::Hex => "hex",
+            IdScheme::Ulid => "ulid",
+            IdScheme::Uuid7 => "uuid7",
+        })
+    }
+}
 
+#[cfg(feature = "sqlite")]
 impl FromSql for WireId {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         let s = value.as_str()?;
@@ -127,286 +271,2009 @@ impl FromSql for WireId {
     }
 }
 
+#[cfg(feature = "sqlite")]
 impl ToSql for WireId {
     fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
         Ok(ToSqlOutput::Borrowed(ValueRef::Text(self.0.as_bytes())))
     }
 }
 
-/// Task status values.
+/// A validated label used to categorize wires (e.g. `backend`, `tests`).
 ///
-/// Wires progress through these states:
-/// - `Todo` - Not yet started
-/// - `InProgress` - Currently being worked on
-/// - `Done` - Completed successfully
-/// - `Cancelled` - Abandoned or no longer needed
+/// # Validation
 ///
-/// # Serialization
+/// Tags are normalized to lowercase and must be 1-64 characters drawn from
+/// ASCII letters, digits, and `-`, `_`, `.`, `:`, `/`.
 ///
-/// Statuses serialize as uppercase strings: `"TODO"`, `"IN_PROGRESS"`, `"DONE"`, `"CANCELLED"`.
+/// # Example
 ///
-/// # CLI Usage
+/// ```
+/// use wr::models::Tag;
 ///
-/// Implements [`ValueEnum`] for use with clap. Accepts case-insensitive values.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
-pub enum Status {
-    #[serde(rename = "TODO")]
-    #[value(alias = "TODO")]
-    Todo,
-    #[serde(rename = "IN_PROGRESS")]
-    #[value(alias = "IN_PROGRESS")]
-    InProgress,
-    #[serde(rename = "DONE")]
-    #[value(alias = "DONE")]
-    Done,
-    #[serde(rename = "CANCELLED")]
-    #[value(alias = "CANCELLED")]
-    Cancelled,
-}
+/// let tag = Tag::new("Backend").unwrap();
+/// assert_eq!(tag.as_str(), "backend");
+///
+/// assert!(Tag::new("two words").is_err());
+/// assert!(Tag::new("").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tag(String);
 
-impl Status {
-    /// Returns the string representation of the status.
+/// Maximum length of a tag in characters
+const MAX_TAG_LEN: usize = 64;
+
+impl Tag {
+    /// Creates a new Tag from a string, validating and lowercasing it.
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```
-    /// use wr::models::Status;
-    /// assert_eq!(Status::InProgress.as_str(), "IN_PROGRESS");
-    /// ```
+    /// Returns an error if the tag is empty, too long, or contains
+    /// characters outside the allowed set.
+    pub fn new(s: &str) -> Result<Self, TagError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(TagError::Empty);
+        }
+        if s.len() > MAX_TAG_LEN {
+            return Err(TagError::TooLong(s.len()));
+        }
+        if !s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '/'))
+        {
+            return Err(TagError::InvalidCharacters(s.to_string()));
+        }
+        Ok(Tag(s.to_ascii_lowercase()))
+    }
+
+    /// Returns the tag as a string slice.
     pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Tag {
+    type Err = TagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Tag::new(s)
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Tag::new(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ToSql for Tag {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(self.0.as_bytes())))
+    }
+}
+
+/// Error type for invalid tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagError {
+    /// Tag is empty or whitespace
+    Empty,
+    /// Tag exceeds the maximum length
+    TooLong(usize),
+    /// Tag contains characters outside the allowed set
+    InvalidCharacters(String),
+}
+
+impl fmt::Display for TagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Status::Todo => "TODO",
-            Status::InProgress => "IN_PROGRESS",
-            Status::Done => "DONE",
-            Status::Cancelled => "CANCELLED",
+            TagError::Empty => write!(f, "Tag cannot be empty"),
+            TagError::TooLong(len) => {
+                write!(
+                    f,
+                    "Tag must be at most {} characters, got {}",
+                    MAX_TAG_LEN, len
+                )
+            }
+            TagError::InvalidCharacters(tag) => write!(
+                f,
+                "Invalid tag '{}': use letters, digits, '-', '_', '.', ':' or '/'",
+                tag
+            ),
         }
     }
+}
 
-    /// Returns whether this status blocks dependent wires.
-    ///
-    /// A dependency is considered blocking if it's not yet complete
-    /// (Done) and hasn't been abandoned (Cancelled).
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use wr::models::Status;
-    /// assert!(Status::Todo.is_blocking());
-    /// assert!(Status::InProgress.is_blocking());
-    /// assert!(!Status::Done.is_blocking());
-    /// assert!(!Status::Cancelled.is_blocking());
-    /// ```
-    pub fn is_blocking(&self) -> bool {
-        matches!(self, Status::Todo | Status::InProgress)
-    }
+impl std::error::Error for TagError {}
 
-    /// Returns the Unicode symbol used to represent this status.
-    ///
-    /// # Symbols
-    ///
-    /// - `✓` (check mark) for Done
-    /// - `●` (filled circle) for InProgress
-    /// - `○` (empty circle) for Todo
-    /// - `✗` (x mark) for Cancelled
+/// The name an agent uses when claiming wires (e.g. `claude-1`, `ci@build-42`).
+///
+/// # Validation
+///
+/// Agent names are case-sensitive and must be 1-64 characters drawn from
+/// ASCII letters, digits, and `-`, `_`, `.`, `:`, `@`, `/`.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::Agent;
+///
+/// let agent = Agent::new("claude-1").unwrap();
+/// assert_eq!(agent.as_str(), "claude-1");
+///
+/// assert!(Agent::new("two words").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Agent(String);
+
+/// Maximum length of an agent name in characters
+const MAX_AGENT_LEN: usize = 64;
+
+impl Agent {
+    /// Creates a new Agent name, validating it.
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```
-    /// use wr::models::Status;
-    /// assert_eq!(Status::Done.symbol(), "✓");
-    /// assert_eq!(Status::InProgress.symbol(), "●");
-    /// assert_eq!(Status::Todo.symbol(), "○");
-    /// assert_eq!(Status::Cancelled.symbol(), "✗");
-    /// ```
-    pub fn symbol(&self) -> &'static str {
-        match self {
-            Status::Done => "✓",
-            Status::InProgress => "●",
-            Status::Todo => "○",
-            Status::Cancelled => "✗",
+    /// Returns an error if the name is empty, too long, or contains
+    /// characters outside the allowed set.
+    pub fn new(s: &str) -> Result<Self, AgentError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(AgentError::Empty);
+        }
+        if s.len() > MAX_AGENT_LEN {
+            return Err(AgentError::TooLong(s.len()));
+        }
+        if !s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '@' | '/'))
+        {
+            return Err(AgentError::InvalidCharacters(s.to_string()));
         }
+        Ok(Agent(s.to_string()))
+    }
+
+    /// Returns the agent name as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
-impl FromStr for Status {
-    type Err = String;
+impl fmt::Display for Agent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Agent {
+    type Err = AgentError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "TODO" => Ok(Status::Todo),
-            "IN_PROGRESS" => Ok(Status::InProgress),
-            "DONE" => Ok(Status::Done),
-            "CANCELLED" => Ok(Status::Cancelled),
-            _ => Err(format!("Invalid status: {}", s)),
+        Agent::new(s)
+    }
+}
+
+impl Serialize for Agent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Agent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Agent::new(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ToSql for Agent {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(self.0.as_bytes())))
+    }
+}
+
+/// Error type for invalid agent names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentError {
+    /// Name is empty or whitespace
+    Empty,
+    /// Name exceeds the maximum length
+    TooLong(usize),
+    /// Name contains characters outside the allowed set
+    InvalidCharacters(String),
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentError::Empty => write!(f, "Agent name cannot be empty"),
+            AgentError::TooLong(len) => {
+                write!(
+                    f,
+                    "Agent name must be at most {} characters, got {}",
+                    MAX_AGENT_LEN, len
+                )
+            }
+            AgentError::InvalidCharacters(name) => write!(
+                f,
+                "Invalid agent name '{}': use letters, digits, '-', '_', '.', ':', '@' or '/'",
+                name
+            ),
         }
     }
 }
 
-/// A wire (task/item) in the tracker.
-///
-/// Wires are the fundamental unit of work tracking. Each wire has:
-/// - A unique 7-character hex ID
-/// - A title describing the task
-/// - An optional detailed description
-/// - A status indicating progress
-/// - Timestamps for creation and last update
-/// - A priority for ordering (higher = more important)
-///
-/// # Construction
+impl std::error::Error for AgentError {}
+
+/// A caller-chosen key that makes a mutating command safe to retry.
 ///
-/// Use [`Wire::new`] to create a new wire with automatic ID generation
-/// and timestamp initialization. For reading from database, fields are
-/// public to allow direct construction.
+/// Keys are opaque strings of 1-128 printable ASCII characters; a UUID is
+/// a good choice.
 ///
 /// # Example
 ///
 /// ```
-/// use wr::models::Wire;
+/// use wr::models::IdempotencyKey;
 ///
-/// let wire = Wire::new("Implement feature X", None, 0).unwrap();
-/// assert!(!wire.id.as_str().is_empty());
+/// let key = IdempotencyKey::new("6f1c0b1e-1f4c-4f0e-9a59-3c1f8f0c2b7d").unwrap();
+/// assert_eq!(key.as_str().len(), 36);
+///
+/// assert!(IdempotencyKey::new("").is_err());
+/// assert!(IdempotencyKey::new("has space").is_err());
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Wire {
-    /// Unique 7-character hexadecimal identifier
-    pub id: WireId,
-    /// Short description of the task
-    pub title: String,
-    /// Optional detailed description
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    /// Current status of the wire
-    pub status: Status,
-    /// Unix timestamp when the wire was created
-    pub created_at: i64,
-    /// Unix timestamp when the wire was last updated
-    pub updated_at: i64,
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+/// Maximum length of an idempotency key in characters
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+
+impl IdempotencyKey {
+    /// Creates a new key, validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is empty, longer than 128 characters, or
+    /// contains whitespace or non-ASCII characters.
+    pub fn new(s: &str) -> Result<Self, String> {
+        if s.is_empty() || s.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(format!(
+                "Idempotency key must be 1-{} characters",
+                MAX_IDEMPOTENCY_KEY_LEN
+            ));
+        }
+        if !s.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(format!(
+                "Invalid idempotency key '{}': use printable ASCII without spaces",
+                s
+            ));
+        }
+        Ok(IdempotencyKey(s.to_string()))
+    }
+
+    /// Returns the key as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for IdempotencyKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IdempotencyKey::new(s)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ToSql for IdempotencyKey {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(self.0.as_bytes())))
+    }
+}
+
+/// The name an integration uses to consume the outbox (e.g. `slack-bridge`),
+/// or a poller uses for its `wr list --since-last` cursor.
+///
+/// Names are 1-64 characters drawn from ASCII letters, digits, and
+/// `-`, `_`, `.`.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::Consumer;
+///
+/// assert!(Consumer::new("slack-bridge").is_ok());
+/// assert!(Consumer::new("no spaces").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Consumer(String);
+
+/// Maximum length of a consumer name in characters
+const MAX_CONSUMER_LEN: usize = 64;
+
+impl Consumer {
+    /// Creates a new consumer name, validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is empty, too long, or contains
+    /// characters outside the allowed set.
+    pub fn new(s: &str) -> Result<Self, String> {
+        if s.is_empty()
+            || s.len() > MAX_CONSUMER_LEN
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(format!(
+                "Invalid consumer '{}': use 1-{} letters, digits, '-', '_' or '.'",
+                s, MAX_CONSUMER_LEN
+            ));
+        }
+        Ok(Consumer(s.to_string()))
+    }
+
+    /// Returns the consumer name as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Consumer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Consumer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Consumer::new(s)
+    }
+}
+
+impl Serialize for Consumer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ToSql for Consumer {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(self.0.as_bytes())))
+    }
+}
+
+/// Task status values.
+///
+/// Wires progress through these states:
+/// - `Todo` - Not yet started
+/// - `InProgress` - Currently being worked on
+/// - `Paused` - Started, then set aside to work on something else
+/// - `Blocked` - Can't go on until something outside the graph happens
+/// - `Done` - Completed successfully
+/// - `Cancelled` - Abandoned or no longer needed
+///
+/// # Serialization
+///
+/// Statuses serialize as uppercase strings: `"TODO"`, `"IN_PROGRESS"`, `"PAUSED"`,
+/// `"BLOCKED"`, `"DONE"`, `"CANCELLED"`.
+///
+/// # CLI Usage
+///
+/// Implements [`ValueEnum`] for use with clap. Accepts case-insensitive values.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ValueEnum,
+)]
+pub enum Status {
+    #[serde(rename = "TODO")]
+    #[value(alias = "TODO")]
+    Todo,
+    #[serde(rename = "IN_PROGRESS")]
+    #[value(alias = "IN_PROGRESS")]
+    InProgress,
+    #[serde(rename = "PAUSED")]
+    #[value(alias = "PAUSED")]
+    Paused,
+    #[serde(rename = "BLOCKED")]
+    #[value(alias = "BLOCKED")]
+    Blocked,
+    #[serde(rename = "DONE")]
+    #[value(alias = "DONE")]
+    Done,
+    #[serde(rename = "CANCELLED")]
+    #[value(alias = "CANCELLED")]
+    Cancelled,
+}
+
+impl Status {
+    /// Returns the string representation of the status.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::models::Status;
+    /// assert_eq!(Status::InProgress.as_str(), "IN_PROGRESS");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            Status::Todo => "TODO",
+            Status::InProgress => "IN_PROGRESS",
+            Status::Paused => "PAUSED",
+            Status::Blocked => "BLOCKED",
+            Status::Done => "DONE",
+            Status::Cancelled => "CANCELLED",
+        }
+    }
+
+    /// Returns whether this status blocks dependent wires.
+    ///
+    /// A dependency is considered blocking if it's not yet complete
+    /// (Done) and hasn't been abandoned (Cancelled). Paused and blocked
+    /// wires are unfinished, so they still block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::models::Status;
+    /// assert!(Status::Todo.is_blocking());
+    /// assert!(Status::InProgress.is_blocking());
+    /// assert!(Status::Paused.is_blocking());
+    /// assert!(Status::Blocked.is_blocking());
+    /// assert!(!Status::Done.is_blocking());
+    /// assert!(!Status::Cancelled.is_blocking());
+    /// ```
+    pub fn is_blocking(&self) -> bool {
+        matches!(
+            self,
+            Status::Todo | Status::InProgress | Status::Paused | Status::Blocked
+        )
+    }
+
+    /// Returns whether a dependency with this status holds its dependents
+    /// back from being ready.
+    ///
+    /// Unfinished dependencies always do; CANCELLED ones only when
+    /// `cancelled_blocks` is set (`ready.cancelled_blocks` in the config).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::models::Status;
+    /// assert!(Status::Cancelled.blocks_dependents(true));
+    /// assert!(!Status::Cancelled.blocks_dependents(false));
+    /// assert!(Status::Paused.blocks_dependents(false));
+    /// assert!(!Status::Done.blocks_dependents(true));
+    /// ```
+    pub fn blocks_dependents(&self, cancelled_blocks: bool) -> bool {
+        self.is_blocking() || (cancelled_blocks && *self == Status::Cancelled)
+    }
+
+    /// Returns the Unicode symbol used to represent this status.
+    ///
+    /// # Symbols
+    ///
+    /// - `✓` (check mark) for Done
+    /// - `●` (filled circle) for InProgress
+    /// - `◐` (half circle) for Paused
+    /// - `⊘` (circled slash) for Blocked
+    /// - `○` (empty circle) for Todo
+    /// - `✗` (x mark) for Cancelled
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::models::Status;
+    /// assert_eq!(Status::Done.symbol(), "✓");
+    /// assert_eq!(Status::InProgress.symbol(), "●");
+    /// assert_eq!(Status::Paused.symbol(), "◐");
+    /// assert_eq!(Status::Blocked.symbol(), "⊘");
+    /// assert_eq!(Status::Todo.symbol(), "○");
+    /// assert_eq!(Status::Cancelled.symbol(), "✗");
+    /// ```
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Status::Done => "✓",
+            Status::InProgress => "●",
+            Status::Paused => "◐",
+            Status::Blocked => "⊘",
+            Status::Todo => "○",
+            Status::Cancelled => "✗",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TODO" => Ok(Status::Todo),
+            "IN_PROGRESS" => Ok(Status::InProgress),
+            "PAUSED" => Ok(Status::Paused),
+            "BLOCKED" => Ok(Status::Blocked),
+            "DONE" => Ok(Status::Done),
+            "CANCELLED" => Ok(Status::Cancelled),
+            _ => Err(format!("Invalid status: {}", s)),
+        }
+    }
+}
+
+/// Why a wire was cancelled, recorded by `wr cancel --reason`.
+///
+/// Written as `duplicate`, `obsolete`, `wontfix` or `superseded-by:<id>`.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::{CancelReason, WireId};
+///
+/// let reason: CancelReason = "superseded-by:a1b2c3d".parse().unwrap();
+/// assert_eq!(reason, CancelReason::SupersededBy(WireId::new("a1b2c3d").unwrap()));
+/// assert_eq!(reason.to_string(), "superseded-by:a1b2c3d");
+/// assert!("forgot".parse::<CancelReason>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CancelReason {
+    /// The same work is tracked by another wire
+    Duplicate,
+    /// The work no longer needs doing
+    Obsolete,
+    /// The work was decided against
+    Wontfix,
+    /// Another wire replaces this one
+    SupersededBy(WireId),
+}
+
+impl CancelReason {
+    /// Prefix of the `superseded-by:<id>` form
+    pub const SUPERSEDED_BY: &'static str = "superseded-by:";
+}
+
+impl fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CancelReason::Duplicate => write!(f, "duplicate"),
+            CancelReason::Obsolete => write!(f, "obsolete"),
+            CancelReason::Wontfix => write!(f, "wontfix"),
+            CancelReason::SupersededBy(id) => write!(f, "{}{}", Self::SUPERSEDED_BY, id),
+        }
+    }
+}
+
+impl FromStr for CancelReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "duplicate" => Ok(CancelReason::Duplicate),
+            "obsolete" => Ok(CancelReason::Obsolete),
+            "wontfix" => Ok(CancelReason::Wontfix),
+            _ => match s.strip_prefix(Self::SUPERSEDED_BY) {
+                Some(id) => WireId::new(id)
+                    .map(CancelReason::SupersededBy)
+                    .map_err(|e| format!("Invalid cancel reason '{}': {}", s, e)),
+                None => Err(format!(
+                    "Invalid cancel reason '{}' (expected duplicate, obsolete, wontfix or superseded-by:<id>)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl Serialize for CancelReason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CancelReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A priority as given to `--priority`: a number, or the name of a level
+/// from the `[priorities]` config, such as `high`.
+///
+/// Names are resolved with
+/// [`PriorityLevels::resolve`](crate::config::PriorityLevels::resolve).
+///
+/// # Example
+///
+/// ```
+/// use wr::models::PriorityArg;
+///
+/// assert_eq!("-3".parse::<PriorityArg>().unwrap(), PriorityArg::Value(-3));
+/// assert_eq!(
+///     "high".parse::<PriorityArg>().unwrap(),
+///     PriorityArg::Named("high".to_string())
+/// );
+/// assert!("Very High".parse::<PriorityArg>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriorityArg {
+    /// A raw priority number
+    Value(i32),
+    /// A named level
+    Named(String),
+}
+
+impl PriorityArg {
+    /// Whether `name` can name a priority level: a lowercase letter
+    /// followed by lowercase letters, digits, `-` or `_`.
+    pub fn is_valid_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        chars.next().is_some_and(|c| c.is_ascii_lowercase())
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+    }
+}
+
+impl FromStr for PriorityArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = s.parse() {
+            return Ok(PriorityArg::Value(value));
+        }
+        if PriorityArg::is_valid_name(s) {
+            return Ok(PriorityArg::Named(s.to_string()));
+        }
+        Err(format!(
+            "Invalid priority '{}' (expected a number or a level name like high)",
+            s
+        ))
+    }
+}
+
+/// A wire (task/item) in the tracker.
+///
+/// Wires are the fundamental unit of work tracking. Each wire has:
+/// - A unique ID
+/// - A title describing the task
+/// - An optional detailed description
+/// - A status indicating progress
+/// - Timestamps for creation and last update
+/// - A priority for ordering (higher = more important)
+/// - Tags for categorization
+///
+/// # Construction
+///
+/// Use [`Wire::new`] to create a new wire with automatic ID generation
+/// and timestamp initialization. For reading from database, fields are
+/// public to allow direct construction.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::Wire;
+///
+/// let wire = Wire::new("Implement feature X", None, 0).unwrap();
+/// assert!(!wire.id.as_str().is_empty());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Wire {
+    /// Unique identifier
+    pub id: WireId,
+    /// Short description of the task
+    pub title: String,
+    /// Optional detailed description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Current status of the wire
+    pub status: Status,
+    /// When the wire was created, in milliseconds since the Unix epoch.
+    /// JSON keeps the name `created_at`.
+    #[serde(rename = "created_at")]
+    pub created_at_ms: i64,
+    /// When the wire was last updated, in milliseconds since the Unix epoch.
+    /// JSON keeps the name `updated_at`.
+    #[serde(rename = "updated_at")]
+    pub updated_at_ms: i64,
     /// Priority level (higher values = higher priority)
     pub priority: i32,
+    /// Labels attached to the wire, sorted alphabetically
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// Why the wire was cancelled, if a reason was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<CancelReason>,
+    /// What a BLOCKED wire is waiting for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+    /// Who the wire is assigned to, set by `wr assign`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<Agent>,
+}
+
+/// Error type for Wire construction failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireConstructionError {
+    /// Title cannot be empty
+    EmptyTitle,
+    /// Title is longer than the configured maximum, in characters
+    TitleTooLong { length: usize, max: usize },
+}
+
+impl fmt::Display for WireConstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireConstructionError::EmptyTitle => write!(f, "Wire title cannot be empty"),
+            WireConstructionError::TitleTooLong { length, max } => write!(
+                f,
+                "Wire title is {} characters, longer than the maximum of {}",
+                length, max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WireConstructionError {}
+
+impl Wire {
+    /// Creates a new wire with automatic ID generation and timestamps.
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - A non-empty title for the wire
+    /// * `description` - Optional detailed description
+    /// * `priority` - Priority level (higher = more important)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the title is empty or contains only whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::models::Wire;
+    ///
+    /// let wire = Wire::new("Fix bug", Some("Critical issue"), 5).unwrap();
+    /// assert_eq!(wire.title, "Fix bug");
+    /// assert_eq!(wire.priority, 5);
+    /// ```
+    pub fn new(
+        title: &str,
+        description: Option<&str>,
+        priority: i32,
+    ) -> Result<Self, WireConstructionError> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(WireConstructionError::EmptyTitle);
+        }
+
+        let now = crate::time::now_ms();
+
+        Ok(Wire {
+            id: crate::generate_id(title),
+            title: title.to_string(),
+            description: description
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+            status: Status::Todo,
+            created_at_ms: now,
+            updated_at_ms: now,
+            priority,
+            tags: vec![],
+            cancel_reason: None,
+            blocked_reason: None,
+            assignee: None,
+        })
+    }
+}
+
+/// A wire with its full dependency information.
+///
+/// This struct includes the wire itself plus lists of:
+/// - Wires this wire depends on (must complete before this one)
+/// - Wires that depend on this wire (blocked until this completes)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WireWithDeps {
+    /// The wire itself (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: Wire,
+    /// Wires this wire depends on
+    pub depends_on: Vec<DependencyInfo>,
+    /// Wires that are blocked by this wire
+    pub blocks: Vec<DependencyInfo>,
+    /// Uncleared external conditions this wire is waiting on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked_on: Vec<ExternalBlocker>,
+    /// Why the wire is PAUSED, if it was paused with a note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<PauseNote>,
+    /// Wires this wire depends on without waiting for them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<Link>,
+}
+
+/// A non-blocking dependency: another wire and how this one relates to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Link {
+    /// The other wire (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: DependencyInfo,
+    pub kind: DependencyKind,
+}
+
+/// Summary information about a wire in a dependency relationship.
+///
+/// Used to display dependency information without loading full wire details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyInfo {
+    /// Wire ID
+    pub id: WireId,
+    /// Wire title
+    pub title: String,
+    /// Current status
+    pub status: Status,
+}
+
+/// A wire reached by following dependencies transitively.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransitiveDependency {
+    /// The wire reached (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: DependencyInfo,
+    /// Number of dependency edges on the shortest path to it; direct
+    /// dependencies (or dependents) are at depth 1
+    pub depth: u32,
+}
+
+impl From<Wire> for WireWithDeps {
+    /// Creates a WireWithDeps with no dependencies.
+    ///
+    /// Useful for wires known to have no blockers (e.g., ready wires).
+    fn from(wire: Wire) -> Self {
+        WireWithDeps {
+            wire,
+            depends_on: vec![],
+            blocks: vec![],
+            blocked_on: vec![],
+            paused: None,
+            links: vec![],
+        }
+    }
+}
+
+/// Why a wire is PAUSED.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PauseNote {
+    pub reason: String,
+    /// Unix timestamp when the wire was paused
+    pub paused_at: i64,
+}
+
+/// A condition outside the repository that wires wait on
+/// (e.g. "waiting for API key").
+///
+/// Wires blocked on an uncleared condition are never ready. One condition can
+/// block several wires; clearing it releases them all.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalBlocker {
+    /// Sequential number used to refer to the blocker
+    pub id: i64,
+    /// What the wires are waiting for
+    pub reason: String,
+    /// Unix timestamp when the blocker was created
+    pub created_at: i64,
+    /// Unix timestamp when the condition was cleared, if it has been
+    pub cleared_at: Option<i64>,
+    /// Wires waiting on this condition
+    pub wires: Vec<WireId>,
+}
+
+/// How a dependency relates its two wires, as given to `wr dep --kind`.
+///
+/// Only [`DependencyKind::Blocks`] holds the dependent back from being
+/// ready; the other kinds record context. Kinds serialize in lowercase.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::DependencyKind;
+///
+/// assert!(DependencyKind::Blocks.is_blocking());
+/// assert!(!DependencyKind::Relates.is_blocking());
+/// assert_eq!("parent".parse::<DependencyKind>(), Ok(DependencyKind::Parent));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    /// The other wire must finish first
+    #[default]
+    Blocks,
+    /// The wires cover related work
+    Relates,
+    /// The wire duplicates the other one
+    Duplicates,
+    /// The other wire is the wire's parent
+    Parent,
+}
+
+impl DependencyKind {
+    /// Returns the lowercase name of the kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyKind::Blocks => "blocks",
+            DependencyKind::Relates => "relates",
+            DependencyKind::Duplicates => "duplicates",
+            DependencyKind::Parent => "parent",
+        }
+    }
+
+    /// Returns whether the dependent waits for the other wire.
+    pub fn is_blocking(&self) -> bool {
+        *self == DependencyKind::Blocks
+    }
+}
+
+impl fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DependencyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocks" => Ok(DependencyKind::Blocks),
+            "relates" => Ok(DependencyKind::Relates),
+            "duplicates" => Ok(DependencyKind::Duplicates),
+            "parent" => Ok(DependencyKind::Parent),
+            _ => Err(format!("Invalid dependency kind: {}", s)),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl FromSql for DependencyKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value
+            .as_str()?
+            .parse()
+            .map_err(|e: String| rusqlite::types::FromSqlError::Other(e.into()))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ToSql for DependencyKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(ValueRef::Text(
+            self.as_str().as_bytes(),
+        )))
+    }
+}
+
+/// A dependency relationship between two wires.
+///
+/// Represents that `wire_id` depends on `depends_on`. For the default
+/// [`DependencyKind::Blocks`], `depends_on` must be completed before
+/// `wire_id` is ready to work on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dependency {
+    /// The wire that has the dependency
+    pub wire_id: WireId,
+    /// The wire it depends on
+    pub depends_on: WireId,
+    /// How the two relate; left out of JSON when blocking
+    #[serde(default, skip_serializing_if = "DependencyKind::is_blocking")]
+    pub kind: DependencyKind,
+}
+
+/// A wire in a [`Snapshot`], with its layout hints alongside its fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotWire {
+    #[serde(flatten)]
+    pub wire: Wire,
+    /// How `wr graph` draws the wire; left out of JSON when empty
+    #[serde(default, skip_serializing_if = "LayoutHints::is_empty")]
+    pub layout: LayoutHints,
+    /// Reasons of the active external blockers it waits on; left out of
+    /// JSON when none
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blockers: Vec<String>,
+    /// Whether it is archived; left out of JSON when not
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// Why it is PAUSED, if it was paused with a note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused: Option<PauseNote>,
+    /// Command that must succeed for it to be ready under
+    /// `wr ready --check-preconditions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precondition: Option<String>,
+}
+
+impl From<Wire> for SnapshotWire {
+    fn from(wire: Wire) -> Self {
+        SnapshotWire {
+            wire,
+            layout: LayoutHints::default(),
+            blockers: Vec::new(),
+            archived: false,
+            paused: None,
+            precondition: None,
+        }
+    }
+}
+
+/// Edges added and removed when reconciling the dependency graph.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyChanges {
+    /// Dependencies that were created
+    pub added: Vec<Dependency>,
+    /// Dependencies that were deleted
+    pub removed: Vec<Dependency>,
+}
+
+/// A complete, portable copy of a wires database.
+///
+/// Produced by `wr export --format json` and consumed by `wr import`.
+/// Wires are sorted by ID and dependencies by `(wire_id, depends_on)`, so
+/// exporting the same data always yields the same file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Format version, bumped on incompatible changes
+    pub version: u32,
+    pub wires: Vec<SnapshotWire>,
+    pub dependencies: Vec<Dependency>,
+    /// Digests of the contents, added by `wr export --manifest`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<SnapshotManifest>,
+}
+
+impl Snapshot {
+    /// The snapshot format version written by this build
+    ///
+    /// Version 3 adds each wire's external blockers, archived flag, pause
+    /// note and precondition. Version 2 records wire
+    /// `created_at` and `updated_at` in milliseconds; version 1 snapshots, in
+    /// seconds, are still accepted by `wr import`, as are version 2 ones.
+    pub const VERSION: u32 = 3;
+
+    /// Computes the digests of this snapshot's contents. Any manifest it
+    /// already carries is not part of them.
+    pub fn compute_manifest(&self) -> SnapshotManifest {
+        let contents = Snapshot {
+            manifest: None,
+            ..self.clone()
+        };
+        SnapshotManifest {
+            algorithm: SnapshotManifest::ALGORITHM.to_string(),
+            digest: sha256_json(&contents),
+            wires: self
+                .wires
+                .iter()
+                .map(|entry| (entry.wire.id.to_string(), sha256_json(entry)))
+                .collect(),
+            dependencies: sha256_json(&self.dependencies),
+        }
+    }
+
+    /// Checks the contents against the manifest written by
+    /// `wr export --manifest`.
+    ///
+    /// The manifest travels in the same file, so this catches corruption but
+    /// not deliberate edits; use [`Snapshot::verify_digest`] for those.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::SnapshotUnverified`] listing what doesn't match:
+    /// each wire changed, added or removed, the dependencies, or a missing
+    /// or unsupported manifest.
+    pub fn check_manifest(&self) -> Result<(), WireError> {
+        let Some(manifest) = &self.manifest else {
+            return Err(WireError::SnapshotUnverified(vec![
+                "snapshot has no manifest (export it with --manifest)".to_string(),
+            ]));
+        };
+        if manifest.algorithm != SnapshotManifest::ALGORITHM {
+            return Err(WireError::SnapshotUnverified(vec![format!(
+                "unsupported manifest algorithm '{}'",
+                manifest.algorithm
+            )]));
+        }
+
+        let actual = self.compute_manifest();
+        if actual.digest == manifest.digest {
+            return Ok(());
+        }
+
+        let mut problems = Vec::new();
+        for (id, digest) in &actual.wires {
+            match manifest.wires.get(id) {
+                None => problems.push(format!("wire {} was added", id)),
+                Some(expected) if expected != digest => {
+                    problems.push(format!("wire {} was changed", id))
+                }
+                Some(_) => {}
+            }
+        }
+        for id in manifest.wires.keys() {
+            if !actual.wires.contains_key(id) {
+                problems.push(format!("wire {} was removed", id));
+            }
+        }
+        if actual.dependencies != manifest.dependencies {
+            problems.push("dependencies were changed".to_string());
+        }
+        if problems.is_empty() {
+            problems.push("snapshot digest does not match".to_string());
+        }
+        Err(WireError::SnapshotUnverified(problems))
+    }
+
+    /// Checks that the raw bytes of a snapshot file have the hex SHA-256
+    /// digest `expected`, as `sha256sum` prints it.
+    ///
+    /// With `expected` obtained separately from the file, this detects any
+    /// change to it, deliberate or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::SnapshotUnverified`] if the digest differs.
+    pub fn verify_digest(bytes: &[u8], expected: &str) -> Result<(), WireError> {
+        use sha2::{Digest, Sha256};
+
+        let actual = format!("{:x}", Sha256::digest(bytes));
+        if actual.eq_ignore_ascii_case(expected.trim()) {
+            return Ok(());
+        }
+        Err(WireError::SnapshotUnverified(vec![format!(
+            "file SHA-256 is {}, expected {}",
+            actual,
+            expected.trim()
+        )]))
+    }
+}
+
+/// SHA-256 digests of a [`Snapshot`], so a copy moved between machines or
+/// kept as an artifact can be checked for corruption.
+///
+/// The digests cover the compact JSON serialization of each part. They are
+/// unkeyed and stored beside the data, so they say which wire was damaged
+/// but can't tell an edit from the original.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Hash function of every digest
+    pub algorithm: String,
+    /// Digest of the whole snapshot: version, wires and dependencies
+    pub digest: String,
+    /// Digest of each wire, by ID
+    pub wires: std::collections::BTreeMap<String, String>,
+    /// Digest of the dependency list
+    pub dependencies: String,
+}
+
+impl SnapshotManifest {
+    /// The only algorithm written and accepted
+    pub const ALGORITHM: &'static str = "sha256";
+}
+
+/// Hex SHA-256 of a value's compact JSON
+fn sha256_json<T: Serialize>(value: &T) -> String {
+    use sha2::{Digest, Sha256};
+
+    let json = serde_json::to_vec(value).expect("snapshot parts serialize to JSON");
+    format!("{:x}", Sha256::digest(json))
+}
+
+/// How `wr import --strategy` settles a wire whose ID already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportStrategy {
+    /// Keep the local wire
+    Ours,
+    /// Overwrite it with the incoming wire
+    Theirs,
+    /// Keep whichever was updated last (local on a tie)
+    Newest,
+    /// Ask for each conflicting wire
+    Interactive,
+}
+
+/// Which copy of a conflicting wire an import kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictSide {
+    /// The wire already in the database
+    Ours,
+    /// The wire from the snapshot
+    Theirs,
+}
+
+/// A field whose local and incoming values differ.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldConflict {
+    pub field: &'static str,
+    pub local: serde_json::Value,
+    pub incoming: serde_json::Value,
+}
+
+/// A snapshot wire whose ID already exists locally.
+///
+/// `fields` is empty when both copies have the same content.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WireConflict {
+    pub id: WireId,
+    #[serde(rename = "local_updated_at")]
+    pub local_updated_at_ms: i64,
+    #[serde(rename = "incoming_updated_at")]
+    pub incoming_updated_at_ms: i64,
+    pub fields: Vec<FieldConflict>,
+    /// The copy that was kept, once resolved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<ConflictSide>,
+}
+
+impl WireConflict {
+    /// Compares the `local` and `incoming` copies of a wire field by field.
+    pub fn between(local: &Wire, incoming: &Wire) -> Self {
+        use serde_json::json;
+
+        let pairs = [
+            ("title", json!(local.title), json!(incoming.title)),
+            (
+                "description",
+                json!(local.description),
+                json!(incoming.description),
+            ),
+            ("status", json!(local.status), json!(incoming.status)),
+            ("priority", json!(local.priority), json!(incoming.priority)),
+            ("tags", json!(local.tags), json!(incoming.tags)),
+            ("assignee", json!(local.assignee), json!(incoming.assignee)),
+            (
+                "cancel_reason",
+                json!(local.cancel_reason),
+                json!(incoming.cancel_reason),
+            ),
+            (
+                "blocked_reason",
+                json!(local.blocked_reason),
+                json!(incoming.blocked_reason),
+            ),
+        ];
+        WireConflict {
+            id: local.id.clone(),
+            local_updated_at_ms: local.updated_at_ms,
+            incoming_updated_at_ms: incoming.updated_at_ms,
+            fields: pairs
+                .into_iter()
+                .filter(|(_, local, incoming)| local != incoming)
+                .map(|(field, local, incoming)| FieldConflict {
+                    field,
+                    local,
+                    incoming,
+                })
+                .collect(),
+            resolution: None,
+        }
+    }
+
+    /// The copy `strategy` keeps, or `None` for
+    /// [`ImportStrategy::Interactive`], which has to ask.
+    pub fn resolve(&self, strategy: ImportStrategy) -> Option<ConflictSide> {
+        match strategy {
+            ImportStrategy::Ours => Some(ConflictSide::Ours),
+            ImportStrategy::Theirs => Some(ConflictSide::Theirs),
+            ImportStrategy::Newest if self.incoming_updated_at_ms > self.local_updated_at_ms => {
+                Some(ConflictSide::Theirs)
+            }
+            ImportStrategy::Newest => Some(ConflictSide::Ours),
+            ImportStrategy::Interactive => None,
+        }
+    }
+}
+
+/// A priority reassigned by `renumber_priorities`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PriorityChange {
+    pub id: WireId,
+    pub title: String,
+    /// Priority before renumbering
+    pub from: i32,
+    /// Priority after renumbering
+    pub to: i32,
+}
+
+/// A wire matched by a full-text search.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub wire: Wire,
+    /// Relevance score; higher is a better match
+    pub score: f64,
 }
 
-/// Error type for Wire construction failures.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum WireConstructionError {
-    /// Title cannot be empty
-    EmptyTitle,
+/// A wire read from one of several repositories by `wr multi`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoWire {
+    /// The repository root the wire was read from, as given on the command line
+    pub repo: String,
+    #[serde(flatten)]
+    pub wire: Wire,
 }
 
-impl fmt::Display for WireConstructionError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// The kind of change recorded in the event log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A wire was created
+    Created,
+    /// Title, description, status or priority changed
+    Updated,
+    /// A wire was deleted
+    Deleted,
+    /// A dependency was added
+    DepAdded,
+    /// A dependency was removed
+    DepRemoved,
+    /// A tag was attached
+    Tagged,
+    /// A tag was removed
+    Untagged,
+    /// An agent claimed the wire
+    Claimed,
+    /// A claim was released
+    Released,
+    /// The wire was blocked on an external condition
+    Blocked,
+    /// An external condition the wire waited on was cleared
+    Unblocked,
+    /// The wire was moved to the archive
+    Archived,
+    /// The wire was taken out of the archive
+    Unarchived,
+    /// The wire's assignee changed
+    Assigned,
+    /// An agent handed the wire off mid-task with `wr handoff`
+    HandedOff,
+    /// The wire's layout hints changed
+    LayoutChanged,
+}
+
+impl EventKind {
+    /// Returns the string stored in the database for this kind.
+    pub fn as_str(&self) -> &'static str {
         match self {
-            WireConstructionError::EmptyTitle => write!(f, "Wire title cannot be empty"),
+            EventKind::Created => "created",
+            EventKind::Updated => "updated",
+            EventKind::Deleted => "deleted",
+            EventKind::DepAdded => "dep_added",
+            EventKind::DepRemoved => "dep_removed",
+            EventKind::Tagged => "tagged",
+            EventKind::Untagged => "untagged",
+            EventKind::Claimed => "claimed",
+            EventKind::Released => "released",
+            EventKind::Blocked => "blocked",
+            EventKind::Unblocked => "unblocked",
+            EventKind::Archived => "archived",
+            EventKind::Unarchived => "unarchived",
+            EventKind::Assigned => "assigned",
+            EventKind::HandedOff => "handed_off",
+            EventKind::LayoutChanged => "layout_changed",
         }
     }
 }
 
-impl std::error::Error for WireConstructionError {}
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
 
-impl Wire {
-    /// Creates a new wire with automatic ID generation and timestamps.
-    ///
-    /// # Arguments
-    ///
-    /// * `title` - A non-empty title for the wire
-    /// * `description` - Optional detailed description
-    /// * `priority` - Priority level (higher = more important)
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the title is empty or contains only whitespace.
+impl FromStr for EventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created" => Ok(EventKind::Created),
+            "updated" => Ok(EventKind::Updated),
+            "deleted" => Ok(EventKind::Deleted),
+            "dep_added" => Ok(EventKind::DepAdded),
+            "dep_removed" => Ok(EventKind::DepRemoved),
+            "tagged" => Ok(EventKind::Tagged),
+            "untagged" => Ok(EventKind::Untagged),
+            "claimed" => Ok(EventKind::Claimed),
+            "released" => Ok(EventKind::Released),
+            "blocked" => Ok(EventKind::Blocked),
+            "unblocked" => Ok(EventKind::Unblocked),
+            "archived" => Ok(EventKind::Archived),
+            "unarchived" => Ok(EventKind::Unarchived),
+            "assigned" => Ok(EventKind::Assigned),
+            "handed_off" => Ok(EventKind::HandedOff),
+            "layout_changed" => Ok(EventKind::LayoutChanged),
+            _ => Err(format!("Invalid event kind: {}", s)),
+        }
+    }
+}
+
+/// An entry in the append-only history of changes to wires.
+///
+/// `data` holds kind-specific details. For `updated` events it maps each
+/// changed field to `{"from": old, "to": new}`; for `created` and `deleted`
+/// it holds a snapshot of the wire.
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    /// Monotonically increasing sequence number
+    pub seq: i64,
+    /// Unix timestamp of the change
+    pub at: i64,
+    /// The wire that changed (may no longer exist)
+    pub wire_id: WireId,
+    /// What happened
+    pub kind: EventKind,
+    /// Details of the change
+    pub data: serde_json::Value,
+}
+
+/// How a wire is drawn in `wr graph` diagrams (DOT and Mermaid).
+///
+/// Wires sharing a `rank` are lined up at the same level, wires sharing a
+/// `cluster` are boxed together under that label, and `color` fills the
+/// wire's node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutHints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cluster: Option<String>,
+    /// A color name (`orange`) or hex code (`#ffcc00`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl LayoutHints {
+    /// Whether no hint is set
+    pub fn is_empty(&self) -> bool {
+        self.rank.is_none() && self.cluster.is_none() && self.color.is_none()
+    }
+
+    /// Checks a color for [`LayoutHints::color`]: a name made of ASCII
+    /// letters, or `#` and 3 or 6 hex digits. Both DOT and Mermaid accept
+    /// these as they are.
     ///
     /// # Example
     ///
     /// ```
-    /// use wr::models::Wire;
+    /// use wr::models::LayoutHints;
     ///
-    /// let wire = Wire::new("Fix bug", Some("Critical issue"), 5).unwrap();
-    /// assert_eq!(wire.title, "Fix bug");
-    /// assert_eq!(wire.priority, 5);
+    /// assert_eq!(LayoutHints::parse_color("#FFCC00").unwrap(), "#ffcc00");
+    /// assert!(LayoutHints::parse_color("red; stroke:blue").is_err());
     /// ```
-    pub fn new(
-        title: &str,
-        description: Option<&str>,
-        priority: i32,
-    ) -> Result<Self, WireConstructionError> {
-        let title = title.trim();
-        if title.is_empty() {
-            return Err(WireConstructionError::EmptyTitle);
+    pub fn parse_color(s: &str) -> Result<String, String> {
+        let s = s.trim();
+        let valid = match s.strip_prefix('#') {
+            Some(hex) => matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic()),
+        };
+        if valid {
+            Ok(s.to_ascii_lowercase())
+        } else {
+            Err(format!(
+                "Invalid color '{}': use a name like 'orange' or a hex code like '#ffcc00'",
+                s
+            ))
         }
+    }
+}
 
-        use std::time::{SystemTime, UNIX_EPOCH};
+/// A time-limited claim on a wire by one agent.
+///
+/// While a lease is active, `wr ready` hides the wire from every other agent.
+/// Expired leases are ignored and may be taken over by anyone.
+#[derive(Debug, Clone, Serialize)]
+pub struct Lease {
+    /// The claimed wire
+    pub wire_id: WireId,
+    /// Agent holding the claim
+    pub agent: Agent,
+    /// Unix timestamp when the claim was taken or last renewed
+    pub claimed_at: i64,
+    /// Unix timestamp after which the claim lapses
+    pub expires_at: i64,
+}
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs() as i64;
+/// A wire picked and started by `wr next`.
+#[derive(Debug, Clone)]
+pub struct NextWire {
+    /// The wire, now IN_PROGRESS
+    pub wire: Wire,
+    /// The claim taken on it, when an agent asked
+    pub lease: Option<Lease>,
+    /// Wires paused to stay within the IN_PROGRESS limit
+    pub paused: Vec<Wire>,
+}
 
-        Ok(Wire {
-            id: crate::generate_id(title),
-            title: title.to_string(),
-            description: description
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty()),
-            status: Status::Todo,
-            created_at: now,
-            updated_at: now,
-            priority,
-        })
+/// A registered outbox consumer and how far it has read.
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboxCursor {
+    pub consumer: Consumer,
+    /// Sequence number of the last acknowledged event (0 if none)
+    pub acked_seq: i64,
+    /// Events recorded after `acked_seq`
+    pub pending: i64,
+    /// Unix timestamp of the last pull or acknowledgement
+    pub updated_at: i64,
+}
+
+/// A wire and, recursively, the wires it depends on (or, reversed, the
+/// wires it blocks), as shown by `wr tree`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyTree {
+    /// The wire at this node (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: DependencyInfo,
+    /// Set when the wire was already expanded elsewhere in the tree; its
+    /// children are then left out
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+    /// The next level of the tree, ordered by ID
+    pub children: Vec<DependencyTree>,
+}
+
+/// Why a wire is or isn't in `wr ready`, as reported by `wr why`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Readiness {
+    /// The wire asked about (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: DependencyInfo,
+    /// Whether the wire is in `wr ready` for the agent asking
+    pub ready: bool,
+    /// Another agent's active claim hiding the wire
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claimed: Option<Lease>,
+    /// Uncleared external conditions the wire is waiting on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocked_on: Vec<ExternalBlocker>,
+    /// Unfinished dependencies, each with what holds it up in turn
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blockers: Vec<Blocker>,
+}
+
+impl Readiness {
+    /// Whether the wire's own status keeps it out of `wr ready`.
+    pub fn status_blocks(&self) -> bool {
+        !matches!(self.wire.status, Status::Todo | Status::InProgress)
     }
 }
 
-/// A wire with its full dependency information.
+/// Everything known about one wire, as assembled by `wr doc` for an agent
+/// about to work on it.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dossier {
+    /// The wire with its direct dependencies and dependents (fields are
+    /// flattened in JSON)
+    #[serde(flatten)]
+    pub wire: WireWithDeps,
+    /// The active claim on the wire, whoever holds it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lease: Option<Lease>,
+    /// Whether the wire is ready, and what holds it up if not
+    pub readiness: Readiness,
+    /// Every wire it depends on, directly or not, nearest first
+    pub upstream: Vec<Wire>,
+    /// The wire's event log, oldest first
+    pub history: Vec<Event>,
+}
+
+/// A wire packaged by `wr handoff` for the next agent to pick up.
+#[derive(Debug, Clone, Serialize)]
+pub struct Handoff {
+    /// Everything `wr doc` reports, taken after the handoff (fields are
+    /// flattened in JSON)
+    #[serde(flatten)]
+    pub dossier: Dossier,
+    /// Unfinished wires it depends on, directly or not, nearest first
+    pub remaining: Vec<Wire>,
+    /// Notes left by every handoff of this wire so far, oldest first
+    pub notes: Vec<HandoffNote>,
+    /// The claim the handoff released, if there was one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub released: Option<Lease>,
+}
+
+/// A note left for the next agent by `wr handoff --note`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HandoffNote {
+    /// Unix timestamp of the handoff
+    pub at: i64,
+    /// Agent that handed the wire off, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    pub note: String,
+}
+
+/// An unfinished dependency keeping a wire out of `wr ready`, and what
+/// keeps that dependency from finishing.
+#[derive(Debug, Clone, Serialize)]
+pub struct Blocker {
+    /// The dependency (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: DependencyInfo,
+    /// Set when the wire was already explained elsewhere in the report; its
+    /// own blockers are then left out
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+    /// Uncleared external conditions it is waiting on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocked_on: Vec<ExternalBlocker>,
+    /// Its own unfinished dependencies
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blockers: Vec<Blocker>,
+}
+
+/// The longest chain of unfinished wires, as reported by `wr critical-path`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CriticalPath {
+    /// Total weight of the chain: one per wire, or the sum of their
+    /// estimates when weighted
+    pub weight: u64,
+    /// Wires along the chain, to be started first to finished last; each
+    /// depends on the one before it
+    pub wires: Vec<Wire>,
+}
+
+/// Wire counts for one status, as reported by `wr stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusCount {
+    pub status: Status,
+    pub count: i64,
+    /// Share of all wires, rounded to one decimal place
+    pub percent: f64,
+    /// Seconds since the least recently updated wire in this status changed
+    /// (`None` if there are no such wires)
+    pub oldest_age: Option<i64>,
+}
+
+/// Totals across every status, as reported by `wr stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsTotal {
+    pub count: i64,
+    /// 100, or 0 if there are no wires
+    pub percent: f64,
+    /// Largest `oldest_age` of any status
+    pub oldest_age: Option<i64>,
+}
+
+/// Per-status wire counts with a totals row.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusStats {
+    /// One row per status, including statuses with no wires
+    pub rows: Vec<StatusCount>,
+    pub total: StatsTotal,
+}
+
+/// Wires finished recently, counted by `wr stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Throughput {
+    /// Finished in the last 24 hours
+    pub day: i64,
+    /// Finished in the last 7 days
+    pub week: i64,
+}
+
+/// Wires that can't be worked on, counted by `wr stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BlockedCounts {
+    /// `BLOCKED` wires
+    pub status: i64,
+    /// `TODO` or `IN_PROGRESS` wires with a dependency that isn't `DONE`
+    pub dependencies: i64,
+    /// `TODO` or `IN_PROGRESS` wires waiting on an external blocker
+    pub external: i64,
+}
+
+/// Everything `wr stats` reports: the per-status rows and totals, plus
+/// throughput, cycle time and blocked counts.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(flatten)]
+    pub status: StatusStats,
+    pub throughput: Throughput,
+    /// Average seconds from start to finish of `DONE` wires; `None` if
+    /// nothing is done yet
+    pub cycle_time: Option<i64>,
+    pub blocked: BlockedCounts,
+}
+
+/// How long estimated work actually took, as reported by
+/// `wr report --accuracy`.
 ///
-/// This struct includes the wire itself plus lists of:
-/// - Wires this wire depends on (must complete before this one)
-/// - Wires that depend on this wire (blocked until this completes)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WireWithDeps {
-    /// The wire itself (fields are flattened in JSON)
+/// Covers `DONE` wires with an `estimate:<n>` tag. Estimates are points,
+/// so the report measures how many seconds a point takes, overall and per
+/// group; a group's `variance` is how far its rate is from the overall
+/// one, in percent (positive means slower than estimated).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccuracyReport {
+    pub overall: Accuracy,
+    /// One group per tag, ordered by tag; `estimate:` tags are left out
+    pub by_tag: Vec<AccuracyGroup>,
+    /// One group per top-level wire (one with dependencies that nothing
+    /// depends on), covering its subtree, ordered by ID
+    pub by_epic: Vec<AccuracyGroup>,
+    /// One group per agent that last claimed the wire, or else its
+    /// assignee, ordered by name; wires with neither come last
+    pub by_actor: Vec<AccuracyGroup>,
+}
+
+/// Estimated and actual effort over a set of finished wires.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Accuracy {
+    pub wires: usize,
+    /// Sum of the wires' estimates, in points
+    pub estimate: u64,
+    /// Sum of the seconds from each wire's start to its finish
+    pub actual: i64,
+    /// Seconds per estimated point, to one decimal place
+    pub per_point: f64,
+}
+
+impl Accuracy {
+    /// Adds a wire estimated at `estimate` points that took `actual` seconds.
+    pub fn add(&mut self, estimate: u64, actual: i64) {
+        self.wires += 1;
+        self.estimate += estimate;
+        self.actual += actual;
+        self.per_point = (self.actual as f64 * 10.0 / self.estimate as f64).round() / 10.0;
+    }
+}
+
+/// [`Accuracy`] for one tag, epic or actor.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccuracyGroup {
+    /// Tag, epic ID or actor; `None` for wires without an actor
+    pub name: Option<String>,
+    /// The epic's title, for epic groups
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     #[serde(flatten)]
-    pub wire: Wire,
-    /// Wires this wire depends on
-    pub depends_on: Vec<DependencyInfo>,
-    /// Wires that are blocked by this wire
-    pub blocks: Vec<DependencyInfo>,
+    pub accuracy: Accuracy,
+    /// Percent by which the group's seconds per point differ from the
+    /// overall rate
+    pub variance: f64,
 }
 
-/// Summary information about a wire in a dependency relationship.
+/// How far along an epic is, as reported by `wr epic progress`.
 ///
-/// Used to display dependency information without loading full wire details.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DependencyInfo {
-    /// Wire ID
-    pub id: WireId,
-    /// Wire title
-    pub title: String,
-    /// Current status
+/// An epic is any wire; its subtree is the epic and everything it depends
+/// on, directly or not.
+#[derive(Debug, Clone, Serialize)]
+pub struct EpicProgress {
+    pub epic: Wire,
+    /// The subtree by status (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub status: StatusStats,
+    /// Percent of the subtree that is DONE, leaving out CANCELLED wires
+    pub complete: f64,
+    /// The epic's ready frontier, as listed by `wr ready --epic`
+    pub ready: Vec<Wire>,
+}
+
+/// One status column of `wr board`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardColumn {
     pub status: Status,
+    /// Highest priority first
+    pub wires: Vec<Wire>,
 }
 
-impl From<Wire> for WireWithDeps {
-    /// Creates a WireWithDeps with no dependencies.
-    ///
-    /// Useful for wires known to have no blockers (e.g., ready wires).
-    fn from(wire: Wire) -> Self {
-        WireWithDeps {
-            wire,
-            depends_on: vec![],
-            blocks: vec![],
+/// One day of `wr burndown`, as of the end of that day (UTC).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BurndownDay {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// Wires not yet `DONE` or `CANCELLED`
+    pub open: i64,
+    /// Wires `DONE` by then
+    pub done: i64,
+    /// Wires that became `DONE` during the day
+    pub completed: i64,
+}
+
+/// Column to sort `wr stats` rows by, largest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsSort {
+    /// Number of wires
+    Count,
+    /// Age of the stalest wire
+    Age,
+}
+
+impl StatusStats {
+    /// Sorts the rows by `sort`, largest first. Ties keep status order.
+    pub fn sort_by(&mut self, sort: StatsSort) {
+        match sort {
+            StatsSort::Count => self.rows.sort_by_key(|row| std::cmp::Reverse(row.count)),
+            StatsSort::Age => self
+                .rows
+                .sort_by_key(|row| std::cmp::Reverse(row.oldest_age)),
         }
     }
 }
 
-/// A dependency relationship between two wires.
+/// What to do about a stale wire, as suggested by `wr report --stale`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StaleAction {
+    /// Long forgotten and nothing waits on it: drop it
+    Cancel,
+    /// Not urgent: lower its priority so fresher work comes first
+    Defer,
+    /// Other unfinished wires wait on it: raise its priority
+    Bump,
+}
+
+impl StaleAction {
+    /// Untouched for this many thresholds, with nothing waiting on it, a
+    /// wire is suggested for cancelling rather than deferring
+    pub const CANCEL_AFTER_THRESHOLDS: i64 = 3;
+
+    /// Suggests an action for a TODO wire untouched for `age` seconds that
+    /// `waiting` unfinished wires depend on.
+    pub fn suggest(age: i64, threshold: i64, waiting: usize) -> Self {
+        if waiting > 0 {
+            StaleAction::Bump
+        } else if age >= threshold.saturating_mul(Self::CANCEL_AFTER_THRESHOLDS) {
+            StaleAction::Cancel
+        } else {
+            StaleAction::Defer
+        }
+    }
+
+    /// Returns the action's name, as used in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StaleAction::Cancel => "cancel",
+            StaleAction::Defer => "defer",
+            StaleAction::Bump => "bump",
+        }
+    }
+
+    /// The command carrying out the action for `wire`.
+    pub fn command(&self, wire: &Wire) -> String {
+        match self {
+            StaleAction::Cancel => format!("wr cancel {} --reason obsolete", wire.id),
+            StaleAction::Defer => {
+                format!("wr update {} --priority {}", wire.id, wire.priority - 1)
+            }
+            StaleAction::Bump => {
+                format!("wr update {} --priority {}", wire.id, wire.priority + 1)
+            }
+        }
+    }
+}
+
+/// A TODO wire nobody has touched in a while.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleWire {
+    /// The wire (fields are flattened in JSON)
+    #[serde(flatten)]
+    pub wire: Wire,
+    /// Seconds since the wire was last updated
+    pub age: i64,
+    /// Number of unfinished wires that depend on it
+    pub waiting: usize,
+    /// Suggested action
+    pub action: StaleAction,
+    /// Command carrying out the suggested action
+    pub command: String,
+}
+
+/// Stale wires sharing a tag.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleGroup {
+    /// The tag, or `None` for untagged wires
+    pub tag: Option<Tag>,
+    /// Wires in the group, least recently updated first
+    pub wires: Vec<StaleWire>,
+}
+
+/// TODO wires untouched for at least `older_than` seconds, grouped by tag,
+/// as reported by `wr report --stale`.
 ///
-/// Represents that `wire_id` depends on `depends_on`, meaning
-/// `depends_on` must be completed before `wire_id` is ready to work on.
-#[derive(Debug, Clone)]
-pub struct Dependency {
-    /// The wire that has the dependency
-    pub wire_id: WireId,
-    /// The wire it depends on
-    pub depends_on: WireId,
+/// A wire with several tags appears in each of their groups.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleReport {
+    /// The threshold in seconds
+    pub older_than: i64,
+    /// Number of distinct stale wires
+    pub count: usize,
+    /// Groups ordered by tag, untagged wires last
+    pub groups: Vec<StaleGroup>,
+}
+
+/// What `wr maintain` removed under the [retention policy](crate::config::RetentionPolicy).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionReport {
+    /// Number of events pruned from the history
+    pub events: usize,
+    /// Archived wires deleted for good
+    pub archived_wires: Vec<WireId>,
+    /// Outbox consumers dropped for inactivity
+    pub outbox_consumers: Vec<String>,
+    /// Backup files removed from `.wires`
+    pub backups: Vec<String>,
+    /// Number of saved idempotency key responses removed
+    pub idempotency_keys: usize,
+    /// Whether nothing was actually removed
+    pub dry_run: bool,
+}
+
+/// What `wr ping` found when checking the repository.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// Whether every check passed
+    pub ok: bool,
+    /// The database checked, if one was found
+    pub database: Option<String>,
+    /// Schema version of the database, before any migration
+    pub schema_version: Option<i64>,
+    /// Schema version this build migrates databases to; older databases are
+    /// upgraded on first use, newer ones are incompatible
+    pub supported_schema_version: i64,
+    /// Whether the database accepts writes
+    pub writable: bool,
+    /// Result of taking and releasing the write lock, if asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<LockCheck>,
+    /// Each check that failed
+    pub problems: Vec<String>,
+}
+
+/// Result of taking and releasing the database's write lock.
+#[derive(Debug, Clone, Serialize)]
+pub struct LockCheck {
+    pub acquired: bool,
+    /// How long taking the lock took, in milliseconds
+    pub wait_ms: u64,
 }
 
 /// Domain-specific errors for wire operations.
@@ -421,8 +2288,100 @@ pub enum WireError {
     AlreadyInitialized(String),
     /// The specified wire ID does not exist
     WireNotFound(String),
-    /// Adding this dependency would create a circular dependency chain
-    CircularDependency(Vec<String>),
+    /// Adding this dependency would create a circular dependency chain.
+    ///
+    /// Holds every wire along the cycle in order, starting and ending with
+    /// the wire the dependency was being added to.
+    CircularDependency(Vec<DependencyInfo>),
+    /// A `title:` lookup matched more than one open wire
+    AmbiguousWire {
+        query: String,
+        candidates: Vec<DependencyInfo>,
+    },
+    /// An idempotency key was reused for a different command
+    IdempotencyKeyReused { key: String, command: String },
+    /// The wire is already claimed by another agent with an active lease
+    AlreadyClaimed(Lease),
+    /// The wire is claimed by a different agent than the one releasing it
+    NotClaimedBy { wire_id: String, agent: Agent },
+    /// The ID given for a new wire was not allocated by `wr reserve`, or has
+    /// already been used
+    NotReserved(String),
+    /// Starting another wire would exceed the IN_PROGRESS limit; holds the
+    /// wires already in progress
+    InProgressLimit {
+        limit: usize,
+        wires: Vec<DependencyInfo>,
+    },
+    /// The configured transitions don't allow this status change; `allowed`
+    /// lists where the wire may go instead
+    InvalidTransition {
+        wire_id: String,
+        from: Status,
+        to: Status,
+        allowed: Vec<Status>,
+    },
+    /// Wires in an imported snapshot already exist locally, and no
+    /// `--strategy` says which copy to keep
+    ImportConflicts(Vec<WireConflict>),
+    /// An imported snapshot doesn't match the digest given for it, or its
+    /// manifest (or has none); holds each problem found
+    SnapshotUnverified(Vec<String>),
+    /// `wr ping` found the repository unusable; holds each failed check
+    Unhealthy(Vec<String>),
+    /// The configured permissions don't let this agent (`None` when it has
+    /// no name) run the command; `allowed` lists what it may run
+    PermissionDenied {
+        actor: Option<String>,
+        operation: String,
+        allowed: Vec<String>,
+    },
+}
+
+impl WireError {
+    /// Process exit status for this error, distinct per kind, so scripts
+    /// can tell failures apart without parsing messages. Errors that aren't
+    /// a `WireError` exit with 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            WireError::NotARepository => 2,
+            WireError::WireNotFound(_) => 3,
+            WireError::CircularDependency(_) => 4,
+            WireError::AlreadyInitialized(_) => 5,
+            WireError::AmbiguousWire { .. } => 6,
+            WireError::IdempotencyKeyReused { .. } => 7,
+            WireError::AlreadyClaimed(_) => 8,
+            WireError::NotClaimedBy { .. } => 9,
+            WireError::NotReserved(_) => 10,
+            WireError::InProgressLimit { .. } => 11,
+            WireError::InvalidTransition { .. } => 12,
+            WireError::ImportConflicts(_) => 13,
+            WireError::SnapshotUnverified(_) => 14,
+            WireError::Unhealthy(_) => 15,
+            WireError::PermissionDenied { .. } => 16,
+        }
+    }
+
+    /// Stable name of this kind of error, reported as `code` in JSON errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            WireError::NotARepository => "not_a_repository",
+            WireError::WireNotFound(_) => "wire_not_found",
+            WireError::CircularDependency(_) => "circular_dependency",
+            WireError::AlreadyInitialized(_) => "already_initialized",
+            WireError::AmbiguousWire { .. } => "ambiguous_wire",
+            WireError::IdempotencyKeyReused { .. } => "idempotency_key_reused",
+            WireError::AlreadyClaimed(_) => "already_claimed",
+            WireError::NotClaimedBy { .. } => "not_claimed_by",
+            WireError::NotReserved(_) => "not_reserved",
+            WireError::InProgressLimit { .. } => "in_progress_limit",
+            WireError::InvalidTransition { .. } => "invalid_transition",
+            WireError::ImportConflicts(_) => "import_conflicts",
+            WireError::SnapshotUnverified(_) => "snapshot_unverified",
+            WireError::Unhealthy(_) => "unhealthy",
+            WireError::PermissionDenied { .. } => "permission_denied",
+        }
+    }
 }
 
 impl fmt::Display for WireError {
@@ -434,7 +2393,105 @@ impl fmt::Display for WireError {
             }
             WireError::WireNotFound(id) => write!(f, "Wire not found: {}", id),
             WireError::CircularDependency(cycle) => {
-                write!(f, "Circular dependency detected: {}", cycle.join(" -> "))
+                let path: Vec<String> = cycle
+                    .iter()
+                    .map(|w| format!("{} \"{}\"", w.id, w.title))
+                    .collect();
+                write!(f, "Circular dependency detected: {}", path.join(" -> "))
+            }
+            WireError::AmbiguousWire { query, candidates } => {
+                let list: Vec<String> = candidates
+                    .iter()
+                    .map(|w| format!("{} \"{}\"", w.id, w.title))
+                    .collect();
+                write!(
+                    f,
+                    "'{}' matches {} open wires: {}",
+                    query,
+                    candidates.len(),
+                    list.join(", ")
+                )
+            }
+            WireError::IdempotencyKeyReused { key, command } => write!(
+                f,
+                "Idempotency key '{}' was already used for `wr {}`",
+                key, command
+            ),
+            WireError::AlreadyClaimed(lease) => write!(
+                f,
+                "Wire {} is already claimed by {} (lease expires at {})",
+                lease.wire_id, lease.agent, lease.expires_at
+            ),
+            WireError::NotClaimedBy { wire_id, agent } => {
+                write!(f, "Wire {} is not claimed by {}", wire_id, agent)
+            }
+            WireError::InProgressLimit { limit, wires } => {
+                let list: Vec<String> = wires
+                    .iter()
+                    .map(|w| format!("{} \"{}\"", w.id, w.title))
+                    .collect();
+                write!(
+                    f,
+                    "Already {} wire(s) IN_PROGRESS (limit {}): {}; finish or pause one first",
+                    wires.len(),
+                    limit,
+                    list.join(", ")
+                )
+            }
+            WireError::NotReserved(id) => write!(
+                f,
+                "ID {} is not reserved (allocate one with `wr reserve`)",
+                id
+            ),
+            WireError::InvalidTransition {
+                wire_id,
+                from,
+                to,
+                allowed,
+            } => {
+                let allowed: Vec<&str> = allowed.iter().map(Status::as_str).collect();
+                write!(
+                    f,
+                    "Wire {} can't go from {} to {} (allowed: {})",
+                    wire_id,
+                    from.as_str(),
+                    to.as_str(),
+                    if allowed.is_empty() {
+                        "none".to_string()
+                    } else {
+                        allowed.join(", ")
+                    }
+                )
+            }
+            WireError::ImportConflicts(conflicts) => {
+                let ids: Vec<&str> = conflicts.iter().map(|c| c.id.as_str()).collect();
+                write!(
+                    f,
+                    "Wire ID already exists for {} wire(s): {} (use --strategy ours|theirs|newest|interactive, or --replace)",
+                    conflicts.len(),
+                    ids.join(", ")
+                )
+            }
+            WireError::SnapshotUnverified(problems) => {
+                write!(f, "Snapshot failed verification: {}", problems.join("; "))
+            }
+            WireError::Unhealthy(problems) => {
+                write!(f, "Repository is unhealthy: {}", problems.join("; "))
+            }
+            WireError::PermissionDenied {
+                actor,
+                operation,
+                allowed,
+            } => {
+                match actor {
+                    Some(actor) => write!(f, "Agent '{}' may not run `wr {}`", actor, operation)?,
+                    None => write!(f, "Agents without a name may not run `wr {}`", operation)?,
+                }
+                if allowed.is_empty() {
+                    write!(f, " (it may only read)")
+                } else {
+                    write!(f, " (allowed: {})", allowed.join(", "))
+                }
             }
         }
     }
@@ -463,6 +2520,62 @@ mod tests {
         assert!(WireId::new("a1b2c3g").is_err()); // 'g' is not hex
     }
 
+    #[test]
+    fn test_wire_id_normalizes_ulid_and_uuid() {
+        let ulid = WireId::new("01hq3v5k8m2n4p6r8t0w2y4a6c").unwrap();
+        assert_eq!(ulid.as_str(), "01HQ3V5K8M2N4P6R8T0W2Y4A6C");
+        let uuid = WireId::new("018F3A2B-7C4D-7E5F-9A6B-1C2D3E4F5A6B").unwrap();
+        assert_eq!(uuid.as_str(), "018f3a2b-7c4d-7e5f-9a6b-1c2d3e4f5a6b");
+    }
+
+    #[test]
+    fn test_wire_id_rejects_malformed_ulid_and_uuid() {
+        // I, L, O and U aren't in the ULID alphabet
+        assert!(WireId::new("01HQ3V5K8M2N4P6R8T0W2Y4A6U").is_err());
+        // A leading 8 would overflow 128 bits
+        assert!(WireId::new("81HQ3V5K8M2N4P6R8T0W2Y4A6C").is_err());
+        // Version 4, not 7
+        assert!(WireId::new("018f3a2b-7c4d-4e5f-9a6b-1c2d3e4f5a6b").is_err());
+        // Wrong variant
+        assert!(WireId::new("018f3a2b-7c4d-7e5f-1a6b-1c2d3e4f5a6b").is_err());
+        assert!(WireId::new("018f3a2b7c4d7e5f9a6b1c2d3e4f5a6b1234").is_err());
+    }
+
+    #[test]
+    fn test_id_scheme_generates_valid_ids() {
+        for (scheme, len) in [
+            (IdScheme::Hex, 7),
+            (IdScheme::Ulid, 26),
+            (IdScheme::Uuid7, 36),
+        ] {
+            let id = scheme.generate("Test wire");
+            assert_eq!(id.as_str().len(), len, "{}", scheme);
+            assert!(scheme.matches(id.as_str()), "{}", id);
+            assert_eq!(WireId::new(id.as_str()).unwrap(), id);
+            assert_ne!(scheme.generate("Test wire"), id);
+        }
+    }
+
+    #[test]
+    fn test_time_based_ids_sort_by_creation() {
+        for scheme in [IdScheme::Ulid, IdScheme::Uuid7] {
+            let first = scheme.generate("First");
+            std::thread::sleep(std::time::Duration::from_millis(2));
+            let second = scheme.generate("Second");
+            assert!(first < second, "{} >= {}", first, second);
+        }
+    }
+
+    #[test]
+    fn test_ulid_encodes_timestamp() {
+        let before = crate::time::now_ms();
+        let id = IdScheme::Ulid.generate("x");
+        let millis = id.as_str()[..10].bytes().fold(0i64, |acc, b| {
+            acc * 32 + CROCKFORD.iter().position(|&c| c == b).unwrap() as i64
+        });
+        assert!(millis >= before && millis <= crate::time::now_ms());
+    }
+
     #[test]
     fn test_wire_id_serialization() {
         let id = WireId::new("a1b2c3d").unwrap();
@@ -473,10 +2586,78 @@ mod tests {
         assert_eq!(parsed, id);
     }
 
+    #[test]
+    fn test_tag_valid_and_lowercased() {
+        assert_eq!(Tag::new("backend").unwrap().as_str(), "backend");
+        assert_eq!(Tag::new("Area/DB").unwrap().as_str(), "area/db");
+        assert_eq!(Tag::new(" p1 ").unwrap().as_str(), "p1");
+    }
+
+    #[test]
+    fn test_tag_invalid() {
+        assert_eq!(Tag::new(""), Err(TagError::Empty));
+        assert!(matches!(
+            Tag::new("a,b"),
+            Err(TagError::InvalidCharacters(_))
+        ));
+        assert!(matches!(
+            Tag::new("two words"),
+            Err(TagError::InvalidCharacters(_))
+        ));
+        assert!(matches!(
+            Tag::new(&"x".repeat(65)),
+            Err(TagError::TooLong(65))
+        ));
+    }
+
+    #[test]
+    fn test_agent_valid_and_case_preserved() {
+        assert_eq!(Agent::new("Claude-1").unwrap().as_str(), "Claude-1");
+        assert_eq!(Agent::new(" ci@host ").unwrap().as_str(), "ci@host");
+    }
+
+    #[test]
+    fn test_agent_invalid() {
+        assert_eq!(Agent::new("  "), Err(AgentError::Empty));
+        assert!(matches!(
+            Agent::new("two words"),
+            Err(AgentError::InvalidCharacters(_))
+        ));
+        assert!(matches!(
+            Agent::new(&"a".repeat(65)),
+            Err(AgentError::TooLong(65))
+        ));
+    }
+
+    #[test]
+    fn test_event_kind_roundtrip() {
+        for kind in [
+            EventKind::Created,
+            EventKind::Updated,
+            EventKind::Deleted,
+            EventKind::DepAdded,
+            EventKind::DepRemoved,
+            EventKind::Tagged,
+            EventKind::Untagged,
+            EventKind::Claimed,
+            EventKind::Released,
+            EventKind::HandedOff,
+            EventKind::LayoutChanged,
+        ] {
+            assert_eq!(kind.as_str().parse::<EventKind>().unwrap(), kind);
+            assert_eq!(
+                serde_json::to_value(kind).unwrap(),
+                serde_json::json!(kind.as_str())
+            );
+        }
+        assert!("renamed".parse::<EventKind>().is_err());
+    }
+
     #[test]
     fn test_status_as_str() {
         assert_eq!(Status::Todo.as_str(), "TODO");
         assert_eq!(Status::InProgress.as_str(), "IN_PROGRESS");
+        assert_eq!(Status::Blocked.as_str(), "BLOCKED");
         assert_eq!(Status::Done.as_str(), "DONE");
         assert_eq!(Status::Cancelled.as_str(), "CANCELLED");
     }
@@ -485,6 +2666,7 @@ mod tests {
     fn test_status_from_str() {
         assert_eq!("TODO".parse::<Status>().unwrap(), Status::Todo);
         assert_eq!("IN_PROGRESS".parse::<Status>().unwrap(), Status::InProgress);
+        assert_eq!("BLOCKED".parse::<Status>().unwrap(), Status::Blocked);
         assert_eq!("DONE".parse::<Status>().unwrap(), Status::Done);
         assert_eq!("CANCELLED".parse::<Status>().unwrap(), Status::Cancelled);
         assert!("INVALID".parse::<Status>().is_err());
@@ -507,9 +2689,13 @@ mod tests {
             title: "Test wire".to_string(),
             description: Some("Test description".to_string()),
             status: Status::Todo,
-            created_at: 1704067200,
-            updated_at: 1704067200,
+            created_at_ms: 1704067200,
+            updated_at_ms: 1704067200,
             priority: 0,
+            tags: vec![],
+            cancel_reason: None,
+            blocked_reason: None,
+            assignee: None,
         };
 
         let json = serde_json::to_string(&wire).unwrap();
@@ -524,9 +2710,13 @@ mod tests {
             title: "Test wire".to_string(),
             description: None,
             status: Status::Todo,
-            created_at: 1704067200,
-            updated_at: 1704067200,
+            created_at_ms: 1704067200,
+            updated_at_ms: 1704067200,
             priority: 0,
+            tags: vec![],
+            cancel_reason: None,
+            blocked_reason: None,
+            assignee: None,
         };
 
         let json = serde_json::to_string(&wire).unwrap();
@@ -547,10 +2737,19 @@ mod tests {
             WireError::WireNotFound("abc1234".to_string()).to_string(),
             "Wire not found: abc1234"
         );
+        let info = |id: &str, title: &str| DependencyInfo {
+            id: WireId::new(id).unwrap(),
+            title: title.to_string(),
+            status: Status::Todo,
+        };
         assert_eq!(
-            WireError::CircularDependency(vec!["a".to_string(), "b".to_string(), "a".to_string()])
-                .to_string(),
-            "Circular dependency detected: a -> b -> a"
+            WireError::CircularDependency(vec![
+                info("aaaaaaa", "Parse"),
+                info("bbbbbbb", "Lex"),
+                info("aaaaaaa", "Parse"),
+            ])
+            .to_string(),
+            "Circular dependency detected: aaaaaaa \"Parse\" -> bbbbbbb \"Lex\" -> aaaaaaa \"Parse\""
         );
     }
 
@@ -0,0 +1,805 @@
+//! Plan files: a batch of wires and the dependencies between them.
+//!
+//! A plan is a JSON document listing wires that don't exist yet. Wires refer
+//! to each other in `depends_on` by their `key` (or, failing that, their
+//! exact title), so a whole dependency chain can be written down before any
+//! IDs are generated:
+//!
+//! ```json
+//! {
+//!   "wires": [
+//!     {"key": "design", "title": "Design the API"},
+//!     {"key": "build", "title": "Implement the API", "depends_on": ["design"]}
+//!   ]
+//! }
+//! ```
+//!
+//! The same wires can also be given as JSON Lines, one wire object per line
+//! (see [`Plan::from_jsonl`]), which is what `wr new --stdin` reads. There a
+//! line may also be just a title.
+//!
+//! Titles can name their dependencies inline, so a plan written in one go
+//! doesn't need keys: trailing `after:"Other title"` (or `after:key`) words
+//! are moved from the title into `depends_on`, and a quoted title is
+//! unquoted:
+//!
+//! ```text
+//! "Implement parser"
+//! "Write tests" after:"Implement parser"
+//! ```
+//!
+//! A few plans ship with `wr` as built-in templates (see [`BUILTIN_TEMPLATES`]).
+
+use crate::models::Tag;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Built-in plan templates, as `(name, JSON source)` pairs.
+pub const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    ("feature", include_str!("../templates/feature.json")),
+    ("bugfix", include_str!("../templates/bugfix.json")),
+];
+
+/// A batch of wires to create together.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Plan {
+    pub wires: Vec<PlanWire>,
+}
+
+/// A wire described by a plan.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlanWire {
+    /// Name other wires in the plan use to refer to this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    /// Keys (or titles) of wires in the same plan this one depends on
+    #[serde(default, alias = "deps", skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+impl PlanWire {
+    /// A wire with only a title.
+    pub fn titled(title: &str) -> Self {
+        PlanWire {
+            key: None,
+            title: title.to_string(),
+            description: None,
+            priority: 0,
+            tags: Vec::new(),
+            depends_on: Vec::new(),
+        }
+    }
+
+    /// Moves trailing `after:"Title"` / `after:key` words from the title to
+    /// `depends_on` (after any listed there already), and unquotes a title
+    /// written in double quotes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::plan::PlanWire;
+    ///
+    /// let mut wire = PlanWire::titled(r#""Write tests" after:"Implement parser" after:ci"#);
+    /// wire.split_inline_deps();
+    /// assert_eq!(wire.title, "Write tests");
+    /// assert_eq!(wire.depends_on, ["Implement parser", "ci"]);
+    /// ```
+    pub fn split_inline_deps(&mut self) {
+        let mut title = self.title.trim_end();
+        let mut deps = Vec::new();
+        while let Some((rest, reference)) = split_last_after(title) {
+            deps.push(reference.to_string());
+            title = rest.trim_end();
+        }
+        if deps.is_empty() {
+            return;
+        }
+
+        self.title = unquote(title.trim()).to_string();
+        self.depends_on.extend(deps.into_iter().rev());
+    }
+}
+
+/// `text` without surrounding double quotes, if it is one quoted string
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .filter(|t| !t.contains('"'))
+        .unwrap_or(text)
+}
+
+/// Splits a trailing `after:"Title"` or `after:key` word off `title`,
+/// returning the text before it and the reference.
+fn split_last_after(title: &str) -> Option<(&str, &str)> {
+    let (rest, reference) = if let Some(quoted) = title.strip_suffix('"') {
+        let open = quoted.rfind('"')?;
+        let rest = quoted[..open].strip_suffix("after:")?;
+        (rest, &quoted[open + 1..])
+    } else {
+        let start = title.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let reference = title[start..].strip_prefix("after:")?;
+        if reference.contains('"') {
+            return None;
+        }
+        (&title[..start], reference)
+    };
+
+    let at_word_start = rest.is_empty() || rest.ends_with(char::is_whitespace);
+    (at_word_start && !reference.trim().is_empty()).then_some((rest, reference))
+}
+
+/// Errors found while loading or validating a plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    /// The plan is not valid JSON or doesn't match the plan format
+    Parse(String),
+    /// No built-in template has this name
+    UnknownTemplate(String),
+    /// The wire at this position in the plan has an empty title
+    EmptyTitle(usize),
+    /// Two wires share the same key
+    DuplicateKey(String),
+    /// A `depends_on` entry matches no key or title in the plan
+    UnknownReference { wire: String, reference: String },
+    /// A `depends_on` entry matches more than one wire by title
+    AmbiguousReference { wire: String, reference: String },
+    /// The plan's dependencies form a cycle (titles, in order)
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::Parse(msg) => write!(f, "Invalid plan: {}", msg),
+            PlanError::UnknownTemplate(name) => {
+                let names: Vec<&str> = BUILTIN_TEMPLATES.iter().map(|(n, _)| *n).collect();
+                write!(
+                    f,
+                    "Unknown template '{}' (not a file; built-in templates: {})",
+                    name,
+                    names.join(", ")
+                )
+            }
+            PlanError::EmptyTitle(index) => {
+                write!(f, "Wire #{} in the plan has an empty title", index + 1)
+            }
+            PlanError::DuplicateKey(key) => write!(f, "Duplicate plan key '{}'", key),
+            PlanError::UnknownReference { wire, reference } => write!(
+                f,
+                "'{}' depends on '{}', which is not a key or title in the plan",
+                wire, reference
+            ),
+            PlanError::AmbiguousReference { wire, reference } => write!(
+                f,
+                "'{}' depends on '{}', which matches several titles in the plan (use a key)",
+                wire, reference
+            ),
+            PlanError::Cycle(titles) => {
+                write!(f, "Plan dependencies form a cycle: {}", titles.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+impl Plan {
+    /// Parses a plan from JSON, moving inline `after:` dependencies out of
+    /// titles.
+    pub fn from_json(source: &str) -> Result<Self, PlanError> {
+        let mut plan: Plan =
+            serde_json::from_str(source).map_err(|e| PlanError::Parse(e.to_string()))?;
+        plan.wires.iter_mut().for_each(PlanWire::split_inline_deps);
+        Ok(plan)
+    }
+
+    /// Parses a plan from JSON Lines: one wire object per line, blank lines
+    /// ignored. A line that isn't a JSON object is a wire's title, and inline
+    /// `after:` dependencies are moved out of titles as in [`Plan::from_json`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::plan::Plan;
+    ///
+    /// let plan = Plan::from_jsonl(
+    ///     "{\"key\": \"a\", \"title\": \"Parse input\"}\n\
+    ///      {\"title\": \"Write tests\", \"deps\": [\"a\"]}\n\
+    ///      \"Release\" after:\"Write tests\"\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(plan.wires[2].title, "Release");
+    /// assert_eq!(plan.resolve().unwrap(), vec![vec![], vec![0], vec![1]]);
+    /// ```
+    pub fn from_jsonl(source: &str) -> Result<Self, PlanError> {
+        let wires = source
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let mut wire = if line.trim_start().starts_with('{') {
+                    serde_json::from_str(line)
+                        .map_err(|e| PlanError::Parse(format!("line {}: {}", i + 1, e)))?
+                } else {
+                    PlanWire::titled(unquote(line.trim()))
+                };
+                wire.split_inline_deps();
+                Ok(wire)
+            })
+            .collect::<Result<Vec<PlanWire>, _>>()?;
+        Ok(Plan { wires })
+    }
+
+    /// Loads the built-in template called `name`.
+    pub fn builtin(name: &str) -> Result<Self, PlanError> {
+        let (_, source) = BUILTIN_TEMPLATES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .ok_or_else(|| PlanError::UnknownTemplate(name.to_string()))?;
+        Plan::from_json(source)
+    }
+
+    /// Resolves every `depends_on` reference to an index into `wires`.
+    ///
+    /// The result has one entry per wire, listing the indices of the wires it
+    /// depends on. References match a key first, then an exact title.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for empty titles, duplicate keys, references that
+    /// match nothing (or several titles), and dependency cycles, so a valid
+    /// plan can always be applied in full.
+    pub fn resolve(&self) -> Result<Vec<Vec<usize>>, PlanError> {
+        let mut keys: HashMap<&str, usize> = HashMap::new();
+        let mut titles: HashMap<&str, Vec<usize>> = HashMap::new();
+
+        for (i, wire) in self.wires.iter().enumerate() {
+            if wire.title.trim().is_empty() {
+                return Err(PlanError::EmptyTitle(i));
+            }
+            if let Some(key) = &wire.key {
+                if keys.insert(key.as_str(), i).is_some() {
+                    return Err(PlanError::DuplicateKey(key.clone()));
+                }
+            }
+            titles.entry(wire.title.trim()).or_default().push(i);
+        }
+
+        let mut edges = Vec::with_capacity(self.wires.len());
+        for wire in &self.wires {
+            let mut deps = Vec::new();
+            for reference in &wire.depends_on {
+                let index = match keys.get(reference.as_str()) {
+                    Some(&i) => i,
+                    None => match titles.get(reference.trim()).map(Vec::as_slice) {
+                        Some([i]) => *i,
+                        Some(_) => {
+                            return Err(PlanError::AmbiguousReference {
+                                wire: wire.title.clone(),
+                                reference: reference.clone(),
+                            })
+                        }
+                        None => {
+                            return Err(PlanError::UnknownReference {
+                                wire: wire.title.clone(),
+                                reference: reference.clone(),
+                            })
+                        }
+                    },
+                };
+                if !deps.contains(&index) {
+                    deps.push(index);
+                }
+            }
+            edges.push(deps);
+        }
+
+        if let Some(cycle) = find_cycle(&edges) {
+            return Err(PlanError::Cycle(
+                cycle
+                    .into_iter()
+                    .map(|i| self.wires[i].title.clone())
+                    .collect(),
+            ));
+        }
+
+        Ok(edges)
+    }
+}
+
+/// Returns the wires along a dependency cycle, starting and ending at the
+/// same wire, if there is one.
+fn find_cycle(edges: &[Vec<usize>]) -> Option<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        New,
+        Active,
+        Finished,
+    }
+
+    fn visit(node: usize, edges: &[Vec<usize>], marks: &mut [Mark], path: &mut Vec<usize>) -> bool {
+        marks[node] = Mark::Active;
+        path.push(node);
+        for &next in &edges[node] {
+            let mark = marks[next];
+            match mark {
+                Mark::Active => {
+                    let start = path.iter().position(|&n| n == next).unwrap_or(0);
+                    path.drain(..start);
+                    path.push(next);
+                    return true;
+                }
+                Mark::New if visit(next, edges, marks, path) => return true,
+                _ => {}
+            }
+        }
+        path.pop();
+        marks[node] = Mark::Finished;
+        false
+    }
+
+    let mut marks = vec![Mark::New; edges.len()];
+    for node in 0..edges.len() {
+        let mut path = Vec::new();
+        if marks[node] == Mark::New && visit(node, edges, &mut marks, &mut path) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+impl From<&crate::models::Snapshot> for Plan {
+    /// The wires of an `wr export` snapshot as a plan, keyed by wire ID.
+    /// Only blocking dependencies carry over; plans have no other kind.
+    fn from(snapshot: &crate::models::Snapshot) -> Self {
+        let wires = snapshot
+            .wires
+            .iter()
+            .map(|entry| &entry.wire)
+            .map(|wire| PlanWire {
+                key: Some(wire.id.to_string()),
+                title: wire.title.clone(),
+                description: wire.description.clone(),
+                priority: wire.priority,
+                tags: wire.tags.clone(),
+                depends_on: snapshot
+                    .dependencies
+                    .iter()
+                    .filter(|dep| dep.wire_id == wire.id && dep.kind.is_blocking())
+                    .map(|dep| dep.depends_on.to_string())
+                    .collect(),
+            })
+            .collect();
+        Plan { wires }
+    }
+}
+
+/// A wire on one side of a [`PlanDiff`]: its key (or, without one, its
+/// title) and its title.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanNode {
+    pub name: String,
+    pub title: String,
+}
+
+/// A wire present in both plans whose details differ.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlanNodeChange {
+    #[serde(flatten)]
+    pub node: PlanNode,
+    /// Names of the fields that differ: title, description, priority, tags
+    pub fields: Vec<&'static str>,
+}
+
+/// A dependency edge: `wire` depends on `depends_on`, both by name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct PlanEdge {
+    pub wire: String,
+    pub depends_on: String,
+}
+
+/// How one plan differs from another, as wires and dependency edges added,
+/// removed or changed.
+///
+/// Wires are matched by key, then by exact title. Added wires and edges use
+/// the new plan's names; removed ones, the old plan's.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PlanDiff {
+    pub added: Vec<PlanNode>,
+    pub removed: Vec<PlanNode>,
+    pub changed: Vec<PlanNodeChange>,
+    pub added_edges: Vec<PlanEdge>,
+    pub removed_edges: Vec<PlanEdge>,
+    /// Graph edit distance: one per wire or edge added or removed, and per
+    /// wire changed
+    pub distance: usize,
+}
+
+impl PlanDiff {
+    /// Compares `old` with `new`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either plan doesn't [resolve](Plan::resolve).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wr::plan::{Plan, PlanDiff};
+    ///
+    /// let old = Plan::from_jsonl("Design\nBuild after:Design\n").unwrap();
+    /// let new = Plan::from_jsonl("Design\nTest after:Design\nBuild after:Test\n").unwrap();
+    /// let diff = PlanDiff::between(&old, &new).unwrap();
+    /// assert_eq!(diff.added[0].title, "Test");
+    /// assert_eq!(diff.removed_edges[0].wire, "Build");
+    /// assert_eq!(diff.distance, 4);
+    /// ```
+    pub fn between(old: &Plan, new: &Plan) -> Result<Self, PlanError> {
+        let old_edges = old.resolve()?;
+        let new_edges = new.resolve()?;
+
+        // For each new wire, the old wire it matches
+        let mut matched: Vec<Option<usize>> = vec![None; new.wires.len()];
+        let mut taken = vec![false; old.wires.len()];
+        let by_key = |a: &PlanWire, b: &PlanWire| a.key.is_some() && a.key == b.key;
+        let by_title = |a: &PlanWire, b: &PlanWire| a.title.trim() == b.title.trim();
+        for same in [&by_key as &dyn Fn(&PlanWire, &PlanWire) -> bool, &by_title] {
+            for (j, wire) in new.wires.iter().enumerate() {
+                if matched[j].is_some() {
+                    continue;
+                }
+                if let Some(i) =
+                    (0..old.wires.len()).find(|&i| !taken[i] && same(&old.wires[i], wire))
+                {
+                    matched[j] = Some(i);
+                    taken[i] = true;
+                }
+            }
+        }
+
+        let mut diff = PlanDiff::default();
+        for (j, wire) in new.wires.iter().enumerate() {
+            match matched[j] {
+                None => diff.added.push(PlanNode::of(wire)),
+                Some(i) => {
+                    let before = &old.wires[i];
+                    let fields: Vec<_> = [
+                        ("title", before.title.trim() != wire.title.trim()),
+                        ("description", before.description != wire.description),
+                        ("priority", before.priority != wire.priority),
+                        ("tags", sorted(&before.tags) != sorted(&wire.tags)),
+                    ]
+                    .into_iter()
+                    .filter_map(|(field, differs)| differs.then_some(field))
+                    .collect();
+                    if !fields.is_empty() {
+                        diff.changed.push(PlanNodeChange {
+                            node: PlanNode::of(wire),
+                            fields,
+                        });
+                    }
+                }
+            }
+        }
+        diff.removed = (0..old.wires.len())
+            .filter(|&i| !taken[i])
+            .map(|i| PlanNode::of(&old.wires[i]))
+            .collect();
+
+        // Old edges in terms of new wire indices, where both ends matched
+        let old_index: HashMap<usize, usize> = matched
+            .iter()
+            .enumerate()
+            .filter_map(|(j, i)| i.map(|i| (i, j)))
+            .collect();
+        let carried: Vec<(usize, usize)> = old_edges
+            .iter()
+            .enumerate()
+            .flat_map(|(i, deps)| deps.iter().map(move |&d| (i, d)))
+            .filter_map(|(i, d)| Some((*old_index.get(&i)?, *old_index.get(&d)?)))
+            .collect();
+        let edge = |plan: &Plan, wire: usize, dep: usize| PlanEdge {
+            wire: PlanNode::of(&plan.wires[wire]).name,
+            depends_on: PlanNode::of(&plan.wires[dep]).name,
+        };
+        for (j, deps) in new_edges.iter().enumerate() {
+            for &d in deps {
+                if !carried.contains(&(j, d)) {
+                    diff.added_edges.push(edge(new, j, d));
+                }
+            }
+        }
+        for (i, deps) in old_edges.iter().enumerate() {
+            for &d in deps {
+                let kept = matches!(
+                    (old_index.get(&i), old_index.get(&d)),
+                    (Some(&j), Some(&e)) if new_edges[j].contains(&e)
+                );
+                if !kept {
+                    diff.removed_edges.push(edge(old, i, d));
+                }
+            }
+        }
+        diff.added_edges.sort();
+        diff.removed_edges.sort();
+
+        diff.distance = diff.added.len()
+            + diff.removed.len()
+            + diff.changed.len()
+            + diff.added_edges.len()
+            + diff.removed_edges.len();
+        Ok(diff)
+    }
+
+    /// Whether the plans have the same wires and dependencies.
+    pub fn is_empty(&self) -> bool {
+        self.distance == 0
+    }
+}
+
+impl PlanNode {
+    fn of(wire: &PlanWire) -> Self {
+        PlanNode {
+            name: wire
+                .key
+                .clone()
+                .unwrap_or_else(|| wire.title.trim().to_string()),
+            title: wire.title.trim().to_string(),
+        }
+    }
+}
+
+/// `tags` in order, without duplicates
+fn sorted(tags: &[Tag]) -> Vec<&Tag> {
+    let mut tags: Vec<&Tag> = tags.iter().collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wire(key: &str, title: &str, depends_on: &[&str]) -> PlanWire {
+        PlanWire {
+            key: Some(key.to_string()),
+            title: title.to_string(),
+            description: None,
+            priority: 0,
+            tags: vec![],
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_builtin_templates_are_valid() {
+        for (name, _) in BUILTIN_TEMPLATES {
+            let plan = Plan::builtin(name).unwrap();
+            assert!(!plan.wires.is_empty());
+            plan.resolve().unwrap();
+        }
+        assert!(matches!(
+            Plan::builtin("nope"),
+            Err(PlanError::UnknownTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_by_key_and_title() {
+        let plan = Plan {
+            wires: vec![
+                wire("a", "First", &[]),
+                wire("b", "Second", &["a"]),
+                wire("c", "Third", &["First", "b"]),
+            ],
+        };
+        assert_eq!(plan.resolve().unwrap(), vec![vec![], vec![0], vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_resolve_rejects_bad_references() {
+        let plan = Plan {
+            wires: vec![wire("a", "First", &["missing"])],
+        };
+        assert!(matches!(
+            plan.resolve(),
+            Err(PlanError::UnknownReference { .. })
+        ));
+
+        let plan = Plan {
+            wires: vec![wire("a", "First", &[]), wire("a", "Second", &[])],
+        };
+        assert_eq!(
+            plan.resolve(),
+            Err(PlanError::DuplicateKey("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_resolve_detects_cycles() {
+        let plan = Plan {
+            wires: vec![
+                wire("a", "First", &["c"]),
+                wire("b", "Second", &["a"]),
+                wire("c", "Third", &["b"]),
+            ],
+        };
+        assert_eq!(
+            plan.resolve(),
+            Err(PlanError::Cycle(vec![
+                "First".to_string(),
+                "Third".to_string(),
+                "Second".to_string(),
+                "First".to_string(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_from_jsonl_reports_line_numbers() {
+        let plan =
+            Plan::from_jsonl("{\"title\": \"a\"}\n\n{\"title\": \"b\", \"deps\": [\"a\"]}\n")
+                .unwrap();
+        assert_eq!(plan.wires.len(), 2);
+        assert_eq!(plan.wires[1].depends_on, vec!["a".to_string()]);
+
+        match Plan::from_jsonl("{\"title\": \"a\"}\n{not json\n") {
+            Err(PlanError::Parse(msg)) => assert!(msg.starts_with("line 2:"), "{}", msg),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_split_inline_deps() {
+        let split = |title: &str| {
+            let mut wire = PlanWire::titled(title);
+            wire.split_inline_deps();
+            (wire.title, wire.depends_on)
+        };
+
+        assert_eq!(
+            split(r#""Write tests" after:"Implement parser""#),
+            (
+                "Write tests".to_string(),
+                vec!["Implement parser".to_string()]
+            )
+        );
+        assert_eq!(
+            split("Deploy after:build after:\"Write docs\""),
+            (
+                "Deploy".to_string(),
+                vec!["build".to_string(), "Write docs".to_string()]
+            )
+        );
+        // Only whole trailing words count
+        assert_eq!(split("Clean up thereafter:now").1, Vec::<String>::new());
+        assert_eq!(split("Run after:x now").1, Vec::<String>::new());
+        assert_eq!(split("\"Quoted\" title").0, "\"Quoted\" title");
+        assert_eq!(split("after:\"\"").1, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_from_jsonl_accepts_title_lines() {
+        let plan = Plan::from_jsonl(
+            "\"Implement parser\"\n\
+             {\"title\": \"Write docs after:\\\"Implement parser\\\"\", \"priority\": 2}\n\
+             \"Write tests\" after:\"Implement parser\"\n",
+        )
+        .unwrap();
+        assert_eq!(plan.wires[0].title, "Implement parser");
+        assert_eq!(plan.wires[1].title, "Write docs");
+        assert_eq!(plan.wires[1].priority, 2);
+        assert_eq!(plan.wires[2].title, "Write tests");
+        assert_eq!(plan.resolve().unwrap(), vec![vec![], vec![0], vec![0]]);
+    }
+
+    #[test]
+    fn test_from_json_rejects_unknown_fields() {
+        assert!(Plan::from_json(r#"{"wires": [{"title": "x", "bogus": 1}]}"#).is_err());
+        let plan = Plan::from_json(r#"{"wires": [{"title": "x", "tags": ["Docs"]}]}"#).unwrap();
+        assert_eq!(plan.wires[0].tags[0].as_str(), "docs");
+    }
+
+    #[test]
+    fn test_diff_of_identical_plans_is_empty() {
+        let plan = Plan {
+            wires: vec![wire("a", "A", &[]), wire("b", "B", &["a"])],
+        };
+        let diff = PlanDiff::between(&plan, &plan).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff, PlanDiff::default());
+    }
+
+    #[test]
+    fn test_diff_matches_by_key_then_title() {
+        let old = Plan {
+            wires: vec![
+                wire("a", "Parse input", &[]),
+                wire("b", "Write tests", &["a"]),
+                wire("c", "Old step", &["a"]),
+            ],
+        };
+        let mut renamed = wire("a", "Parse all input", &[]);
+        renamed.priority = 2;
+        let new = Plan {
+            wires: vec![
+                renamed,
+                // Same title, new key: still the same wire
+                wire("tests", "Write tests", &["a", "docs"]),
+                wire("docs", "Write docs", &[]),
+            ],
+        };
+
+        let diff = PlanDiff::between(&old, &new).unwrap();
+        let node = |name: &str, title: &str| PlanNode {
+            name: name.to_string(),
+            title: title.to_string(),
+        };
+        let edge = |wire: &str, depends_on: &str| PlanEdge {
+            wire: wire.to_string(),
+            depends_on: depends_on.to_string(),
+        };
+        assert_eq!(diff.added, vec![node("docs", "Write docs")]);
+        assert_eq!(diff.removed, vec![node("c", "Old step")]);
+        assert_eq!(
+            diff.changed,
+            vec![PlanNodeChange {
+                node: node("a", "Parse all input"),
+                fields: vec!["title", "priority"],
+            }]
+        );
+        assert_eq!(diff.added_edges, vec![edge("tests", "docs")]);
+        assert_eq!(diff.removed_edges, vec![edge("c", "a")]);
+        assert_eq!(diff.distance, 5);
+    }
+
+    #[test]
+    fn test_diff_rejects_invalid_plan() {
+        let good = Plan {
+            wires: vec![wire("a", "A", &[])],
+        };
+        let bad = Plan {
+            wires: vec![wire("a", "A", &["missing"])],
+        };
+        assert!(matches!(
+            PlanDiff::between(&good, &bad),
+            Err(PlanError::UnknownReference { .. })
+        ));
+    }
+
+    #[test]
+    fn test_plan_from_snapshot_keys_by_id() {
+        use crate::models::{Dependency, Snapshot, Wire, WireId};
+
+        let mut first = Wire::new("First", None, 0).unwrap();
+        first.id = WireId::new("aaaaaaa").unwrap();
+        let mut second = Wire::new("Second", None, 1).unwrap();
+        second.id = WireId::new("bbbbbbb").unwrap();
+        let snapshot = Snapshot {
+            version: Snapshot::VERSION,
+            wires: vec![first.into(), second.into()],
+            dependencies: vec![Dependency {
+                wire_id: WireId::new("bbbbbbb").unwrap(),
+                depends_on: WireId::new("aaaaaaa").unwrap(),
+                kind: Default::default(),
+            }],
+            manifest: None,
+        };
+
+        let plan = Plan::from(&snapshot);
+        assert_eq!(plan.wires[1].key.as_deref(), Some("bbbbbbb"));
+        assert_eq!(plan.wires[1].priority, 1);
+        assert_eq!(plan.resolve().unwrap(), vec![vec![], vec![0]]);
+    }
+}
@@ -0,0 +1,366 @@
+//! A high-level handle on a wires repository for library users.
+//!
+//! [`WireStore`] owns the database connection and exposes the common
+//! operations with typed arguments, so embedding wires doesn't require
+//! touching `rusqlite` or the free functions in [`db`].
+//!
+//! ```no_run
+//! use wr::models::Status;
+//! use wr::store::{ListFilter, WireStore};
+//!
+//! let store = WireStore::open()?;
+//! let schema = store.create("Design schema", None, 1)?;
+//! let api = store.create("Build API", Some("REST endpoints"), 0)?;
+//! store.add_dep(&api.id, &schema.id)?;
+//!
+//! assert_eq!(store.ready()?.len(), 1);
+//! store.set_status(&schema.id, Status::Done)?;
+//!
+//! let todo = store.list(&ListFilter {
+//!     status: Some(Status::Todo),
+//!     ..Default::default()
+//! })?;
+//! assert_eq!(todo[0].id, api.id);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use anyhow::Result;
+use rusqlite::Connection;
+use std::path::Path;
+
+use crate::db;
+#[cfg(doc)]
+use crate::models::WireError;
+use crate::models::{Agent, Status, Tag, TransitiveDependency, Wire, WireId, WireWithDeps};
+
+/// Which wires [`WireStore::list`] returns. The default is every wire that
+/// isn't archived.
+#[derive(Debug, Clone, Default)]
+pub struct ListFilter {
+    /// Only wires with this status
+    pub status: Option<Status>,
+    /// Only wires carrying this tag
+    pub tag: Option<Tag>,
+    /// Only wires assigned to this agent
+    pub assignee: Option<Agent>,
+    /// Include archived wires
+    pub include_archived: bool,
+}
+
+/// An open wires repository.
+///
+/// Changes are checked against the repository's `[permissions]` as the
+/// store's agent, which is `WR_AGENT` unless [`WireStore::as_agent`] sets
+/// one, the same way `wr` checks its commands.
+#[derive(Debug)]
+pub struct WireStore {
+    conn: Connection,
+    agent: Option<String>,
+}
+
+impl WireStore {
+    /// Opens the repository containing the current directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::NotARepository`] if there is none.
+    pub fn open() -> Result<Self> {
+        Ok(WireStore {
+            conn: db::open()?,
+            agent: default_agent(),
+        })
+    }
+
+    /// Opens the repository containing `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::NotARepository`] if there is none.
+    pub fn open_at(path: &Path) -> Result<Self> {
+        Ok(WireStore {
+            conn: db::open_at(path)?,
+            agent: default_agent(),
+        })
+    }
+
+    /// Creates a repository in `path` and opens it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` already holds a repository.
+    pub fn init(path: &Path) -> Result<Self> {
+        db::init(path)?;
+        Self::open_at(path)
+    }
+
+    /// Acts as `agent` for `[permissions]`, instead of `WR_AGENT`.
+    pub fn as_agent(mut self, agent: &Agent) -> Self {
+        self.agent = Some(agent.to_string());
+        self
+    }
+
+    /// Checks the store's agent may run the `wr` command `operation`.
+    fn authorize(&self, operation: &str) -> Result<()> {
+        db::authorize(&self.conn, self.agent.as_deref(), operation)
+    }
+
+    /// Creates a TODO wire.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the title is empty, or
+    /// [`WireError::PermissionDenied`] if the agent may not run `wr new`.
+    pub fn create(&self, title: &str, description: Option<&str>, priority: i32) -> Result<Wire> {
+        self.authorize("new")?;
+        let mut wire = Wire::new(title, description, priority)?;
+        wire.id = db::id_scheme(&self.conn)?.generate(&wire.title);
+        db::insert_wire(&self.conn, &mut wire)?;
+        Ok(wire)
+    }
+
+    /// Gets a wire with its dependencies, or `None` if it doesn't exist.
+    pub fn get(&self, id: &WireId) -> Result<Option<WireWithDeps>> {
+        if db::get_wire(&self.conn, id.as_str())?.is_none() {
+            return Ok(None);
+        }
+        db::get_wire_with_deps(&self.conn, id.as_str()).map(Some)
+    }
+
+    /// Lists wires matching `filter`, highest priority first.
+    pub fn list(&self, filter: &ListFilter) -> Result<Vec<Wire>> {
+        let filter = db::WireFilter {
+            statuses: filter.status.into_iter().collect(),
+            tag: filter.tag.clone(),
+            assignee: filter.assignee.clone(),
+            include_archived: filter.include_archived,
+            ..Default::default()
+        };
+        let wires = db::filter_wires(&self.conn, &filter)?;
+        Ok(wires.into_iter().map(|wd| wd.wire).collect())
+    }
+
+    /// Lists wires ready to work on, as `wr ready` does.
+    pub fn ready(&self) -> Result<Vec<Wire>> {
+        db::get_ready_wires(&self.conn)
+    }
+
+    /// Sets a wire's status. Permissions treat it as the command for that
+    /// status (`start`, `done`, ...), or `update` for TODO.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+    pub fn set_status(&self, id: &WireId, status: Status) -> Result<()> {
+        self.authorize(match status {
+            Status::Todo => "update",
+            Status::InProgress => "start",
+            Status::Paused => "pause",
+            Status::Blocked => "block",
+            Status::Done => "done",
+            Status::Cancelled => "cancel",
+        })?;
+        db::update_wire(&self.conn, id.as_str(), None, None, Some(status), None)
+    }
+
+    /// Sets a wire's priority.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+    pub fn set_priority(&self, id: &WireId, priority: i32) -> Result<()> {
+        self.authorize("update")?;
+        db::update_wire(&self.conn, id.as_str(), None, None, None, Some(priority))
+    }
+
+    /// Assigns a wire to `assignee`, or unassigns it when `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+    pub fn assign(&self, id: &WireId, assignee: Option<&Agent>) -> Result<()> {
+        self.authorize(if assignee.is_some() {
+            "assign"
+        } else {
+            "unassign"
+        })?;
+        db::assign_wire(&self.conn, id.as_str(), assignee)
+    }
+
+    /// Makes `id` depend on `depends_on`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::WireNotFound`] if either wire doesn't exist, or
+    /// [`WireError::CircularDependency`] if the dependency would close a cycle.
+    pub fn add_dep(&self, id: &WireId, depends_on: &WireId) -> Result<()> {
+        self.authorize("dep")?;
+        db::add_dependency(&self.conn, id.as_str(), depends_on.as_str())
+    }
+
+    /// Removes the dependency of `id` on `depends_on`, if there is one.
+    pub fn remove_dep(&self, id: &WireId, depends_on: &WireId) -> Result<()> {
+        self.authorize("undep")?;
+        db::remove_dependency(&self.conn, id.as_str(), depends_on.as_str())
+    }
+
+    /// Everything `id` depends on, directly or not, nearest first.
+    pub fn transitive_dependencies(&self, id: &WireId) -> Result<Vec<TransitiveDependency>> {
+        db::get_transitive_dependencies(&self.conn, id.as_str())
+    }
+
+    /// Everything that depends on `id`, directly or not, nearest first.
+    pub fn transitive_dependents(&self, id: &WireId) -> Result<Vec<TransitiveDependency>> {
+        db::get_transitive_dependents(&self.conn, id.as_str())
+    }
+
+    /// Adds a tag to a wire. Returns `false` if it already had it.
+    pub fn add_tag(&self, id: &WireId, tag: &Tag) -> Result<bool> {
+        self.authorize("tag add")?;
+        db::add_tag(&self.conn, id.as_str(), tag)
+    }
+
+    /// Removes a tag from a wire. Returns `false` if it didn't have it.
+    pub fn remove_tag(&self, id: &WireId, tag: &Tag) -> Result<bool> {
+        self.authorize("tag rm")?;
+        db::remove_tag(&self.conn, id.as_str(), tag)
+    }
+
+    /// Deletes a wire and its dependency edges.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WireError::WireNotFound`] if the wire doesn't exist.
+    pub fn delete(&self, id: &WireId) -> Result<()> {
+        self.authorize("rm")?;
+        db::delete_wire(&self.conn, id.as_str())
+    }
+}
+
+/// `WR_AGENT`, if set and not empty
+fn default_agent() -> Option<String> {
+    std::env::var("WR_AGENT")
+        .ok()
+        .filter(|agent| !agent.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WireError;
+    use tempfile::TempDir;
+
+    fn setup_store() -> (TempDir, WireStore) {
+        let temp_dir = TempDir::new().unwrap();
+        let store = WireStore::init(temp_dir.path()).unwrap();
+        (temp_dir, store)
+    }
+
+    #[test]
+    fn test_create_get_and_set_status() {
+        let (_temp_dir, store) = setup_store();
+        let wire = store.create("Write docs", None, 2).unwrap();
+
+        let found = store.get(&wire.id).unwrap().unwrap();
+        assert_eq!(found.wire.title, "Write docs");
+        assert_eq!(found.wire.priority, 2);
+
+        store.set_status(&wire.id, Status::Done).unwrap();
+        assert_eq!(
+            store.get(&wire.id).unwrap().unwrap().wire.status,
+            Status::Done
+        );
+
+        let missing = WireId::new("0000000").unwrap();
+        assert!(store.get(&missing).unwrap().is_none());
+        assert!(store.set_status(&missing, Status::Done).is_err());
+    }
+
+    #[test]
+    fn test_dependencies_and_ready() {
+        let (_temp_dir, store) = setup_store();
+        let schema = store.create("Schema", None, 0).unwrap();
+        let api = store.create("API", None, 0).unwrap();
+        store.add_dep(&api.id, &schema.id).unwrap();
+        assert!(store.add_dep(&schema.id, &api.id).is_err());
+
+        let ready: Vec<WireId> = store.ready().unwrap().into_iter().map(|w| w.id).collect();
+        assert_eq!(ready, vec![schema.id.clone()]);
+        assert_eq!(store.transitive_dependencies(&api.id).unwrap().len(), 1);
+
+        store.remove_dep(&api.id, &schema.id).unwrap();
+        assert_eq!(store.ready().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_list_filter() {
+        let (_temp_dir, store) = setup_store();
+        let tagged = store.create("Tagged", None, 0).unwrap();
+        let done = store.create("Done", None, 0).unwrap();
+        store
+            .add_tag(&tagged.id, &Tag::new("backend").unwrap())
+            .unwrap();
+        store.set_status(&done.id, Status::Done).unwrap();
+
+        let by_tag = store
+            .list(&ListFilter {
+                tag: Some(Tag::new("backend").unwrap()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_tag.len(), 1);
+        assert_eq!(by_tag[0].id, tagged.id);
+
+        let by_status = store
+            .list(&ListFilter {
+                status: Some(Status::Done),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_status[0].id, done.id);
+        assert_eq!(store.list(&ListFilter::default()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_permissions_apply_to_the_store_agent() {
+        let (temp_dir, store) = setup_store();
+        let wire = store.create("Write docs", None, 0).unwrap();
+        std::fs::write(
+            temp_dir
+                .path()
+                .join(".wires")
+                .join(crate::config::CONFIG_NAME),
+            "[permissions]\nworker = [\"start\", \"done\"]\n",
+        )
+        .unwrap();
+
+        let store = store.as_agent(&Agent::new("worker").unwrap());
+        store.set_status(&wire.id, Status::InProgress).unwrap();
+        let err = store.create("More docs", None, 0).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<WireError>(),
+            Some(WireError::PermissionDenied { .. })
+        ));
+        assert!(store.delete(&wire.id).is_err());
+        assert!(store.get(&wire.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_create_normalizes_title() {
+        let (temp_dir, store) = setup_store();
+        std::fs::write(
+            temp_dir
+                .path()
+                .join(".wires")
+                .join(crate::config::CONFIG_NAME),
+            "[titles]\ncollapse_whitespace = true\n",
+        )
+        .unwrap();
+
+        let wire = store.create("Write   the docs", None, 0).unwrap();
+        assert_eq!(wire.title, "Write the docs");
+        assert_eq!(
+            store.get(&wire.id).unwrap().unwrap().wire.title,
+            "Write the docs"
+        );
+    }
+}
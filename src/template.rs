@@ -0,0 +1,405 @@
+//! Variable expansion for wire titles and descriptions, and output
+//! templates for `--template`.
+//!
+//! Title and description templates use `{name}` placeholders which are
+//! replaced with values supplied as `--var name=value`. Literal braces are
+//! written as `{{` and `}}`.
+//!
+//! Output templates ([`OutputTemplate`]) instead use `{{field}}`
+//! placeholders naming wire fields, like `{{id}} {{status}} {{title}}`, so
+//! single braces pass through unchanged.
+
+use crate::format::{wire_column, WireColumn, WIRE_COLUMNS};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A single `name=value` template variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateVar {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for TemplateVar {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| TemplateError::InvalidVar(s.to_string()))?;
+        let name = name.trim();
+        if !is_valid_name(name) {
+            return Err(TemplateError::InvalidVar(s.to_string()));
+        }
+        Ok(TemplateVar {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+/// A set of template variables, keyed by name. Later definitions win.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vars(BTreeMap<String, String>);
+
+impl Vars {
+    pub fn new() -> Self {
+        Vars(BTreeMap::new())
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<TemplateVar> for Vars {
+    fn from_iter<I: IntoIterator<Item = TemplateVar>>(iter: I) -> Self {
+        let mut vars = Vars::new();
+        for var in iter {
+            vars.insert(var.name, var.value);
+        }
+        vars
+    }
+}
+
+/// Errors that can occur while parsing variables or expanding a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `--var` argument was not of the form `name=value`
+    InvalidVar(String),
+    /// The template references a variable that was not supplied
+    UndefinedVariable(String),
+    /// A `{` without a matching `}`, or a stray `}`
+    UnbalancedBrace(usize),
+    /// An output template has a `{{` without a matching `}}`
+    UnclosedPlaceholder(usize),
+    /// An output template names a field wires don't have
+    UnknownField(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::InvalidVar(s) => write!(
+                f,
+                "Invalid variable '{}': expected name=value (name uses letters, digits, '_' or '-')",
+                s
+            ),
+            TemplateError::UndefinedVariable(name) => {
+                write!(f, "Undefined template variable: {{{}}}", name)
+            }
+            TemplateError::UnbalancedBrace(pos) => write!(
+                f,
+                "Unbalanced brace at position {} (use {{{{ or }}}} for a literal brace)",
+                pos
+            ),
+            TemplateError::UnclosedPlaceholder(pos) => {
+                write!(f, "Unclosed {{{{ at position {}", pos)
+            }
+            TemplateError::UnknownField(name) => write!(
+                f,
+                "Unknown template field '{}' (known: {})",
+                name,
+                WIRE_COLUMNS
+                    .iter()
+                    .map(|column| column.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Expand `{name}` placeholders in `template` using `vars`.
+///
+/// # Errors
+///
+/// Returns an error if a placeholder names a variable not in `vars`,
+/// or if braces are unbalanced.
+///
+/// # Example
+///
+/// ```
+/// use wr::template::{expand, Vars};
+///
+/// let mut vars = Vars::new();
+/// vars.insert("module", "parser");
+/// assert_eq!(expand("Fix {module} panics", &vars).unwrap(), "Fix parser panics");
+/// assert_eq!(expand("Keep {{braces}}", &vars).unwrap(), "Keep {braces}");
+/// ```
+pub fn expand(template: &str, vars: &Vars) -> Result<String, TemplateError> {
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|&(_, c)| c) == Some('{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek().map(|&(_, c)| c) == Some('}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, c)) if c != '{' => name.push(c),
+                        _ => return Err(TemplateError::UnbalancedBrace(pos)),
+                    }
+                }
+                let name = name.trim();
+                let value = vars
+                    .get(name)
+                    .ok_or_else(|| TemplateError::UndefinedVariable(name.to_string()))?;
+                output.push_str(value);
+            }
+            '}' => return Err(TemplateError::UnbalancedBrace(pos)),
+            _ => output.push(c),
+        }
+    }
+
+    Ok(output)
+}
+
+/// A `--template` for printing wires, parsed once and rendered per wire.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::Wire;
+/// use wr::template::OutputTemplate;
+///
+/// let template: OutputTemplate = "Refs: {{ id }} ({{status}})".parse().unwrap();
+/// let wire = Wire::new("Fix parser", None, 0).unwrap();
+/// assert_eq!(template.render(&wire), format!("Refs: {} (TODO)", wire.id));
+/// assert!("{{owner}}".parse::<OutputTemplate>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate(Vec<Segment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Field(&'static WireColumn),
+}
+
+impl OutputTemplate {
+    /// Renders the template for one wire.
+    pub fn render(&self, wire: &crate::models::Wire) -> String {
+        let mut output = String::new();
+        for segment in &self.0 {
+            match segment {
+                Segment::Text(text) => output.push_str(text),
+                Segment::Field(column) => output.push_str(&column.value(wire)),
+            }
+        }
+        output
+    }
+}
+
+impl FromStr for OutputTemplate {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut rest = s;
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_string()));
+            }
+            let after = &rest[start + 2..];
+            let end = after.find("}}").ok_or(TemplateError::UnclosedPlaceholder(
+                s.len() - rest.len() + start,
+            ))?;
+            let name = after[..end].trim();
+            let column =
+                wire_column(name).ok_or_else(|| TemplateError::UnknownField(name.to_string()))?;
+            segments.push(Segment::Field(column));
+            rest = &after[end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+        Ok(OutputTemplate(segments))
+    }
+}
+
+/// Wire fields picked for one column each, as in `--columns id,title,status`.
+///
+/// Names are those of the [`WIRE_COLUMNS`], the same fields
+/// [`OutputTemplate`] can name.
+///
+/// # Example
+///
+/// ```
+/// use wr::models::Wire;
+/// use wr::template::Columns;
+///
+/// let columns: Columns = "id, status".parse().unwrap();
+/// let wire = Wire::new("Fix parser", None, 0).unwrap();
+/// assert_eq!(columns.values(&wire), vec![wire.id.to_string(), "TODO".to_string()]);
+/// assert!("id,owner".parse::<Columns>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns(Vec<&'static WireColumn>);
+
+impl Columns {
+    /// The field names, in order
+    pub fn names(&self) -> Vec<&'static str> {
+        self.0.iter().map(|column| column.name).collect()
+    }
+
+    /// The columns, in order
+    pub fn columns(&self) -> &[&'static WireColumn] {
+        &self.0
+    }
+
+    /// The wire's value for each column.
+    pub fn values(&self, wire: &crate::models::Wire) -> Vec<String> {
+        self.0.iter().map(|column| column.value(wire)).collect()
+    }
+}
+
+impl Default for Columns {
+    /// `id`, `title`, `status`, `priority`, `tags` and `assignee`
+    fn default() -> Self {
+        "id,title,status,priority,tags,assignee"
+            .parse()
+            .expect("default columns exist")
+    }
+}
+
+impl FromStr for Columns {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|name| {
+                let name = name.trim();
+                wire_column(name).ok_or_else(|| TemplateError::UnknownField(name.to_string()))
+            })
+            .collect::<Result<_, _>>()
+            .map(Columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vars {
+        let mut vars = Vars::new();
+        for (name, value) in pairs {
+            vars.insert(*name, *value);
+        }
+        vars
+    }
+
+    #[test]
+    fn test_expand_substitutes_variables() {
+        let vars = vars(&[("module", "parser"), ("n", "3")]);
+        assert_eq!(
+            expand("Fix {module} panics ({n})", &vars).unwrap(),
+            "Fix parser panics (3)"
+        );
+    }
+
+    #[test]
+    fn test_expand_escaped_braces() {
+        let vars = vars(&[("x", "1")]);
+        assert_eq!(expand("{{x}} = {x}", &vars).unwrap(), "{x} = 1");
+    }
+
+    #[test]
+    fn test_expand_undefined_variable() {
+        let err = expand("Fix {module}", &Vars::new()).unwrap_err();
+        assert_eq!(err, TemplateError::UndefinedVariable("module".to_string()));
+    }
+
+    #[test]
+    fn test_expand_unbalanced_braces() {
+        let vars = vars(&[("x", "1")]);
+        assert_eq!(
+            expand("Fix {x", &vars).unwrap_err(),
+            TemplateError::UnbalancedBrace(4)
+        );
+        assert_eq!(
+            expand("Fix x}", &vars).unwrap_err(),
+            TemplateError::UnbalancedBrace(5)
+        );
+    }
+
+    #[test]
+    fn test_template_var_parse() {
+        let var: TemplateVar = "module=parser=v2".parse().unwrap();
+        assert_eq!(var.name, "module");
+        assert_eq!(var.value, "parser=v2");
+
+        assert!("module".parse::<TemplateVar>().is_err());
+        assert!("=value".parse::<TemplateVar>().is_err());
+        assert!("bad name=x".parse::<TemplateVar>().is_err());
+    }
+
+    #[test]
+    fn test_later_vars_override_earlier() {
+        let vars: Vars = ["a=1", "a=2"]
+            .iter()
+            .map(|s| s.parse::<TemplateVar>().unwrap())
+            .collect();
+        assert_eq!(vars.get("a"), Some("2"));
+    }
+
+    #[test]
+    fn test_output_template_renders_fields() {
+        let mut wire = crate::models::Wire::new("Fix {parser}", None, 2).unwrap();
+        wire.tags = vec!["a".parse().unwrap(), "b".parse().unwrap()];
+        let template: OutputTemplate = "[{{status}}] {{title}} #{{ tags }}{{description}}"
+            .parse()
+            .unwrap();
+        assert_eq!(template.render(&wire), "[TODO] Fix {parser} #a,b");
+    }
+
+    #[test]
+    fn test_columns_parse() {
+        let columns: Columns = "title, id".parse().unwrap();
+        assert_eq!(columns.names(), ["title", "id"]);
+        assert_eq!(
+            "id,".parse::<Columns>().unwrap_err(),
+            TemplateError::UnknownField(String::new())
+        );
+        assert_eq!(Columns::default().names()[0], "id");
+    }
+
+    #[test]
+    fn test_output_template_errors() {
+        assert_eq!(
+            "id: {{id".parse::<OutputTemplate>().unwrap_err(),
+            TemplateError::UnclosedPlaceholder(4)
+        );
+        assert_eq!(
+            "{{owner}}".parse::<OutputTemplate>().unwrap_err(),
+            TemplateError::UnknownField("owner".to_string())
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Time helpers: the current Unix timestamp, human-friendly durations and
+//! ages, and UTC formatting for display.
+//!
+//! Timestamps are in seconds, except those named with an `_ms` suffix, such
+//! as wire `created_at_ms` and `updated_at_ms` (see [`now_ms`]). Those two
+//! keep the names `created_at` and `updated_at` in JSON and exports.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Returns the current time as seconds since the Unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs() as i64
+}
+
+/// Returns the current time as milliseconds since the Unix epoch, the
+/// precision of wire timestamps.
+pub fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as i64
+}
+
+/// Parses a duration such as `30s`, `30m`, `2h`, `7d` or `2w`.
+///
+/// A bare number is taken as seconds. The error is a plain message so this
+/// can be used directly as a clap `value_parser`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wr::time::parse_duration;
+///
+/// assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(30 * 60));
+/// assert_eq!(parse_duration("2w").unwrap(), Duration::from_secs(14 * 86400));
+/// assert!(parse_duration("soon").is_err());
+/// ```
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (digits, unit) = s.split_at(split);
+
+    let invalid = || {
+        format!(
+            "Invalid duration '{}': use a number followed by s, m, h, d or w (e.g. 30m)",
+            s
+        )
+    };
+
+    let value: u64 = digits.parse().map_err(|_| invalid())?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+
+    value
+        .checked_mul(multiplier)
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
+/// Parses a point in time given either as a Unix timestamp (`1767225600`)
+/// or as a duration before now (`2h`, `7d`).
+///
+/// # Example
+///
+/// ```
+/// use wr::time::{now, parse_since};
+///
+/// assert_eq!(parse_since("1767225600").unwrap(), 1767225600);
+/// assert!(parse_since("1h").unwrap() <= now() - 3600);
+/// ```
+pub fn parse_since(s: &str) -> Result<i64, String> {
+    let s = s.trim();
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        return s.parse().map_err(|_| format!("Invalid timestamp '{}'", s));
+    }
+
+    let ago = parse_duration(s).map_err(|_| {
+        format!(
+            "Invalid time '{}': use a Unix timestamp or a duration ago like 30m, 2h or 7d",
+            s
+        )
+    })?;
+    Ok(now().saturating_sub(ago.as_secs() as i64))
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM:SS` in UTC.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(wr::time::format_utc(0), "1970-01-01 00:00:00");
+/// assert_eq!(wr::time::format_utc(1767225600), "2026-01-01 00:00:00");
+/// ```
+pub fn format_utc(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86400);
+    let secs = timestamp.rem_euclid(86400);
+
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Formats a number of seconds as a short age in its largest whole unit,
+/// such as `45s`, `12m`, `3h`, `5d` or `2w`.
+///
+/// # Example
+///
+/// ```
+/// use wr::time::format_age;
+///
+/// assert_eq!(format_age(90), "1m");
+/// assert_eq!(format_age(3 * 86400), "3d");
+/// ```
+pub fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    let units = [
+        (7 * 24 * 60 * 60, "w"),
+        (24 * 60 * 60, "d"),
+        (60 * 60, "h"),
+        (60, "m"),
+    ];
+    units
+        .iter()
+        .find(|(size, _)| secs >= *size)
+        .map(|(size, unit)| format!("{}{}", secs / size, unit))
+        .unwrap_or_else(|| format!("{}s", secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("45").unwrap().as_secs(), 45);
+        assert_eq!(parse_duration("45s").unwrap().as_secs(), 45);
+        assert_eq!(parse_duration("30m").unwrap().as_secs(), 1800);
+        assert_eq!(parse_duration("2h").unwrap().as_secs(), 7200);
+        assert_eq!(parse_duration("7d").unwrap().as_secs(), 604800);
+        assert_eq!(parse_duration("1w").unwrap().as_secs(), 604800);
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("10y").is_err());
+        assert!(parse_duration("-5m").is_err());
+        assert!(parse_duration("1.5h").is_err());
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("1700000000").unwrap(), 1700000000);
+        let an_hour_ago = parse_since("1h").unwrap();
+        assert!((now() - 3600 - an_hour_ago).abs() <= 1);
+        assert!(parse_since("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00");
+        assert_eq!(format_utc(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(format_utc(1_700_000_000), "2023-11-14 22:13:20");
+        assert_eq!(format_utc(-1), "1969-12-31 23:59:59");
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(0), "0s");
+        assert_eq!(format_age(59), "59s");
+        assert_eq!(format_age(3600), "1h");
+        assert_eq!(format_age(13 * 86400), "1w");
+        assert_eq!(format_age(-5), "0s");
+    }
+}
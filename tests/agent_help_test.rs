@@ -0,0 +1,175 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+use wr::models::{Agent, Lease, Status, WireError, WireId};
+
+mod common;
+use common::wr;
+
+#[test]
+fn test_agent_help_text_has_contract_sections() {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .arg("agent-help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("LOOP"))
+        .stdout(predicate::str::contains("EXIT CODES"))
+        .stdout(predicate::str::contains(
+            "wr claim <ID> --agent <AGENT> [--ttl <TTL>]",
+        ));
+}
+
+fn agent_help_json() -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .args(["agent-help", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_agent_help_json_lists_every_command() {
+    let json = agent_help_json();
+    let usages: Vec<&str> = json["commands"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["usage"].as_str().unwrap())
+        .collect();
+
+    for prefix in [
+        "wr new ",
+        "wr ready ",
+        "wr done <ID>",
+        "wr tag add <ID_OR_TAG> [TAG]",
+    ] {
+        assert!(
+            usages.iter().any(|u| u.starts_with(prefix)),
+            "missing {}",
+            prefix
+        );
+    }
+    // Subcommands carry their parent's options
+    assert!(usages
+        .iter()
+        .any(|u| u.starts_with("wr multi list") && u.contains("--roots <ROOTS>")));
+    // Groups without their own behaviour aren't listed
+    assert!(!usages.contains(&"wr tag"));
+    assert!(json["exit_codes"]["1"].is_string());
+}
+
+/// One error of each kind. The match stops compiling when a kind is
+/// added, as a reminder to list it here and in `wr agent-help`.
+fn every_error_kind() -> Vec<WireError> {
+    let errors = vec![
+        WireError::NotARepository,
+        WireError::AlreadyInitialized(".".to_string()),
+        WireError::WireNotFound("abc1234".to_string()),
+        WireError::CircularDependency(vec![]),
+        WireError::AmbiguousWire {
+            query: "parser".to_string(),
+            candidates: vec![],
+        },
+        WireError::IdempotencyKeyReused {
+            key: "k".to_string(),
+            command: "new".to_string(),
+        },
+        WireError::AlreadyClaimed(Lease {
+            wire_id: WireId::new("abc1234").unwrap(),
+            agent: Agent::new("alice").unwrap(),
+            claimed_at: 0,
+            expires_at: 60,
+        }),
+        WireError::NotClaimedBy {
+            wire_id: "abc1234".to_string(),
+            agent: Agent::new("alice").unwrap(),
+        },
+        WireError::NotReserved("abc1234".to_string()),
+        WireError::InProgressLimit {
+            limit: 1,
+            wires: vec![],
+        },
+        WireError::InvalidTransition {
+            wire_id: "abc1234".to_string(),
+            from: Status::Done,
+            to: Status::Todo,
+            allowed: vec![],
+        },
+        WireError::ImportConflicts(vec![]),
+        WireError::SnapshotUnverified(vec![]),
+        WireError::Unhealthy(vec![]),
+        WireError::PermissionDenied {
+            actor: None,
+            operation: "rm".to_string(),
+            allowed: vec![],
+        },
+    ];
+    for error in &errors {
+        match error {
+            WireError::NotARepository
+            | WireError::AlreadyInitialized(_)
+            | WireError::WireNotFound(_)
+            | WireError::CircularDependency(_)
+            | WireError::AmbiguousWire { .. }
+            | WireError::IdempotencyKeyReused { .. }
+            | WireError::AlreadyClaimed(_)
+            | WireError::NotClaimedBy { .. }
+            | WireError::NotReserved(_)
+            | WireError::InProgressLimit { .. }
+            | WireError::InvalidTransition { .. }
+            | WireError::ImportConflicts(_)
+            | WireError::SnapshotUnverified(_)
+            | WireError::Unhealthy(_)
+            | WireError::PermissionDenied { .. } => {}
+        }
+    }
+    errors
+}
+
+#[test]
+fn test_agent_help_exit_codes_match_errors() {
+    let json = agent_help_json();
+    let exit_codes = json["exit_codes"].as_object().unwrap();
+
+    let errors = every_error_kind();
+    for error in &errors {
+        let meaning = exit_codes[&error.exit_code().to_string()].as_str().unwrap();
+        assert!(
+            meaning.ends_with(&format!("{})", error.code())),
+            "exit code {} is listed as {:?}, not {}",
+            error.exit_code(),
+            meaning,
+            error.code()
+        );
+    }
+    // Besides the errors: success, other failures and usage errors
+    assert_eq!(exit_codes.len(), errors.len() + 3);
+}
+
+#[test]
+fn test_agent_help_wire_shape_matches_show() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let wire = wr(&temp_dir, &["new", "Wire A", "--description", "Details"]);
+    let shown = wr(&temp_dir, &["show", wire["id"].as_str().unwrap()]);
+    let mut fields: Vec<&str> = shown
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|k| k.as_str())
+        .collect();
+    fields.sort();
+
+    let json = agent_help_json();
+    let shape = json["shapes"]["wire"].as_str().unwrap();
+    let mut listed: Vec<&str> = shape
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .split(", ")
+        .collect();
+    listed.sort();
+    assert_eq!(listed, fields);
+}
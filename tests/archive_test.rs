@@ -0,0 +1,128 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn ids(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_archive_done_hides_from_list_and_graph() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let finished = create_wire(&temp_dir, "Finished");
+    let dropped = create_wire(&temp_dir, "Dropped");
+    let open = create_wire(&temp_dir, "Open");
+    wr(&temp_dir, &["dep", &open, &finished]);
+    wr(&temp_dir, &["done", &finished]);
+    wr(&temp_dir, &["cancel", &dropped]);
+
+    let json = wr(&temp_dir, &["archive", "--done"]);
+    assert_eq!(json["archived"].as_array().unwrap().len(), 2);
+
+    assert_eq!(ids(&wr(&temp_dir, &["list"])), vec![open.clone()]);
+    assert_eq!(
+        ids(&wr(&temp_dir, &["list", "--include-archived"])).len(),
+        3
+    );
+
+    let graph = wr(&temp_dir, &["graph"]);
+    assert_eq!(graph["nodes"].as_array().unwrap().len(), 1);
+    assert!(graph["edges"].as_array().unwrap().is_empty());
+    let graph = wr(&temp_dir, &["graph", "--include-archived"]);
+    assert_eq!(graph["edges"].as_array().unwrap().len(), 1);
+
+    // Still reachable directly, and the open wire stays ready
+    assert_eq!(wr(&temp_dir, &["show", &finished])["status"], "DONE");
+    assert_eq!(ids(&wr(&temp_dir, &["ready"])), vec![open]);
+}
+
+#[test]
+fn test_archive_older_than_skips_recent() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Just finished");
+    wr(&temp_dir, &["done", &id]);
+
+    let json = wr(&temp_dir, &["archive", "--done", "--older-than", "30d"]);
+    assert!(json["archived"].as_array().unwrap().is_empty());
+
+    let json = wr(&temp_dir, &["archive", "--done", "--dry-run"]);
+    assert_eq!(json["archived"][0]["id"], id.as_str());
+    assert_eq!(ids(&wr(&temp_dir, &["list"])), vec![id]);
+}
+
+#[test]
+fn test_archive_rejects_open_wire_and_unarchive_restores() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let open = create_wire(&temp_dir, "Open");
+    let done = create_wire(&temp_dir, "Done");
+    wr(&temp_dir, &["done", &done]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["archive", &open])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only DONE or CANCELLED"));
+
+    wr(&temp_dir, &["archive", &done]);
+    assert_eq!(ids(&wr(&temp_dir, &["list"])), vec![open.clone()]);
+
+    assert_eq!(wr(&temp_dir, &["unarchive", &done])["action"], "unarchived");
+    assert_eq!(ids(&wr(&temp_dir, &["list"])).len(), 2);
+}
+
+#[test]
+fn test_graph_apply_keeps_archived_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let done = create_wire(&temp_dir, "Done");
+    let open = create_wire(&temp_dir, "Open");
+    wr(&temp_dir, &["dep", &open, &done]);
+    wr(&temp_dir, &["done", &done]);
+    wr(&temp_dir, &["archive", "--done"]);
+
+    let graph = wr(&temp_dir, &["graph"]);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "apply"])
+        .write_stdin(graph.to_string())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["removed"].as_array().unwrap().is_empty());
+}
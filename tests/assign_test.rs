@@ -0,0 +1,119 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_assign_sets_and_clears_assignee() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Write docs");
+
+    let assigned = wr(&temp_dir, &["assign", &id, "alice"]);
+    assert_eq!(assigned["id"], id.as_str());
+    assert_eq!(assigned["assignee"], "alice");
+    assert_eq!(wr(&temp_dir, &["show", &id])["assignee"], "alice");
+
+    let unassigned = wr(&temp_dir, &["unassign", &id]);
+    assert!(unassigned["assignee"].is_null());
+    assert!(wr(&temp_dir, &["show", &id]).get("assignee").is_none());
+}
+
+#[test]
+fn test_list_and_ready_filter_by_assignee() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let docs = create_wire(&temp_dir, "Write docs");
+    let tests = create_wire(&temp_dir, "Write tests");
+    create_wire(&temp_dir, "Unassigned");
+    wr(&temp_dir, &["assign", &docs, "alice"]);
+    wr(&temp_dir, &["assign", &tests, "bob"]);
+
+    let listed = wr(&temp_dir, &["list", "--assignee", "alice"]);
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["id"], docs.as_str());
+
+    let ready = wr(&temp_dir, &["ready", "--assignee", "bob"]);
+    assert_eq!(ready.as_array().unwrap().len(), 1);
+    assert_eq!(ready[0]["id"], tests.as_str());
+
+    assert_eq!(wr(&temp_dir, &["ready"]).as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_list_and_ready_combine_tag_and_assignee() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let api = create_wire(&temp_dir, "Build API");
+    let schema = create_wire(&temp_dir, "Design schema");
+    let copy = create_wire(&temp_dir, "Write copy");
+    wr(&temp_dir, &["tag", "add", &api, "backend"]);
+    wr(&temp_dir, &["tag", "add", &schema, "backend"]);
+    wr(&temp_dir, &["assign", &api, "alice"]);
+    wr(&temp_dir, &["assign", &copy, "alice"]);
+
+    let args = ["--tag", "backend", "--assignee", "alice"];
+    for command in ["list", "ready"] {
+        let wires = wr(&temp_dir, &[&[command][..], &args[..]].concat());
+        assert_eq!(wires.as_array().unwrap().len(), 1, "{}", command);
+        assert_eq!(wires[0]["id"], api.as_str());
+    }
+
+    let ready = wr(&temp_dir, &["ready", "--tag", "backend"]);
+    assert_eq!(ready.as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_assign_shows_in_table() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Write docs");
+    wr(&temp_dir, &["assign", &id, "alice"]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &id, "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Assignee: alice"));
+}
+
+#[test]
+fn test_assign_rejects_invalid_agent() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Write docs");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["assign", &id, "two words"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid agent name"));
+}
@@ -0,0 +1,143 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn ready_ids(dir: &TempDir) -> Vec<String> {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("ready")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json.as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_block_on_hides_wires_until_cleared() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let deploy = create_wire(&temp_dir, "Deploy");
+    let smoke = create_wire(&temp_dir, "Smoke test");
+    let other = create_wire(&temp_dir, "Unrelated");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["block-on", "waiting for API key", &deploy, &smoke])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["reason"], "waiting for API key");
+    assert_eq!(json["wires"].as_array().unwrap().len(), 2);
+
+    assert_eq!(ready_ids(&temp_dir), vec![other.clone()]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &deploy])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"blocked_on\""));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["unblock-ext", "waiting for API key"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("cleared"));
+
+    assert_eq!(ready_ids(&temp_dir).len(), 3);
+}
+
+#[test]
+fn test_unblock_ext_by_number() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Wait for vendor");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["block-on", "vendor reply", &id])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let number = json["id"].to_string();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("blockers")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["unblock-ext", &number])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&id));
+
+    // Already cleared
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["unblock-ext", &number])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No active external blocker"));
+}
+
+#[test]
+fn test_block_on_missing_wire_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["block-on", "anything", "abc1234"])
+        .assert()
+        .failure();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("blockers")
+        .assert()
+        .success()
+        .stdout("[]\n");
+}
@@ -0,0 +1,147 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+/// Runs a command expected to fail and returns its exit code and JSON error
+fn wr_err(dir: &TempDir, args: &[&str]) -> (Option<i32>, serde_json::Value) {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    let json = serde_json::from_slice(&output.stderr).unwrap();
+    (output.status.code(), json)
+}
+
+#[test]
+fn test_block_sets_status_and_reason() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Call the payments API");
+
+    let json = wr(
+        &temp_dir,
+        &["block", &wire_id, "--reason", "waiting on API key"],
+    );
+    assert_eq!(json["status"], "BLOCKED");
+    assert_eq!(json["reason"], "waiting on API key");
+
+    let json = wr(&temp_dir, &["show", &wire_id]);
+    assert_eq!(json["status"], "BLOCKED");
+    assert_eq!(json["blocked_reason"], "waiting on API key");
+}
+
+#[test]
+fn test_blocked_wires_are_not_ready_and_are_listed() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let blocked = create_wire(&temp_dir, "Deploy to production");
+    let free = create_wire(&temp_dir, "Write docs");
+    wr(&temp_dir, &["block", &blocked, "--reason", "change freeze"]);
+
+    let ready = wr(&temp_dir, &["ready"]);
+    let ids: Vec<&str> = ready
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![free.as_str()]);
+
+    let listed = wr(&temp_dir, &["blocked"]);
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["id"], blocked.as_str());
+    assert_eq!(listed[0]["blocked_reason"], "change freeze");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["blocked", "-f", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("blocked: change freeze"));
+}
+
+#[test]
+fn test_blocked_wire_still_blocks_dependents() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let upstream = create_wire(&temp_dir, "Get credentials");
+    let downstream = create_wire(&temp_dir, "Use credentials");
+    wr(&temp_dir, &["dep", &downstream, &upstream]);
+    wr(&temp_dir, &["block", &upstream, "--reason", "vendor"]);
+
+    let ready = wr(&temp_dir, &["ready"]);
+    assert!(ready.as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_unblock_returns_wire_to_todo() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Migrate database");
+    wr(
+        &temp_dir,
+        &["block", &wire_id, "--reason", "backup running"],
+    );
+
+    let json = wr(&temp_dir, &["unblock", &wire_id]);
+    assert_eq!(json["status"], "TODO");
+
+    let json = wr(&temp_dir, &["show", &wire_id]);
+    assert!(json.get("blocked_reason").is_none());
+
+    let (code, json) = wr_err(&temp_dir, &["unblock", &wire_id]);
+    assert_eq!(code, Some(12));
+    assert_eq!(json["code"], "invalid_transition");
+    assert_eq!(json["from"], "TODO");
+    assert_eq!(json["to"], "TODO");
+}
+
+#[test]
+fn test_block_rejects_finished_wires_and_empty_reasons() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Already shipped");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["block", &wire_id, "--reason", "  "])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be empty"));
+
+    wr(&temp_dir, &["done", &wire_id]);
+    let (code, json) = wr_err(&temp_dir, &["block", &wire_id, "--reason", "too late"]);
+    assert_eq!(code, Some(12));
+    assert_eq!(json["code"], "invalid_transition");
+    assert_eq!(json["from"], "DONE");
+    assert_eq!(json["to"], "BLOCKED");
+}
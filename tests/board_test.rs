@@ -0,0 +1,103 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn statuses(json: &serde_json::Value) -> Vec<&str> {
+    json.as_array()
+        .unwrap()
+        .iter()
+        .map(|column| column["status"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_board_json_groups_wires_by_status() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Todo");
+    let started = create_wire(&temp_dir, "Started");
+    wr(&temp_dir, &["start", &started]);
+
+    let json: serde_json::Value = serde_json::from_str(&wr(&temp_dir, &["board"])).unwrap();
+    // PAUSED and BLOCKED only appear when they have wires
+    assert_eq!(
+        statuses(&json),
+        ["TODO", "IN_PROGRESS", "DONE", "CANCELLED"]
+    );
+    assert_eq!(json[0]["wires"][0]["title"], "Todo");
+    assert_eq!(json[1]["wires"][0]["id"], started.as_str());
+    assert!(json[2]["wires"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_board_honors_hide_statuses() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "hide_statuses = [\"CANCELLED\"]\n",
+    )
+    .unwrap();
+    create_wire(&temp_dir, "Todo");
+
+    let json: serde_json::Value = serde_json::from_str(&wr(&temp_dir, &["board"])).unwrap();
+    assert_eq!(statuses(&json), ["TODO", "IN_PROGRESS", "DONE"]);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&wr(&temp_dir, &["board", "--show-hidden"])).unwrap();
+    assert_eq!(statuses(&json).last(), Some(&"CANCELLED"));
+}
+
+#[test]
+fn test_board_table_fits_width() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "A title far too long to fit in a narrow column");
+
+    let table = wr(&temp_dir, &["board", "-f", "table", "--width", "60"]);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("TODO (1)"));
+    assert!(
+        lines.iter().all(|line| line.chars().count() <= 60),
+        "{}",
+        table
+    );
+    assert!(lines[2].starts_with(&id));
+    assert!(lines[2].ends_with('…'));
+}
@@ -0,0 +1,74 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title, "--priority", "3"]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_bump_and_drop_move_priority_relatively() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Triage me");
+
+    let json = wr(&temp_dir, &["bump", &id]);
+    assert_eq!(json["id"], id.as_str());
+    assert_eq!(json["from"], 3);
+    assert_eq!(json["priority"], 4);
+
+    assert_eq!(wr(&temp_dir, &["bump", &id, "+2"])["priority"], 6);
+    assert_eq!(wr(&temp_dir, &["drop", &id, "-5"])["priority"], 1);
+    assert_eq!(wr(&temp_dir, &["drop", &id, "2"])["priority"], -1);
+    assert_eq!(wr(&temp_dir, &["show", &id])["priority"], -1);
+
+    // Each change is undone on its own
+    wr(&temp_dir, &["undo"]);
+    assert_eq!(wr(&temp_dir, &["show", &id])["priority"], 1);
+}
+
+#[test]
+fn test_bump_and_drop_reject_the_opposite_sign() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Triage me");
+
+    for args in [["bump", &id, "-3"], ["drop", &id, "+3"]] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(args)
+            .assert()
+            .code(64)
+            .stderr(predicates::str::contains("goes the other way"));
+    }
+    assert_eq!(wr(&temp_dir, &["show", &id])["priority"], 3);
+}
+
+#[test]
+fn test_touch_refreshes_updated_at_only() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Still relevant");
+    let before = wr(&temp_dir, &["show", &id]);
+    std::thread::sleep(std::time::Duration::from_millis(5));
+
+    let json = wr(&temp_dir, &["touch", &id]);
+    assert_eq!(json["id"], id.as_str());
+    assert!(json["updated_at"].as_i64().unwrap() > before["updated_at"].as_i64().unwrap());
+
+    let after = wr(&temp_dir, &["show", &id]);
+    assert_eq!(after["updated_at"], json["updated_at"]);
+    assert_eq!(after["priority"], before["priority"]);
+    assert_eq!(after["status"], before["status"]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["touch", "fffffff"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,68 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_burndown_json_has_a_day_per_date() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let done = create_wire(&temp_dir, "Done");
+    create_wire(&temp_dir, "Open");
+    let cancelled = create_wire(&temp_dir, "Cancelled");
+    wr(&temp_dir, &["done", &done]);
+    wr(&temp_dir, &["cancel", &cancelled]);
+
+    let json = wr(&temp_dir, &["burndown", "--since", "6d"]);
+    let days = json.as_array().unwrap();
+    // Six days back through today, possibly one more across midnight
+    assert!(days.len() >= 7, "{:?}", days);
+    assert_eq!(days[0]["open"], 0);
+    assert_eq!(days[0]["done"], 0);
+
+    let today = days.last().unwrap();
+    assert_eq!(today["open"], 1);
+    assert_eq!(today["done"], 1);
+    assert_eq!(today["completed"], 1);
+    assert_eq!(today["date"].as_str().unwrap().len(), 10);
+}
+
+#[test]
+fn test_burndown_csv_has_header() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Open");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["burndown", "--since", "1d", "--format", "csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("date,open,done,completed\n"))
+        .stdout(predicate::str::ends_with(",1,0,0\n"));
+}
@@ -0,0 +1,121 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn write_config(dir: &TempDir, contents: &str) {
+    fs::write(dir.path().join(".wires").join("config.toml"), contents).unwrap();
+}
+
+#[test]
+fn test_cancel_with_reason() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Old approach");
+
+    let json = wr(&temp_dir, &["cancel", &id, "--reason", "obsolete"]);
+    assert_eq!(json["status"], "CANCELLED");
+    assert_eq!(json["cancel_reason"], "obsolete");
+
+    let json = wr(&temp_dir, &["show", &id]);
+    assert_eq!(json["cancel_reason"], "obsolete");
+
+    // Reopening drops the reason
+    wr(&temp_dir, &["update", &id, "--status", "todo"]);
+    assert!(wr(&temp_dir, &["show", &id]).get("cancel_reason").is_none());
+}
+
+#[test]
+fn test_cancel_superseded_by() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let old = create_wire(&temp_dir, "Old approach");
+    let new = create_wire(&temp_dir, "New approach");
+
+    let reason = format!("superseded-by:{}", new);
+    let json = wr(&temp_dir, &["cancel", &old, "--reason", &reason]);
+    assert_eq!(json["cancel_reason"], reason);
+
+    // Title references work too
+    let newest = create_wire(&temp_dir, "Newest approach");
+    let json = wr(
+        &temp_dir,
+        &["cancel", &new, "--reason", "superseded-by:title:Newest"],
+    );
+    assert_eq!(json["cancel_reason"], format!("superseded-by:{}", newest));
+
+    for bad in [
+        format!("superseded-by:{}", new),
+        "superseded-by:0000000".to_string(),
+        "forgot".to_string(),
+    ] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["cancel", &new, "--reason", &bad])
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn test_cancel_reason_can_be_required() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    write_config(&temp_dir, "require_cancel_reason = true\n");
+    let id = create_wire(&temp_dir, "Old approach");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["cancel", &id])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cancel reason is required"));
+
+    let json = wr(&temp_dir, &["cancel", &id, "-r", "wontfix"]);
+    assert_eq!(json["cancel_reason"], "wontfix");
+}
+
+#[test]
+fn test_cancel_reason_survives_export_and_import() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Old approach");
+    wr(&temp_dir, &["cancel", &id, "--reason", "duplicate"]);
+
+    let snapshot = wr(&temp_dir, &["export", "--format", "json"]);
+    assert_eq!(snapshot["wires"][0]["cancel_reason"], "duplicate");
+
+    let path = temp_dir.path().join("snapshot.json");
+    fs::write(&path, snapshot.to_string()).unwrap();
+    let other = TempDir::new().unwrap();
+    init_test_repo(&other);
+    wr(&other, &["import", path.to_str().unwrap()]);
+    assert_eq!(wr(&other, &["show", &id])["cancel_reason"], "duplicate");
+}
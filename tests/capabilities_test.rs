@@ -0,0 +1,42 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn capabilities(dir: &TempDir) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["capabilities", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_capabilities_outside_repo() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let json = capabilities(&temp_dir);
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(json["schema_version"].as_i64().unwrap() > 0);
+    assert!(json["repo_schema_version"].is_null());
+    for feature in ["serve", "mcp", "postgres", "encryption"] {
+        assert!(json["features"][feature].is_boolean(), "{}", feature);
+    }
+    assert_eq!(json["features"]["async"], cfg!(feature = "async"));
+    assert_eq!(json["features"]["tui"], cfg!(feature = "tui"));
+}
+
+#[test]
+fn test_capabilities_reports_repo_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("init")
+        .assert()
+        .success();
+
+    let json = capabilities(&temp_dir);
+    assert_eq!(json["repo_schema_version"], json["schema_version"]);
+}
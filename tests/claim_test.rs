@@ -0,0 +1,181 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn claim(dir: &TempDir, wire_id: &str, agent: &str) -> std::process::Output {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["claim", wire_id, "--agent", agent])
+        .env_remove("WR_AGENT")
+        .output()
+        .unwrap()
+}
+
+fn ready_ids(dir: &TempDir, agent: Option<&str>) -> Vec<String> {
+    let mut cmd = Command::cargo_bin("wr").unwrap();
+    cmd.current_dir(dir).arg("ready").env_remove("WR_AGENT");
+    if let Some(agent) = agent {
+        cmd.env("WR_AGENT", agent);
+    }
+    let output = cmd.output().unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json.as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_claim_returns_lease() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Task");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["claim", &id, "--agent", "agent-1", "--ttl", "2h"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["wire_id"], id.as_str());
+    assert_eq!(json["agent"], "agent-1");
+    assert_eq!(
+        json["expires_at"].as_i64().unwrap() - json["claimed_at"].as_i64().unwrap(),
+        7200
+    );
+}
+
+#[test]
+fn test_claim_conflicts_with_other_agent() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Task");
+
+    assert!(claim(&temp_dir, &id, "agent-1").status.success());
+    // Same agent renews
+    assert!(claim(&temp_dir, &id, "agent-1").status.success());
+
+    let output = claim(&temp_dir, &id, "agent-2");
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains("already claimed by agent-1"));
+    assert_eq!(json["lease"]["agent"], "agent-1");
+}
+
+#[test]
+fn test_ready_hides_wires_claimed_by_others() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let claimed = create_wire(&temp_dir, "Claimed");
+    let free = create_wire(&temp_dir, "Free");
+
+    assert!(claim(&temp_dir, &claimed, "agent-1").status.success());
+
+    assert_eq!(ready_ids(&temp_dir, Some("agent-2")), vec![free.clone()]);
+    assert_eq!(ready_ids(&temp_dir, None), vec![free.clone()]);
+
+    let mine = ready_ids(&temp_dir, Some("agent-1"));
+    assert_eq!(mine.len(), 2);
+    assert!(mine.contains(&claimed));
+}
+
+#[test]
+fn test_release_makes_wire_ready_again() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Task");
+
+    assert!(claim(&temp_dir, &id, "agent-1").status.success());
+
+    // Another agent can't release it
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["release", &id, "--agent", "agent-2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not claimed by agent-2"));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["release", &id, "--agent", "agent-1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"released\""));
+
+    assert_eq!(ready_ids(&temp_dir, Some("agent-2")), vec![id.clone()]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["release", &id])
+        .env_remove("WR_AGENT")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"unchanged\""));
+}
+
+#[test]
+fn test_claim_requires_agent_and_valid_ttl() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Task");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["claim", &id])
+        .env_remove("WR_AGENT")
+        .assert()
+        .failure();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["claim", &id, "--agent", "a", "--ttl", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid duration"));
+}
+
+#[test]
+fn test_claim_nonexistent_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let output = claim(&temp_dir, "abcdef0", "agent-1");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Wire not found"));
+}
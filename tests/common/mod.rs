@@ -0,0 +1,25 @@
+//! Helpers shared by the integration tests.
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+/// Runs `wr` in `dir` with `args`, asserting it succeeds, and parses its
+/// stdout as JSON. `WR_AGENT` and `WIRES_DIR` are cleared so the caller's
+/// environment doesn't pick the agent or the repository.
+pub fn wr(dir: &TempDir, args: &[&str]) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .env_remove("WR_AGENT")
+        .env_remove("WIRES_DIR")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "wr {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
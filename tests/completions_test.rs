@@ -0,0 +1,60 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_completions_prints_a_script_per_shell() {
+    for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", shell);
+        let script = String::from_utf8(output.stdout).unwrap();
+        assert!(script.contains("COMPLETE"), "{}: {}", shell, script);
+    }
+}
+
+#[test]
+fn test_completions_offer_wire_ids_with_titles() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Fix login bug");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .env("COMPLETE", "fish")
+        .args(["--", "wr", "done", ""])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let candidates = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        candidates.contains(&format!("{}\tFix login bug\n", id)),
+        "{}",
+        candidates
+    );
+}
@@ -0,0 +1,279 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn ids(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn write_config(dir: &TempDir, contents: &str) {
+    fs::write(dir.path().join(".wires").join("config.toml"), contents).unwrap();
+}
+
+#[test]
+fn test_hide_statuses_applies_to_list_and_graph() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let dropped = create_wire(&temp_dir, "Dropped");
+    let open = create_wire(&temp_dir, "Open");
+    wr(&temp_dir, &["dep", &open, &dropped]);
+    wr(&temp_dir, &["cancel", &dropped]);
+    write_config(&temp_dir, "hide_statuses = [\"CANCELLED\"]\n");
+
+    assert_eq!(ids(&wr(&temp_dir, &["list"])), vec![open.clone()]);
+    assert_eq!(ids(&wr(&temp_dir, &["list", "--show-hidden"])).len(), 2);
+    // Asking for the status explicitly overrides the config
+    assert_eq!(
+        ids(&wr(&temp_dir, &["list", "--status", "cancelled"])),
+        vec![dropped.clone()]
+    );
+
+    let graph = wr(&temp_dir, &["graph"]);
+    assert_eq!(graph["nodes"].as_array().unwrap().len(), 1);
+    assert!(graph["edges"].as_array().unwrap().is_empty());
+    let graph = wr(&temp_dir, &["graph", "--show-hidden"]);
+    assert_eq!(graph["edges"].as_array().unwrap().len(), 1);
+
+    // Hidden, not deleted
+    assert_eq!(wr(&temp_dir, &["show", &dropped])["status"], "CANCELLED");
+}
+
+#[test]
+fn test_graph_apply_keeps_edges_to_hidden_wires() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let dropped = create_wire(&temp_dir, "Dropped");
+    let open = create_wire(&temp_dir, "Open");
+    wr(&temp_dir, &["dep", &open, &dropped]);
+    wr(&temp_dir, &["cancel", &dropped]);
+    write_config(&temp_dir, "hide_statuses = [\"CANCELLED\"]\n");
+
+    let graph = wr(&temp_dir, &["graph"]);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "apply"])
+        .write_stdin(graph.to_string())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let graph = wr(&temp_dir, &["graph", "--show-hidden"]);
+    assert_eq!(graph["edges"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_invalid_config_is_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    write_config(&temp_dir, "hide_statuses = [\"ABANDONED\"]\n");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("list")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("config.toml"));
+}
+
+#[test]
+fn test_title_policy_applies_to_new_update_and_import() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    write_config(
+        &temp_dir,
+        "[titles]\ncollapse_whitespace = true\nstrip_emoji = true\nmax_length = 20\n",
+    );
+
+    let created = wr(&temp_dir, &["new", "🚀  Ship   the\trelease ✅"]);
+    assert_eq!(created["title"], "Ship the release");
+    let id = created["id"].as_str().unwrap().to_string();
+
+    wr(&temp_dir, &["update", &id, "--title", "  Ship 🎉 it  "]);
+    assert_eq!(wr(&temp_dir, &["show", &id])["title"], "Ship it");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "A title that is far too long"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("longer than the maximum of 20"));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &id, "--title", "🔥"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("title cannot be empty"));
+
+    let mut snapshot = wr(&temp_dir, &["export", "--format", "json"]);
+    snapshot["wires"][0]["title"] = "⭐ Imported   wire".into();
+    let path = temp_dir.path().join("snapshot.json");
+    fs::write(&path, snapshot.to_string()).unwrap();
+    wr(&temp_dir, &["import", path.to_str().unwrap(), "--replace"]);
+    assert_eq!(wr(&temp_dir, &["show", &id])["title"], "Imported wire");
+}
+
+#[test]
+fn test_config_set_and_get() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    write_config(
+        &temp_dir,
+        "# Shared with the team\nrequire_cancel_reason = true\n",
+    );
+
+    let json = wr(&temp_dir, &["config", "set", "in_progress.limit", "2"]);
+    assert_eq!(
+        json,
+        serde_json::json!({"key": "in_progress.limit", "value": 2})
+    );
+    wr(
+        &temp_dir,
+        &["config", "set", "hide_statuses", r#"["CANCELLED"]"#],
+    );
+    wr(&temp_dir, &["config", "set", "format", "markdown"]);
+
+    let written = fs::read_to_string(temp_dir.path().join(".wires").join("config.toml")).unwrap();
+    assert!(written.starts_with("# Shared with the team\nrequire_cancel_reason = true\n"));
+    assert!(written.contains("[in_progress]\nlimit = 2\n"));
+
+    let json = wr(&temp_dir, &["config", "get", "in_progress"]);
+    assert_eq!(json["value"]["limit"], 2);
+    assert_eq!(json["value"]["when_full"], "pause");
+
+    let all = wr(&temp_dir, &["config", "get"]);
+    assert_eq!(all["require_cancel_reason"], true);
+    assert_eq!(all["format"], "markdown");
+    assert_eq!(all["default_priority"], 0);
+}
+
+#[test]
+fn test_config_set_rejects_bad_keys_and_values() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    write_config(&temp_dir, "default_priority = 3\n");
+
+    for (args, message) in [
+        (
+            ["config", "set", "colour", "never"],
+            "Unknown config key: colour",
+        ),
+        (
+            ["config", "set", "color", "sometimes"],
+            "unknown variant `sometimes`",
+        ),
+        (
+            ["config", "set", "default_priority", "high"],
+            "Invalid value for default_priority",
+        ),
+    ] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(message));
+    }
+
+    let written = fs::read_to_string(temp_dir.path().join(".wires").join("config.toml")).unwrap();
+    assert_eq!(written, "default_priority = 3\n");
+}
+
+#[test]
+fn test_default_priority_applies_to_new() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    write_config(&temp_dir, "default_priority = 5\n");
+
+    assert_eq!(wr(&temp_dir, &["new", "Defaulted"])["priority"], 5);
+    assert_eq!(
+        wr(&temp_dir, &["new", "Explicit", "--priority", "0"])["priority"],
+        0
+    );
+}
+
+#[test]
+fn test_color_always_colors_piped_tables() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Colored");
+    wr(&temp_dir, &["done", &id]);
+
+    let table = |dir: &TempDir| {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(dir)
+            .env_remove("NO_COLOR")
+            .args(["list", "-f", "table"])
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(!table(&temp_dir).contains('\u{1b}'));
+    write_config(&temp_dir, "color = \"always\"\n");
+    assert!(table(&temp_dir).contains('\u{1b}'));
+}
+
+#[test]
+fn test_ready_weights_rank_unblocking_wires_first() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let urgent = wr(&temp_dir, &["new", "Urgent", "--priority", "2"])["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let foundation = create_wire(&temp_dir, "Foundation");
+    for title in ["Wall", "Roof"] {
+        let id = create_wire(&temp_dir, title);
+        wr(&temp_dir, &["dep", &id, &foundation]);
+    }
+
+    assert_eq!(
+        ids(&wr(&temp_dir, &["ready"])),
+        [urgent.clone(), foundation.clone()]
+    );
+
+    write_config(&temp_dir, "[ready]\nunblocks = 5\n");
+    assert_eq!(
+        ids(&wr(&temp_dir, &["ready"])),
+        [foundation.clone(), urgent]
+    );
+    assert_eq!(wr(&temp_dir, &["next"])["id"], foundation.as_str());
+}
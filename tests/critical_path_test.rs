@@ -0,0 +1,88 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn path_ids(value: &serde_json::Value) -> Vec<String> {
+    value["wires"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_critical_path_longest_chain_first_step_first() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let schema = create_wire(&temp_dir, "Schema");
+    let api = create_wire(&temp_dir, "API");
+    let ui = create_wire(&temp_dir, "UI");
+    let docs = create_wire(&temp_dir, "Docs");
+    wr(&temp_dir, &["dep", &api, &schema]);
+    wr(&temp_dir, &["dep", &ui, &api]);
+    wr(&temp_dir, &["dep", &docs, &schema]);
+
+    let json = wr(&temp_dir, &["critical-path"]);
+    assert_eq!(path_ids(&json), vec![schema, api, ui]);
+    assert_eq!(json["weight"], 3);
+}
+
+#[test]
+fn test_critical_path_weighted_by_estimate_tags() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let a = create_wire(&temp_dir, "A");
+    let b = create_wire(&temp_dir, "B");
+    let big = create_wire(&temp_dir, "Big");
+    wr(&temp_dir, &["dep", &b, &a]);
+    wr(&temp_dir, &["tag", "add", &big, "estimate:8"]);
+
+    assert_eq!(path_ids(&wr(&temp_dir, &["critical-path"])), vec![a, b]);
+
+    let json = wr(&temp_dir, &["critical-path", "--weighted"]);
+    assert_eq!(path_ids(&json), vec![big]);
+    assert_eq!(json["weight"], 8);
+}
+
+#[test]
+fn test_critical_path_empty_repo() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let json = wr(&temp_dir, &["critical-path"]);
+    assert!(json["wires"].as_array().unwrap().is_empty());
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["critical-path", "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No wires found."));
+}
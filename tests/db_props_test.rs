@@ -0,0 +1,432 @@
+//! Property tests for the storage layer.
+//!
+//! Each property drives `wr::db` with random operations and checks the
+//! database against a simple in-memory model, plus invariants that must
+//! hold no matter what ran before: the dependency graph stays acyclic,
+//! every row points at a wire that exists, and the event log replays to
+//! the current graph. Graph and readiness results are also compared with
+//! the in-memory rules in `wr::graph`.
+
+use proptest::prelude::*;
+use rusqlite::Connection;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::thread;
+use tempfile::TempDir;
+use wr::config::{InProgressPolicy, TransitionPolicy};
+use wr::db;
+use wr::graph;
+use wr::models::{CancelReason, EventKind, Status, Wire, WireError, WireId};
+
+const WIRES: usize = 6;
+
+type Edges = BTreeSet<(String, String)>;
+
+fn setup_repo() -> (TempDir, Connection) {
+    let temp_dir = TempDir::new().unwrap();
+    db::init(temp_dir.path()).unwrap();
+    let conn = db::open_at(temp_dir.path()).unwrap();
+    (temp_dir, conn)
+}
+
+fn create_wires(conn: &Connection, count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| {
+            let mut wire = Wire::new(&format!("Wire {}", i), None, 0).unwrap();
+            db::insert_wire(conn, &mut wire).unwrap();
+            wire.id.to_string()
+        })
+        .collect()
+}
+
+fn stored_edges(conn: &Connection) -> Edges {
+    db::list_dependencies(conn)
+        .unwrap()
+        .into_iter()
+        .map(|d| (d.wire_id.to_string(), d.depends_on.to_string()))
+        .collect()
+}
+
+/// Wires reachable from `from` by following `edges` forwards
+fn reachable(edges: &Edges, from: &str) -> BTreeSet<String> {
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![from.to_string()];
+    while let Some(current) = stack.pop() {
+        for (_, next) in edges.iter().filter(|(a, _)| *a == current) {
+            if seen.insert(next.clone()) {
+                stack.push(next.clone());
+            }
+        }
+    }
+    seen
+}
+
+fn error_kind(result: &anyhow::Result<()>) -> Option<&WireError> {
+    result.as_ref().err().and_then(|e| e.downcast_ref())
+}
+
+/// Invariants every database must satisfy, whatever ran before
+/// Checks that `cycle` is what adding the edge `a -> b` would close: it
+/// runs from `a` through `b` and back to `a` along edges in `edges`.
+fn check_cycle(edges: &Edges, cycle: &[String], a: &str, b: &str) -> Result<(), TestCaseError> {
+    prop_assert!(cycle.len() >= 2, "{:?}", cycle);
+    prop_assert_eq!(cycle.first().map(String::as_str), Some(a));
+    prop_assert_eq!(cycle.last().map(String::as_str), Some(a));
+    prop_assert_eq!(cycle[1].as_str(), b);
+    if a != b {
+        for pair in cycle[1..].windows(2) {
+            prop_assert!(edges.contains(&(pair[0].clone(), pair[1].clone())));
+        }
+    }
+    Ok(())
+}
+
+fn check_invariants(conn: &Connection) -> Result<(), TestCaseError> {
+    let edges = stored_edges(conn);
+
+    // Acyclic: no wire reaches itself
+    for (wire_id, _) in &edges {
+        prop_assert!(
+            !reachable(&edges, wire_id).contains(wire_id),
+            "{} is on a cycle",
+            wire_id
+        );
+    }
+
+    // Referential integrity, with and without foreign key enforcement
+    let violations: i64 = conn
+        .query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    prop_assert_eq!(violations, 0);
+    for (table, column) in [
+        ("dependencies", "wire_id"),
+        ("dependencies", "depends_on"),
+        ("tags", "wire_id"),
+        ("pauses", "wire_id"),
+        ("external_blocks", "wire_id"),
+    ] {
+        let dangling: i64 = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {table} t
+                     WHERE NOT EXISTS (SELECT 1 FROM wires w WHERE w.id = t.{column})"
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        prop_assert_eq!(dangling, 0, "dangling {}.{}", table, column);
+    }
+
+    // Pause notes and cancel and block reasons only describe the current status
+    let stray: i64 = conn
+        .query_row(
+            "SELECT (SELECT COUNT(*) FROM pauses p JOIN wires w ON w.id = p.wire_id
+                     WHERE w.status != 'PAUSED')
+                  + (SELECT COUNT(*) FROM wires
+                     WHERE cancel_reason IS NOT NULL AND status != 'CANCELLED')
+                  + (SELECT COUNT(*) FROM wires
+                     WHERE blocked_reason IS NOT NULL AND status != 'BLOCKED')",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    prop_assert_eq!(stray, 0);
+
+    // The event log replays to the current graph
+    let mut replayed = Edges::new();
+    for event in db::list_events(conn, &db::EventFilter::default()).unwrap() {
+        let wire_id = event.wire_id.to_string();
+        let depends_on = || event.data["depends_on"].as_str().unwrap().to_string();
+        match event.kind {
+            EventKind::DepAdded => {
+                prop_assert!(replayed.insert((wire_id, depends_on())));
+            }
+            EventKind::DepRemoved => {
+                prop_assert!(replayed.remove(&(wire_id, depends_on())));
+            }
+            EventKind::Deleted => replayed.retain(|(a, b)| *a != wire_id && *b != wire_id),
+            _ => {}
+        }
+    }
+    prop_assert_eq!(replayed, edges);
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+enum GraphOp {
+    Add(usize, usize),
+    Remove(usize, usize),
+    Delete(usize),
+}
+
+fn graph_op() -> impl Strategy<Value = GraphOp> {
+    prop_oneof![
+        6 => (0..WIRES, 0..WIRES).prop_map(|(a, b)| GraphOp::Add(a, b)),
+        2 => (0..WIRES, 0..WIRES).prop_map(|(a, b)| GraphOp::Remove(a, b)),
+        1 => (0..WIRES).prop_map(GraphOp::Delete),
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum StatusOp {
+    Set(usize, Status),
+    Start(usize),
+    Pause(usize, bool),
+    Resume(usize),
+    Block(usize),
+    Unblock(usize),
+    Cancel(usize, Option<usize>),
+    Depend(usize, usize),
+}
+
+fn any_status() -> impl Strategy<Value = Status> {
+    prop_oneof![
+        Just(Status::Todo),
+        Just(Status::InProgress),
+        Just(Status::Paused),
+        Just(Status::Blocked),
+        Just(Status::Done),
+        Just(Status::Cancelled),
+    ]
+}
+
+fn status_op() -> impl Strategy<Value = StatusOp> {
+    prop_oneof![
+        (0..WIRES, any_status()).prop_map(|(i, s)| StatusOp::Set(i, s)),
+        (0..WIRES).prop_map(StatusOp::Start),
+        (0..WIRES, any::<bool>()).prop_map(|(i, note)| StatusOp::Pause(i, note)),
+        (0..WIRES).prop_map(StatusOp::Resume),
+        (0..WIRES).prop_map(StatusOp::Block),
+        (0..WIRES).prop_map(StatusOp::Unblock),
+        (0..WIRES, proptest::option::of(0..WIRES)).prop_map(|(i, by)| StatusOp::Cancel(i, by)),
+        (0..WIRES, 0..WIRES).prop_map(|(a, b)| StatusOp::Depend(a, b)),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_graph_mutations_match_model(ops in proptest::collection::vec(graph_op(), 1..40)) {
+        let (_temp_dir, conn) = setup_repo();
+        let ids = create_wires(&conn, WIRES);
+        let mut alive: BTreeSet<String> = ids.iter().cloned().collect();
+        let mut model = Edges::new();
+
+        for op in ops {
+            match op {
+                GraphOp::Add(a, b) => {
+                    let (a, b) = (&ids[a], &ids[b]);
+                    let wires = db::list_wires_with_deps(&conn, &db::ListFilter::default()).unwrap();
+                    let in_memory = graph::find_cycle(&wires, a, b);
+                    let result = db::add_dependency(&conn, a, b);
+                    if !alive.contains(a) || !alive.contains(b) {
+                        prop_assert!(matches!(error_kind(&result), Some(WireError::WireNotFound(_))));
+                    } else if a == b || reachable(&model, b).contains(a) {
+                        let Some(WireError::CircularDependency(cycle)) = error_kind(&result) else {
+                            return Err(TestCaseError::fail(format!("{:?}", result)));
+                        };
+                        // Both report a real path through the graph
+                        let cycle: Vec<String> = cycle.iter().map(|w| w.id.to_string()).collect();
+                        check_cycle(&model, &cycle, a, b)?;
+                        check_cycle(&model, &in_memory.unwrap_or_default(), a, b)?;
+                    } else {
+                        prop_assert_eq!(in_memory, None);
+                        prop_assert!(result.is_ok(), "{:?}", result);
+                        model.insert((a.clone(), b.clone()));
+                    }
+                }
+                GraphOp::Remove(a, b) => {
+                    db::remove_dependency(&conn, &ids[a], &ids[b]).unwrap();
+                    model.remove(&(ids[a].clone(), ids[b].clone()));
+                }
+                GraphOp::Delete(i) => {
+                    let result = db::delete_wire(&conn, &ids[i]);
+                    if alive.remove(&ids[i]) {
+                        prop_assert!(result.is_ok(), "{:?}", result);
+                        model.retain(|(a, b)| *a != ids[i] && *b != ids[i]);
+                    } else {
+                        prop_assert!(matches!(error_kind(&result), Some(WireError::WireNotFound(_))));
+                    }
+                }
+            }
+
+            prop_assert_eq!(&stored_edges(&conn), &model);
+            check_invariants(&conn)?;
+        }
+
+        for id in &alive {
+            let expected = reachable(&model, id);
+            let actual: BTreeSet<String> = db::get_transitive_dependencies(&conn, id)
+                .unwrap()
+                .into_iter()
+                .map(|d| d.wire.id.to_string())
+                .collect();
+            prop_assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn prop_status_transitions_match_model(ops in proptest::collection::vec(status_op(), 1..40)) {
+        let (_temp_dir, conn) = setup_repo();
+        let ids = create_wires(&conn, WIRES);
+        let policy = InProgressPolicy::default();
+        let transitions = TransitionPolicy::default();
+        let mut model: BTreeMap<String, Status> =
+            ids.iter().map(|id| (id.clone(), Status::Todo)).collect();
+
+        for op in ops {
+            let (i, to, precondition, result) = match op {
+                StatusOp::Set(i, to) => {
+                    let result = db::update_wire(&conn, &ids[i], None, None, Some(to), None);
+                    (i, to, true, result)
+                }
+                StatusOp::Start(i) => {
+                    let result = db::start_wire(&conn, &ids[i], None, &policy).map(drop);
+                    (i, Status::InProgress, true, result)
+                }
+                StatusOp::Pause(i, note) => {
+                    let reason = note.then_some("Waiting on review");
+                    let result = db::pause_wire(&conn, &ids[i], reason);
+                    if result.is_ok() {
+                        prop_assert_eq!(
+                            db::get_pause_note(&conn, &ids[i]).unwrap().is_some(),
+                            note
+                        );
+                    }
+                    (i, Status::Paused, model[&ids[i]].is_blocking(), result)
+                }
+                StatusOp::Resume(i) => {
+                    let result = db::resume_wire(&conn, &ids[i], None, &policy).map(drop);
+                    (i, Status::InProgress, model[&ids[i]] == Status::Paused, result)
+                }
+                StatusOp::Block(i) => {
+                    let result = db::block_wire(&conn, &ids[i], "Waiting on credentials");
+                    (i, Status::Blocked, model[&ids[i]].is_blocking(), result)
+                }
+                StatusOp::Unblock(i) => {
+                    let result = db::unblock_wire(&conn, &ids[i]);
+                    (i, Status::Todo, model[&ids[i]] == Status::Blocked, result)
+                }
+                StatusOp::Cancel(i, by) => {
+                    let reason = by.map(|j| CancelReason::SupersededBy(WireId::new(&ids[j]).unwrap()));
+                    let result = db::cancel_wire(&conn, &ids[i], reason.as_ref());
+                    (i, Status::Cancelled, by != Some(i), result)
+                }
+                StatusOp::Depend(a, b) => {
+                    // Cycles are rejected; other properties cover that
+                    let _ = db::add_dependency(&conn, &ids[a], &ids[b]);
+                    continue;
+                }
+            };
+
+            // An operation succeeds exactly when its own precondition and
+            // the transition policy both allow it, and changes nothing if not
+            let from = model[&ids[i]];
+            let allowed = transitions.check(&ids[i], from, to).is_ok();
+            prop_assert_eq!(result.is_ok(), precondition && allowed, "{:?}", result);
+            if precondition && !allowed {
+                let invalid = matches!(
+                    error_kind(&result),
+                    Some(WireError::InvalidTransition { .. })
+                );
+                prop_assert!(invalid, "{:?}", result);
+            }
+            if result.is_ok() {
+                model.insert(ids[i].clone(), to);
+            }
+
+            for (id, status) in &model {
+                let wire = db::get_wire(&conn, id).unwrap().unwrap();
+                prop_assert_eq!(wire.status, *status, "{}", id);
+            }
+            check_invariants(&conn)?;
+
+            // Ready means workable with every dependency DONE
+            let edges = stored_edges(&conn);
+            let expected: BTreeSet<&String> = model
+                .iter()
+                .filter(|(_, s)| matches!(s, Status::Todo | Status::InProgress))
+                .filter(|(id, _)| {
+                    edges
+                        .iter()
+                        .filter(|(a, _)| a == *id)
+                        .all(|(_, b)| model[b] == Status::Done)
+                })
+                .map(|(id, _)| id)
+                .collect();
+            let ready = db::get_ready_wires(&conn).unwrap();
+            let actual: BTreeSet<&String> = ready
+                .iter()
+                .map(|w| ids.iter().find(|id| **id == w.id.as_str()).unwrap())
+                .collect();
+            prop_assert_eq!(&actual, &expected);
+
+            // The in-memory rules pick the same wires
+            let wires = db::list_wires_with_deps(&conn, &db::ListFilter::default()).unwrap();
+            let in_memory: BTreeSet<&String> = graph::ready(&wires)
+                .iter()
+                .map(|w| ids.iter().find(|id| **id == w.id.as_str()).unwrap())
+                .collect();
+            prop_assert_eq!(in_memory, actual);
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn prop_concurrent_batches_keep_graph_acyclic(
+        batches in proptest::collection::vec(
+            proptest::collection::vec((0..WIRES, 0..WIRES, any::<bool>()), 1..20),
+            2..5,
+        )
+    ) {
+        let (temp_dir, conn) = setup_repo();
+        let ids = create_wires(&conn, WIRES);
+
+        let handles: Vec<_> = batches
+            .into_iter()
+            .map(|batch| {
+                let root = temp_dir.path().to_path_buf();
+                let ids = ids.clone();
+                thread::spawn(move || apply_batch(&root, &ids, &batch))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        check_invariants(&conn)?;
+    }
+}
+
+/// Adds (`true`) or removes (`false`) dependency edges on a separate
+/// connection. Rejected cycles and lock timeouts are fine; anything else
+/// is a bug.
+fn apply_batch(root: &Path, ids: &[String], batch: &[(usize, usize, bool)]) {
+    let conn = db::open_at(root).unwrap();
+    for &(a, b, add) in batch {
+        let result = if add {
+            db::add_dependency(&conn, &ids[a], &ids[b])
+        } else {
+            db::remove_dependency(&conn, &ids[a], &ids[b])
+        };
+        if let Err(e) = result {
+            let busy = e
+                .downcast_ref::<rusqlite::Error>()
+                .and_then(rusqlite::Error::sqlite_error_code)
+                == Some(rusqlite::ErrorCode::DatabaseBusy);
+            assert!(
+                busy || matches!(e.downcast_ref(), Some(WireError::CircularDependency(_))),
+                "{:?}",
+                e
+            );
+        }
+    }
+}
@@ -292,3 +292,160 @@ fn test_dep_allows_diamond_structure() {
     let deps = show_json["depends_on"].as_array().unwrap();
     assert_eq!(deps.len(), 2);
 }
+
+#[test]
+fn test_dep_cycle_error_includes_titles() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("dep")
+        .arg(&wire_a)
+        .arg(&wire_b)
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("dep")
+        .arg(&wire_b)
+        .arg(&wire_a)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains(&format!("{} \"Wire B\"", wire_b)));
+
+    let cycle = json["cycle"].as_array().unwrap();
+    let titles: Vec<&str> = cycle.iter().map(|w| w["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Wire B", "Wire A", "Wire B"]);
+    assert_eq!(cycle[0]["id"], wire_b.as_str());
+    assert_eq!(cycle[1]["status"], "TODO");
+}
+
+#[test]
+fn test_dep_idempotency_key_replays_after_graph_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    let dep = || {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["dep", &wire_a, &wire_b, "--idempotency-key", "edge-1"])
+            .assert()
+            .success()
+    };
+
+    dep();
+
+    // Remove the edge; a retry of the original request must not re-add it
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["undep", &wire_a, &wire_b])
+        .assert()
+        .success();
+
+    dep().stdout(predicate::str::contains("\"added\""));
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &wire_a])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["depends_on"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_dep_full_returns_dependent_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &wire_a, &wire_b, "--full"])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["id"], wire_a.as_str());
+    assert_eq!(json["depends_on"][0]["id"], wire_b.as_str());
+    assert_eq!(json["depends_on"][0]["title"], "Wire B");
+}
+
+#[test]
+fn test_dep_kind_relates_does_not_block_ready() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &wire_a, &wire_b, "--kind", "relates"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["kind"], "relates");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["ready", "-f", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 2);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &wire_a])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["depends_on"].as_array().unwrap().is_empty());
+    assert_eq!(json["links"][0]["id"], wire_b.as_str());
+    assert_eq!(json["links"][0]["kind"], "relates");
+
+    // A related wire may relate back without forming a cycle
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &wire_b, &wire_a, "-k", "relates"])
+        .assert()
+        .success();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "--format", "dot"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "\"{}\" -> \"{}\" [label=\"relates\", style=dashed];",
+            wire_a, wire_b
+        )));
+}
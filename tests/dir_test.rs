@@ -0,0 +1,104 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_dir_flag_targets_another_repository() {
+    let repo = TempDir::new().unwrap();
+    let elsewhere = TempDir::new().unwrap();
+    init_test_repo(&repo);
+    let id = create_wire(&repo, "Remote wire");
+    let dir = repo.path().to_str().unwrap();
+
+    let json = wr(&elsewhere, &["--dir", dir, "show", &id]);
+    assert_eq!(json["title"], "Remote wire");
+
+    // The flag is global, so it may also follow the subcommand
+    let json = wr(&elsewhere, &["list", "--dir", dir]);
+    assert_eq!(json.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_wires_dir_env_var() {
+    let repo = TempDir::new().unwrap();
+    let elsewhere = TempDir::new().unwrap();
+    init_test_repo(&repo);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&elsewhere)
+        .env("WIRES_DIR", repo.path())
+        .args(["new", "Via env"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json = wr(&repo, &["list"]);
+    assert_eq!(json[0]["title"], "Via env");
+}
+
+#[test]
+fn test_dir_skips_upward_search() {
+    let repo = TempDir::new().unwrap();
+    init_test_repo(&repo);
+    let nested = repo.path().join("src");
+    std::fs::create_dir(&nested).unwrap();
+
+    // Found by searching upward from the subdirectory...
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&nested)
+        .env_remove("WIRES_DIR")
+        .arg("list")
+        .assert()
+        .success();
+
+    // ...but not when named directly
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&repo)
+        .env_remove("WIRES_DIR")
+        .args(["--dir", nested.to_str().unwrap(), "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a wires repository"));
+}
+
+#[test]
+fn test_init_with_dir() {
+    let target = TempDir::new().unwrap();
+    let elsewhere = TempDir::new().unwrap();
+
+    let json = wr(
+        &elsewhere,
+        &["init", "--dir", target.path().to_str().unwrap()],
+    );
+    assert_eq!(json["status"], "initialized");
+    assert!(target.path().join(".wires").exists());
+    assert!(!elsewhere.path().join(".wires").exists());
+}
@@ -0,0 +1,111 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_doc_json_includes_context() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let schema = create_wire(&temp_dir, "Schema");
+    wr(
+        &temp_dir,
+        &["update", &schema, "--description", "users and orgs"],
+    );
+    let api = create_wire(&temp_dir, "API");
+    let client = create_wire(&temp_dir, "Client");
+    wr(&temp_dir, &["dep", &api, &schema]);
+    wr(&temp_dir, &["dep", &client, &api]);
+    wr(&temp_dir, &["claim", &client, "--agent", "claude-1"]);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&wr(&temp_dir, &["doc", &client, "--agent", "claude-1"])).unwrap();
+    assert_eq!(json["id"], client.as_str());
+    assert_eq!(json["depends_on"][0]["id"], api.as_str());
+    assert_eq!(json["lease"]["agent"], "claude-1");
+    assert_eq!(json["readiness"]["ready"], false);
+    assert_eq!(json["readiness"]["blockers"][0]["id"], api.as_str());
+
+    // Every wire upstream, nearest first, with its description
+    let upstream: Vec<&str> = json["upstream"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(upstream, [api.as_str(), schema.as_str()]);
+    assert_eq!(json["upstream"][1]["description"], "users and orgs");
+
+    let kinds: Vec<&str> = json["history"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["created", "dep_added", "claimed"]);
+}
+
+#[test]
+fn test_doc_markdown() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let schema = create_wire(&temp_dir, "Schema");
+    let api = create_wire(&temp_dir, "API");
+    wr(&temp_dir, &["dep", &api, &schema]);
+    wr(&temp_dir, &["done", &schema]);
+
+    let output = wr(&temp_dir, &["doc", &api, "-f", "markdown"]);
+    assert!(output.starts_with(&format!("### `{}` API\n", api)));
+    assert!(output.contains(&format!("**`{}` API** is ready.", api)));
+    assert!(output.contains(&format!("#### Upstream\n\n- [x] `{}` Schema\n", schema)));
+    assert!(output.contains("#### History\n\n"));
+    assert!(output.contains(" created\n"));
+}
+
+#[test]
+fn test_doc_unknown_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["doc", "fffffff"])
+        .assert()
+        .failure();
+}
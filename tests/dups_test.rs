@@ -0,0 +1,113 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_dups_reports_alike_open_wires() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let first = create_wire(&temp_dir, "Fix parser panic on empty input");
+    let second = create_wire(&temp_dir, "fix: parser panic on empty input!");
+    create_wire(&temp_dir, "Write release notes");
+
+    let pairs = wr(&temp_dir, &["dups"]);
+    assert_eq!(pairs.as_array().unwrap().len(), 1);
+    assert_eq!(pairs[0]["first"]["id"], first.as_str());
+    assert_eq!(pairs[0]["second"]["id"], second.as_str());
+    assert_eq!(pairs[0]["similarity"], 100);
+
+    // Finished wires only count with --all
+    wr(&temp_dir, &["done", &second]);
+    assert!(wr(&temp_dir, &["dups"]).as_array().unwrap().is_empty());
+    assert_eq!(
+        wr(&temp_dir, &["dups", "--all"]).as_array().unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_dups_skips_pairs_marked_as_duplicates() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let first = create_wire(&temp_dir, "Add login page");
+    let second = create_wire(&temp_dir, "Add the login page");
+    assert_eq!(
+        wr(&temp_dir, &["dups", "--threshold", "50"])
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(wr(&temp_dir, &["dups", "--threshold", "100"])
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    wr(&temp_dir, &["dep", &second, &first, "--kind", "duplicates"]);
+    assert!(wr(&temp_dir, &["dups", "--threshold", "50"])
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dups", "-f", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No likely duplicates."));
+}
+
+#[test]
+fn test_new_warns_about_alike_open_wire_when_configured() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let first = create_wire(&temp_dir, "Fix parser panic");
+
+    // Off by default
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Fix the parser panic"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[duplicates]\nthreshold = 90\nwarn_on_new = true\n",
+    )
+    .unwrap();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "fix parser panic!"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "wr: warning: 100% alike to open wire {}: Fix parser panic",
+            first
+        )));
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["-q", "new", "Fix parser panic?"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Write release notes"])
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+}
@@ -0,0 +1,96 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+/// An editor command that replaces the buffer with `text`
+fn editor_writing(dir: &TempDir, text: &str) -> String {
+    let replacement = dir.path().join("edited.md");
+    std::fs::write(&replacement, text).unwrap();
+    format!("cp {}", replacement.display())
+}
+
+fn edit(dir: &TempDir, id: &str, editor: &str) -> Command {
+    let mut cmd = Command::cargo_bin("wr").unwrap();
+    cmd.current_dir(dir)
+        .env_remove("VISUAL")
+        .env("EDITOR", editor)
+        .args(["edit", id]);
+    cmd
+}
+
+#[test]
+fn test_edit_saves_title_tags_and_description() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Old title");
+    wr(&temp_dir, &["tag", "add", &id, "stale"]);
+
+    let editor = editor_writing(
+        &temp_dir,
+        "+++\ntitle = \"New title\"\ntags = [\"bug\"]\n+++\n\nFirst paragraph.\n\nSecond paragraph.\n",
+    );
+    let output = edit(&temp_dir, &id, &editor).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["action"], "edited");
+
+    let wire = wr(&temp_dir, &["show", &id]);
+    assert_eq!(wire["title"], "New title");
+    assert_eq!(wire["description"], "First paragraph.\n\nSecond paragraph.");
+    assert_eq!(wire["tags"], serde_json::json!(["bug"]));
+}
+
+#[test]
+fn test_edit_without_changes_records_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Title");
+    let events = wr(&temp_dir, &["log", "--wire", &id])
+        .as_array()
+        .unwrap()
+        .len();
+
+    edit(&temp_dir, &id, "true")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"action\":\"unchanged\""));
+
+    let log = wr(&temp_dir, &["log", "--wire", &id]);
+    assert_eq!(log.as_array().unwrap().len(), events);
+}
+
+#[test]
+fn test_edit_keeps_unreadable_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Title");
+    let editor = editor_writing(&temp_dir, "no front matter here\n");
+
+    edit(&temp_dir, &id, &editor)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("front matter"))
+        .stderr(predicate::str::contains("edits not saved"));
+
+    assert_eq!(wr(&temp_dir, &["show", &id])["title"], "Title");
+}
+
+#[test]
+fn test_edit_failing_editor_saves_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Title");
+
+    edit(&temp_dir, &id, "false")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Editor 'false' exited"));
+}
@@ -0,0 +1,105 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr(dir: &TempDir, args: &[&str]) -> std::process::Output {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_envelope_wraps_results_with_the_request() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Wrapped");
+
+    let output = wr(&temp_dir, &["--envelope", "done", &id]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with(r#"{"ok":true,"command":"done","args":{"id":"#));
+
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["args"], serde_json::json!({ "id": id }));
+    assert_eq!(json["data"]["status"], "DONE");
+}
+
+#[test]
+fn test_envelope_echoes_subcommand_path_and_flags() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Tagged");
+
+    let output = wr(&temp_dir, &["tag", "add", &id, "backend", "--envelope"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["command"], "tag add");
+    assert_eq!(
+        json["args"],
+        serde_json::json!({ "id": id, "name": "backend" })
+    );
+
+    // Only arguments actually given are echoed, not defaults
+    let output = wr(&temp_dir, &["--envelope", "list", "--leaves", "-s", "todo"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["command"], "list");
+    assert_eq!(
+        json["args"],
+        serde_json::json!({ "leaves": true, "status": ["todo"] })
+    );
+    assert_eq!(json["data"][0]["id"], id.as_str());
+}
+
+#[test]
+fn test_envelope_wraps_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Loop");
+
+    let output = wr(&temp_dir, &["--envelope", "dep", &id, &id]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["command"], "dep");
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains("Circular dependency"));
+    assert!(json["cycle"].is_array());
+}
+
+#[test]
+fn test_without_envelope_output_is_unwrapped() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Plain");
+
+    let output = wr(&temp_dir, &["done", &id]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("ok").is_none());
+    assert_eq!(json["status"], "DONE");
+}
@@ -0,0 +1,123 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn ids(wires: &serde_json::Value) -> Vec<String> {
+    wires
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+/// epic depends on schema and api; api depends on auth. Unrelated is outside.
+fn setup_epic(dir: &TempDir) -> (String, String, String, String, String) {
+    wr(dir, &["init"]);
+    let epic = create_wire(dir, "Billing");
+    let schema = create_wire(dir, "Schema");
+    let api = create_wire(dir, "API");
+    let auth = create_wire(dir, "Auth");
+    let unrelated = create_wire(dir, "Unrelated");
+    wr(dir, &["dep", &epic, &schema]);
+    wr(dir, &["dep", &epic, &api]);
+    wr(dir, &["dep", &api, &auth]);
+    (epic, schema, api, auth, unrelated)
+}
+
+#[test]
+fn test_ready_epic_scopes_to_subtree() {
+    let temp_dir = TempDir::new().unwrap();
+    let (epic, schema, _api, auth, unrelated) = setup_epic(&temp_dir);
+
+    let mut ready = ids(&wr(&temp_dir, &["ready", "--epic", &epic, "-f", "json"]));
+    ready.sort();
+    let mut expected = vec![schema.clone(), auth.clone()];
+    expected.sort();
+    assert_eq!(ready, expected);
+
+    let all = ids(&wr(&temp_dir, &["ready", "-f", "json"]));
+    assert!(all.contains(&unrelated));
+}
+
+#[test]
+fn test_ready_epic_includes_epic_once_unblocked() {
+    let temp_dir = TempDir::new().unwrap();
+    let (epic, schema, api, auth, _unrelated) = setup_epic(&temp_dir);
+    for id in [&schema, &auth, &api] {
+        wr(&temp_dir, &["done", id]);
+    }
+
+    let ready = ids(&wr(&temp_dir, &["ready", "--epic", &epic, "-f", "json"]));
+    assert_eq!(ready, vec![epic]);
+}
+
+#[test]
+fn test_ready_epic_unknown_wire_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["ready", "--epic", "nope", "-f", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Wire not found"));
+}
+
+#[test]
+fn test_epic_progress_counts_subtree() {
+    let temp_dir = TempDir::new().unwrap();
+    let (epic, schema, api, auth, _unrelated) = setup_epic(&temp_dir);
+    wr(&temp_dir, &["cancel", &schema]);
+    wr(&temp_dir, &["done", &auth]);
+    wr(&temp_dir, &["start", &api]);
+
+    let json = wr(&temp_dir, &["epic", "progress", &epic, "-f", "json"]);
+    assert_eq!(json["epic"]["id"], epic.as_str());
+    // The epic is part of its own subtree
+    assert_eq!(json["total"]["count"], 4);
+    // 1 of 3 non-cancelled wires is done
+    assert_eq!(json["complete"], 33.3);
+    assert_eq!(ids(&json["ready"]), vec![api]);
+
+    let count = |status: &str| {
+        json["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["status"] == status)
+            .unwrap()["count"]
+            .clone()
+    };
+    assert_eq!(count("DONE"), 1);
+    assert_eq!(count("CANCELLED"), 1);
+    assert_eq!(count("IN_PROGRESS"), 1);
+    assert_eq!(count("TODO"), 1);
+}
+
+#[test]
+fn test_epic_progress_table() {
+    let temp_dir = TempDir::new().unwrap();
+    let (epic, _schema, _api, _auth, _unrelated) = setup_epic(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["epic", "progress", &epic, "-f", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Billing  (0.0% complete)"))
+        .stdout(predicate::str::contains("TOTAL"))
+        .stdout(predicate::str::contains("Schema"))
+        .stdout(predicate::str::contains("Unrelated").not());
+}
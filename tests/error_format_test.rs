@@ -83,3 +83,83 @@ fn test_invalid_status_rejected_by_clap() {
         .failure()
         .stderr(predicate::str::contains("invalid value 'INVALID'"));
 }
+
+#[test]
+fn test_exit_code_and_code_per_error_kind() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("list")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(json["code"], "not_a_repository");
+
+    init_test_repo(&temp_dir);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", "fffffff"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(json["code"], "wire_not_found");
+
+    let ids: Vec<String> = ["A", "B"]
+        .iter()
+        .map(|title| {
+            let output = Command::cargo_bin("wr")
+                .unwrap()
+                .current_dir(&temp_dir)
+                .args(["new", title])
+                .output()
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+            json["id"].as_str().unwrap().to_string()
+        })
+        .collect();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &ids[0], &ids[1]])
+        .assert()
+        .success();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &ids[1], &ids[0]])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("\"code\":\"circular_dependency\""));
+}
+
+#[test]
+fn test_other_errors_exit_1_and_usage_errors_64() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["import", "missing.json"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("\"code\":\"error\""));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "--no-such-flag"])
+        .assert()
+        .code(64);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .arg("--help")
+        .assert()
+        .code(0);
+}
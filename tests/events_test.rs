@@ -0,0 +1,126 @@
+use assert_cmd::Command;
+use std::io::{BufRead, BufReader};
+use std::sync::mpsc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn parse_lines(output: &str) -> Vec<serde_json::Value> {
+    output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_events_prints_jsonl() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let a = create_wire(&temp_dir, "A");
+    let b = create_wire(&temp_dir, "B");
+    wr(&temp_dir, &["dep", &b, &a]);
+
+    let events = parse_lines(&wr(&temp_dir, &["events"]));
+    let kinds: Vec<&str> = events.iter().map(|e| e["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, ["created", "created", "dep_added"]);
+
+    let seq = events[0]["seq"].as_i64().unwrap().to_string();
+    let events = parse_lines(&wr(&temp_dir, &["events", "--after", &seq]));
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["wire_id"], b.as_str());
+
+    let events = parse_lines(&wr(&temp_dir, &["events", "--kind", "dep_added"]));
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["data"]["depends_on"], a.as_str());
+}
+
+#[test]
+fn test_events_rejects_unknown_kind() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["events", "--kind", "exploded"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_events_follow_streams_new_events() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let old = create_wire(&temp_dir, "Before following");
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("wr"))
+        .current_dir(&temp_dir)
+        .args(["events", "--follow"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    let next = || -> serde_json::Value {
+        let line = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        serde_json::from_str(&line).unwrap()
+    };
+
+    // Give the follower time to start before making changes
+    std::thread::sleep(Duration::from_millis(500));
+    let new = create_wire(&temp_dir, "While following");
+    wr(&temp_dir, &["done", &old]);
+
+    let event = next();
+    assert_eq!(event["kind"], "created");
+    assert_eq!(event["wire_id"], new.as_str());
+    let event = next();
+    assert_eq!(event["kind"], "updated");
+    assert_eq!(event["data"]["status"]["to"], "DONE");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
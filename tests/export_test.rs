@@ -0,0 +1,162 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_export_graphml_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("export")
+        .arg("--format")
+        .arg("graphml")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("<?xml"));
+    assert!(stdout.contains("<graph id=\"wires\" edgedefault=\"directed\">"));
+    assert!(stdout.contains("</graphml>"));
+    assert!(!stdout.contains("<node"));
+}
+
+#[test]
+fn test_export_graphml_nodes_and_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("dep")
+        .arg(&wire_a)
+        .arg(&wire_b)
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("export")
+        .arg("--format")
+        .arg("graphml")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("<node id=\"{}\">", wire_a)));
+    assert!(stdout.contains(&format!("<node id=\"{}\">", wire_b)));
+    assert!(stdout.contains("<data key=\"status\">TODO</data>"));
+    assert!(stdout.contains("<data key=\"priority\">0</data>"));
+    assert!(stdout.contains(&format!("source=\"{}\" target=\"{}\"", wire_a, wire_b)));
+}
+
+#[test]
+fn test_export_graphml_escapes_titles() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    create_wire(&temp_dir, "Use <Vec> & \"quotes\"");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("export")
+        .arg("--format")
+        .arg("graphml")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Use &lt;Vec&gt; &amp; &quot;quotes&quot;"));
+}
+
+fn export_graphml(dir: &TempDir) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["export", "--format", "graphml"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn dep(dir: &TempDir, args: &[&str]) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_export_graphml_is_sorted_and_stable() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let mut ids: Vec<String> = ["One", "Two", "Three", "Four"]
+        .iter()
+        .map(|title| create_wire(&temp_dir, title))
+        .collect();
+    dep(&temp_dir, &["dep", &ids[3], &ids[0]]);
+    dep(&temp_dir, &["dep", &ids[1], &ids[2]]);
+    dep(&temp_dir, &["dep", &ids[0], &ids[2]]);
+
+    let first = export_graphml(&temp_dir);
+    assert_eq!(export_graphml(&temp_dir), first);
+
+    let node_order: Vec<&str> = first
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("<node id=\""))
+        .map(|rest| &rest[..7])
+        .collect();
+    ids.sort();
+    assert_eq!(node_order, ids);
+
+    let edges: Vec<&str> = first.lines().filter(|l| l.contains("<edge")).collect();
+    let mut sorted = edges.clone();
+    sorted.sort();
+    assert_eq!(edges, sorted);
+
+    // Removing one edge leaves the others' lines untouched
+    let removed = edges[0].to_string();
+    let source = removed.split("source=\"").nth(1).unwrap()[..7].to_string();
+    let target = removed.split("target=\"").nth(1).unwrap()[..7].to_string();
+    dep(&temp_dir, &["undep", &source, &target]);
+    let after = export_graphml(&temp_dir);
+    let remaining: Vec<&str> = after.lines().filter(|l| l.contains("<edge")).collect();
+    assert_eq!(remaining, edges[1..]);
+}
@@ -199,3 +199,362 @@ fn test_show_format_includes_symbols() {
     assert!(stdout.contains("[pri:"));
     assert!(stdout.contains("Show wire"));
 }
+
+#[test]
+fn test_list_format_markdown() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let finished = create_wire(&temp_dir, "Finished wire");
+    let blocker = create_wire(&temp_dir, "Blocker");
+    let blocked = create_wire(&temp_dir, "Blocked wire");
+
+    for args in [
+        vec!["done", finished.as_str()],
+        vec!["dep", blocked.as_str(), blocker.as_str()],
+    ] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(&args)
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "-f", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "- [x] `{}` Finished wire",
+            finished
+        )))
+        .stdout(predicate::str::contains(format!(
+            "- [ ] `{}` Blocked wire — blocked by `{}`",
+            blocked, blocker
+        )));
+}
+
+#[test]
+fn test_show_format_markdown() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let dep = create_wire(&temp_dir, "Dependency");
+    let id = create_wire(&temp_dir, "Main wire");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &id, &dep])
+        .assert()
+        .success();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &id, "-f", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Main wire"))
+        .stdout(predicate::str::contains(format!(
+            "- [ ] `{}` Dependency",
+            dep
+        )));
+}
+
+fn wr_stdout(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_list_format_jsonl() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let first = create_wire(&temp_dir, "First");
+    let second = create_wire(&temp_dir, "Second");
+
+    let stdout = wr_stdout(&temp_dir, &["list", "-f", "jsonl"]);
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    let mut ids: Vec<_> = lines.iter().map(|w| w["id"].as_str().unwrap()).collect();
+    ids.sort();
+    let mut expected = vec![first.as_str(), second.as_str()];
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[test]
+fn test_ready_format_jsonl_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    assert_eq!(wr_stdout(&temp_dir, &["ready", "-f", "jsonl"]), "");
+
+    create_wire(&temp_dir, "Ready");
+    let stdout = wr_stdout(&temp_dir, &["ready", "-f", "jsonl"]);
+    assert_eq!(stdout.lines().count(), 1);
+    let wire: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(wire["title"], "Ready");
+}
+
+#[test]
+fn test_show_format_jsonl_is_one_line() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Single");
+
+    let stdout = wr_stdout(&temp_dir, &["show", &id, "-f", "jsonl"]);
+    assert_eq!(stdout.lines().count(), 1);
+    let wire: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(wire["id"], id.as_str());
+}
+
+#[test]
+fn test_graph_format_jsonl() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let api = create_wire(&temp_dir, "API");
+    let db = create_wire(&temp_dir, "Database");
+    wr_stdout(&temp_dir, &["dep", &api, &db]);
+
+    let stdout = wr_stdout(&temp_dir, &["graph", "-f", "jsonl"]);
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["type"], "node");
+    assert_eq!(lines[1]["type"], "node");
+    assert_eq!(lines[2]["type"], "edge");
+    assert_eq!(lines[2]["from"], api.as_str());
+    assert_eq!(lines[2]["to"], db.as_str());
+}
+
+#[test]
+fn test_pretty_indents_json() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Pretty");
+
+    let compact = wr_stdout(&temp_dir, &["list"]);
+    assert_eq!(compact.lines().count(), 1);
+
+    let pretty = wr_stdout(&temp_dir, &["list", "--pretty"]);
+    assert!(pretty.lines().count() > 1);
+    assert!(pretty.contains("\n    \"title\": \"Pretty\""));
+    let a: serde_json::Value = serde_json::from_str(&compact).unwrap();
+    let b: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+    assert_eq!(a, b);
+
+    // Still one value per line for JSON Lines
+    let jsonl = wr_stdout(&temp_dir, &["--pretty", "list", "-f", "jsonl"]);
+    assert_eq!(jsonl.lines().count(), 1);
+}
+
+#[test]
+fn test_list_format_csv() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Fix \"quoted\", parser");
+
+    let stdout = wr_stdout(&temp_dir, &["list", "-f", "csv"]);
+    assert_eq!(
+        stdout,
+        format!(
+            "id,title,status,priority,tags,assignee\n{},\"Fix \"\"quoted\"\", parser\",TODO,0,,\n",
+            id
+        )
+    );
+}
+
+#[test]
+fn test_ready_format_tsv_with_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Ready, set");
+
+    let stdout = wr_stdout(&temp_dir, &["ready", "-f", "tsv", "--columns", "title, id"]);
+    assert_eq!(stdout, format!("title\tid\nReady, set\t{}\n", id));
+}
+
+#[test]
+fn test_list_table_with_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Parse input");
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &id, "--priority", "12"])
+        .assert()
+        .success();
+
+    let stdout = wr_stdout(
+        &temp_dir,
+        &[
+            "list",
+            "-f",
+            "table",
+            "--columns",
+            "id,title,priority,age,assignee",
+        ],
+    );
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert!(lines[0].starts_with("ID  "), "{}", stdout);
+    assert!(lines[0].ends_with("PRIORITY  AGE  ASSIGNEE"), "{}", stdout);
+    // Numbers are right-aligned under their heading; empty cells trail off
+    assert!(
+        lines[1].starts_with(&format!("{}  Parse input        12   0s", id)),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_columns_age_in_csv() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Fresh");
+
+    let stdout = wr_stdout(&temp_dir, &["list", "-f", "csv", "--columns", "title,age"]);
+    assert_eq!(stdout, "title,age\nFresh,0s\n");
+}
+
+#[test]
+fn test_columns_rejects_unknown_field() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "-f", "csv", "--columns", "id,owner"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown template field 'owner'"));
+}
+
+#[test]
+fn test_quiet_prints_only_new_id_and_nothing_on_status_change() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["-q", "new", "Quiet wire"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let id = String::from_utf8(output.stdout).unwrap().trim().to_string();
+    assert!(!id.is_empty() && !id.starts_with('{'));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &id, "--quiet"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn test_quiet_silences_every_change() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let quiet = |args: &[&str]| {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .arg("-q")
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "wr -q {:?} failed", args);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    let id = quiet(&["new", "Quiet wire"]).trim().to_string();
+    let other = quiet(&["new", "Other wire"]).trim().to_string();
+    let old = quiet(&["new", "Old wire"]).trim().to_string();
+    for args in [
+        vec!["block", &id, "--reason", "waiting"],
+        vec!["unblock", &id],
+        vec!["update", &id, "--title", "Renamed"],
+        vec!["update", &id, "--status", "done"],
+        vec!["handoff", &other, "--note", "halfway"],
+        vec!["tag", "add", &other, "backend"],
+        vec!["dep", &other, &id],
+        vec!["supersede", &old, &other],
+        vec!["rm", &id],
+    ] {
+        assert_eq!(quiet(&args), "", "wr -q {:?} printed output", args);
+    }
+
+    // Reports of what a dry run would do are still printed
+    assert!(quiet(&["renumber-priorities", "--dry-run"]).starts_with('{'));
+}
+
+#[test]
+fn test_verbose_logs_database_and_sql_to_stderr() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["-v", "list", "-f", "json"])
+        .assert()
+        .success()
+        .stdout("[]\n")
+        .stderr(predicate::str::contains("wr: database "))
+        .stderr(predicate::str::contains("wr: sql "));
+}
+
+#[test]
+fn test_database_and_config_are_read_once_per_command() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Wire A");
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[titles]\ncollapse_whitespace = true\n",
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["-v", "update", &id, "--title", "Wire  B"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.matches("wr: database ").count(), 1, "{}", stderr);
+    assert_eq!(stderr.matches("wr: config ").count(), 1, "{}", stderr);
+}
+
+#[test]
+fn test_quiet_conflicts_with_verbose() {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .args(["-q", "-v", "list"])
+        .assert()
+        .failure();
+}
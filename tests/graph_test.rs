@@ -209,6 +209,29 @@ fn test_graph_format_dot_with_nodes() {
     assert!(stdout.contains("TODO"));
 }
 
+#[test]
+fn test_graph_format_dot_escapes_hostile_titles() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire = create_wire(&temp_dir, "Fix \"quoted\" C:\\path {x} [y] — ünïcode");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "--format", "dot"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("charset=\"UTF-8\";"));
+    assert!(stdout.contains(&format!(
+        "\"{}\" [label=\"Fix \\\"quoted\\\" C:\\\\path {{x}} [y] — ünïcode\\nTODO\"];",
+        wire
+    )));
+}
+
 #[test]
 fn test_graph_format_dot_with_edges() {
     let temp_dir = TempDir::new().unwrap();
@@ -242,3 +265,398 @@ fn test_graph_format_dot_with_edges() {
     // Should have an edge from A to B (A depends on B)
     assert!(stdout.contains("->"));
 }
+
+#[test]
+fn test_graph_format_dot_is_sorted() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let mut ids: Vec<String> = ["One", "Two", "Three"]
+        .iter()
+        .map(|title| create_wire(&temp_dir, title))
+        .collect();
+    for (from, to) in [(2, 0), (1, 0), (2, 1)] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["dep", &ids[from], &ids[to]])
+            .assert()
+            .success();
+    }
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "--format", "dot"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let nodes: Vec<&str> = stdout
+        .lines()
+        .filter(|l| l.contains("[label="))
+        .map(|l| &l.trim()[1..8])
+        .collect();
+    ids.sort();
+    assert_eq!(nodes, ids);
+
+    let edges: Vec<&str> = stdout.lines().filter(|l| l.contains("->")).collect();
+    let mut sorted = edges.clone();
+    sorted.sort();
+    assert_eq!(edges, sorted);
+}
+
+#[test]
+fn test_graph_format_adjacency() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+    let wire_c = create_wire(&temp_dir, "Wire C");
+
+    // A depends on B
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("dep")
+        .arg(&wire_a)
+        .arg(&wire_b)
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("graph")
+        .arg("--format")
+        .arg("adjacency")
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let map = json.as_object().unwrap();
+
+    // Every wire is a key, even without dependencies
+    assert_eq!(map.len(), 3);
+    assert_eq!(json[&wire_a], serde_json::json!([wire_b]));
+    assert_eq!(json[&wire_b], serde_json::json!([]));
+    assert_eq!(json[&wire_c], serde_json::json!([]));
+}
+
+#[test]
+fn test_graph_invalid_format() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("graph")
+        .arg("--format")
+        .arg("yaml")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Valid: json, dot, adjacency"));
+}
+
+fn graph_edges(dir: &TempDir) -> Vec<(String, String)> {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("graph")
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["from"].as_str().unwrap().to_string(),
+                e["to"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_graph_apply_adds_and_removes_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+    let wire_c = create_wire(&temp_dir, "Wire C");
+
+    // Start with A -> B
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("dep")
+        .arg(&wire_a)
+        .arg(&wire_b)
+        .assert()
+        .success();
+
+    // Desired graph: only B -> C
+    let input = serde_json::json!({ "edges": [{ "from": wire_b, "to": wire_c }] });
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("graph")
+        .arg("apply")
+        .write_stdin(input.to_string())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["added"].as_array().unwrap().len(), 1);
+    assert_eq!(json["removed"].as_array().unwrap().len(), 1);
+    assert_eq!(json["removed"][0]["from"], wire_a.as_str());
+
+    assert_eq!(graph_edges(&temp_dir), vec![(wire_b, wire_c)]);
+}
+
+#[test]
+fn test_graph_apply_accepts_adjacency() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    let input = serde_json::json!({ wire_a.clone(): [wire_b.clone()], wire_b.clone(): [] });
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("graph")
+        .arg("apply")
+        .write_stdin(input.to_string())
+        .assert()
+        .success();
+
+    assert_eq!(graph_edges(&temp_dir), vec![(wire_a, wire_b)]);
+}
+
+#[test]
+fn test_graph_labels_and_apply_keeps_non_blocking_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &wire_a, &wire_b, "--kind", "parent"])
+        .assert()
+        .success();
+
+    assert!(graph_stdout(&temp_dir, "mermaid")
+        .contains(&format!("{} -. parent .-> {}", wire_a, wire_b)));
+    let adjacency: serde_json::Value =
+        serde_json::from_str(&graph_stdout(&temp_dir, "adjacency")).unwrap();
+    assert!(adjacency[&wire_a].as_array().unwrap().is_empty());
+
+    // Applying a graph without blocking edges leaves the link alone
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "apply"])
+        .write_stdin(serde_json::json!({ "edges": [] }).to_string())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["removed"].as_array().unwrap().is_empty());
+
+    let graph: serde_json::Value = serde_json::from_str(&graph_stdout(&temp_dir, "json")).unwrap();
+    assert_eq!(graph["edges"][0]["kind"], "parent");
+}
+
+#[test]
+fn test_graph_apply_rejects_cycle_atomically() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+    let wire_c = create_wire(&temp_dir, "Wire C");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("dep")
+        .arg(&wire_a)
+        .arg(&wire_b)
+        .assert()
+        .success();
+
+    // A -> B -> C -> A is a cycle
+    let input = serde_json::json!({ "edges": [
+        { "from": wire_a, "to": wire_b },
+        { "from": wire_b, "to": wire_c },
+        { "from": wire_c, "to": wire_a },
+    ]});
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("graph")
+        .arg("apply")
+        .write_stdin(input.to_string())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Circular dependency"));
+
+    // Nothing from the rejected graph was kept
+    assert_eq!(graph_edges(&temp_dir), vec![(wire_a, wire_b)]);
+}
+
+#[test]
+fn test_graph_apply_dry_run() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    let input = serde_json::json!({ "edges": [{ "from": wire_a, "to": wire_b }] });
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("graph")
+        .arg("apply")
+        .arg("--dry-run")
+        .write_stdin(input.to_string())
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["added"].as_array().unwrap().len(), 1);
+    assert_eq!(json["dry_run"], true);
+
+    assert!(graph_edges(&temp_dir).is_empty());
+}
+
+fn graph_layout(dir: &TempDir, args: &[&str]) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["graph", "layout"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn graph_stdout(dir: &TempDir, format: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["graph", "-f", format])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_graph_layout_merges_and_clears_hints() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire = create_wire(&temp_dir, "Wire A");
+
+    graph_layout(&temp_dir, &[&wire, "--cluster", "Backend"]);
+    let json = graph_layout(&temp_dir, &[&wire, "--color", "#FFCC00"]);
+    assert_eq!(
+        json["layout"],
+        serde_json::json!({ "cluster": "Backend", "color": "#ffcc00" })
+    );
+
+    let json = graph_layout(&temp_dir, &[&wire, "--clear", "--rank", "top"]);
+    assert_eq!(json["layout"], serde_json::json!({ "rank": "top" }));
+
+    let graph: serde_json::Value = serde_json::from_str(&graph_stdout(&temp_dir, "json")).unwrap();
+    assert_eq!(graph["nodes"][0]["layout"]["rank"], "top");
+}
+
+#[test]
+fn test_graph_layout_rejects_invalid_color() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire = create_wire(&temp_dir, "Wire A");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["graph", "layout", &wire, "--color", "red; x"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_graph_format_dot_with_layout_hints() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+    graph_layout(
+        &temp_dir,
+        &[&wire_a, "--cluster", "Backend", "--color", "salmon"],
+    );
+    graph_layout(&temp_dir, &[&wire_a, "--rank", "r1"]);
+    graph_layout(&temp_dir, &[&wire_b, "--rank", "r1"]);
+
+    let dot = graph_stdout(&temp_dir, "dot");
+    assert!(dot.contains("subgraph \"cluster_0\" {\n        label=\"Backend\";"));
+    assert!(dot.contains(&format!(
+        "\"{}\" [label=\"Wire A\\nTODO\", style=filled, fillcolor=\"salmon\"];",
+        wire_a
+    )));
+    let mut ranked = [wire_a, wire_b];
+    ranked.sort();
+    assert!(dot.contains(&format!(
+        "{{ rank=same; \"{}\"; \"{}\"; }}",
+        ranked[0], ranked[1]
+    )));
+}
+
+#[test]
+fn test_graph_format_mermaid() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_a = create_wire(&temp_dir, "Say \"hi\"");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &wire_a, &wire_b])
+        .assert()
+        .success();
+    graph_layout(
+        &temp_dir,
+        &[&wire_b, "--cluster", "Backend", "--color", "#0af"],
+    );
+
+    let mermaid = graph_stdout(&temp_dir, "mermaid");
+    assert!(mermaid.starts_with("flowchart LR\n"));
+    assert!(mermaid.contains(&format!("{}[\"Say #quot;hi#quot;<br/>TODO\"]", wire_a)));
+    assert!(mermaid.contains(&format!(
+        "    subgraph cluster_0 [\"Backend\"]\n        {}[\"Wire B<br/>TODO\"]\n    end",
+        wire_b
+    )));
+    assert!(mermaid.contains(&format!("{} --> {}", wire_a, wire_b)));
+    assert!(mermaid.contains(&format!("style {} fill:#0af", wire_b)));
+}
@@ -0,0 +1,99 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_handoff_releases_claim_and_packages_context() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let feature = create_wire(&temp_dir, "Feature");
+    let schema = create_wire(&temp_dir, "Schema");
+    let docs = create_wire(&temp_dir, "Docs");
+    wr(&temp_dir, &["dep", &feature, &schema]);
+    wr(&temp_dir, &["dep", &feature, &docs]);
+    wr(&temp_dir, &["done", &schema]);
+    wr(&temp_dir, &["start", &feature]);
+    wr(&temp_dir, &["claim", &feature, "--agent", "alice"]);
+
+    let json = wr(
+        &temp_dir,
+        &[
+            "handoff",
+            &feature,
+            "--agent",
+            "alice",
+            "--note",
+            "Stopped at the migration",
+        ],
+    );
+    assert_eq!(json["id"], feature.as_str());
+    assert_eq!(json["status"], "IN_PROGRESS");
+    assert_eq!(json["released"]["agent"], "alice");
+    assert!(json.get("lease").is_none());
+    let remaining: Vec<_> = json["remaining"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(remaining, vec![docs.as_str()]);
+    assert_eq!(json["notes"][0]["agent"], "alice");
+    assert_eq!(json["notes"][0]["note"], "Stopped at the migration");
+    assert_eq!(
+        json["history"].as_array().unwrap().last().unwrap()["kind"],
+        "handed_off"
+    );
+
+    // The next agent can claim it straight away
+    wr(&temp_dir, &["claim", &feature, "--agent", "bob"]);
+}
+
+#[test]
+fn test_handoff_keeps_earlier_notes() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Feature");
+
+    wr(&temp_dir, &["handoff", &id, "--note", "first"]);
+    wr(&temp_dir, &["handoff", &id]);
+    let json = wr(&temp_dir, &["handoff", &id, "-n", "second"]);
+    let notes: Vec<_> = json["notes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["note"].as_str().unwrap())
+        .collect();
+    assert_eq!(notes, vec!["first", "second"]);
+    assert!(json.get("released").is_none());
+}
+
+#[test]
+fn test_handoff_refuses_another_agents_claim() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Feature");
+    wr(&temp_dir, &["claim", &id, "--agent", "alice"]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["handoff", &id, "--agent", "bob"])
+        .assert()
+        .code(9)
+        .stderr(predicate::str::contains("not_claimed_by"));
+
+    let log = wr(&temp_dir, &["log", "--wire", &id]);
+    assert!(log
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|event| event["kind"] != "handed_off"));
+}
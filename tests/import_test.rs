@@ -0,0 +1,549 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr(dir: &TempDir, args: &[&str]) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .assert()
+        .success();
+}
+
+fn export_json(dir: &TempDir) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["export", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn populate(dir: &TempDir) -> (String, String) {
+    let a = create_wire(dir, "Wire A");
+    let b = create_wire(dir, "Wire B");
+    wr(dir, &["dep", &a, &b]);
+    wr(dir, &["done", &b]);
+    wr(
+        dir,
+        &["update", &a, "--description", "Details", "--priority", "3"],
+    );
+    wr(dir, &["tag", "add", &a, "backend"]);
+    wr(dir, &["graph", "layout", &a, "--cluster", "Backend"]);
+    (a, b)
+}
+
+#[test]
+fn test_export_json_round_trips_through_import() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let (a, _) = populate(&source);
+
+    let exported = export_json(&source);
+    let snapshot: serde_json::Value = serde_json::from_str(&exported).unwrap();
+    let wire = snapshot["wires"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|wire| wire["id"] == a.as_str())
+        .unwrap();
+    assert_eq!(wire["layout"]["cluster"], "Backend");
+    let path = source.path().join("wires.json");
+    std::fs::write(&path, &exported).unwrap();
+
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .arg("import")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"wires\":2"))
+        .stdout(predicate::str::contains("\"dependencies\":1"));
+
+    // Byte-for-byte identical, including timestamps, tags and layout hints
+    assert_eq!(export_json(&target), exported);
+}
+
+/// Sorted IDs of the wires a listing command such as `ready` prints
+fn ids(dir: &TempDir, args: &[&str]) -> Vec<String> {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut ids: Vec<String> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_import_keeps_external_blockers() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let (a, _) = populate(&source);
+    let waiting = create_wire(&source, "Wire C");
+    wr(&source, &["block-on", "waiting for API key", &waiting]);
+    assert_eq!(ids(&source, &["ready"]), vec![a.clone()]);
+
+    let exported = export_json(&source);
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    wr_stdin(&target, &["import", "-"], &exported);
+
+    assert_eq!(ids(&target, &["ready"]), vec![a]);
+    assert_eq!(export_json(&target), exported);
+
+    wr(&target, &["unblock-ext", "waiting for API key"]);
+    assert!(ids(&target, &["ready"]).contains(&waiting));
+}
+
+#[test]
+fn test_import_keeps_archived_wires_archived() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let (a, b) = populate(&source);
+    wr(&source, &["archive", &b]);
+
+    let exported = export_json(&source);
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    wr_stdin(&target, &["import", "-"], &exported);
+
+    assert_eq!(ids(&target, &["list"]), vec![a.clone()]);
+    let mut all = vec![a, b];
+    all.sort();
+    assert_eq!(ids(&target, &["list", "--include-archived"]), all);
+    assert_eq!(export_json(&target), exported);
+}
+
+#[test]
+fn test_import_keeps_pause_reasons() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let id = create_wire(&source, "Set aside");
+    wr(
+        &source,
+        &["pause", &id, "--reason", "waiting for design review"],
+    );
+
+    let exported = export_json(&source);
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    wr_stdin(&target, &["import", "-"], &exported);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["show", &id, "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["status"], "PAUSED");
+    assert_eq!(json["paused"]["reason"], "waiting for design review");
+    assert_eq!(export_json(&target), exported);
+}
+
+#[test]
+fn test_import_keeps_preconditions() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let held = create_wire(&source, "Holds");
+    let failing = create_wire(&source, "Fails");
+    wr(&source, &["precondition", "set", &held, "true"]);
+    wr(&source, &["precondition", "set", &failing, "false"]);
+    let ready = ids(&source, &["ready", "--check-preconditions"]);
+    assert_eq!(ready, vec![held]);
+
+    let exported = export_json(&source);
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    wr_stdin(&target, &["import", "-"], &exported);
+
+    assert_eq!(ids(&target, &["ready", "--check-preconditions"]), ready);
+    assert_eq!(export_json(&target), exported);
+}
+
+#[test]
+fn test_export_json_is_sorted() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    populate(&temp_dir);
+    create_wire(&temp_dir, "Wire C");
+
+    let json: serde_json::Value = serde_json::from_str(&export_json(&temp_dir)).unwrap();
+    assert_eq!(json["version"], 3);
+
+    let ids: Vec<&str> = json["wires"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    let mut sorted = ids.clone();
+    sorted.sort();
+    assert_eq!(ids, sorted);
+}
+
+#[test]
+fn test_import_rejects_existing_ids_atomically() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    populate(&temp_dir);
+
+    let exported = export_json(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["import", "-"])
+        .write_stdin(exported.clone())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    assert_eq!(export_json(&temp_dir), exported);
+}
+
+#[test]
+fn test_import_replace_rebuilds_database() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    populate(&temp_dir);
+
+    let exported = export_json(&temp_dir);
+    create_wire(&temp_dir, "Added after export");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["import", "-", "--replace"])
+        .write_stdin(exported.clone())
+        .assert()
+        .success();
+
+    assert_eq!(export_json(&temp_dir), exported);
+}
+
+#[test]
+fn test_import_rejects_unknown_version() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["import", "-"])
+        .write_stdin(r#"{"version": 99, "wires": [], "dependencies": []}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported snapshot version 99"));
+}
+
+#[test]
+fn test_import_converts_version_1_timestamps_to_milliseconds() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let snapshot = r#"{"version": 1, "wires": [{"id": "abc1234", "title": "Old wire",
+        "description": null, "status": "TODO", "created_at": 1700000000,
+        "updated_at": 1700000100, "priority": 0}], "dependencies": []}"#;
+    wr_stdin(&temp_dir, &["import", "-"], snapshot);
+
+    let json: serde_json::Value = serde_json::from_str(&export_json(&temp_dir)).unwrap();
+    assert_eq!(json["version"], 3);
+    assert_eq!(json["wires"][0]["created_at"], 1_700_000_000_000_i64);
+    assert_eq!(json["wires"][0]["updated_at"], 1_700_000_100_000_i64);
+}
+
+fn wr_stdin(dir: &TempDir, args: &[&str], stdin: &str) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .write_stdin(stdin.to_string())
+        .assert()
+        .success();
+}
+
+fn import_with_strategy(dir: &TempDir, snapshot: &str, strategy: &str) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["import", "-", "--strategy", strategy])
+        .write_stdin(snapshot.to_string())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn title(dir: &TempDir, id: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["show", id])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["title"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_import_conflict_report_lists_fields() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let (a, _) = populate(&temp_dir);
+    let exported = export_json(&temp_dir);
+    wr(&temp_dir, &["update", &a, "--title", "Renamed locally"]);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["import", "-"])
+        .write_stdin(exported)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    let conflicts = error["conflicts"].as_array().unwrap();
+    assert_eq!(conflicts.len(), 2);
+    let renamed = conflicts.iter().find(|c| c["id"] == a.as_str()).unwrap();
+    assert_eq!(renamed["fields"][0]["field"], "title");
+    assert_eq!(renamed["fields"][0]["local"], "Renamed locally");
+    assert_eq!(renamed["fields"][0]["incoming"], "Wire A");
+}
+
+#[test]
+fn test_import_strategy_theirs_ours_and_newest() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let (a, _) = populate(&temp_dir);
+    let exported = export_json(&temp_dir);
+    wr(&temp_dir, &["update", &a, "--title", "Renamed locally"]);
+
+    // The local edit is newer than the snapshot
+    let json = import_with_strategy(&temp_dir, &exported, "newest");
+    assert_eq!(json["conflicts"][0]["resolution"], "ours");
+    assert_eq!(title(&temp_dir, &a), "Renamed locally");
+
+    let json = import_with_strategy(&temp_dir, &exported, "ours");
+    assert_eq!(json["conflicts"].as_array().unwrap().len(), 1);
+    assert_eq!(title(&temp_dir, &a), "Renamed locally");
+
+    let json = import_with_strategy(&temp_dir, &exported, "theirs");
+    assert_eq!(json["conflicts"][0]["id"], a.as_str());
+    assert_eq!(json["conflicts"][0]["resolution"], "theirs");
+    assert_eq!(export_json(&temp_dir), exported);
+
+    // Nothing differs any more
+    let json = import_with_strategy(&temp_dir, &exported, "theirs");
+    assert!(json["conflicts"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_import_interactive_needs_a_terminal() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["import", "-", "--strategy", "interactive"])
+        .write_stdin(r#"{"version": 2, "wires": [], "dependencies": []}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs a terminal"));
+}
+
+#[test]
+fn test_import_checks_manifest() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let (a, _) = populate(&source);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&source)
+        .args(["export", "--format", "json", "--manifest"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let signed = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&signed).unwrap();
+    assert_eq!(json["manifest"]["algorithm"], "sha256");
+    assert_eq!(json["manifest"]["wires"].as_object().unwrap().len(), 2);
+
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-", "--check-manifest"])
+        .write_stdin(signed.clone())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"manifest_checked\":true"));
+    assert_eq!(export_json(&target), export_json(&source));
+
+    // An edited wire is named, and nothing is imported
+    let tampered = signed.replace("\"Details\"", "\"Other details\"");
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-", "--check-manifest"])
+        .write_stdin(tampered)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(
+        error["problems"],
+        serde_json::json!([format!("wire {} was changed", a)])
+    );
+    assert!(!export_json(&target).contains("Wire A"));
+}
+
+#[test]
+fn test_import_check_manifest_needs_a_manifest() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    populate(&temp_dir);
+    let unsigned = export_json(&temp_dir);
+
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-", "--check-manifest"])
+        .write_stdin(unsigned.clone())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no manifest"));
+
+    // Without --check-manifest, snapshots with and without one import alike
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-"])
+        .write_stdin(unsigned)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_import_verify_checks_file_digest() {
+    use sha2::{Digest, Sha256};
+
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    populate(&source);
+    let snapshot = export_json(&source);
+    let digest = format!("{:x}", Sha256::digest(snapshot.as_bytes()));
+
+    // Any byte changed fails, even where the JSON means the same
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-", "--verify", &digest])
+        .write_stdin(format!(" {}", snapshot))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(14));
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["code"], "snapshot_unverified");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-", "--verify", &digest.to_uppercase()])
+        .write_stdin(snapshot)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"verified\":true"));
+}
+
+#[test]
+fn test_import_keeps_ids_of_another_scheme() {
+    let source = TempDir::new().unwrap();
+    init_test_repo(&source);
+    let (a, b) = populate(&source);
+    let snapshot = export_json(&source);
+
+    let target = TempDir::new().unwrap();
+    init_test_repo(&target);
+    std::fs::write(
+        target.path().join(".wires/config.toml"),
+        "id_scheme = \"ulid\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["import", "-"])
+        .write_stdin(snapshot)
+        .assert()
+        .success();
+
+    // The hex IDs live alongside new ULIDs, and edges can join them
+    let ulid = create_wire(&target, "After the import");
+    assert_eq!(ulid.len(), 26);
+    wr(&target, &["dep", &ulid, &a]);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&target)
+        .args(["show", &ulid])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["depends_on"][0]["id"], a.as_str());
+    assert_eq!(a.len(), 7);
+    assert_eq!(b.len(), 7);
+}
@@ -2,6 +2,9 @@ use assert_cmd::Command;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
+mod common;
+use common::wr;
+
 #[test]
 fn test_init_creates_database() {
     let temp_dir = TempDir::new().unwrap();
@@ -51,3 +54,189 @@ fn test_init_output_is_json() {
     assert_eq!(json["status"], "initialized");
     assert!(json["path"].as_str().unwrap().ends_with(".wires/wires.db"));
 }
+
+#[test]
+fn test_init_force_recreates_with_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    wr(&temp_dir, &["new", "Old wire"]);
+
+    let json = wr(&temp_dir, &["init", "--force"]);
+    assert_eq!(json["status"], "reinitialized");
+
+    let backup = std::path::PathBuf::from(json["backup"].as_str().unwrap());
+    assert!(backup.exists());
+    assert!(backup.starts_with(temp_dir.path().join(".wires")));
+
+    // The backup is a complete database holding the old wire
+    let conn = rusqlite::Connection::open(&backup).unwrap();
+    let title: String = conn
+        .query_row("SELECT title FROM wires", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(title, "Old wire");
+
+    let list = wr(&temp_dir, &["list"]);
+    assert!(list.as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_init_force_on_fresh_directory() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let json = wr(&temp_dir, &["init", "--force"]);
+    assert_eq!(json["status"], "reinitialized");
+    assert!(json["backup"].is_null());
+    assert!(temp_dir.path().join(".wires").join("wires.db").exists());
+}
+
+#[test]
+fn test_init_upgrade() {
+    let temp_dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--upgrade"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a wires repository"));
+
+    wr(&temp_dir, &["init"]);
+    let json = wr(&temp_dir, &["init", "--upgrade"]);
+    assert_eq!(json["status"], "up_to_date");
+    assert_eq!(json["from_version"], json["to_version"]);
+
+    // A repository created before schema migrations existed
+    let legacy = TempDir::new().unwrap();
+    std::fs::create_dir(legacy.path().join(".wires")).unwrap();
+    let conn = rusqlite::Connection::open(legacy.path().join(".wires").join("wires.db")).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE wires (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            description TEXT,
+            status TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            priority INTEGER DEFAULT 0
+        );
+        CREATE TABLE dependencies (
+            wire_id TEXT NOT NULL,
+            depends_on TEXT NOT NULL,
+            PRIMARY KEY (wire_id, depends_on)
+        );",
+    )
+    .unwrap();
+    drop(conn);
+
+    let json = wr(&legacy, &["init", "--upgrade"]);
+    assert_eq!(json["status"], "upgraded");
+    assert_eq!(json["from_version"], 0);
+
+    wr(&legacy, &["new", "Works", "--idempotency-key", "k1"]);
+}
+
+#[test]
+fn test_init_force_and_upgrade_conflict() {
+    let temp_dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--force", "--upgrade"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_init_with_builtin_template() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--template", "feature"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["status"], "initialized");
+    let created = json["created"].as_array().unwrap();
+    assert_eq!(created.len(), 5);
+
+    // Only the first step of the chain is ready
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("ready")
+        .output()
+        .unwrap();
+    let ready: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ready = ready.as_array().unwrap();
+    assert_eq!(ready.len(), 1);
+    assert_eq!(ready[0]["id"], created[0]["id"]);
+}
+
+#[test]
+fn test_init_with_plan_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let plan = temp_dir.path().join("plan.json");
+    std::fs::write(
+        &plan,
+        r#"{"wires": [
+            {"key": "a", "title": "Parse input", "tags": ["parser"]},
+            {"title": "Report errors", "depends_on": ["a"]}
+        ]}"#,
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--template", "plan.json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let second = json["created"][1]["id"].as_str().unwrap();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", second])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["depends_on"][0]["title"], "Parse input");
+}
+
+#[test]
+fn test_init_with_invalid_template_creates_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("plan.json"),
+        r#"{"wires": [{"title": "Orphan", "depends_on": ["missing"]}]}"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--template", "plan.json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a key or title in the plan"));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["init", "--template", "no-such-template"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "built-in templates: feature, bugfix",
+        ));
+
+    assert!(!temp_dir.path().join(".wires").exists());
+}
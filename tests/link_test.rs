@@ -0,0 +1,80 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_link_is_symmetric_and_non_blocking() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let parser = create_wire(&temp_dir, "Fix parser");
+    let lexer = create_wire(&temp_dir, "Rewrite lexer");
+
+    let json = wr(&temp_dir, &["link", &parser, &lexer]);
+    assert_eq!(json["action"], "linked");
+    assert_eq!(json["related"], lexer.as_str());
+
+    // Both ends see the link, and neither waits on the other
+    let shown = wr(&temp_dir, &["show", &lexer]);
+    assert_eq!(shown["links"][0]["id"], parser.as_str());
+    assert_eq!(shown["links"][0]["kind"], "relates");
+    assert!(shown["depends_on"].as_array().unwrap().is_empty());
+    assert_eq!(wr(&temp_dir, &["ready"]).as_array().unwrap().len(), 2);
+
+    // Linking the other way round adds nothing
+    wr(&temp_dir, &["link", &lexer, &parser]);
+    assert_eq!(
+        wr(&temp_dir, &["graph"])["edges"].as_array().unwrap().len(),
+        1
+    );
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &parser, "-f", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Related:"))
+        .stdout(predicate::str::contains("Rewrite lexer"));
+
+    let json = wr(&temp_dir, &["unlink", &lexer, &parser]);
+    assert_eq!(json["action"], "unlinked");
+    assert!(wr(&temp_dir, &["show", &parser]).get("links").is_none());
+
+    wr(&temp_dir, &["undo"]);
+    assert_eq!(
+        wr(&temp_dir, &["show", &parser])["links"][0]["id"],
+        lexer.as_str()
+    );
+}
+
+#[test]
+fn test_link_refuses_wires_joined_by_a_dependency() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let api = create_wire(&temp_dir, "API");
+    let schema = create_wire(&temp_dir, "Schema");
+    wr(&temp_dir, &["dep", &api, &schema]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["link", &schema, &api])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("blocks dependency"));
+
+    // Unlinking leaves the blocking dependency in place
+    wr(&temp_dir, &["unlink", &api, &schema]);
+    assert_eq!(
+        wr(&temp_dir, &["show", &api])["depends_on"][0]["id"],
+        schema.as_str()
+    );
+}
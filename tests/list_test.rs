@@ -1,5 +1,4 @@
 use assert_cmd::Command;
-use predicates::prelude::*;
 use tempfile::TempDir;
 
 fn init_test_repo(dir: &TempDir) {
@@ -104,3 +103,189 @@ fn test_list_filter_returns_empty_for_nonmatching_status() {
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert_eq!(json.as_array().unwrap().len(), 0);
 }
+
+fn list_ids(dir: &TempDir, args: &[&str]) -> Vec<String> {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("list")
+        .args(args)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut ids: Vec<String> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_list_top_level_and_leaves() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    for title in ["Release", "Build", "Setup", "Unrelated"] {
+        create_wire(&temp_dir, title);
+    }
+    let id = |title: &str| {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["show", &format!("title:{}", title)])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["id"].as_str().unwrap().to_string()
+    };
+    let (release, build, setup, unrelated) =
+        (id("Release"), id("Build"), id("Setup"), id("Unrelated"));
+    for (wire, dep) in [(&release, &build), (&build, &setup)] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["dep", wire, dep])
+            .assert()
+            .success();
+    }
+
+    let sorted = |mut ids: Vec<String>| {
+        ids.sort();
+        ids
+    };
+    assert_eq!(
+        list_ids(&temp_dir, &["--top-level"]),
+        sorted(vec![release.clone(), unrelated.clone()])
+    );
+    assert_eq!(
+        list_ids(&temp_dir, &["--leaves"]),
+        sorted(vec![setup.clone(), unrelated.clone()])
+    );
+    // Both: wires with no edges at all
+    assert_eq!(
+        list_ids(&temp_dir, &["--top-level", "--leaves"]),
+        vec![unrelated]
+    );
+}
+
+#[test]
+fn test_list_combines_status_priority_and_title_filters() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let new = |args: &[&str]| {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .arg("new")
+            .args(args)
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["id"].as_str().unwrap().to_string()
+    };
+    let login = new(&["Fix auth login", "-p", "5"]);
+    let tokens = new(&["Rotate Auth tokens", "-p", "3"]);
+    new(&["Auth docs", "-p", "1"]);
+    new(&["Speed up search", "-p", "9"]);
+    let finished = new(&["Auth audit", "-p", "4"]);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["start", &tokens])
+        .assert()
+        .success();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &finished])
+        .assert()
+        .success();
+
+    let mut expected = vec![login, tokens];
+    expected.sort();
+    assert_eq!(
+        list_ids(
+            &temp_dir,
+            &[
+                "--status",
+                "todo",
+                "--status",
+                "in-progress",
+                "--priority-min",
+                "3",
+                "--title-contains",
+                "auth",
+            ]
+        ),
+        expected
+    );
+}
+
+#[test]
+fn test_list_since_last_returns_only_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    create_wire(&temp_dir, "Wire 1");
+    create_wire(&temp_dir, "Wire 2");
+
+    // The first call catches the cursor up on everything
+    assert_eq!(list_ids(&temp_dir, &["--since-last"]).len(), 2);
+    assert!(list_ids(&temp_dir, &["--since-last"]).is_empty());
+
+    create_wire(&temp_dir, "Wire 3");
+    let changed = list_ids(&temp_dir, &["--since-last"]);
+    assert_eq!(changed.len(), 1);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &changed[0], "--title", "Wire 3 renamed"])
+        .assert()
+        .success();
+    assert_eq!(list_ids(&temp_dir, &["--since-last"]), changed);
+
+    // Each named cursor keeps its own position
+    assert_eq!(
+        list_ids(&temp_dir, &["--since-last", "--cursor", "other"]).len(),
+        3
+    );
+    assert!(list_ids(&temp_dir, &["--since-last", "--cursor", "other"]).is_empty());
+}
+
+#[test]
+fn test_list_cursor_requires_since_last() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "--cursor", "poller"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_list_template_renders_each_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Wire 1");
+    create_wire(&temp_dir, "Wire 2");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "--template", "{{status}}: {{title}} [{{assignee}}]"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "TODO: Wire 2 []\nTODO: Wire 1 []\n"
+    );
+}
@@ -0,0 +1,153 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr(dir: &TempDir, args: &[&str]) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .assert()
+        .success();
+}
+
+fn log_ndjson(dir: &TempDir, args: &[&str]) -> Vec<serde_json::Value> {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["log", "--format", "ndjson"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_log_records_lifecycle() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let a = create_wire(&temp_dir, "Wire A");
+    let b = create_wire(&temp_dir, "Wire B");
+    wr(&temp_dir, &["dep", &a, &b]);
+    wr(&temp_dir, &["start", &b]);
+    wr(&temp_dir, &["tag", "add", &b, "backend"]);
+    wr(&temp_dir, &["rm", &a]);
+
+    let events = log_ndjson(&temp_dir, &[]);
+    let kinds: Vec<&str> = events.iter().map(|e| e["kind"].as_str().unwrap()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "created",
+            "created",
+            "dep_added",
+            "updated",
+            "tagged",
+            "deleted"
+        ]
+    );
+
+    // Sequence numbers increase
+    let seqs: Vec<i64> = events.iter().map(|e| e["seq"].as_i64().unwrap()).collect();
+    assert!(seqs.windows(2).all(|w| w[0] < w[1]));
+
+    let updated = &events[3];
+    assert_eq!(updated["wire_id"], b.as_str());
+    assert_eq!(updated["data"]["status"]["from"], "TODO");
+    assert_eq!(updated["data"]["status"]["to"], "IN_PROGRESS");
+
+    let deleted = &events[5];
+    assert_eq!(deleted["data"]["wire"]["title"], "Wire A");
+    assert_eq!(deleted["data"]["depends_on"][0], b.as_str());
+}
+
+#[test]
+fn test_log_filters_by_wire_and_since() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let a = create_wire(&temp_dir, "Wire A");
+    create_wire(&temp_dir, "Wire B");
+
+    let events = log_ndjson(&temp_dir, &["--wire", &a]);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["wire_id"], a.as_str());
+
+    assert_eq!(log_ndjson(&temp_dir, &["--since", "1h"]).len(), 2);
+    assert_eq!(log_ndjson(&temp_dir, &["--since", "99999999999"]).len(), 0);
+}
+
+#[test]
+fn test_log_skips_no_op_updates() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let a = create_wire(&temp_dir, "Wire A");
+    wr(&temp_dir, &["update", &a, "--title", "Wire A"]);
+    wr(&temp_dir, &["update", &a, "--priority", "2"]);
+
+    let events = log_ndjson(&temp_dir, &[]);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1]["data"]["priority"]["to"], 2);
+    assert!(events[1]["data"].get("title").is_none());
+}
+
+#[test]
+fn test_log_json_is_array() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Wire A");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("log")
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_log_rejects_bad_since() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["log", "--since", "yesterday"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid time"));
+}
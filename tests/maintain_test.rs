@@ -0,0 +1,132 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_with_retention(dir: &TempDir, retention: &str) {
+    wr(dir, &["init"]);
+    std::fs::write(
+        dir.path().join(".wires/config.toml"),
+        format!("[retention]\n{}", retention),
+    )
+    .unwrap();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let json = wr(dir, &["new", title]);
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_maintain_without_retention_keeps_everything() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let id = create_wire(&temp_dir, "Finished");
+    wr(&temp_dir, &["done", &id]);
+    wr(&temp_dir, &["archive", &id]);
+
+    let json = wr(&temp_dir, &["maintain"]);
+    assert_eq!(json["events"], 0);
+    assert_eq!(json["archived_wires"], serde_json::json!([]));
+    assert_eq!(json["dry_run"], false);
+
+    assert_eq!(wr(&temp_dir, &["log"]).as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_maintain_deletes_expired_archived_wires() {
+    let temp_dir = TempDir::new().unwrap();
+    init_with_retention(&temp_dir, "archived = \"0s\"\n");
+    let archived = create_wire(&temp_dir, "Archived");
+    wr(&temp_dir, &["done", &archived]);
+    wr(&temp_dir, &["archive", &archived]);
+    let finished = create_wire(&temp_dir, "Finished, not archived");
+    wr(&temp_dir, &["done", &finished]);
+
+    let json = wr(&temp_dir, &["maintain", "--dry-run"]);
+    assert_eq!(json["archived_wires"], serde_json::json!([archived]));
+    assert_eq!(json["dry_run"], true);
+    wr(&temp_dir, &["show", &archived]);
+
+    let json = wr(&temp_dir, &["maintain"]);
+    assert_eq!(json["archived_wires"], serde_json::json!([archived]));
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &archived])
+        .assert()
+        .failure();
+    wr(&temp_dir, &["show", &finished]);
+
+    // The deletion stays in the history
+    let log = wr(&temp_dir, &["log", "--wire", &archived]);
+    assert_eq!(log.as_array().unwrap().last().unwrap()["kind"], "deleted");
+}
+
+#[test]
+fn test_maintain_prunes_history_up_to_outbox_position() {
+    let temp_dir = TempDir::new().unwrap();
+    init_with_retention(&temp_dir, "history = \"0s\"\n");
+    create_wire(&temp_dir, "First");
+    create_wire(&temp_dir, "Second");
+    create_wire(&temp_dir, "Third");
+
+    let pulled = wr(&temp_dir, &["outbox", "pull", "--consumer", "indexer"]);
+    let first_seq = pulled["events"][0]["seq"].as_i64().unwrap();
+    wr(
+        &temp_dir,
+        &[
+            "outbox",
+            "ack",
+            "--consumer",
+            "indexer",
+            &first_seq.to_string(),
+        ],
+    );
+
+    let json = wr(&temp_dir, &["maintain"]);
+    assert_eq!(json["events"], 1);
+
+    // Events the consumer hasn't acknowledged are kept
+    let log = wr(&temp_dir, &["log"]);
+    assert_eq!(log.as_array().unwrap().len(), 2);
+    let pulled = wr(&temp_dir, &["outbox", "pull", "--consumer", "indexer"]);
+    assert_eq!(pulled["events"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_maintain_drops_stale_outbox_consumers_before_pruning() {
+    let temp_dir = TempDir::new().unwrap();
+    init_with_retention(&temp_dir, "history = \"0s\"\noutbox = \"0s\"\n");
+    create_wire(&temp_dir, "First");
+    wr(&temp_dir, &["outbox", "pull", "--consumer", "gone"]);
+
+    let json = wr(&temp_dir, &["maintain"]);
+    assert_eq!(json["outbox_consumers"], serde_json::json!(["gone"]));
+    assert_eq!(json["events"], 1);
+    assert!(wr(&temp_dir, &["log"]).as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_maintain_removes_expired_backups() {
+    let temp_dir = TempDir::new().unwrap();
+    init_with_retention(&temp_dir, "backups = \"0s\"\n");
+    let backup = wr(&temp_dir, &["init", "--force"])["backup"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let name = std::path::Path::new(&backup)
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+
+    let json = wr(&temp_dir, &["maintain", "--dry-run"]);
+    assert_eq!(json["backups"], serde_json::json!([name]));
+    assert!(std::path::Path::new(&backup).exists());
+
+    wr(&temp_dir, &["maintain"]);
+    assert!(!std::path::Path::new(&backup).exists());
+    assert!(temp_dir.path().join(".wires/wires.db").exists());
+}
@@ -0,0 +1,110 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str, priority: i32) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["new", title, &format!("--priority={}", priority)])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn roots(a: &TempDir, b: &TempDir) -> String {
+    format!("{},{}", a.path().display(), b.path().display())
+}
+
+#[test]
+fn test_multi_list_merges_repos() {
+    let repo_a = TempDir::new().unwrap();
+    let repo_b = TempDir::new().unwrap();
+    init_test_repo(&repo_a);
+    init_test_repo(&repo_b);
+    let wire_a = create_wire(&repo_a, "Wire in A", 0);
+    let wire_b = create_wire(&repo_b, "Wire in B", 0);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .args(["multi", "--roots", &roots(&repo_a, &repo_b), "list"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wires = json.as_array().unwrap();
+    assert_eq!(wires.len(), 2);
+
+    let repo_of = |id: &str| {
+        wires.iter().find(|w| w["id"] == id).unwrap()["repo"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(repo_of(&wire_a), repo_a.path().display().to_string());
+    assert_eq!(repo_of(&wire_b), repo_b.path().display().to_string());
+}
+
+#[test]
+fn test_multi_ready_orders_by_priority() {
+    let repo_a = TempDir::new().unwrap();
+    let repo_b = TempDir::new().unwrap();
+    init_test_repo(&repo_a);
+    init_test_repo(&repo_b);
+    create_wire(&repo_a, "Low", 1);
+    create_wire(&repo_b, "High", 5);
+    let blocked = create_wire(&repo_b, "Blocked", 9);
+    let blocker = create_wire(&repo_b, "Blocker", 2);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&repo_b)
+        .args(["dep", &blocked, &blocker])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .env("WR_ROOTS", roots(&repo_a, &repo_b))
+        .args(["multi", "ready"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let titles: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, vec!["High", "Blocker", "Low"]);
+}
+
+#[test]
+fn test_multi_names_missing_repo() {
+    let repo_a = TempDir::new().unwrap();
+    let not_a_repo = TempDir::new().unwrap();
+    init_test_repo(&repo_a);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .args(["multi", "--roots", &roots(&repo_a, &not_a_repo), "list"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            not_a_repo.path().display().to_string(),
+        ))
+        .stderr(predicate::str::contains("Not a wires repository"));
+}
@@ -65,6 +65,46 @@ fn test_new_with_priority() {
     assert_eq!(json["priority"], 1);
 }
 
+#[test]
+fn test_new_with_named_priority() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[priorities]\nurgent = 9\n",
+    )
+    .unwrap();
+
+    for (name, value) in [("high", 2), ("low", 0), ("urgent", 9), ("-1", -1)] {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["new", "Named priority", "-p", name])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "-p {}", name);
+
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["priority"], value);
+    }
+}
+
+#[test]
+fn test_new_unknown_priority_name_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Named priority", "--priority", "urgent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown priority 'urgent' (known: high, med, low)",
+        ));
+}
+
 #[test]
 fn test_new_generates_unique_ids() {
     let temp_dir = TempDir::new().unwrap();
@@ -105,3 +145,417 @@ fn test_new_fails_without_init() {
         .failure()
         .stderr(predicate::str::contains("Not a wires repository"));
 }
+
+#[test]
+fn test_new_expands_template_vars() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args([
+            "new",
+            "Fix {module} panics",
+            "--description",
+            "Panics in {module} ({count} reports), see {{notes}}",
+            "--var",
+            "module=parser",
+            "--var",
+            "count=3",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["title"], "Fix parser panics");
+
+    let id = json["id"].as_str().unwrap();
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", id])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["description"],
+        "Panics in parser (3 reports), see {notes}"
+    );
+}
+
+#[test]
+fn test_new_without_vars_keeps_braces() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Handle {braces} literally"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Handle {braces} literally"));
+}
+
+#[test]
+fn test_new_undefined_template_var_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Fix {module} in {crate}", "--var", "module=parser"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Undefined template variable: {crate}",
+        ));
+}
+
+#[test]
+fn test_new_invalid_var_syntax_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Fix {module}", "--var", "module"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_new_idempotency_key_prevents_duplicates() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let run = || {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["new", "Retried wire", "--idempotency-key", "req-42"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+
+    let first = run();
+    let second = run();
+    assert_eq!(first, second);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("list")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 1);
+
+    // A different key creates a new wire
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Retried wire", "--idempotency-key", "req-43"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(first["id"].as_str().unwrap()).not());
+}
+
+#[test]
+fn test_new_idempotency_key_rejects_other_command() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Wire A", "--idempotency-key", "shared"])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Wire B"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wire_b = json["id"].as_str().unwrap();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", wire_b, wire_b, "--idempotency-key", "shared"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already used for `wr new`"));
+}
+
+#[test]
+fn test_new_full_returns_complete_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Full wire", "-d", "Details", "--full"])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["description"], "Details");
+    assert!(json["updated_at"].is_number());
+    assert!(json["depends_on"].as_array().unwrap().is_empty());
+    assert!(json["blocks"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_new_stdin_creates_batch_with_deps() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let input = r#"{"key": "parse", "title": "Parse input", "priority": 5}
+{"title": "Write tests", "description": "Cover edge cases", "deps": ["parse"]}
+
+{"title": "Ship it", "deps": ["Write tests"]}
+"#;
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "--stdin"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let created = json["created"].as_array().unwrap();
+    assert_eq!(created.len(), 3);
+    assert_eq!(created[0]["key"], "parse");
+    assert_eq!(created[0]["priority"], 5);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", created[1]["id"].as_str().unwrap()])
+        .output()
+        .unwrap();
+    let wire: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(wire["description"], "Cover edge cases");
+    assert_eq!(wire["depends_on"][0]["id"], created[0]["id"]);
+}
+
+#[test]
+fn test_new_stdin_inline_after_syntax() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let input = r#""Implement parser"
+"Write tests" after:"Implement parser"
+{"title": "Release after:\"Write tests\"", "priority": 3}
+"#;
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "--stdin"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let created = json["created"].as_array().unwrap();
+    let titles: Vec<_> = created
+        .iter()
+        .map(|w| w["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["Implement parser", "Write tests", "Release"]);
+    assert_eq!(created[2]["priority"], 3);
+
+    for (wire, dep) in [(&created[1], &created[0]), (&created[2], &created[1])] {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["show", wire["id"].as_str().unwrap()])
+            .output()
+            .unwrap();
+        let shown: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(shown["depends_on"][0]["id"], dep["id"]);
+    }
+}
+
+#[test]
+fn test_new_stdin_is_all_or_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "--stdin"])
+        .write_stdin("{\"title\": \"Fine\"}\n{\"title\": \"Broken\", \"deps\": [\"missing\"]}\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing"));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "--format", "json"])
+        .assert()
+        .success()
+        .stdout("[]\n");
+}
+
+#[test]
+fn test_new_stdin_rejects_title() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Title", "--stdin"])
+        .write_stdin("")
+        .assert()
+        .failure();
+}
+
+fn show_description(dir: &TempDir, stdout: &[u8]) -> serde_json::Value {
+    let json: serde_json::Value = serde_json::from_slice(stdout).unwrap();
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["show", json["id"].as_str().unwrap()])
+        .output()
+        .unwrap();
+    let wire: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    wire["description"].clone()
+}
+
+#[test]
+fn test_new_description_file_keeps_newlines() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let plan = temp_dir.path().join("plan.md");
+    std::fs::write(&plan, "# Plan\n\n- step one\n  - detail\n\n").unwrap();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Planned", "--description-file"])
+        .arg(&plan)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        show_description(&temp_dir, &output.stdout),
+        "# Plan\n\n- step one\n  - detail"
+    );
+}
+
+#[test]
+fn test_new_description_from_stdin() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Piped", "-d", "-"])
+        .write_stdin("it's \"quoted\" $HOME\nsecond line\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        show_description(&temp_dir, &output.stdout),
+        "it's \"quoted\" $HOME\nsecond line"
+    );
+}
+
+#[test]
+fn test_new_description_file_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Missing", "--description-file", "nope.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Failed to read description from nope.md",
+        ));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Both", "-d", "x", "--description-file", "nope.md"])
+        .assert()
+        .code(64);
+}
+
+#[test]
+fn test_new_uses_configured_id_scheme() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let new = |title: &str| -> String {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["new", title])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["id"].as_str().unwrap().to_string()
+    };
+    let hex = new("Before the switch");
+    assert_eq!(hex.len(), 7);
+
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "id_scheme = \"ulid\"\n",
+    )
+    .unwrap();
+    let ulid = new("After the switch");
+    assert_eq!(ulid.len(), 26);
+    assert_eq!(ulid, ulid.to_uppercase());
+
+    // Existing IDs keep working, and ULIDs may be typed in lowercase
+    for id in [hex, ulid.to_lowercase()] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["show", &id])
+            .assert()
+            .success();
+    }
+
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "id_scheme = \"uuid7\"\n",
+    )
+    .unwrap();
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "--stdin"])
+        .write_stdin("{\"title\": \"Batched\"}\n")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match(r#""id":\s*"[0-9a-f]{8}-[0-9a-f]{4}-7[0-9a-f]{3}-"#).unwrap(),
+        );
+}
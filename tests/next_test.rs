@@ -0,0 +1,218 @@
+use assert_cmd::Command;
+use std::thread;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn next(dir: &TempDir, agent: Option<&str>) -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("wr").unwrap();
+    cmd.current_dir(dir).arg("next").env_remove("WR_AGENT");
+    if let Some(agent) = agent {
+        cmd.args(["--agent", agent]);
+    }
+    let output = cmd.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn set_priority(dir: &TempDir, wire_id: &str, priority: &str) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["update", wire_id, "--priority", priority])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_next_starts_highest_priority_ready_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let low = create_wire(&temp_dir, "Low");
+    let high = create_wire(&temp_dir, "High");
+    set_priority(&temp_dir, &high, "5");
+
+    let json = next(&temp_dir, None);
+    assert_eq!(json["id"], high.as_str());
+    assert_eq!(json["status"], "IN_PROGRESS");
+    assert!(json["depends_on"].is_array());
+    assert!(json.get("lease").is_none());
+
+    // IN_PROGRESS wires are taken, so the next call moves on
+    assert_eq!(next(&temp_dir, None)["id"], low.as_str());
+    assert!(next(&temp_dir, None).is_null());
+}
+
+#[test]
+fn test_next_skips_blocked_wires() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let base = create_wire(&temp_dir, "Base");
+    let blocked = create_wire(&temp_dir, "Blocked");
+    set_priority(&temp_dir, &blocked, "9");
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &blocked, &base])
+        .assert()
+        .success();
+
+    assert_eq!(next(&temp_dir, None)["id"], base.as_str());
+    assert!(next(&temp_dir, None).is_null());
+}
+
+#[test]
+fn test_next_claims_for_agent() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Task");
+
+    let json = next(&temp_dir, Some("alice"));
+    assert_eq!(json["id"], id.as_str());
+    assert_eq!(json["lease"]["agent"], "alice");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["claim", &id, "--agent", "bob"])
+        .env_remove("WR_AGENT")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_concurrent_next_never_hands_out_the_same_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    for i in 0..3 {
+        create_wire(&temp_dir, &format!("Task {}", i));
+    }
+
+    let handles: Vec<_> = (0..5)
+        .map(|i| {
+            let dir = temp_dir.path().to_path_buf();
+            thread::spawn(move || {
+                let output = Command::cargo_bin("wr")
+                    .unwrap()
+                    .current_dir(&dir)
+                    .args(["next", "--agent", &format!("agent-{}", i)])
+                    .output()
+                    .unwrap();
+                assert!(output.status.success());
+                let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+                json["id"].as_str().map(String::from)
+            })
+        })
+        .collect();
+
+    let mut ids: Vec<String> = handles
+        .into_iter()
+        .filter_map(|h| h.join().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 3);
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 3, "each wire is handed out once");
+}
+
+#[test]
+fn test_ready_claim_starts_a_limited_batch() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let high = create_wire(&temp_dir, "High");
+    let mid = create_wire(&temp_dir, "Mid");
+    create_wire(&temp_dir, "Low");
+    set_priority(&temp_dir, &high, "3");
+    set_priority(&temp_dir, &mid, "2");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args([
+            "ready", "--claim", "--agent", "alice", "--limit", "2", "-f", "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ids: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, [high.as_str(), mid.as_str()]);
+    assert!(json
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|w| w["status"] == "IN_PROGRESS"));
+
+    // Another agent only sees what is left
+    assert_eq!(next(&temp_dir, Some("bob"))["title"], "Low");
+}
+
+#[test]
+fn test_concurrent_ready_claim_never_hands_out_the_same_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    for i in 0..6 {
+        create_wire(&temp_dir, &format!("Task {}", i));
+    }
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let dir = temp_dir.path().to_path_buf();
+            thread::spawn(move || {
+                let output = Command::cargo_bin("wr")
+                    .unwrap()
+                    .current_dir(&dir)
+                    .args(["ready", "--claim", "--limit", "2", "-f", "json"])
+                    .env("WR_AGENT", format!("agent-{}", i))
+                    .output()
+                    .unwrap();
+                assert!(output.status.success());
+                let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+                json.as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|w| w["id"].as_str().unwrap().to_string())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut ids: Vec<String> = handles
+        .into_iter()
+        .flat_map(|h| h.join().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 6);
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 6, "each wire is handed out once");
+}
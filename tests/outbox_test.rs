@@ -0,0 +1,124 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn pull(dir: &TempDir, consumer: &str) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["outbox", "pull", "--consumer", consumer])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn ack(dir: &TempDir, consumer: &str, seq: i64) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["outbox", "ack", "--consumer", consumer, &seq.to_string()])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_outbox_redelivers_until_acknowledged() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "First");
+
+    let batch = pull(&temp_dir, "bridge");
+    assert_eq!(batch["events"].as_array().unwrap().len(), 1);
+    assert_eq!(batch["events"][0]["wire_id"], id.as_str());
+
+    // Not acknowledged: delivered again
+    let again = pull(&temp_dir, "bridge");
+    assert_eq!(again["events"], batch["events"]);
+
+    ack(&temp_dir, "bridge", batch["last_seq"].as_i64().unwrap());
+    assert!(pull(&temp_dir, "bridge")["events"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    // Changes made while the consumer is away are waiting for it
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &id])
+        .assert()
+        .success();
+    let batch = pull(&temp_dir, "bridge");
+    assert_eq!(batch["events"][0]["kind"], "updated");
+}
+
+#[test]
+fn test_outbox_consumers_are_independent() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "One");
+    create_wire(&temp_dir, "Two");
+
+    let batch = pull(&temp_dir, "a");
+    ack(&temp_dir, "a", batch["last_seq"].as_i64().unwrap());
+    assert_eq!(pull(&temp_dir, "b")["events"].as_array().unwrap().len(), 2);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["outbox", "status"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["consumer"], "a");
+    assert_eq!(json[0]["pending"], 0);
+    assert_eq!(json[1]["consumer"], "b");
+    assert_eq!(json[1]["pending"], 2);
+}
+
+#[test]
+fn test_outbox_ack_validation() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "One");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["outbox", "ack", "--consumer", "never-pulled", "1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown outbox consumer"));
+
+    pull(&temp_dir, "c");
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["outbox", "ack", "--consumer", "c", "99"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("the last event is 1"));
+}
@@ -0,0 +1,95 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_pause_with_reason_and_resume() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Parked work");
+    wr(&temp_dir, &["start", &id]);
+
+    let json = wr(&temp_dir, &["pause", &id, "--reason", "waiting for review"]);
+    assert_eq!(json["status"], "PAUSED");
+    assert_eq!(json["reason"], "waiting for review");
+
+    let json = wr(&temp_dir, &["show", &id]);
+    assert_eq!(json["paused"]["reason"], "waiting for review");
+    assert!(wr(&temp_dir, &["ready"]).as_array().unwrap().is_empty());
+
+    let json = wr(&temp_dir, &["resume", &id]);
+    assert_eq!(json["status"], "IN_PROGRESS");
+    assert!(wr(&temp_dir, &["show", &id]).get("paused").is_none());
+}
+
+#[test]
+fn test_paused_wire_still_blocks_dependents() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let base = create_wire(&temp_dir, "Base");
+    let top = create_wire(&temp_dir, "Top");
+    wr(&temp_dir, &["dep", &top, &base]);
+    wr(&temp_dir, &["pause", &base]);
+
+    assert!(wr(&temp_dir, &["ready"]).as_array().unwrap().is_empty());
+}
+
+/// Runs a command expected to fail and returns its exit code and JSON error
+fn wr_err(dir: &TempDir, args: &[&str]) -> (Option<i32>, serde_json::Value) {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    let json = serde_json::from_slice(&output.stderr).unwrap();
+    (output.status.code(), json)
+}
+
+#[test]
+fn test_pause_and_resume_reject_wrong_status() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Todo");
+
+    let (code, json) = wr_err(&temp_dir, &["resume", &id]);
+    assert_eq!(code, Some(12));
+    assert_eq!(json["code"], "invalid_transition");
+    assert_eq!(json["from"], "TODO");
+    assert_eq!(json["to"], "IN_PROGRESS");
+
+    wr(&temp_dir, &["done", &id]);
+    let (code, json) = wr_err(&temp_dir, &["pause", &id]);
+    assert_eq!(code, Some(12));
+    assert_eq!(json["code"], "invalid_transition");
+    assert_eq!(json["from"], "DONE");
+    assert_eq!(json["to"], "PAUSED");
+    assert!(!json["allowed"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!("PAUSED")));
+}
@@ -0,0 +1,105 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn setup(permissions: &str) -> (TempDir, String) {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, None, &["init"]).success();
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Existing"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        format!("[permissions]\n{}", permissions),
+    )
+    .unwrap();
+    (temp_dir, json["id"].as_str().unwrap().to_string())
+}
+
+fn wr(dir: &TempDir, agent: Option<&str>, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut command = Command::cargo_bin("wr").unwrap();
+    command.current_dir(dir).env_remove("WR_AGENT").args(args);
+    if let Some(agent) = agent {
+        command.env("WR_AGENT", agent);
+    }
+    command.assert()
+}
+
+#[test]
+fn test_permissions_restrict_listed_agents() {
+    let (temp_dir, id) = setup("planner = [\"new\", \"dep\"]\nworker = [\"start\", \"done\"]\n");
+
+    wr(&temp_dir, Some("planner"), &["new", "Planned"]).success();
+    wr(&temp_dir, Some("planner"), &["rm", &id])
+        .failure()
+        .code(16)
+        .stderr(predicate::str::contains("\"code\":\"permission_denied\""))
+        .stderr(predicate::str::contains("\"allowed\":[\"new\",\"dep\"]"));
+
+    wr(&temp_dir, Some("worker"), &["start", &id]).success();
+    wr(&temp_dir, Some("worker"), &["new", "Sneaky"])
+        .failure()
+        .code(16)
+        .stderr(predicate::str::contains(
+            "Agent 'worker' may not run `wr new`",
+        ));
+
+    // Reading is always allowed, and unlisted agents are unrestricted
+    wr(&temp_dir, Some("worker"), &["list"]).success();
+    wr(&temp_dir, Some("worker"), &["show", &id]).success();
+    wr(&temp_dir, None, &["done", &id]).success();
+}
+
+#[test]
+fn test_permissions_use_agent_flag_and_anyone_rule() {
+    let (temp_dir, id) = setup("\"*\" = []\nworker = [\"claim\"]\n");
+
+    wr(&temp_dir, None, &["new", "Anonymous"])
+        .failure()
+        .code(16);
+    wr(&temp_dir, Some("stranger"), &["tag", "add", &id, "x"])
+        .failure()
+        .stderr(predicate::str::contains("tag add"));
+    wr(&temp_dir, None, &["claim", &id, "--agent", "worker"]).success();
+}
+
+#[test]
+fn test_permissions_reject_unknown_commands() {
+    let (temp_dir, _id) = setup("planner = [\"delete\"]\n");
+
+    wr(&temp_dir, None, &["list"])
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown command 'delete' for 'planner' in [permissions]",
+        ));
+}
+
+#[test]
+fn test_permissions_check_ready_claim_as_claim_and_start() {
+    let (temp_dir, _id) = setup("reader = [\"claim\"]\nworker = [\"claim\", \"start\"]\n");
+
+    wr(&temp_dir, Some("reader"), &["ready"]).success();
+    wr(&temp_dir, Some("reader"), &["ready", "--claim"])
+        .failure()
+        .code(16)
+        .stderr(predicate::str::contains("may not run `wr start`"));
+    wr(&temp_dir, Some("worker"), &["ready", "--claim"]).success();
+}
+
+#[test]
+fn test_invalid_config_denies_restricted_agents() {
+    let (temp_dir, id) = setup("worker = [\"start\"]\n");
+    let config = temp_dir.path().join(".wires/config.toml");
+    let valid = std::fs::read_to_string(&config).unwrap();
+    std::fs::write(&config, format!("{}[titles]\nmax_lenght = 80\n", valid)).unwrap();
+
+    wr(&temp_dir, Some("worker"), &["rm", &id])
+        .failure()
+        .stderr(predicate::str::contains("config.toml"));
+    // Reads don't need permission
+    wr(&temp_dir, Some("worker"), &["show", &id]).success();
+}
@@ -0,0 +1,80 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn ping(dir: &TempDir, args: &[&str]) -> (i32, serde_json::Value) {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("ping")
+        .args(args)
+        .output()
+        .unwrap();
+    let json = serde_json::from_slice(&output.stdout).unwrap();
+    (output.status.code().unwrap(), json)
+}
+
+#[test]
+fn test_ping_healthy_repository() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let (code, json) = ping(&temp_dir, &["--lock"]);
+    assert_eq!(code, 0);
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["writable"], true);
+    assert_eq!(json["schema_version"], json["supported_schema_version"]);
+    assert_eq!(json["lock"]["acquired"], true);
+    assert_eq!(json["problems"], serde_json::json!([]));
+}
+
+#[test]
+fn test_ping_omits_lock_unless_asked() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let (_, json) = ping(&temp_dir, &[]);
+    assert!(json.get("lock").is_none());
+}
+
+#[test]
+fn test_ping_outside_repository_is_unhealthy() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let (code, json) = ping(&temp_dir, &[]);
+    assert_eq!(code, 15);
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["database"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_ping_rejects_newer_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let conn = rusqlite::Connection::open(temp_dir.path().join(".wires/wires.db")).unwrap();
+    conn.pragma_update(None, "user_version", 999).unwrap();
+    drop(conn);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("ping")
+        .assert()
+        .code(15)
+        .stdout(predicate::str::contains("\"schema_version\":999"))
+        .stderr(predicate::str::contains("\"code\":\"unhealthy\""))
+        .stderr(predicate::str::contains("newer than this build supports"));
+
+    // Checking doesn't migrate or otherwise change the database
+    let (_, json) = ping(&temp_dir, &[]);
+    assert_eq!(json["schema_version"], 999);
+}
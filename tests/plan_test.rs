@@ -0,0 +1,124 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn write(dir: &TempDir, name: &str, contents: &str) {
+    std::fs::write(dir.path().join(name), contents).unwrap();
+}
+
+#[test]
+fn test_plan_compare_reports_structural_diff() {
+    let temp_dir = TempDir::new().unwrap();
+    write(
+        &temp_dir,
+        "old.json",
+        r#"{"wires": [
+            {"key": "design", "title": "Design the API"},
+            {"key": "build", "title": "Implement the API", "depends_on": ["design"]}
+        ]}"#,
+    );
+    write(
+        &temp_dir,
+        "new.jsonl",
+        "{\"key\": \"design\", \"title\": \"Design the API\", \"priority\": 2}\n\
+         \"Write tests\" after:design\n\
+         {\"key\": \"build\", \"title\": \"Implement the API\", \"deps\": [\"Write tests\"]}\n",
+    );
+
+    let json = wr(
+        &temp_dir,
+        &["plan", "compare", "old.json", "new.jsonl", "-f", "json"],
+    );
+    assert_eq!(
+        json["added"],
+        serde_json::json!([{"name": "Write tests", "title": "Write tests"}])
+    );
+    assert_eq!(json["removed"], serde_json::json!([]));
+    assert_eq!(json["changed"][0]["name"], "design");
+    assert_eq!(
+        json["changed"][0]["fields"],
+        serde_json::json!(["priority"])
+    );
+    assert_eq!(
+        json["added_edges"],
+        serde_json::json!([
+            {"wire": "Write tests", "depends_on": "design"},
+            {"wire": "build", "depends_on": "Write tests"}
+        ])
+    );
+    assert_eq!(
+        json["removed_edges"],
+        serde_json::json!([{"wire": "build", "depends_on": "design"}])
+    );
+    assert_eq!(json["distance"], 5);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["plan", "compare", "old.json", "new.jsonl", "-f", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  + Write tests\n"))
+        .stdout(predicate::str::contains(
+            "  ~ design  Design the API  (priority)",
+        ))
+        .stdout(predicate::str::contains("  - build → design"))
+        .stdout(predicate::str::contains("Distance: 5"));
+}
+
+#[test]
+fn test_plan_compare_snapshots() {
+    let temp_dir = TempDir::new().unwrap();
+    wr(&temp_dir, &["init"]);
+    let a = wr(&temp_dir, &["new", "Schema"])["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let b = wr(&temp_dir, &["new", "API"])["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let before = wr(&temp_dir, &["export", "--format", "json"]);
+    write(&temp_dir, "before.json", &before.to_string());
+
+    wr(&temp_dir, &["dep", &b, &a]);
+    let after = wr(&temp_dir, &["export", "--format", "json"]);
+    write(&temp_dir, "after.json", &after.to_string());
+
+    let json = wr(
+        &temp_dir,
+        &["plan", "compare", "before.json", "after.json", "-f", "json"],
+    );
+    assert_eq!(
+        json["added_edges"],
+        serde_json::json!([{"wire": b, "depends_on": a}])
+    );
+    assert_eq!(json["distance"], 1);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["plan", "compare", "after.json", "after.json", "-f", "table"])
+        .assert()
+        .success()
+        .stdout("No differences.\n");
+}
+
+#[test]
+fn test_plan_compare_invalid_plan_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    write(&temp_dir, "ok.jsonl", "\"First\"\n");
+    write(&temp_dir, "bad.jsonl", "\"Second\" after:nothing\n");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["plan", "compare", "ok.jsonl", "bad.jsonl"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("bad.jsonl"))
+        .stderr(predicate::str::contains("not a key or title"));
+}
@@ -0,0 +1,119 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr_json(dir: &TempDir, args: &[&str]) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn ready_ids(dir: &TempDir, args: &[&str]) -> Vec<String> {
+    let mut command = vec!["ready", "-f", "json"];
+    command.extend_from_slice(args);
+    wr_json(dir, &command)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_ready_check_preconditions() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let migrate = create_wire(&temp_dir, "Run migration");
+    let plain = create_wire(&temp_dir, "No probe");
+
+    let json = wr_json(
+        &temp_dir,
+        &[
+            "precondition",
+            "set",
+            &migrate,
+            "test -f migrations/0003.sql",
+        ],
+    );
+    assert_eq!(json["precondition"], "test -f migrations/0003.sql");
+
+    // Probes only run when asked for
+    assert_eq!(ready_ids(&temp_dir, &[]).len(), 2);
+    assert_eq!(ready_ids(&temp_dir, &["--check-preconditions"]), [plain]);
+
+    // Run from the repository directory, wherever wr is invoked
+    std::fs::create_dir(temp_dir.path().join("migrations")).unwrap();
+    std::fs::write(temp_dir.path().join("migrations/0003.sql"), "").unwrap();
+    let json: serde_json::Value = serde_json::from_slice(
+        &Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(temp_dir.path().join("migrations"))
+            .args(["ready", "-f", "json", "--check-preconditions"])
+            .output()
+            .unwrap()
+            .stdout,
+    )
+    .unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_precondition_check_and_clear() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire = create_wire(&temp_dir, "Deploy");
+
+    wr_json(&temp_dir, &["precondition", "set", &wire, "false"]);
+    let json = wr_json(&temp_dir, &["precondition", "check", &wire]);
+    assert_eq!(json["precondition"], "false");
+    assert_eq!(json["holds"], false);
+
+    let json = wr_json(&temp_dir, &["precondition", "clear", &wire]);
+    assert!(json["precondition"].is_null());
+    let json = wr_json(&temp_dir, &["precondition", "check", &wire]);
+    assert_eq!(json["holds"], true);
+    assert_eq!(ready_ids(&temp_dir, &["--check-preconditions"]), [wire]);
+}
+
+#[test]
+fn test_precondition_unknown_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["precondition", "set", "fffffff", "true"])
+        .assert()
+        .failure();
+}
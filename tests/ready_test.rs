@@ -247,6 +247,56 @@ fn test_ready_includes_wires_with_completed_dependencies() {
     assert_eq!(wires[0]["id"], wire_with_dep);
 }
 
+#[test]
+fn test_ready_cancelled_dependencies_block_unless_configured() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let dep_wire = create_wire(&temp_dir, "Dropped");
+    let wire_with_dep = create_wire(&temp_dir, "Waiting");
+    add_dependency(&temp_dir, &wire_with_dep, &dep_wire);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["cancel", &dep_wire])
+        .assert()
+        .success();
+
+    let ready = |args: &[&str]| {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .arg("ready")
+            .args(args)
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json.as_array().unwrap().len()
+    };
+    let table = || {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["list", "-f", "table"])
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(ready(&[]), 0);
+    assert!(table().contains(&format!("blocked by {}", dep_wire)));
+    assert_eq!(ready(&["--cancelled-blocks", "false"]), 1);
+
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[ready]\ncancelled_blocks = false\n",
+    )
+    .unwrap();
+    assert_eq!(ready(&[]), 1);
+    assert!(!table().contains("blocked by"));
+    assert_eq!(ready(&["--cancelled-blocks", "true"]), 0);
+}
+
 #[test]
 fn test_ready_complex_scenario() {
     let temp_dir = TempDir::new().unwrap();
@@ -290,3 +340,94 @@ fn test_ready_complex_scenario() {
     assert_eq!(wires[3]["id"], todo_low);
     assert_eq!(wires[4]["id"], blocker);
 }
+
+#[test]
+fn test_ready_claimable_excludes_in_progress() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_todo = create_wire(&temp_dir, "TODO wire");
+    let wire_in_progress = create_wire(&temp_dir, "In progress wire");
+    let blocked = create_wire(&temp_dir, "Blocked wire");
+    add_dependency(&temp_dir, &blocked, &wire_todo);
+
+    start_wire(&temp_dir, &wire_in_progress);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["ready", "--claimable"])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wires = json.as_array().unwrap();
+
+    assert_eq!(wires.len(), 1);
+    assert_eq!(wires[0]["id"], wire_todo);
+}
+
+#[test]
+fn test_ready_template_prints_one_line_per_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let low = create_wire_with_priority(&temp_dir, "Low", 0);
+    let high = create_wire_with_priority(&temp_dir, "High", 5);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["ready", "--template", "{{priority}} {{id}} {{title}}"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("5 {} High\n0 {} Low\n", high, low)
+    );
+}
+
+#[test]
+fn test_ready_watch_prints_again_on_change() {
+    use std::io::{BufRead, BufReader};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let first = create_wire(&temp_dir, "First");
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("wr"))
+        .current_dir(&temp_dir)
+        .args(["ready", "--watch", "-f", "json"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    let next = || -> Vec<String> {
+        let line = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        json.as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(next(), [first.as_str()]);
+    let second = create_wire(&temp_dir, "Second");
+    assert_eq!(next(), [first.as_str(), second.as_str()]);
+    done_wire(&temp_dir, &first);
+    assert_eq!(next().len(), 1);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
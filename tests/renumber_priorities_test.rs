@@ -0,0 +1,115 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str, priority: i32) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["new", title, &format!("--priority={}", priority)])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn priority_of(dir: &TempDir, id: &str) -> i64 {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(["show", id])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["priority"].as_i64().unwrap()
+}
+
+fn renumber(dir: &TempDir, args: &[&str]) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("renumber-priorities")
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_renumber_priorities_preserves_order() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let low = create_wire(&temp_dir, "Low", -3);
+    let mid = create_wire(&temp_dir, "Mid", 2);
+    let high = create_wire(&temp_dir, "High", 7);
+
+    let json = renumber(&temp_dir, &[]);
+    assert_eq!(json["dry_run"], false);
+    assert_eq!(json["changed"].as_array().unwrap().len(), 3);
+
+    assert_eq!(priority_of(&temp_dir, &low), 10);
+    assert_eq!(priority_of(&temp_dir, &mid), 20);
+    assert_eq!(priority_of(&temp_dir, &high), 30);
+}
+
+#[test]
+fn test_renumber_priorities_skips_closed_wires() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let open = create_wire(&temp_dir, "Open", 1);
+    let done = create_wire(&temp_dir, "Done", 5);
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &done])
+        .assert()
+        .success();
+
+    renumber(&temp_dir, &["--step", "100"]);
+
+    assert_eq!(priority_of(&temp_dir, &open), 100);
+    assert_eq!(priority_of(&temp_dir, &done), 5);
+}
+
+#[test]
+fn test_renumber_priorities_dry_run() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let id = create_wire(&temp_dir, "Task", 3);
+
+    let json = renumber(&temp_dir, &["--dry-run"]);
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["changed"][0]["id"], id.as_str());
+    assert_eq!(json["changed"][0]["from"], 3);
+    assert_eq!(json["changed"][0]["to"], 10);
+
+    assert_eq!(priority_of(&temp_dir, &id), 3);
+}
+
+#[test]
+fn test_renumber_priorities_rejects_non_positive_step() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["renumber-priorities", "--step", "0"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,208 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_report_stale_groups_by_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let tagged = create_wire(&temp_dir, "Tagged");
+    let base = create_wire(&temp_dir, "Base");
+    let top = create_wire(&temp_dir, "Top");
+    wr(&temp_dir, &["tag", "add", &tagged, "backend"]);
+    wr(&temp_dir, &["dep", &top, &base]);
+    wr(&temp_dir, &["done", &top]);
+
+    let json = wr(&temp_dir, &["report", "--stale", "--older-than", "0s"]);
+    assert_eq!(json["older_than"], 0);
+    // DONE wires are never stale
+    assert_eq!(json["count"], 2);
+
+    let groups = json["groups"].as_array().unwrap();
+    assert_eq!(groups[0]["tag"], "backend");
+    assert_eq!(groups[0]["wires"][0]["id"], tagged.as_str());
+    assert!(groups[1]["tag"].is_null());
+
+    let stale = &groups[1]["wires"][0];
+    assert_eq!(stale["id"], base.as_str());
+    // Its only dependent is finished, so nothing is waiting on it
+    assert_eq!(stale["waiting"], 0);
+    assert_eq!(stale["action"], "cancel");
+    assert_eq!(
+        stale["command"],
+        format!("wr cancel {} --reason obsolete", base)
+    );
+}
+
+#[test]
+fn test_report_stale_suggests_bump_when_others_wait() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let base = create_wire(&temp_dir, "Base");
+    let top = create_wire(&temp_dir, "Top");
+    wr(&temp_dir, &["dep", &top, &base]);
+
+    let json = wr(&temp_dir, &["report", "--stale", "--older-than", "0s"]);
+    let wires = json["groups"][0]["wires"].as_array().unwrap();
+    let stale = wires.iter().find(|w| w["id"] == base.as_str()).unwrap();
+    assert_eq!(stale["waiting"], 1);
+    assert_eq!(stale["action"], "bump");
+    assert_eq!(stale["command"], format!("wr update {} --priority 1", base));
+}
+
+#[test]
+fn test_report_stale_default_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "Fresh");
+
+    let json = wr(&temp_dir, &["report", "--stale"]);
+    assert_eq!(json["older_than"], 30 * 24 * 60 * 60);
+    assert_eq!(json["count"], 0);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["report", "--stale", "-f", "table"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No TODO wires untouched for 4w"));
+}
+
+/// Moves the wire's start back by `secs`, as if it took that long
+fn backdate_start(dir: &TempDir, wire_id: &str, secs: i64) {
+    let conn = rusqlite::Connection::open(dir.path().join(".wires/wires.db")).unwrap();
+    conn.execute(
+        "UPDATE events SET at = at - ?1
+         WHERE wire_id = ?2 AND json_extract(data, '$.status.to') = 'IN_PROGRESS'",
+        rusqlite::params![secs, wire_id],
+    )
+    .unwrap();
+}
+
+fn finish(dir: &TempDir, wire_id: &str, estimate: &str, took: i64) {
+    wr(dir, &["tag", "add", wire_id, estimate]);
+    wr(dir, &["start", wire_id]);
+    wr(dir, &["done", wire_id]);
+    backdate_start(dir, wire_id, took);
+}
+
+#[test]
+fn test_report_accuracy_groups_by_tag_epic_and_actor() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let epic = create_wire(&temp_dir, "Epic");
+    let slow = create_wire(&temp_dir, "Slow");
+    let fast = create_wire(&temp_dir, "Fast");
+    let unestimated = create_wire(&temp_dir, "Unestimated");
+    wr(&temp_dir, &["dep", &epic, &slow]);
+    wr(&temp_dir, &["dep", &epic, &fast]);
+    wr(&temp_dir, &["tag", "add", &slow, "backend"]);
+    wr(&temp_dir, &["claim", &slow, "--agent", "alice"]);
+    finish(&temp_dir, &slow, "estimate:2", 7200);
+    finish(&temp_dir, &fast, "estimate:1", 1800);
+    wr(&temp_dir, &["done", &unestimated]);
+
+    let json = wr(&temp_dir, &["report", "--accuracy"]);
+    let overall = &json["overall"];
+    assert_eq!(overall["wires"], 2);
+    assert_eq!(overall["estimate"], 3);
+    let actual = overall["actual"].as_i64().unwrap();
+    assert!((9000..9010).contains(&actual), "{}", actual);
+
+    let by_tag = json["by_tag"].as_array().unwrap();
+    assert_eq!(by_tag.len(), 1);
+    assert_eq!(by_tag[0]["name"], "backend");
+    assert_eq!(by_tag[0]["estimate"], 2);
+
+    let by_epic = json["by_epic"].as_array().unwrap();
+    assert_eq!(by_epic.len(), 1);
+    assert_eq!(by_epic[0]["name"], epic.as_str());
+    assert_eq!(by_epic[0]["title"], "Epic");
+    assert_eq!(by_epic[0]["wires"], 2);
+
+    let by_actor = json["by_actor"].as_array().unwrap();
+    assert_eq!(by_actor[0]["name"], "alice");
+    assert!(by_actor[0]["variance"].as_f64().unwrap() > 15.0);
+    assert!(by_actor[1]["name"].is_null());
+    assert!(by_actor[1]["variance"].as_f64().unwrap() < -35.0);
+}
+
+#[test]
+fn test_report_accuracy_epic_covers_the_epic_like_epic_progress() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let epic = create_wire(&temp_dir, "Epic");
+    let task = create_wire(&temp_dir, "Task");
+    wr(&temp_dir, &["dep", &epic, &task]);
+    finish(&temp_dir, &task, "estimate:1", 1800);
+    finish(&temp_dir, &epic, "estimate:2", 3600);
+
+    let progress = wr(&temp_dir, &["epic", "progress", &epic]);
+    assert_eq!(progress["total"]["count"], 2);
+
+    let json = wr(&temp_dir, &["report", "--accuracy"]);
+    let by_epic = json["by_epic"].as_array().unwrap();
+    assert_eq!(by_epic.len(), 1);
+    assert_eq!(by_epic[0]["name"], epic.as_str());
+    assert_eq!(by_epic[0]["wires"], progress["total"]["count"]);
+    assert_eq!(by_epic[0]["estimate"], 3);
+}
+
+#[test]
+fn test_report_accuracy_without_estimates() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Done");
+    wr(&temp_dir, &["done", &id]);
+
+    let json = wr(&temp_dir, &["report", "--accuracy"]);
+    assert_eq!(json["overall"]["wires"], 0);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["report", "--accuracy", "-f", "table"])
+        .assert()
+        .success()
+        .stdout("No DONE wires with an estimate:<n> tag.\n");
+}
+
+#[test]
+fn test_report_requires_one_kind() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    for args in [&["report"][..], &["report", "--stale", "--accuracy"]] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(args)
+            .assert()
+            .code(64);
+    }
+}
@@ -0,0 +1,92 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_reserve_then_new_with_id() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let json = wr(&temp_dir, &["reserve", "3"]);
+    let reserved: Vec<&str> = json["reserved"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_str().unwrap())
+        .collect();
+    assert_eq!(reserved.len(), 3);
+
+    // Reserving creates nothing
+    assert!(wr(&temp_dir, &["list"]).as_array().unwrap().is_empty());
+
+    let json = wr(&temp_dir, &["new", "Filled in later", "--id", reserved[1]]);
+    assert_eq!(json["id"], reserved[1]);
+    assert_eq!(
+        wr(&temp_dir, &["show", reserved[1]])["title"],
+        "Filled in later"
+    );
+}
+
+#[test]
+fn test_new_with_unreserved_or_used_id_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Guess", "--id", "abcdef0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not reserved"));
+
+    let json = wr(&temp_dir, &["reserve"]);
+    let id = json["reserved"][0].as_str().unwrap();
+    wr(&temp_dir, &["new", "First", "--id", id]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Second", "--id", id])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not reserved"));
+    assert_eq!(wr(&temp_dir, &["list"]).as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_reserve_follows_id_scheme() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "id_scheme = \"ulid\"\n",
+    )
+    .unwrap();
+
+    let json = wr(&temp_dir, &["reserve", "2"]);
+    for id in json["reserved"].as_array().unwrap() {
+        assert_eq!(id.as_str().unwrap().len(), 26);
+    }
+
+    // An ID of another scheme was never handed out
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["new", "Hex", "--id", "abcdef0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not_reserved"));
+}
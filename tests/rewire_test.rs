@@ -0,0 +1,98 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn depends_on(dir: &TempDir, wire_id: &str) -> Vec<String> {
+    let mut ids: Vec<String> = wr(dir, &["show", wire_id])["depends_on"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["id"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn test_rewire_moves_incoming_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let from = create_wire(&temp_dir, "Big task");
+    let to = create_wire(&temp_dir, "Second half");
+    let a = create_wire(&temp_dir, "Dependent A");
+    let b = create_wire(&temp_dir, "Dependent B");
+    wr(&temp_dir, &["dep", &a, &from]);
+    wr(&temp_dir, &["dep", &b, &from]);
+
+    let json = wr(&temp_dir, &["rewire", &from, &to]);
+    assert_eq!(json["from"], from.as_str());
+    assert_eq!(json["to"], to.as_str());
+    let mut expected = vec![a.clone(), b.clone()];
+    expected.sort();
+    assert_eq!(json["moved_dependents"], serde_json::json!(expected));
+
+    assert_eq!(depends_on(&temp_dir, &a), vec![to.clone()]);
+    assert_eq!(depends_on(&temp_dir, &b), vec![to.clone()]);
+    // The source wire itself is untouched
+    assert_eq!(wr(&temp_dir, &["show", &from])["status"], "TODO");
+}
+
+#[test]
+fn test_rewire_is_cycle_checked_and_atomic() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let from = create_wire(&temp_dir, "Base");
+    let a = create_wire(&temp_dir, "Dependent A");
+    let to = create_wire(&temp_dir, "Top");
+    wr(&temp_dir, &["dep", &a, &from]);
+    wr(&temp_dir, &["dep", &to, &a]);
+
+    // a -> to would close to -> a -> to
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["rewire", &from, &to])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ircular"));
+    assert_eq!(depends_on(&temp_dir, &a), vec![from.clone()]);
+}
+
+#[test]
+fn test_rewire_onto_itself_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Task");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["rewire", &id, &id])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,126 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str, description: Option<&str>) -> String {
+    let mut cmd = Command::cargo_bin("wr").unwrap();
+    cmd.current_dir(dir).arg("new").arg(title);
+    if let Some(desc) = description {
+        cmd.arg("--description").arg(desc);
+    }
+    let output = cmd.output().unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn search(dir: &TempDir, args: &[&str]) -> Vec<String> {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("search")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json.as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_search_matches_title_and_description() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let title_hit = create_wire(&temp_dir, "Fix parser panics", None);
+    let desc_hit = create_wire(&temp_dir, "Crash report", Some("The parser fails on emoji"));
+    create_wire(&temp_dir, "Write docs", None);
+
+    let ids = search(&temp_dir, &["parser"]);
+    // Title matches rank above description matches
+    assert_eq!(ids, vec![title_hit, desc_hit]);
+}
+
+#[test]
+fn test_search_requires_all_terms_and_matches_prefixes() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let both = create_wire(&temp_dir, "Refactor parsing of config files", None);
+    create_wire(&temp_dir, "Refactor logging", None);
+
+    assert_eq!(search(&temp_dir, &["refactor", "pars"]), vec![both]);
+    assert_eq!(search(&temp_dir, &["refactor"]).len(), 2);
+}
+
+#[test]
+fn test_search_reflects_updates_and_deletes() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let id = create_wire(&temp_dir, "Old title", None);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &id, "--title", "Shiny new title"])
+        .assert()
+        .success();
+
+    assert!(search(&temp_dir, &["old"]).is_empty());
+    assert_eq!(search(&temp_dir, &["shiny"]), vec![id.clone()]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["rm", &id])
+        .assert()
+        .success();
+
+    assert!(search(&temp_dir, &["shiny"]).is_empty());
+}
+
+#[test]
+fn test_search_treats_operators_literally() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    create_wire(&temp_dir, "Cats and dogs", None);
+
+    // Would be a syntax error if passed to FTS5 unquoted
+    assert!(search(&temp_dir, &["AND", "\"", "OR("]).is_empty());
+}
+
+#[test]
+fn test_search_limit_and_table() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    for i in 0..3 {
+        create_wire(&temp_dir, &format!("Task {}", i), None);
+    }
+
+    assert_eq!(search(&temp_dir, &["task", "--limit", "2"]).len(), 2);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["search", "task", "-f", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Task 1"));
+}
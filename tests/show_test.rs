@@ -110,3 +110,68 @@ fn test_show_output_is_valid_json() {
     assert!(json.get("depends_on").is_some());
     assert!(json.get("blocks").is_some());
 }
+
+#[test]
+fn test_show_template_prints_fields() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Fix parser");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &wire_id, "--template", "Refs: {{id}} ({{title}})"])
+        .assert()
+        .success()
+        .stdout(format!("Refs: {} (Fix parser)\n", wire_id));
+}
+
+#[test]
+fn test_show_template_rejects_unknown_field() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Fix parser");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &wire_id, "--template", "{{owner}}"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("Unknown template field 'owner'"));
+}
+
+#[test]
+fn test_show_table_wraps_description_to_columns() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Long notes");
+    let description = format!(
+        "{}\n\n- {}",
+        "lorem ".repeat(20).trim_end(),
+        "ipsum ".repeat(10).trim_end()
+    );
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &wire_id, "--description", &description])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .env("COLUMNS", "40")
+        .args(["show", &wire_id, "-f", "table"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(
+        stdout.lines().all(|line| line.chars().count() <= 40),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("lorem\n\n- ipsum"), "{}", stdout);
+    assert!(stdout.contains("\n  ipsum"), "{}", stdout);
+}
@@ -0,0 +1,128 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_stats_json_rows_and_totals() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "One");
+    let done = create_wire(&temp_dir, "Two");
+    wr(&temp_dir, &["done", &done]);
+
+    let json = wr(&temp_dir, &["stats"]);
+    let rows = json["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 6);
+    assert_eq!(rows[0]["status"], "TODO");
+    assert_eq!(rows[0]["count"], 1);
+    assert_eq!(rows[0]["percent"], 50.0);
+    assert_eq!(rows[1]["status"], "IN_PROGRESS");
+    assert!(rows[1]["oldest_age"].is_null());
+    assert_eq!(json["total"]["count"], 2);
+    assert_eq!(json["total"]["percent"], 100.0);
+}
+
+#[test]
+fn test_stats_sort_by_count() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    for title in ["One", "Two"] {
+        let id = create_wire(&temp_dir, title);
+        wr(&temp_dir, &["cancel", &id]);
+    }
+
+    let json = wr(&temp_dir, &["stats", "--sort", "count"]);
+    assert_eq!(json["rows"][0]["status"], "CANCELLED");
+    assert_eq!(json["rows"][0]["count"], 2);
+}
+
+#[test]
+fn test_stats_table_has_totals_row() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    create_wire(&temp_dir, "One");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["stats", "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("STATUS"))
+        .stdout(predicate::str::contains("TOTAL"))
+        .stdout(predicate::str::contains("100.0%"));
+}
+
+#[test]
+fn test_stats_reports_throughput_and_blocked() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let done = create_wire(&temp_dir, "Done");
+    wr(&temp_dir, &["start", &done]);
+    wr(&temp_dir, &["done", &done]);
+    let waiting = create_wire(&temp_dir, "Waiting");
+    let open = create_wire(&temp_dir, "Open");
+    wr(&temp_dir, &["dep", &waiting, &open]);
+
+    let json = wr(&temp_dir, &["stats"]);
+    assert_eq!(json["throughput"]["day"], 1);
+    assert_eq!(json["throughput"]["week"], 1);
+    assert!(json["cycle_time"].as_i64().unwrap() >= 0);
+    assert_eq!(json["blocked"]["status"], 0);
+    assert_eq!(json["blocked"]["dependencies"], 1);
+    assert_eq!(json["blocked"]["external"], 0);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["stats", "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 done in the last day"))
+        .stdout(predicate::str::contains("1 waiting on dependencies"));
+}
+
+#[test]
+fn test_stats_blocked_follows_cancelled_blocks() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let waiting = create_wire(&temp_dir, "Waiting");
+    let dropped = create_wire(&temp_dir, "Dropped");
+    wr(&temp_dir, &["dep", &waiting, &dropped]);
+    wr(&temp_dir, &["cancel", &dropped]);
+
+    assert_eq!(wr(&temp_dir, &["stats"])["blocked"]["dependencies"], 1);
+
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[ready]\ncancelled_blocks = false\n",
+    )
+    .unwrap();
+    assert_eq!(wr(&temp_dir, &["stats"])["blocked"]["dependencies"], 0);
+    assert_eq!(wr(&temp_dir, &["ready"]).as_array().unwrap().len(), 1);
+}
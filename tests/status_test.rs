@@ -144,3 +144,289 @@ fn test_cancel_nonexistent_wire() {
         .failure()
         .stderr(predicate::str::contains("Wire not found"));
 }
+
+#[test]
+fn test_start_accepts_title_reference() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let id = create_wire(&temp_dir, "Fix parser panics");
+    create_wire(&temp_dir, "Write docs");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["start", "title:parser"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&id))
+        .stdout(predicate::str::contains("IN_PROGRESS"));
+}
+
+#[test]
+fn test_title_reference_ambiguous_lists_candidates() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let first = create_wire(&temp_dir, "Fix parser panics");
+    let second = create_wire(&temp_dir, "Parser docs");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", "title:parser"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains("matches 2 open wires"));
+    let ids: Vec<&str> = json["candidates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    assert!(ids.contains(&first.as_str()));
+    assert!(ids.contains(&second.as_str()));
+}
+
+#[test]
+fn test_title_reference_skips_closed_wires() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let id = create_wire(&temp_dir, "Fix parser panics");
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &id])
+        .assert()
+        .success();
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["cancel", "title:parser"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Wire not found: title:parser"));
+}
+
+#[test]
+fn test_done_full_returns_wire_with_deps() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let blocker = create_wire(&temp_dir, "Blocker");
+    let id = create_wire(&temp_dir, "Task");
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["dep", &id, &blocker])
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &id, "--full"])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["title"], "Task");
+    assert_eq!(json["status"], "DONE");
+    assert_eq!(json["depends_on"][0]["id"], blocker.as_str());
+    // Warnings are still reported alongside the full object
+    assert_eq!(json["warnings"][0]["wire_id"], blocker.as_str());
+}
+
+fn start(dir: &TempDir, args: &[&str]) -> std::process::Output {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("start")
+        .args(args)
+        .env_remove("WR_AGENT")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_start_over_limit_pauses_previous_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[in_progress]\nlimit = 1\n",
+    )
+    .unwrap();
+    let first = create_wire(&temp_dir, "First");
+    let second = create_wire(&temp_dir, "Second");
+
+    assert!(start(&temp_dir, &[&first]).status.success());
+    let output = start(&temp_dir, &[&second]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["paused"][0]["id"], first.as_str());
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &first])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["status"], "PAUSED");
+    assert!(json["paused"]["reason"].as_str().unwrap().contains(&second));
+}
+
+#[test]
+fn test_start_over_limit_can_reject() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[in_progress]\nlimit = 1\nwhen_full = \"reject\"\n",
+    )
+    .unwrap();
+    let first = create_wire(&temp_dir, "First");
+    let second = create_wire(&temp_dir, "Second");
+
+    assert!(start(&temp_dir, &[&first]).status.success());
+    let output = start(&temp_dir, &[&second]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("limit 1"));
+
+    // Another agent's limit is counted separately
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["claim", &second, "--agent", "bob"])
+        .assert()
+        .success();
+    assert!(start(&temp_dir, &[&second, "--agent", "bob"])
+        .status
+        .success());
+}
+
+#[test]
+fn test_start_with_agent_claims_and_counts_toward_limit() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[in_progress]\nlimit = 1\nwhen_full = \"reject\"\n",
+    )
+    .unwrap();
+    let first = create_wire(&temp_dir, "First");
+    let second = create_wire(&temp_dir, "Second");
+
+    // No `wr claim` beforehand: starting claims the wire for the agent
+    assert!(start(&temp_dir, &[&first, "--agent", "alice"])
+        .status
+        .success());
+    let output = start(&temp_dir, &[&second, "--agent", "alice"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("limit 1"));
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &first])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["status"], "IN_PROGRESS");
+
+    // The claim start took keeps other agents off the wire
+    assert!(!start(&temp_dir, &[&first, "--agent", "bob"])
+        .status
+        .success());
+    assert!(start(&temp_dir, &[&second, "--agent", "bob"])
+        .status
+        .success());
+}
+
+#[test]
+fn test_start_without_limit_allows_many() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    for title in ["One", "Two", "Three"] {
+        let id = create_wire(&temp_dir, title);
+        assert!(start(&temp_dir, &[&id]).status.success());
+    }
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "--status", "in-progress", "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Three"))
+        .stdout(predicate::str::contains("One"));
+}
+
+#[test]
+fn test_finished_wire_must_be_reopened_before_starting() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Dropped then revived");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["cancel", &wire_id])
+        .assert()
+        .success();
+
+    let output = start(&temp_dir, &[&wire_id]);
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(json["from"], "CANCELLED");
+    assert_eq!(json["to"], "IN_PROGRESS");
+    assert_eq!(json["allowed"], serde_json::json!(["TODO"]));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &wire_id, "--status", "todo"])
+        .assert()
+        .success();
+    assert!(start(&temp_dir, &[&wire_id]).status.success());
+}
+
+#[test]
+fn test_transitions_are_configurable() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    std::fs::write(
+        temp_dir.path().join(".wires/config.toml"),
+        "[transitions]\nTODO = [\"IN_PROGRESS\", \"CANCELLED\"]\nDONE = [\"TODO\", \"IN_PROGRESS\"]\n",
+    )
+    .unwrap();
+    let wire_id = create_wire(&temp_dir, "Must be started first");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &wire_id])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "can't go from TODO to DONE (allowed: IN_PROGRESS, CANCELLED)",
+        ));
+
+    assert!(start(&temp_dir, &[&wire_id]).status.success());
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["done", &wire_id])
+        .assert()
+        .success();
+    assert!(start(&temp_dir, &[&wire_id]).status.success());
+}
@@ -0,0 +1,83 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_supersede_cancels_and_moves_dependents() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let old = create_wire(&temp_dir, "Old plan");
+    let new = create_wire(&temp_dir, "New plan");
+    let dependent = create_wire(&temp_dir, "Ship it");
+    wr(&temp_dir, &["dep", &dependent, &old]);
+
+    let json = wr(&temp_dir, &["supersede", &old, &new]);
+    assert_eq!(json["status"], "CANCELLED");
+    assert_eq!(json["cancel_reason"], format!("superseded-by:{}", new));
+    assert_eq!(json["superseded_by"], new.as_str());
+    assert_eq!(json["moved_dependents"], serde_json::json!([dependent]));
+
+    let json = wr(&temp_dir, &["show", &dependent]);
+    let deps: Vec<&str> = json["depends_on"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(deps, vec![new.as_str()]);
+}
+
+#[test]
+fn test_supersede_rejects_done_and_cycles() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let old = create_wire(&temp_dir, "Old plan");
+    let new = create_wire(&temp_dir, "New plan");
+    wr(&temp_dir, &["dep", &new, &old]);
+
+    // new would end up depending on itself
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["supersede", &old, &new])
+        .assert()
+        .failure();
+    assert_eq!(wr(&temp_dir, &["show", &old])["status"], "TODO");
+
+    wr(&temp_dir, &["done", &old]);
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["supersede", &old, &new])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(12));
+    let json: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(json["code"], "invalid_transition");
+    assert_eq!(json["from"], "DONE");
+    assert_eq!(json["to"], "CANCELLED");
+}
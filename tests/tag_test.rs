@@ -0,0 +1,296 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn tag(dir: &TempDir, action: &str, wire_id: &str, tag: &str) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("tag")
+        .arg(action)
+        .arg(wire_id)
+        .arg(tag)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_tag_add_shows_in_wire_json() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_id = create_wire(&temp_dir, "Tagged wire");
+
+    let json = tag(&temp_dir, "add", &wire_id, "Backend");
+    assert_eq!(json["tag"], "backend");
+    assert_eq!(json["action"], "added");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("show")
+        .arg(&wire_id)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["tags"], serde_json::json!(["backend"]));
+}
+
+#[test]
+fn test_tag_add_twice_is_unchanged() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_id = create_wire(&temp_dir, "Tagged wire");
+
+    tag(&temp_dir, "add", &wire_id, "tests");
+    let json = tag(&temp_dir, "add", &wire_id, "tests");
+    assert_eq!(json["action"], "unchanged");
+}
+
+#[test]
+fn test_tag_rm() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_id = create_wire(&temp_dir, "Tagged wire");
+
+    tag(&temp_dir, "add", &wire_id, "tests");
+    let json = tag(&temp_dir, "rm", &wire_id, "tests");
+    assert_eq!(json["action"], "removed");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("show")
+        .arg(&wire_id)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["tags"], serde_json::json!([]));
+}
+
+#[test]
+fn test_list_filter_by_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let backend = create_wire(&temp_dir, "Backend work");
+    create_wire(&temp_dir, "Other work");
+
+    tag(&temp_dir, "add", &backend, "backend");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("list")
+        .arg("--tag")
+        .arg("backend")
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let wires = json.as_array().unwrap();
+    assert_eq!(wires.len(), 1);
+    assert_eq!(wires[0]["id"], backend.as_str());
+}
+
+#[test]
+fn test_tag_list_counts() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_a = create_wire(&temp_dir, "Wire A");
+    let wire_b = create_wire(&temp_dir, "Wire B");
+
+    tag(&temp_dir, "add", &wire_a, "backend");
+    tag(&temp_dir, "add", &wire_b, "backend");
+    tag(&temp_dir, "add", &wire_b, "tests");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("tag")
+        .arg("list")
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            { "tag": "backend", "count": 2 },
+            { "tag": "tests", "count": 1 }
+        ])
+    );
+}
+
+#[test]
+fn test_tag_invalid_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_id = create_wire(&temp_dir, "Tagged wire");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("tag")
+        .arg("add")
+        .arg(&wire_id)
+        .arg("two words")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid tag"));
+}
+
+#[test]
+fn test_tag_nonexistent_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("tag")
+        .arg("add")
+        .arg("abcdef0")
+        .arg("backend")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Wire not found"));
+}
+
+fn tag_bulk(dir: &TempDir, args: &[&str]) -> serde_json::Value {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("tag")
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_tag_add_bulk_by_status() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let shipped = create_wire(&temp_dir, "Shipped");
+    let also_shipped = create_wire(&temp_dir, "Also shipped");
+    let open = create_wire(&temp_dir, "Still open");
+    for id in [&shipped, &also_shipped] {
+        Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["done", id])
+            .assert()
+            .success();
+    }
+    tag(&temp_dir, "add", &also_shipped, "q3");
+
+    let preview = tag_bulk(&temp_dir, &["add", "q3", "--status", "done", "--dry-run"]);
+    assert_eq!(preview["ids"], serde_json::json!([shipped]));
+    assert_eq!(preview["matched"], 2);
+    assert_eq!(preview["dry_run"], true);
+
+    let json = tag_bulk(&temp_dir, &["add", "q3", "--status", "done"]);
+    assert_eq!(json["action"], "added");
+    assert_eq!(json["ids"], serde_json::json!([shipped]));
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["list", "--tag", "q3"])
+        .output()
+        .unwrap();
+    let listed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let ids: Vec<_> = listed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 2);
+    assert!(!ids.contains(&open.as_str()));
+}
+
+#[test]
+fn test_tag_rm_bulk_by_tag_and_age() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let a = create_wire(&temp_dir, "A");
+    let b = create_wire(&temp_dir, "B");
+    tag(&temp_dir, "add", &a, "triage");
+    tag(&temp_dir, "add", &b, "triage");
+
+    // Nothing is a week old yet
+    let json = tag_bulk(
+        &temp_dir,
+        &["rm", "triage", "--tag", "triage", "--older-than", "7d"],
+    );
+    assert_eq!(json["matched"], 0);
+
+    let json = tag_bulk(
+        &temp_dir,
+        &["rm", "triage", "--tag", "triage", "--older-than", "0s"],
+    );
+    assert_eq!(json["action"], "removed");
+    assert_eq!(json["ids"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_tag_bulk_needs_filters() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Wire");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["tag", "add", "backend"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Missing tag"));
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["tag", "add", &id, "backend", "--status", "todo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not both"));
+}
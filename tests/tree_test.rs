@@ -0,0 +1,130 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr_text(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// api depends on db and auth; auth depends on db.
+fn setup_diamond(dir: &TempDir) -> (String, String, String) {
+    let api = create_wire(dir, "API");
+    let auth = create_wire(dir, "Auth");
+    let db = create_wire(dir, "Database");
+    wr(dir, &["dep", &api, &auth]);
+    wr(dir, &["dep", &api, &db]);
+    wr(dir, &["dep", &auth, &db]);
+    (api, auth, db)
+}
+
+#[test]
+fn test_tree_json_is_nested() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let (api, _auth, db) = setup_diamond(&temp_dir);
+
+    let json = wr(&temp_dir, &["tree", &api]);
+    assert_eq!(json["id"], api.as_str());
+    assert_eq!(json["children"].as_array().unwrap().len(), 2);
+
+    let all: Vec<&serde_json::Value> = json["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|c| std::iter::once(c).chain(c["children"].as_array().unwrap()))
+        .collect();
+    let db_nodes: Vec<_> = all.iter().filter(|n| n["id"] == db.as_str()).collect();
+    assert_eq!(db_nodes.len(), 2);
+    assert_eq!(
+        db_nodes.iter().filter(|n| n["repeated"] == true).count(),
+        1,
+        "db is expanded once"
+    );
+}
+
+#[test]
+fn test_tree_reverse_shows_what_a_wire_blocks() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let (api, auth, db) = setup_diamond(&temp_dir);
+
+    let json = wr(&temp_dir, &["tree", &db, "--reverse"]);
+    let mut ids: Vec<&str> = json["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_str().unwrap())
+        .collect();
+    ids.sort();
+    let mut expected = vec![api.as_str(), auth.as_str()];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    // A wire with nothing below it is a single node
+    let json = wr(&temp_dir, &["tree", &api, "--reverse"]);
+    assert!(json["children"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_tree_table_and_markdown() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let (api, _auth, db) = setup_diamond(&temp_dir);
+
+    let output = wr_text(&temp_dir, &["tree", &api, "-f", "table"]);
+    assert!(output.starts_with(&format!("○ {}  API\n", api)));
+    assert!(output.contains("├── "));
+    assert!(output.contains(&format!("── ○ {}  Database", db)));
+    assert!(output.contains("└── "));
+    assert!(output.contains("(see above)"));
+
+    let output = wr_text(&temp_dir, &["tree", &api, "-f", "markdown"]);
+    assert!(output.starts_with(&format!("- [ ] `{}` API\n", api)));
+    assert!(output.contains(&format!("    - [ ] `{}` Database", db)));
+}
+
+#[test]
+fn test_tree_nonexistent_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["tree", "0000000"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "tui")]
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_tui_needs_a_terminal() {
+    let dir = TempDir::new().unwrap();
+    init_test_repo(&dir);
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&dir)
+        .arg("tui")
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("needs a terminal"), "{}", stderr);
+}
@@ -0,0 +1,126 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_undo_reverts_status_change() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Wrong wire");
+
+    wr(&temp_dir, &["start", &id]);
+    wr(&temp_dir, &["done", &id]);
+
+    let json = wr(&temp_dir, &["undo"]);
+    assert_eq!(json["undone"][0]["kind"], "updated");
+    assert_eq!(wr(&temp_dir, &["show", &id])["status"], "IN_PROGRESS");
+
+    wr(&temp_dir, &["undo"]);
+    assert_eq!(wr(&temp_dir, &["show", &id])["status"], "TODO");
+}
+
+#[test]
+fn test_undo_restores_deleted_wire_with_edges() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let a = create_wire(&temp_dir, "A");
+    let b = create_wire(&temp_dir, "B");
+    let c = create_wire(&temp_dir, "C");
+    wr(&temp_dir, &["dep", &b, &a]);
+    wr(&temp_dir, &["dep", &c, &b]);
+    wr(&temp_dir, &["tag", "add", &b, "backend"]);
+
+    wr(&temp_dir, &["rm", &b]);
+    wr(&temp_dir, &["undo"]);
+
+    let json = wr(&temp_dir, &["show", &b]);
+    assert_eq!(json["title"], "B");
+    assert_eq!(json["tags"][0], "backend");
+    assert_eq!(json["depends_on"][0]["id"], a.as_str());
+    assert_eq!(json["blocks"][0]["id"], c.as_str());
+}
+
+#[test]
+fn test_undo_multiple_and_dependency() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let a = create_wire(&temp_dir, "A");
+    let b = create_wire(&temp_dir, "B");
+    wr(&temp_dir, &["dep", &b, &a]);
+
+    let json = wr(&temp_dir, &["undo", "2"]);
+    let kinds: Vec<&str> = json["undone"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, vec!["dep_added", "created"]);
+
+    let list = wr(&temp_dir, &["list"]);
+    let list = list.as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0]["id"], a.as_str());
+}
+
+#[test]
+fn test_undo_skips_its_own_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Only");
+    wr(&temp_dir, &["update", &id, "--title", "Renamed"]);
+
+    wr(&temp_dir, &["undo"]);
+    // The second undo reverts the creation, not the first undo
+    wr(&temp_dir, &["undo"]);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .arg("undo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Nothing to undo"));
+}
+
+#[test]
+fn test_undo_too_many_changes_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Keep me");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["undo", "5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Only 1 change(s) can be undone"));
+
+    assert_eq!(wr(&temp_dir, &["show", &id])["title"], "Keep me");
+}
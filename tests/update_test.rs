@@ -166,3 +166,88 @@ fn test_update_nonexistent_wire() {
         .failure()
         .stderr(predicate::str::contains("Wire not found"));
 }
+
+#[test]
+fn test_update_full_returns_complete_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let id = create_wire(&temp_dir, "Original");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &id, "--title", "Renamed", "--full"])
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["id"], id.as_str());
+    assert_eq!(json["title"], "Renamed");
+    assert!(json["blocks"].is_array());
+}
+
+#[test]
+fn test_update_named_priority() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+
+    let wire_id = create_wire(&temp_dir, "Reprioritize me");
+
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &wire_id, "--priority", "med"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["priority"], 1);
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["show", &wire_id, "--format", "table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[pri:med]"));
+}
+
+#[test]
+fn test_update_description_from_file_and_stdin() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let wire_id = create_wire(&temp_dir, "Wire");
+    let notes = temp_dir.path().join("notes.md");
+    std::fs::write(&notes, "First\n\nSecond\n").unwrap();
+
+    let description = || {
+        let output = Command::cargo_bin("wr")
+            .unwrap()
+            .current_dir(&temp_dir)
+            .args(["show", &wire_id])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["description"].clone()
+    };
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &wire_id, "--description-file"])
+        .arg(&notes)
+        .assert()
+        .success();
+    assert_eq!(description(), "First\n\nSecond");
+
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(&temp_dir)
+        .args(["update", &wire_id, "--description-file", "-"])
+        .write_stdin("From stdin\n")
+        .assert()
+        .success();
+    assert_eq!(description(), "From stdin");
+}
@@ -0,0 +1,95 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+mod common;
+use common::wr;
+
+fn init_test_repo(dir: &TempDir) {
+    Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .assert()
+        .success();
+}
+
+fn create_wire(dir: &TempDir, title: &str) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .arg("new")
+        .arg(title)
+        .output()
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+fn wr_text(dir: &TempDir, args: &[&str]) -> String {
+    let output = Command::cargo_bin("wr")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_why_explains_transitive_blockers() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let api = create_wire(&temp_dir, "API");
+    let auth = create_wire(&temp_dir, "Auth");
+    let db = create_wire(&temp_dir, "Database");
+    wr(&temp_dir, &["dep", &api, &auth]);
+    wr(&temp_dir, &["dep", &auth, &db]);
+    wr(&temp_dir, &["block-on", "waiting for API key", &db]);
+
+    let json = wr(&temp_dir, &["why", &api]);
+    assert_eq!(json["ready"], false);
+    assert_eq!(json["blockers"][0]["id"], auth.as_str());
+    let nested = &json["blockers"][0]["blockers"][0];
+    assert_eq!(nested["id"], db.as_str());
+    assert_eq!(nested["blocked_on"][0]["reason"], "waiting for API key");
+
+    let output = wr_text(&temp_dir, &["why", &api, "-f", "table"]);
+    assert!(output.contains("not ready"));
+    assert!(output.contains("waiting on #1 \"waiting for API key\""));
+
+    let output = wr_text(&temp_dir, &["why", &api, "-f", "markdown"]);
+    assert!(output.contains("**Blocked by:**"));
+    assert!(output.contains(&format!("  - [ ] `{}` Database", db)));
+}
+
+#[test]
+fn test_why_ready_wire() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let id = create_wire(&temp_dir, "Free");
+
+    let json = wr(&temp_dir, &["why", &id]);
+    assert_eq!(json["ready"], true);
+    assert!(json.get("blockers").is_none());
+    assert!(wr_text(&temp_dir, &["why", &id, "-f", "table"]).contains("— ready"));
+}
+
+#[test]
+fn test_why_reports_status_and_claims() {
+    let temp_dir = TempDir::new().unwrap();
+    init_test_repo(&temp_dir);
+    let done = create_wire(&temp_dir, "Finished");
+    wr(&temp_dir, &["done", &done]);
+    let output = wr_text(&temp_dir, &["why", &done, "-f", "table"]);
+    assert!(output.contains("status DONE is never ready"));
+
+    let claimed = create_wire(&temp_dir, "Claimed");
+    wr(&temp_dir, &["claim", &claimed, "--agent", "alice"]);
+    let json = wr(&temp_dir, &["why", &claimed, "--agent", "bob"]);
+    assert_eq!(json["ready"], false);
+    assert_eq!(json["claimed"]["agent"], "alice");
+    let json = wr(&temp_dir, &["why", &claimed, "--agent", "alice"]);
+    assert_eq!(json["ready"], true);
+}